@@ -25,6 +25,7 @@ pub fn criterion_benchmark(c: &mut Criterion) {
                         &[],
                         BabyBearPoseidon2::new(),
                     )
+                    .unwrap()
                 })
             },
         );
@@ -239,7 +239,8 @@ impl CpuChip {
 
         let is_jump_instruction = local.selectors.is_jal + local.selectors.is_jalr;
 
-        // Verify that the local.pc + 4 is saved in op_a for both jump instructions.
+        // Verify that the pc of the next instruction (local.pc + 4, or local.pc + 2 for compressed
+        // instructions) is saved in op_a for both jump instructions.
         // When op_a is set to register X0, the RISC-V spec states that the jump instruction will
         // not have a return destination address (it is effectively a GOTO command).  In this case,
         // we shouldn't verify the return address.
@@ -248,7 +249,7 @@ impl CpuChip {
             .when_not(local.instruction.op_a_0)
             .assert_eq(
                 local.op_a_val().reduce::<AB>(),
-                local.pc + AB::F::from_canonical_u8(4),
+                local.pc + local.instruction.size::<AB>(),
             );
 
         // Verify that the word form of local.pc is correct for JAL instructions.
@@ -325,7 +326,9 @@ impl CpuChip {
         // with different information. Read more about the format in runtime::syscall::SyscallCode.
         let syscall_id = syscall_code[0];
         let send_to_table = syscall_code[1]; // Does the syscall have a table that should be sent.
-        let num_cycles = syscall_code[2]; // How many extra cycles to increment the clk for the syscall.
+
+        // How many extra cycles to increment the clk for the syscall, as a little-endian u16.
+        let num_cycles = syscall_code[2] + syscall_code[3] * AB::F::from_canonical_u32(1 << 8);
 
         // Check that the ecall_mul_send_to_table column is equal to send_to_table * is_ecall_instruction.
         // This is a separate column because it is used as a multiplicity in an interaction which
@@ -496,19 +499,20 @@ impl CpuChip {
             is_branch_instruction + local.selectors.is_jal + local.selectors.is_jalr + is_halt,
         );
 
-        // Verify that the pc increments by 4 for all instructions except branch, jump and halt instructions.
+        // Verify that the pc increments by the instruction size for all instructions except branch, jump
+        // and halt instructions.
         // The other case is handled by eval_jump, eval_branch and eval_ecall (for halt).
         builder
             .when_transition()
             .when(next.is_real)
             .when(local.is_sequential_instr)
-            .assert_eq(local.pc + AB::Expr::from_canonical_u8(4), next.pc);
+            .assert_eq(local.pc + local.instruction.size::<AB>(), next.pc);
 
-        // When the last row is real and it's a sequential instruction, assert that local.next_pc <==> local.pc + 4
+        // When the last row is real and it's a sequential instruction, assert that local.next_pc <==> local.pc + size
         builder
             .when(local.is_real)
             .when(local.is_sequential_instr)
-            .assert_eq(local.pc + AB::Expr::from_canonical_u8(4), local.next_pc);
+            .assert_eq(local.pc + local.instruction.size::<AB>(), local.next_pc);
     }
 
     /// Constraints related to the COMMIT and COMMIT_DEFERRED_PROOFS instructions.
@@ -1,4 +1,5 @@
-use p3_field::PrimeField;
+use p3_air::AirBuilder;
+use p3_field::{AbstractField, PrimeField};
 use sp1_derive::AlignedBorrow;
 use std::mem::size_of;
 use std::{iter::once, vec::IntoIter};
@@ -26,6 +27,9 @@ pub struct InstructionCols<T> {
 
     /// Flags to indicate if op_a is register 0.
     pub op_a_0: T,
+
+    /// Whether the instruction was expanded from a 16-bit compressed instruction.
+    pub is_compressed: T,
 }
 
 impl<F: PrimeField> InstructionCols<F> {
@@ -36,6 +40,14 @@ impl<F: PrimeField> InstructionCols<F> {
         self.op_c = instruction.op_c.into();
 
         self.op_a_0 = F::from_bool(instruction.op_a == Register::X0 as u32);
+        self.is_compressed = F::from_bool(instruction.compressed);
+    }
+}
+
+impl<V: Copy> InstructionCols<V> {
+    /// The size of the instruction in bytes: 2 for compressed instructions and 4 otherwise.
+    pub fn size<AB: AirBuilder<Var = V>>(&self) -> AB::Expr {
+        AB::Expr::from_canonical_u8(4) - AB::Expr::two() * self.is_compressed
     }
 }
 
@@ -49,6 +61,7 @@ impl<T> IntoIterator for InstructionCols<T> {
             .chain(self.op_b)
             .chain(self.op_c)
             .chain(once(self.op_a_0))
+            .chain(once(self.is_compressed))
             .collect::<Vec<_>>()
             .into_iter()
     }
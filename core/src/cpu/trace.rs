@@ -545,8 +545,7 @@ impl CpuChip {
 
             let syscall_id = cols.op_a_access.prev_value[0];
             // let send_to_table = cols.op_a_access.prev_value[1];
-            // let num_cycles = cols.op_a_access.prev_value[2];
-            // let is_halt = cols.op_a_access.prev_value[3];
+            // let num_cycles = cols.op_a_access.prev_value[2] + cols.op_a_access.prev_value[3] * 256;
 
             // Populate `is_enter_unconstrained`.
             ecall_cols
@@ -620,7 +619,8 @@ mod tests {
 
     use super::*;
 
-    use crate::runtime::{tests::simple_program, Instruction, Runtime};
+    use crate::runtime::tests::{compressed_program, simple_program};
+    use crate::runtime::{Instruction, Runtime};
     use crate::utils::run_test;
 
     #[test]
@@ -638,6 +638,7 @@ mod tests {
                 op_c: 2,
                 imm_b: false,
                 imm_c: false,
+                compressed: false,
             },
             a: 1,
             a_record: None,
@@ -674,4 +675,9 @@ mod tests {
         let program = simple_program();
         run_test(program).unwrap();
     }
+
+    #[test]
+    fn prove_compressed() {
+        run_test(compressed_program()).unwrap();
+    }
 }
@@ -0,0 +1,284 @@
+//! Expansion of the RV32C compressed instructions into their RV32I equivalents.
+//!
+//! Reference: The RISC-V Instruction Set Manual, Volume I, Chapter 16 ("C" Standard Extension for
+//! Compressed Instructions).
+
+/// The 32-bit encoding of `unimp`, which compressed instructions without an RV32IM equivalent
+/// (such as the floating point loads and stores) expand to.
+pub const UNIMP_INSTRUCTION: u32 = 0xc000_1073;
+
+const OPCODE_LOAD: u32 = 0b000_0011;
+const OPCODE_OP_IMM: u32 = 0b001_0011;
+const OPCODE_STORE: u32 = 0b010_0011;
+const OPCODE_OP: u32 = 0b011_0011;
+const OPCODE_LUI: u32 = 0b011_0111;
+const OPCODE_BRANCH: u32 = 0b110_0011;
+const OPCODE_JALR: u32 = 0b110_0111;
+const OPCODE_JAL: u32 = 0b110_1111;
+const EBREAK_INSTRUCTION: u32 = 0x0010_0073;
+
+/// Returns whether the low halfword of an instruction belongs to a 16-bit compressed instruction.
+#[inline]
+pub fn is_compressed(halfword: u16) -> bool {
+    halfword & 0b11 != 0b11
+}
+
+/// Expands a 16-bit compressed instruction into the 32-bit encoding of its RV32I equivalent.
+///
+/// Returns `None` for illegal instructions and for instructions that have no RV32IM equivalent.
+pub fn decompress(instruction: u16) -> Option<u32> {
+    let c = instruction as u32;
+    let funct3 = bits(c, 15, 13);
+
+    // The full register fields, and the 3-bit register fields which address x8-x15.
+    let rd = bits(c, 11, 7);
+    let rs2 = bits(c, 6, 2);
+    let rd_prime = bits(c, 4, 2) + 8;
+    let rs1_prime = bits(c, 9, 7) + 8;
+
+    match (c & 0b11, funct3) {
+        // C.ADDI4SPN: addi rd', x2, nzuimm
+        (0b00, 0b000) => {
+            let imm = bits(c, 12, 11) << 4 | bits(c, 10, 7) << 6 | bit(c, 6) << 2 | bit(c, 5) << 3;
+            (imm != 0).then(|| i_type(imm, 2, 0b000, rd_prime, OPCODE_OP_IMM))
+        }
+        // C.LW: lw rd', uimm(rs1')
+        (0b00, 0b010) => Some(i_type(
+            lw_sw_offset(c),
+            rs1_prime,
+            0b010,
+            rd_prime,
+            OPCODE_LOAD,
+        )),
+        // C.SW: sw rs2', uimm(rs1')
+        (0b00, 0b110) => Some(s_type(lw_sw_offset(c), rd_prime, rs1_prime, 0b010)),
+
+        // C.ADDI (and C.NOP): addi rd, rd, imm
+        (0b01, 0b000) => Some(i_type(ci_imm(c), rd, 0b000, rd, OPCODE_OP_IMM)),
+        // C.JAL: jal x1, offset
+        (0b01, 0b001) => Some(j_type(cj_offset(c), 1)),
+        // C.LI: addi rd, x0, imm
+        (0b01, 0b010) => Some(i_type(ci_imm(c), 0, 0b000, rd, OPCODE_OP_IMM)),
+        // C.ADDI16SP: addi x2, x2, nzimm
+        (0b01, 0b011) if rd == 2 => {
+            let imm = sign_extend(
+                bit(c, 12) << 9
+                    | bit(c, 6) << 4
+                    | bit(c, 5) << 6
+                    | bits(c, 4, 3) << 7
+                    | bit(c, 2) << 5,
+                10,
+            );
+            (imm != 0).then(|| i_type(imm, 2, 0b000, 2, OPCODE_OP_IMM))
+        }
+        // C.LUI: lui rd, nzimm
+        (0b01, 0b011) => {
+            let imm = ci_imm(c);
+            (imm != 0).then(|| imm << 12 | rd << 7 | OPCODE_LUI)
+        }
+        (0b01, 0b100) => match bits(c, 11, 10) {
+            // C.SRLI and C.SRAI: srli/srai rd', rd', shamt (shamt[5] must be zero on RV32)
+            0b00 if bit(c, 12) == 0 => {
+                Some(i_type(rs2, rs1_prime, 0b101, rs1_prime, OPCODE_OP_IMM))
+            }
+            0b01 if bit(c, 12) == 0 => Some(i_type(
+                0x400 | rs2,
+                rs1_prime,
+                0b101,
+                rs1_prime,
+                OPCODE_OP_IMM,
+            )),
+            // C.ANDI: andi rd', rd', imm
+            0b10 => Some(i_type(
+                ci_imm(c),
+                rs1_prime,
+                0b111,
+                rs1_prime,
+                OPCODE_OP_IMM,
+            )),
+            // C.SUB, C.XOR, C.OR and C.AND: op rd', rd', rs2'
+            0b11 if bit(c, 12) == 0 => {
+                let (funct7, funct3) = match bits(c, 6, 5) {
+                    0b00 => (0b010_0000, 0b000),
+                    0b01 => (0, 0b100),
+                    0b10 => (0, 0b110),
+                    _ => (0, 0b111),
+                };
+                Some(r_type(funct7, rd_prime, rs1_prime, funct3, rs1_prime))
+            }
+            _ => None,
+        },
+        // C.J: jal x0, offset
+        (0b01, 0b101) => Some(j_type(cj_offset(c), 0)),
+        // C.BEQZ and C.BNEZ: beq/bne rs1', x0, offset
+        (0b01, 0b110) => Some(b_type(cb_offset(c), rs1_prime, 0b000)),
+        (0b01, 0b111) => Some(b_type(cb_offset(c), rs1_prime, 0b001)),
+
+        // C.SLLI: slli rd, rd, shamt (shamt[5] must be zero on RV32)
+        (0b10, 0b000) if bit(c, 12) == 0 => Some(i_type(rs2, rd, 0b001, rd, OPCODE_OP_IMM)),
+        // C.LWSP: lw rd, uimm(x2)
+        (0b10, 0b010) if rd != 0 => {
+            let imm = bit(c, 12) << 5 | bits(c, 6, 4) << 2 | bits(c, 3, 2) << 6;
+            Some(i_type(imm, 2, 0b010, rd, OPCODE_LOAD))
+        }
+        (0b10, 0b100) => match (bit(c, 12), rd, rs2) {
+            // C.JR: jalr x0, 0(rs1)
+            (0, rs1, 0) if rs1 != 0 => Some(i_type(0, rs1, 0b000, 0, OPCODE_JALR)),
+            // C.MV: add rd, x0, rs2
+            (0, rd, rs2) if rs2 != 0 => Some(r_type(0, rs2, 0, 0b000, rd)),
+            // C.EBREAK: ebreak
+            (1, 0, 0) => Some(EBREAK_INSTRUCTION),
+            // C.JALR: jalr x1, 0(rs1)
+            (1, rs1, 0) => Some(i_type(0, rs1, 0b000, 1, OPCODE_JALR)),
+            // C.ADD: add rd, rd, rs2
+            (1, rd, rs2) => Some(r_type(0, rs2, rd, 0b000, rd)),
+            _ => None,
+        },
+        // C.SWSP: sw rs2, uimm(x2)
+        (0b10, 0b110) => {
+            let imm = bits(c, 12, 9) << 2 | bits(c, 8, 7) << 6;
+            Some(s_type(imm, rs2, 2, 0b010))
+        }
+
+        _ => None,
+    }
+}
+
+/// Extracts the bits `hi..=lo` of `value`.
+#[inline]
+fn bits(value: u32, hi: u32, lo: u32) -> u32 {
+    (value >> lo) & ((1 << (hi - lo + 1)) - 1)
+}
+
+#[inline]
+fn bit(value: u32, index: u32) -> u32 {
+    bits(value, index, index)
+}
+
+/// Sign-extends the low `width` bits of `value`.
+#[inline]
+fn sign_extend(value: u32, width: u32) -> u32 {
+    let shift = 32 - width;
+    (((value << shift) as i32) >> shift) as u32
+}
+
+/// The 6-bit signed immediate of the CI format.
+fn ci_imm(c: u32) -> u32 {
+    sign_extend(bit(c, 12) << 5 | bits(c, 6, 2), 6)
+}
+
+/// The word offset of C.LW and C.SW.
+fn lw_sw_offset(c: u32) -> u32 {
+    bits(c, 12, 10) << 3 | bit(c, 6) << 2 | bit(c, 5) << 6
+}
+
+/// The jump offset of C.J and C.JAL.
+fn cj_offset(c: u32) -> u32 {
+    sign_extend(
+        bit(c, 12) << 11
+            | bit(c, 11) << 4
+            | bits(c, 10, 9) << 8
+            | bit(c, 8) << 10
+            | bit(c, 7) << 6
+            | bit(c, 6) << 7
+            | bits(c, 5, 3) << 1
+            | bit(c, 2) << 5,
+        12,
+    )
+}
+
+/// The branch offset of C.BEQZ and C.BNEZ.
+fn cb_offset(c: u32) -> u32 {
+    sign_extend(
+        bit(c, 12) << 8
+            | bits(c, 11, 10) << 3
+            | bits(c, 6, 5) << 6
+            | bits(c, 4, 3) << 1
+            | bit(c, 2) << 5,
+        9,
+    )
+}
+
+fn r_type(funct7: u32, rs2: u32, rs1: u32, funct3: u32, rd: u32) -> u32 {
+    funct7 << 25 | rs2 << 20 | rs1 << 15 | funct3 << 12 | rd << 7 | OPCODE_OP
+}
+
+fn i_type(imm: u32, rs1: u32, funct3: u32, rd: u32, opcode: u32) -> u32 {
+    (imm & 0xfff) << 20 | rs1 << 15 | funct3 << 12 | rd << 7 | opcode
+}
+
+fn s_type(imm: u32, rs2: u32, rs1: u32, funct3: u32) -> u32 {
+    bits(imm, 11, 5) << 25
+        | rs2 << 20
+        | rs1 << 15
+        | funct3 << 12
+        | bits(imm, 4, 0) << 7
+        | OPCODE_STORE
+}
+
+fn b_type(imm: u32, rs1: u32, funct3: u32) -> u32 {
+    bit(imm, 12) << 31
+        | bits(imm, 10, 5) << 25
+        | rs1 << 15
+        | funct3 << 12
+        | bits(imm, 4, 1) << 8
+        | bit(imm, 11) << 7
+        | OPCODE_BRANCH
+}
+
+fn j_type(imm: u32, rd: u32) -> u32 {
+    bit(imm, 20) << 31
+        | bits(imm, 10, 1) << 21
+        | bit(imm, 11) << 20
+        | bits(imm, 19, 12) << 12
+        | rd << 7
+        | OPCODE_JAL
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decompress() {
+        // Each compressed instruction and its expansion, as assembled by llvm-mc.
+        let cases: &[(u16, u32, &str)] = &[
+            (0x0040, 0x00410413, "c.addi4spn s0, sp, 4"),
+            (0x4080, 0x0004a403, "c.lw s0, 0(s1)"),
+            (0xc0c0, 0x0084a223, "c.sw s0, 4(s1)"),
+            (0x0001, 0x00000013, "c.nop"),
+            (0x157d, 0xfff50513, "c.addi a0, -1"),
+            (0x2011, 0x004000ef, "c.jal 4"),
+            (0x4515, 0x00500513, "c.li a0, 5"),
+            (0x7139, 0xfc010113, "c.addi16sp sp, -64"),
+            (0x6505, 0x00001537, "c.lui a0, 1"),
+            (0x8405, 0x40145413, "c.srai s0, 1"),
+            (0x8005, 0x00145413, "c.srli s0, 1"),
+            (0x883d, 0x00f47413, "c.andi s0, 15"),
+            (0x8c05, 0x40940433, "c.sub s0, s1"),
+            (0x8c25, 0x00944433, "c.xor s0, s1"),
+            (0x8c45, 0x00946433, "c.or s0, s1"),
+            (0x8c65, 0x00947433, "c.and s0, s1"),
+            (0xbffd, 0xfffff06f, "c.j -2"),
+            (0xc011, 0x00040263, "c.beqz s0, 4"),
+            (0xfc6d, 0xfe041de3, "c.bnez s0, -6"),
+            (0x050a, 0x00251513, "c.slli a0, 2"),
+            (0x4532, 0x00c12503, "c.lwsp a0, 12(sp)"),
+            (0x8082, 0x00008067, "c.jr ra"),
+            (0x852e, 0x00b00533, "c.mv a0, a1"),
+            (0x9002, 0x00100073, "c.ebreak"),
+            (0x9502, 0x000500e7, "c.jalr a0"),
+            (0x952e, 0x00b50533, "c.add a0, a1"),
+            (0xc62a, 0x00a12623, "c.swsp a0, 12(sp)"),
+        ];
+        for &(compressed, expanded, asm) in cases {
+            assert!(is_compressed(compressed), "{}", asm);
+            assert_eq!(decompress(compressed), Some(expanded), "{}", asm);
+        }
+
+        // The all-zero halfword is defined to be illegal, and there are no floating point loads.
+        assert_eq!(decompress(0x0000), None);
+        assert_eq!(decompress(0x2000), None);
+        assert!(!is_compressed(0x0513));
+    }
+}
@@ -6,18 +6,25 @@ use elf::endian::LittleEndian;
 use elf::file::Class;
 use elf::ElfBytes;
 
+use super::{decompress, is_compressed, SymbolTable, UNIMP_INSTRUCTION};
+
 /// The maximum size of the memory in bytes.
 pub const MAXIMUM_MEMORY_SIZE: u32 = u32::MAX;
 
 /// The size of a word in bytes.
 pub const WORD_SIZE: usize = 4;
 
-/// A RV32IM ELF file.
+/// A RV32IM ELF file, optionally with compressed (RV32C) instructions.
 #[derive(Debug, Clone)]
 pub struct Elf {
     /// The instructions of the program encoded as 32-bits.
+    ///
+    /// Compressed instructions are expanded to the encoding of their RV32I equivalent.
     pub instructions: Vec<u32>,
 
+    /// Whether each instruction was a 16-bit compressed instruction.
+    pub compressed: Vec<bool>,
+
     /// The start address of the program.
     pub pc_start: u32,
 
@@ -26,21 +33,28 @@ pub struct Elf {
 
     /// The initial memory image, useful for global constants.
     pub memory_image: BTreeMap<u32, u32>,
+
+    /// The function symbols of the program.
+    pub symbols: SymbolTable,
 }
 
 impl Elf {
     /// Create a new ELF file.
     pub fn new(
         instructions: Vec<u32>,
+        compressed: Vec<bool>,
         pc_start: u32,
         pc_base: u32,
         memory_image: BTreeMap<u32, u32>,
+        symbols: SymbolTable,
     ) -> Self {
         Self {
             instructions,
+            compressed,
             pc_start,
             pc_base,
             memory_image,
+            symbols,
         }
     }
 
@@ -68,8 +82,9 @@ impl Elf {
             .try_into()
             .expect("e_entry was larger than 32 bits");
 
-        // Make sure the entrypoint is valid.
-        if entry == MAXIMUM_MEMORY_SIZE || entry % WORD_SIZE as u32 != 0 {
+        // Make sure the entrypoint is valid. Instructions are only halfword aligned, since they may
+        // be compressed.
+        if entry == MAXIMUM_MEMORY_SIZE || entry % 2 != 0 {
             panic!("invalid entrypoint");
         }
 
@@ -80,6 +95,7 @@ impl Elf {
         }
 
         let mut instructions: Vec<u32> = Vec::new();
+        let mut compressed: Vec<bool> = Vec::new();
         let mut base_address = u32::MAX;
 
         // Only read segments that are executable instructions that are also PT_LOAD.
@@ -123,7 +139,7 @@ impl Elf {
                 .try_into()
                 .expect("offset was larger than 32 bits");
 
-            // Read the segment and decode each word as an instruction.
+            // Read the segment into the memory image.
             for i in (0..mem_size).step_by(WORD_SIZE) {
                 let addr = vaddr.checked_add(i).expect("invalid segment vaddr");
                 if addr == MAXIMUM_MEMORY_SIZE {
@@ -145,12 +161,44 @@ impl Elf {
                     word |= (*byte as u32) << (j * 8);
                 }
                 image.insert(addr, word);
-                if (segment.p_flags & PF_X) != 0 {
-                    instructions.push(word);
+            }
+
+            // Decode the instructions of executable segments, which are either 16-bit compressed
+            // instructions or 32-bit instructions.
+            if (segment.p_flags & PF_X) != 0 {
+                let halfword = |i: u32| {
+                    (i..min(i + 2, file_size)).fold(0, |halfword, j| {
+                        let byte = input
+                            .get((offset + j) as usize)
+                            .expect("invalid segment offset");
+                        halfword | (*byte as u32) << ((j - i) * 8)
+                    })
+                };
+                let mut i = 0;
+                while i < file_size {
+                    let low = halfword(i);
+                    if is_compressed(low as u16) {
+                        instructions.push(decompress(low as u16).unwrap_or(UNIMP_INSTRUCTION));
+                        compressed.push(true);
+                        i += 2;
+                    } else {
+                        instructions.push(low | halfword(i + 2) << 16);
+                        compressed.push(false);
+                        i += 4;
+                    }
                 }
             }
         }
 
-        Elf::new(instructions, entry, base_address, image)
+        let symbols = SymbolTable::parse(&elf);
+
+        Elf::new(
+            instructions,
+            compressed,
+            entry,
+            base_address,
+            image,
+            symbols,
+        )
     }
 }
@@ -1,8 +1,12 @@
+mod compressed;
 mod elf;
 mod instruction;
+mod symbols;
 
+pub use compressed::*;
 pub use elf::*;
 pub use instruction::*;
+pub use symbols::*;
 
 use std::{collections::BTreeMap, fs::File, io::Read};
 
@@ -12,31 +16,38 @@ impl Program {
     /// Create a new program.
     pub fn new(instructions: Vec<Instruction>, pc_start: u32, pc_base: u32) -> Self {
         Self {
+            pc_index: Program::index_instructions(&instructions),
             instructions,
             pc_start,
             pc_base,
             memory_image: BTreeMap::new(),
+            symbols: SymbolTable::default(),
         }
     }
 
-    /// Disassemble a RV32IM ELF to a program that be executed by the VM.
+    /// Disassemble a RV32IM(C) ELF to a program that be executed by the VM.
     pub fn from(input: &[u8]) -> Self {
         // Decode the bytes as an ELF.
         let elf = Elf::decode(input);
 
-        // Transpile the RV32IM instructions.
-        let instructions = transpile(&elf.instructions);
+        // Transpile the RV32IM instructions, keeping track of which were compressed.
+        let mut instructions = transpile(&elf.instructions);
+        for (instruction, compressed) in instructions.iter_mut().zip(elf.compressed) {
+            instruction.compressed = compressed;
+        }
 
         // Return the program.
         Program {
+            pc_index: Program::index_instructions(&instructions),
             instructions,
             pc_start: elf.pc_start,
             pc_base: elf.pc_base,
             memory_image: elf.memory_image,
+            symbols: elf.symbols,
         }
     }
 
-    /// Disassemble a RV32IM ELF to a program that be executed by the VM from a file path.
+    /// Disassemble a RV32IM(C) ELF to a program that be executed by the VM from a file path.
     pub fn from_elf(path: &str) -> Self {
         let mut elf_code = Vec::new();
         File::open(path)
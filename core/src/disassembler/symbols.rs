@@ -0,0 +1,77 @@
+use elf::abi::STT_FUNC;
+use elf::endian::LittleEndian;
+use elf::ElfBytes;
+
+/// A function symbol from an ELF's symbol table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Symbol {
+    /// The demangled name of the function.
+    pub name: String,
+
+    /// The address of the first instruction of the function.
+    pub start: u32,
+
+    /// The size of the function in bytes, or zero if unknown.
+    pub size: u32,
+}
+
+/// The function symbols of an ELF, sorted by address.
+#[derive(Debug, Clone, Default)]
+pub struct SymbolTable {
+    symbols: Vec<Symbol>,
+}
+
+impl SymbolTable {
+    /// Read the function symbols of a parsed ELF. An ELF without a symbol table has no symbols.
+    pub fn parse(elf: &ElfBytes<LittleEndian>) -> Self {
+        let mut symbols = Vec::new();
+        if let Some((symtab, strtab)) = elf.symbol_table().expect("failed to parse symbol table") {
+            for symbol in symtab.iter() {
+                if symbol.st_symtype() != STT_FUNC || symbol.st_value == 0 {
+                    continue;
+                }
+                let name = strtab
+                    .get(symbol.st_name as usize)
+                    .expect("failed to parse symbol name");
+                symbols.push(Symbol {
+                    name: format!("{:#}", rustc_demangle::demangle(name)),
+                    start: symbol.st_value as u32,
+                    size: symbol.st_size as u32,
+                });
+            }
+        }
+        symbols.sort_by_key(|symbol| symbol.start);
+        symbols.dedup_by_key(|symbol| symbol.start);
+        Self { symbols }
+    }
+
+    /// Get the symbol at the given index.
+    pub fn get(&self, index: usize) -> &Symbol {
+        &self.symbols[index]
+    }
+
+    /// Iterate over the symbols in order of address.
+    pub fn iter(&self) -> impl Iterator<Item = &Symbol> {
+        self.symbols.iter()
+    }
+
+    /// The index of the function containing `pc`, if any.
+    pub fn index_of(&self, pc: u32) -> Option<usize> {
+        let index = self.symbols.partition_point(|symbol| symbol.start <= pc);
+        let index = index.checked_sub(1)?;
+        let symbol = &self.symbols[index];
+        (symbol.size == 0 || pc < symbol.start + symbol.size).then_some(index)
+    }
+
+    /// The function containing `pc`, if any.
+    pub fn lookup(&self, pc: u32) -> Option<&Symbol> {
+        self.index_of(pc).map(|index| &self.symbols[index])
+    }
+
+    /// Whether `pc` is the first instruction of a function.
+    pub fn is_function_start(&self, pc: u32) -> bool {
+        self.symbols
+            .binary_search_by_key(&pc, |symbol| symbol.start)
+            .is_ok()
+    }
+}
@@ -1,5 +1,6 @@
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
+use crate::runtime::GuestEnv;
 use crate::utils::Buffer;
 
 /// Standard input for the prover.
@@ -9,6 +10,9 @@ pub struct SP1Stdin {
     /// a vec of bytes at a time.
     pub buffer: Vec<Vec<u8>>,
     pub ptr: usize,
+    /// The environment variables, arguments, random seed and files of the guest.
+    #[serde(default)]
+    pub env: GuestEnv,
 }
 
 /// Public values for the prover.
@@ -23,6 +27,7 @@ impl SP1Stdin {
         Self {
             buffer: Vec::new(),
             ptr: 0,
+            env: GuestEnv::default(),
         }
     }
 
@@ -31,6 +36,7 @@ impl SP1Stdin {
         Self {
             buffer: vec![data.to_vec()],
             ptr: 0,
+            env: GuestEnv::default(),
         }
     }
 
@@ -63,6 +69,48 @@ impl SP1Stdin {
     pub fn write_vec(&mut self, vec: Vec<u8>) {
         self.buffer.push(vec);
     }
+
+    /// Set an environment variable of the guest, read with `std::env::var`.
+    pub fn set_env_var(&mut self, name: &str, value: &str) {
+        self.env.vars.insert(name.to_string(), value.to_string());
+    }
+
+    /// Set the arguments of the guest, read with `std::env::args`. By convention, the first one is
+    /// the program name.
+    pub fn set_args<S: AsRef<str>>(&mut self, args: &[S]) {
+        self.env.args = args.iter().map(|arg| arg.as_ref().to_string()).collect();
+    }
+
+    /// Have the guest commit its environment variables and arguments to its public values before
+    /// running, so the verifier can check them. They are read back first with
+    /// `public_values.read::<CommittedEnv>()`.
+    pub fn commit_env(&mut self) {
+        self.env.commit = true;
+    }
+
+    /// Seed the random number generator behind the guest's `getrandom`, which makes randomized
+    /// programs reproducible for a given seed.
+    pub fn set_random_seed(&mut self, seed: [u8; 32]) {
+        self.env.random_seed = Some(seed);
+    }
+
+    /// Have the guest commit its random seed to its public values before running. It is read back
+    /// first with `public_values.read::<CommittedEnv>()`.
+    pub fn commit_random_seed(&mut self) {
+        self.env.commit_random_seed = true;
+    }
+
+    /// Add a file to the read-only filesystem of the guest, read with `sp1_zkvm::fs`.
+    pub fn add_file(&mut self, path: &str, contents: Vec<u8>) {
+        self.env.files.insert(path.to_string(), contents);
+    }
+
+    /// Have the guest commit the SHA-256 hash of each of its files to its public values before
+    /// running, and check the files it opens against them. They are read back first with
+    /// `public_values.read::<CommittedEnv>()`.
+    pub fn commit_file_hashes(&mut self) {
+        self.env.commit_file_hashes = true;
+    }
 }
 
 impl SP1PublicValues {
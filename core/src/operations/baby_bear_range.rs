@@ -0,0 +1,79 @@
+use p3_air::AirBuilder;
+use p3_field::AbstractField;
+use p3_field::Field;
+use sp1_derive::AlignedBorrow;
+
+use crate::air::SP1AirBuilder;
+use crate::air::Word;
+use crate::bytes::ByteLookupEvent;
+use crate::bytes::ByteOpcode;
+use crate::runtime::ExecutionRecord;
+
+/// The most significant byte of the largest canonical BabyBear element, `p - 1 = 0x78000000`.
+const BABYBEAR_MAX_TOP_BYTE: u8 = 0x78;
+
+/// A set of columns needed to check that a word is the canonical encoding of a BabyBear element,
+/// i.e. that it is at most `p - 1 = 0x78000000`.
+#[derive(AlignedBorrow, Default, Debug, Clone, Copy)]
+#[repr(C)]
+pub struct BabyBearRangeCheckOperation<T> {
+    /// Whether the most significant byte is `0x78`, in which case the other bytes must be zero.
+    pub is_top: T,
+
+    /// Whether the most significant byte is less than `0x78`.
+    pub is_lt: T,
+}
+
+impl<F: Field> BabyBearRangeCheckOperation<F> {
+    pub fn populate(&mut self, record: &mut ExecutionRecord, shard: u32, value: u32) {
+        let bytes = value.to_le_bytes();
+        if bytes[3] == BABYBEAR_MAX_TOP_BYTE {
+            debug_assert_eq!(bytes[..3], [0, 0, 0], "{} is not a BabyBear element", value);
+            self.is_top = F::one();
+        } else {
+            debug_assert!(bytes[3] < BABYBEAR_MAX_TOP_BYTE);
+            self.is_lt = F::one();
+            record.add_byte_lookup_event(ByteLookupEvent::new(
+                shard,
+                ByteOpcode::LTU,
+                1,
+                0,
+                bytes[3] as u32,
+                BABYBEAR_MAX_TOP_BYTE as u32,
+            ));
+        }
+        record.add_u8_range_checks(shard, &bytes);
+    }
+
+    pub fn eval<AB: SP1AirBuilder>(
+        builder: &mut AB,
+        value: Word<AB::Var>,
+        cols: BabyBearRangeCheckOperation<AB::Var>,
+        shard: AB::Var,
+        is_real: AB::Expr,
+    ) {
+        builder.assert_bool(cols.is_top);
+        builder.assert_bool(cols.is_lt);
+        builder.assert_eq(cols.is_top + cols.is_lt, is_real.clone());
+
+        // If the most significant byte is 0x78, the word must be exactly 0x78000000.
+        builder
+            .when(cols.is_top)
+            .assert_eq(value[3], AB::F::from_canonical_u8(BABYBEAR_MAX_TOP_BYTE));
+        for i in 0..3 {
+            builder.when(cols.is_top).assert_zero(value[i]);
+        }
+
+        // Otherwise, the most significant byte must be less than 0x78.
+        builder.send_byte(
+            AB::F::from_canonical_u32(ByteOpcode::LTU as u32),
+            AB::F::one(),
+            value[3],
+            AB::F::from_canonical_u8(BABYBEAR_MAX_TOP_BYTE),
+            shard,
+            cols.is_lt,
+        );
+
+        builder.slice_range_check_u8(&value.0, shard, is_real);
+    }
+}
@@ -79,18 +79,44 @@ impl<F: PrimeField32, P: FieldParameters> FieldOpCols<F, P> {
             return result;
         }
 
+        let (result, carry) = self.populate_carry(a, b, &modulus, op);
+        debug_assert!(&carry < &modulus);
+        result
+    }
+
+    /// Populates the columns for `a op b` modulo a caller-supplied modulus rather than
+    /// `P::modulus()`. Only `Add` and `Mul` are supported, and the quotient `(a op b) / modulus`
+    /// must fit in `P::NB_LIMBS` limbs.
+    pub fn populate_with_modulus(
+        &mut self,
+        a: &BigUint,
+        b: &BigUint,
+        modulus: &BigUint,
+        op: FieldOperation,
+    ) -> BigUint {
+        let (result, carry) = self.populate_carry(a, b, modulus, op);
+        debug_assert!(carry.bits() as usize <= P::nb_bits());
+        result
+    }
+
+    /// Populates the columns for `a op b` modulo `modulus`, returning the result and the carry.
+    fn populate_carry(
+        &mut self,
+        a: &BigUint,
+        b: &BigUint,
+        modulus: &BigUint,
+        op: FieldOperation,
+    ) -> (BigUint, BigUint) {
         let p_a: Polynomial<F> = P::to_limbs_field::<F, _>(a).into();
         let p_b: Polynomial<F> = P::to_limbs_field::<F, _>(b).into();
 
         // Compute field addition in the integers.
-        let modulus = &P::modulus();
         let (result, carry) = match op {
             FieldOperation::Add => ((a + b) % modulus, (a + b - (a + b) % modulus) / modulus),
             FieldOperation::Mul => ((a * b) % modulus, (a * b - (a * b) % modulus) / modulus),
             FieldOperation::Sub | FieldOperation::Div => unreachable!(),
         };
         debug_assert!(&result < modulus);
-        debug_assert!(&carry < modulus);
         match op {
             FieldOperation::Add => debug_assert_eq!(&carry * modulus, a + b - &result),
             FieldOperation::Mul => debug_assert_eq!(&carry * modulus, a * b - &result),
@@ -98,7 +124,10 @@ impl<F: PrimeField32, P: FieldParameters> FieldOpCols<F, P> {
         }
 
         // Make little endian polynomial limbs.
-        let p_modulus: Polynomial<F> = P::to_limbs_field::<F, _>(modulus).into();
+        let p_modulus: Polynomial<F> = modulus_limbs::<P>(modulus)
+            .into_iter()
+            .map(F::from_canonical_u8)
+            .collect();
         let p_result: Polynomial<F> = P::to_limbs_field::<F, _>(&result).into();
         let p_carry: Polynomial<F> = P::to_limbs_field::<F, _>(&carry).into();
 
@@ -124,10 +153,21 @@ impl<F: PrimeField32, P: FieldParameters> FieldOpCols<F, P> {
         self.witness_low = Limbs(p_witness_low.try_into().unwrap());
         self.witness_high = Limbs(p_witness_high.try_into().unwrap());
 
-        result
+        (result, carry)
     }
 }
 
+/// Returns the little-endian limbs of a modulus, padded so that `carry * modulus` has degree
+/// `P::NB_WITNESS_LIMBS`. This is one more limb than `P::NB_LIMBS` for fields whose witness is
+/// sized for moduli of up to `2^(P::nb_bits())`.
+pub fn modulus_limbs<P: FieldParameters>(modulus: &BigUint) -> Vec<u8> {
+    let mut bytes = modulus.to_bytes_le();
+    let len = P::NB_WITNESS_LIMBS - P::NB_LIMBS + 2;
+    debug_assert!(bytes.len() <= len);
+    bytes.resize(len, 0u8);
+    bytes
+}
+
 impl<V: Copy, P: FieldParameters> FieldOpCols<V, P> {
     #[allow(unused_variables)]
     pub fn eval<
@@ -143,6 +183,27 @@ impl<V: Copy, P: FieldParameters> FieldOpCols<V, P> {
     ) where
         V: Into<AB::Expr>,
         Limbs<V, P::Limbs>: Copy,
+    {
+        let p_limbs = Polynomial::from_iter(P::modulus_field_iter::<AB::F>().map(AB::Expr::from));
+        self.eval_with_modulus::<AB, A, B>(builder, a, b, &p_limbs, op);
+    }
+
+    /// Evaluates `a op b` modulo the given modulus polynomial, whose limbs are laid out as in
+    /// [`modulus_limbs`].
+    pub fn eval_with_modulus<
+        AB: SP1AirBuilder<Var = V>,
+        A: Into<Polynomial<AB::Expr>> + Clone,
+        B: Into<Polynomial<AB::Expr>> + Clone,
+    >(
+        &self,
+        builder: &mut AB,
+        a: &A,
+        b: &B,
+        modulus: &Polynomial<AB::Expr>,
+        op: FieldOperation,
+    ) where
+        V: Into<AB::Expr>,
+        Limbs<V, P::Limbs>: Copy,
     {
         let p_a_param: Polynomial<AB::Expr> = (*a).clone().into();
         let p_b: Polynomial<AB::Expr> = (*b).clone().into();
@@ -157,8 +218,7 @@ impl<V: Copy, P: FieldParameters> FieldOpCols<V, P> {
             FieldOperation::Mul | FieldOperation::Div => p_a * p_b,
         };
         let p_op_minus_result: Polynomial<AB::Expr> = p_op - p_result;
-        let p_limbs = Polynomial::from_iter(P::modulus_field_iter::<AB::F>().map(AB::Expr::from));
-        let p_vanishing = p_op_minus_result - &(&p_carry * &p_limbs);
+        let p_vanishing = p_op_minus_result - &(&p_carry * modulus);
         let p_witness_low = self.witness_low.0.iter().into();
         let p_witness_high = self.witness_high.0.iter().into();
         eval_field_operation::<AB, P>(builder, &p_vanishing, &p_witness_low, &p_witness_high);
@@ -0,0 +1,90 @@
+use p3_air::AirBuilder;
+use p3_field::AbstractField;
+use p3_field::Field;
+use sp1_derive::AlignedBorrow;
+
+use super::U64_SIZE;
+use crate::air::SP1AirBuilder;
+use crate::runtime::ExecutionRecord;
+
+/// A set of columns needed to compute the wrapping sum of up to 256 64-bit values.
+///
+/// Since every byte of the operands, the result and the carries is range checked, the constraint
+/// `sum of operand bytes + carry[i - 1] = value[i] + 256 * carry[i]` cannot wrap around the field
+/// and so determines `value` and `carry` uniquely.
+#[derive(AlignedBorrow, Default, Debug, Clone, Copy)]
+#[repr(C)]
+pub struct AddU64Operation<T> {
+    /// The result of the sum of the operands.
+    pub value: [T; U64_SIZE],
+
+    /// The carry for the `i`th byte.
+    pub carry: [T; U64_SIZE],
+}
+
+impl<F: Field> AddU64Operation<F> {
+    pub fn populate(&mut self, record: &mut ExecutionRecord, shard: u32, operands: &[u64]) -> u64 {
+        let expected = operands.iter().fold(0u64, |acc, x| acc.wrapping_add(*x));
+
+        let base = 256;
+        let mut carry = [0u8; U64_SIZE];
+        for i in 0..U64_SIZE {
+            let mut res = operands
+                .iter()
+                .map(|x| x.to_le_bytes()[i] as u32)
+                .sum::<u32>();
+            if i > 0 {
+                res += carry[i - 1] as u32;
+            }
+            carry[i] = (res / base) as u8;
+            self.value[i] = F::from_canonical_u32(res % base);
+            self.carry[i] = F::from_canonical_u8(carry[i]);
+        }
+        debug_assert_eq!(self.value, super::u64_to_bytes::<F>(expected));
+
+        // Range check.
+        {
+            for operand in operands.iter() {
+                record.add_u8_range_checks(shard, &operand.to_le_bytes());
+            }
+            record.add_u8_range_checks(shard, &expected.to_le_bytes());
+            record.add_u8_range_checks(shard, &carry);
+        }
+        expected
+    }
+
+    pub fn eval<AB: SP1AirBuilder>(
+        builder: &mut AB,
+        operands: &[[AB::Var; U64_SIZE]],
+        shard: AB::Var,
+        is_real: AB::Var,
+        cols: AddU64Operation<AB::Var>,
+    ) {
+        // Range check each byte.
+        {
+            for operand in operands.iter() {
+                builder.slice_range_check_u8(operand, shard, is_real);
+            }
+            builder.slice_range_check_u8(&cols.value, shard, is_real);
+            builder.slice_range_check_u8(&cols.carry, shard, is_real);
+        }
+
+        builder.assert_bool(is_real);
+
+        // For each byte, assert that the difference between the carried result and the
+        // non-carried result is the product of carry and base.
+        let base = AB::F::from_canonical_u32(256);
+        for i in 0..U64_SIZE {
+            let mut overflow: AB::Expr = operands
+                .iter()
+                .fold(AB::Expr::zero(), |acc, operand| acc + operand[i])
+                - cols.value[i];
+            if i > 0 {
+                overflow += cols.carry[i - 1].into();
+            }
+            builder
+                .when(is_real)
+                .assert_eq(cols.carry[i] * base, overflow);
+        }
+    }
+}
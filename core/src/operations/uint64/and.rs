@@ -0,0 +1,60 @@
+use p3_field::AbstractField;
+use p3_field::Field;
+use sp1_derive::AlignedBorrow;
+
+use super::U64_SIZE;
+use crate::air::SP1AirBuilder;
+use crate::bytes::ByteLookupEvent;
+use crate::bytes::ByteOpcode;
+use crate::runtime::ExecutionRecord;
+
+/// A set of columns needed to compute the and of two 64-bit values.
+#[derive(AlignedBorrow, Default, Debug, Clone, Copy)]
+#[repr(C)]
+pub struct AndU64Operation<T> {
+    /// The result of `x & y`.
+    pub value: [T; U64_SIZE],
+}
+
+impl<F: Field> AndU64Operation<F> {
+    pub fn populate(&mut self, record: &mut ExecutionRecord, shard: u32, x: u64, y: u64) -> u64 {
+        let expected = x & y;
+        let x_bytes = x.to_le_bytes();
+        let y_bytes = y.to_le_bytes();
+        for i in 0..U64_SIZE {
+            let and = x_bytes[i] & y_bytes[i];
+            self.value[i] = F::from_canonical_u8(and);
+
+            let byte_event = ByteLookupEvent {
+                shard,
+                opcode: ByteOpcode::AND,
+                a1: and as u32,
+                a2: 0,
+                b: x_bytes[i] as u32,
+                c: y_bytes[i] as u32,
+            };
+            record.add_byte_lookup_event(byte_event);
+        }
+        expected
+    }
+
+    pub fn eval<AB: SP1AirBuilder>(
+        builder: &mut AB,
+        a: [AB::Var; U64_SIZE],
+        b: [AB::Var; U64_SIZE],
+        cols: AndU64Operation<AB::Var>,
+        shard: AB::Var,
+        is_real: AB::Var,
+    ) {
+        for i in 0..U64_SIZE {
+            builder.send_byte(
+                AB::F::from_canonical_u32(ByteOpcode::AND as u32),
+                cols.value[i],
+                a[i],
+                b[i],
+                shard,
+                is_real,
+            );
+        }
+    }
+}
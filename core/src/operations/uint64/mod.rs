@@ -0,0 +1,35 @@
+//! Operations on 64-bit values, used by precompiles for hash functions with 64-bit words.
+//!
+//! A 64-bit value is represented as its 8 little-endian bytes. In memory, this is the low word
+//! followed by the high word.
+
+mod add;
+mod and;
+mod fixed_rotate_right;
+mod fixed_shift_right;
+mod not;
+mod xor;
+
+pub use add::*;
+pub use and::*;
+pub use fixed_rotate_right::*;
+pub use fixed_shift_right::*;
+pub use not::*;
+pub use xor::*;
+
+use p3_field::Field;
+
+use crate::air::Word;
+
+/// The number of bytes in a 64-bit value.
+pub const U64_SIZE: usize = 8;
+
+/// Converts a u64 into its little-endian field bytes.
+pub fn u64_to_bytes<F: Field>(value: u64) -> [F; U64_SIZE] {
+    value.to_le_bytes().map(F::from_canonical_u8)
+}
+
+/// Concatenates the low and high words of a 64-bit value.
+pub fn u64_from_words<T: Copy>(lo: &Word<T>, hi: &Word<T>) -> [T; U64_SIZE] {
+    [lo[0], lo[1], lo[2], lo[3], hi[0], hi[1], hi[2], hi[3]]
+}
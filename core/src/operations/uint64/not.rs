@@ -0,0 +1,56 @@
+use p3_air::AirBuilder;
+use p3_field::AbstractField;
+use p3_field::Field;
+use sp1_derive::AlignedBorrow;
+
+use super::U64_SIZE;
+use crate::air::SP1AirBuilder;
+use crate::bytes::ByteOpcode;
+use crate::runtime::ExecutionRecord;
+
+/// A set of columns needed to compute the not of a 64-bit value.
+#[derive(AlignedBorrow, Default, Debug, Clone, Copy)]
+#[repr(C)]
+pub struct NotU64Operation<T> {
+    /// The result of `!x`.
+    pub value: [T; U64_SIZE],
+}
+
+impl<F: Field> NotU64Operation<F> {
+    pub fn populate(&mut self, record: &mut ExecutionRecord, shard: u32, x: u64) -> u64 {
+        let expected = !x;
+        let x_bytes = x.to_le_bytes();
+        for i in 0..U64_SIZE {
+            self.value[i] = F::from_canonical_u8(!x_bytes[i]);
+        }
+        record.add_u8_range_checks(shard, &x_bytes);
+        expected
+    }
+
+    pub fn eval<AB: SP1AirBuilder>(
+        builder: &mut AB,
+        a: [AB::Var; U64_SIZE],
+        cols: NotU64Operation<AB::Var>,
+        shard: AB::Var,
+        is_real: AB::Var,
+    ) {
+        for i in (0..U64_SIZE).step_by(2) {
+            builder.send_byte_pair(
+                AB::F::from_canonical_u32(ByteOpcode::U8Range as u32),
+                AB::F::zero(),
+                AB::F::zero(),
+                a[i],
+                a[i + 1],
+                shard,
+                is_real,
+            );
+        }
+
+        // For any byte b, b + !b = 0xFF.
+        for i in 0..U64_SIZE {
+            builder
+                .when(is_real)
+                .assert_eq(cols.value[i] + a[i], AB::F::from_canonical_u8(u8::MAX));
+        }
+    }
+}
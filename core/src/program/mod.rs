@@ -64,11 +64,9 @@ impl<F: PrimeField> MachineAir<F> for ProgramChip {
     fn generate_preprocessed_trace(&self, program: &Self::Program) -> Option<RowMajorMatrix<F>> {
         let rows = program
             .instructions
-            .clone()
-            .into_iter()
-            .enumerate()
-            .map(|(i, instruction)| {
-                let pc = program.pc_base + (i as u32 * 4);
+            .iter()
+            .zip(program.instruction_pcs())
+            .map(|(&instruction, pc)| {
                 let mut row = [F::zero(); NUM_PROGRAM_PREPROCESSED_COLS];
                 let cols: &mut ProgramPreprocessedCols<F> = row.as_mut_slice().borrow_mut();
                 cols.pc = F::from_canonical_u32(pc);
@@ -115,12 +113,8 @@ impl<F: PrimeField> MachineAir<F> for ProgramChip {
 
         let rows = input
             .program
-            .instructions
-            .clone()
-            .into_iter()
-            .enumerate()
-            .map(|(i, _)| {
-                let pc = input.program.pc_base + (i as u32 * 4);
+            .instruction_pcs()
+            .map(|pc| {
                 let mut row = [F::zero(); NUM_PROGRAM_MULT_COLS];
                 let cols: &mut ProgramMultiplicityCols<F> = row.as_mut_slice().borrow_mut();
                 cols.shard = F::from_canonical_u32(input.index);
@@ -215,6 +209,8 @@ mod tests {
                 pc_start: 0,
                 pc_base: 0,
                 memory_image: BTreeMap::new(),
+                symbols: Default::default(),
+                pc_index: Vec::new(),
             }),
             ..Default::default()
         };
@@ -0,0 +1,166 @@
+use std::collections::{BTreeMap, HashMap};
+use std::io::{self, Write};
+
+use addr2line::object;
+
+use crate::disassembler::{Elf, SymbolTable};
+
+/// Records which instructions of a guest program are executed, and reports the coverage of its
+/// source lines as an lcov tracefile.
+///
+/// Instructions are mapped to source lines through the DWARF line table of the ELF, so line
+/// coverage is only available for ELFs built with debug info. Instruction coverage is always
+/// available.
+pub struct Coverage {
+    pc_base: u32,
+
+    /// The number of times each instruction was executed, indexed by `(pc - pc_base) / 2` since
+    /// instructions may be compressed.
+    hits: Vec<u64>,
+
+    /// The number of instructions of the program.
+    num_instructions: usize,
+
+    /// The source files of the program.
+    files: Vec<String>,
+
+    /// The file index and line of each instruction which has a line table entry.
+    lines: HashMap<u32, (usize, u32)>,
+
+    symbols: SymbolTable,
+}
+
+impl Coverage {
+    /// Create a coverage recorder for the program of the given ELF.
+    pub fn new(elf_bytes: &[u8]) -> Self {
+        let elf = Elf::decode(elf_bytes);
+        let code_size: u64 = elf.compressed.iter().map(|&c| if c { 2 } else { 4 }).sum();
+        let pc_end = elf.pc_base as u64 + code_size;
+
+        let mut files = Vec::new();
+        let mut file_indices = HashMap::new();
+        let mut lines = HashMap::new();
+        // Debug info is optional, so an ELF without it only loses the line coverage.
+        let dwarf = object::File::parse(elf_bytes)
+            .ok()
+            .and_then(|file| addr2line::Context::new(&file).ok());
+        if let Some(dwarf) = dwarf {
+            if let Ok(ranges) = dwarf.find_location_range(elf.pc_base as u64, pc_end) {
+                for (start, len, location) in ranges {
+                    let (Some(file), Some(line)) = (location.file, location.line) else {
+                        continue;
+                    };
+                    let file = *file_indices.entry(file.to_string()).or_insert_with(|| {
+                        files.push(file.to_string());
+                        files.len() - 1
+                    });
+                    let start = start.max(elf.pc_base as u64);
+                    let end = (start + len).min(pc_end);
+                    for pc in (start..end).step_by(2) {
+                        lines.insert(pc as u32, (file, line));
+                    }
+                }
+            }
+        }
+
+        Self {
+            pc_base: elf.pc_base,
+            hits: vec![0; code_size as usize / 2],
+            num_instructions: elf.instructions.len(),
+            files,
+            lines,
+            symbols: elf.symbols,
+        }
+    }
+
+    /// Record the execution of the instruction at `pc`.
+    #[inline]
+    pub fn record(&mut self, pc: u32) {
+        let index = (pc.wrapping_sub(self.pc_base) / 2) as usize;
+        if let Some(hits) = self.hits.get_mut(index) {
+            *hits += 1;
+        }
+    }
+
+    /// The number of instructions executed at least once, and the number of instructions.
+    pub fn instruction_coverage(&self) -> (usize, usize) {
+        let executed = self.hits.iter().filter(|&&hits| hits > 0).count();
+        (executed, self.num_instructions)
+    }
+
+    fn hits(&self, pc: u32) -> u64 {
+        let index = (pc.wrapping_sub(self.pc_base) / 2) as usize;
+        self.hits.get(index).copied().unwrap_or_default()
+    }
+
+    /// Write the line and function coverage as an lcov tracefile.
+    ///
+    /// The count of a line is the largest number of times one of its instructions was executed.
+    pub fn write_lcov<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        let mut line_hits = vec![BTreeMap::<u32, u64>::new(); self.files.len()];
+        for (&pc, &(file, line)) in self.lines.iter() {
+            let hits = line_hits[file].entry(line).or_default();
+            *hits = (*hits).max(self.hits(pc));
+        }
+
+        // Functions are attributed to the file and line of their first instruction.
+        let mut functions = vec![Vec::new(); self.files.len()];
+        for symbol in self.symbols.iter() {
+            if let Some(&(file, line)) = self.lines.get(&symbol.start) {
+                functions[file].push((line, symbol.name.as_str(), self.hits(symbol.start)));
+            }
+        }
+
+        writeln!(writer, "TN:")?;
+        for (file, path) in self.files.iter().enumerate() {
+            writeln!(writer, "SF:{}", path)?;
+            for (line, name, _) in functions[file].iter() {
+                writeln!(writer, "FN:{},{}", line, name)?;
+            }
+            for (_, name, hits) in functions[file].iter() {
+                writeln!(writer, "FNDA:{},{}", hits, name)?;
+            }
+            writeln!(writer, "FNF:{}", functions[file].len())?;
+            let functions_hit = functions[file].iter().filter(|(_, _, hits)| *hits > 0);
+            writeln!(writer, "FNH:{}", functions_hit.count())?;
+            for (line, hits) in line_hits[file].iter() {
+                writeln!(writer, "DA:{},{}", line, hits)?;
+            }
+            writeln!(writer, "LF:{}", line_hits[file].len())?;
+            let lines_hit = line_hits[file].values().filter(|&&hits| hits > 0);
+            writeln!(writer, "LH:{}", lines_hit.count())?;
+            writeln!(writer, "end_of_record")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::runtime::{Program, Runtime};
+    use crate::utils::tests::FIBONACCI_ELF;
+
+    #[test]
+    fn test_coverage_fibonacci() {
+        let mut runtime = Runtime::new(Program::from(FIBONACCI_ELF));
+        runtime.coverage = Some(Coverage::new(FIBONACCI_ELF));
+        runtime.run();
+        let coverage = runtime.coverage.take().unwrap();
+
+        let (executed, total) = coverage.instruction_coverage();
+        assert!(executed > 0 && executed < total);
+
+        let mut lcov = Vec::new();
+        coverage.write_lcov(&mut lcov).unwrap();
+        let lcov = String::from_utf8(lcov).unwrap();
+        assert_eq!(
+            lcov.matches("SF:").count(),
+            lcov.matches("end_of_record").count()
+        );
+        for line in lcov.lines().filter(|line| line.starts_with("DA:")) {
+            let (_, hits) = line.rsplit_once(',').unwrap();
+            hits.parse::<u64>().unwrap();
+        }
+    }
+}
@@ -0,0 +1,313 @@
+//! Differential testing of the runtime against the reference RV32IM executor of `rrs-lib`.
+//!
+//! A program is run in both in lockstep, and the pc, the registers and the memory written by each
+//! instruction are compared after every step. Syscalls and unconstrained blocks have no reference
+//! semantics, so the reference state is synced from the runtime after them.
+
+use std::collections::HashMap;
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use rrs_lib::instruction_executor::InstructionExecutor;
+use rrs_lib::{HartState, MemAccessSize, Memory};
+
+use super::{Opcode, Program, Runtime};
+use crate::disassembler::transpile;
+use crate::utils::tests::FIBONACCI_ELF;
+
+/// The memory of the reference executor, which records the words written by each step.
+#[derive(Default)]
+struct ReferenceMemory {
+    words: HashMap<u32, u32>,
+    writes: Vec<u32>,
+}
+
+impl ReferenceMemory {
+    fn word(&self, addr: u32) -> u32 {
+        self.words.get(&addr).copied().unwrap_or_default()
+    }
+
+    /// Copies the memory of the runtime, skipping the registers which live at the bottom of it.
+    fn sync(&mut self, runtime: &Runtime) {
+        for (&addr, record) in runtime.state.memory.iter() {
+            if addr >= 32 {
+                self.words.insert(addr, record.value);
+            }
+        }
+    }
+}
+
+fn access_mask(size: MemAccessSize) -> u32 {
+    match size {
+        MemAccessSize::Byte => 0xff,
+        MemAccessSize::HalfWord => 0xffff,
+        MemAccessSize::Word => 0xffff_ffff,
+    }
+}
+
+impl Memory for ReferenceMemory {
+    fn read_mem(&mut self, addr: u32, size: MemAccessSize) -> Option<u32> {
+        let shift = (addr % 4) * 8;
+        Some((self.word(addr - addr % 4) >> shift) & access_mask(size))
+    }
+
+    fn write_mem(&mut self, addr: u32, size: MemAccessSize, store_data: u32) -> bool {
+        let (addr, shift) = (addr - addr % 4, (addr % 4) * 8);
+        let mask = access_mask(size) << shift;
+        let word = self.word(addr);
+        self.words
+            .insert(addr, (word & !mask) | ((store_data << shift) & mask));
+        self.writes.push(addr);
+        true
+    }
+}
+
+/// Runs the program in the runtime and in the reference executor in lockstep, panicking at the
+/// first instruction after which they disagree.
+fn run_lockstep(program: Program) {
+    let mut memory = ReferenceMemory::default();
+    memory.words.extend(program.memory_image.iter());
+    let mut hart = HartState::new();
+    hart.pc = program.pc_start;
+
+    let mut runtime = Runtime::new(program);
+    runtime.emit_events = false;
+    runtime.initialize();
+
+    loop {
+        let pc = runtime.state.pc;
+        let instruction = runtime.fetch();
+        let store_addr =
+            matches!(instruction.opcode, Opcode::SB | Opcode::SH | Opcode::SW).then(|| {
+                let (_, rs1, imm) = instruction.s_type();
+                let addr = runtime.register(rs1).wrapping_add(imm);
+                addr - addr % 4
+            });
+        let unconstrained = runtime.unconstrained;
+
+        let done = runtime.execute_cycle();
+
+        if unconstrained || runtime.unconstrained || instruction.is_ecall_instruction() {
+            memory.sync(&runtime);
+            hart.registers = runtime.registers();
+            hart.pc = runtime.state.pc;
+        } else {
+            memory.writes.clear();
+            let mut executor = InstructionExecutor {
+                hart_state: &mut hart,
+                mem: &mut memory,
+            };
+            if let Err(e) = executor.step() {
+                panic!(
+                    "reference failed at pc 0x{:08x} ({:?}): {:?}",
+                    pc, instruction, e
+                );
+            }
+
+            let context = format!("after pc 0x{:08x} ({:?})", pc, instruction);
+            assert_eq!(runtime.state.pc, hart.pc, "pc differs {}", context);
+            assert_eq!(
+                runtime.registers(),
+                hart.registers,
+                "registers differ {}",
+                context
+            );
+            for addr in memory.writes.iter().copied().chain(store_addr) {
+                assert_eq!(
+                    runtime.word(addr),
+                    memory.word(addr),
+                    "memory at 0x{:08x} differs {}",
+                    addr,
+                    context
+                );
+            }
+        }
+
+        if done {
+            break;
+        }
+    }
+}
+
+/// The base address of random programs, which is held in `x30` as the base of their jalrs.
+const PC_BASE: u32 = 0x1000;
+
+/// The address of the scratch memory used by the loads and stores of random programs, which is
+/// held in `x31`.
+const SCRATCH_ADDR: u32 = 0x1_0000;
+
+/// The size of the scratch memory, kept small so that loads often read back earlier stores.
+const SCRATCH_SIZE: u32 = 64;
+
+/// Register values which exercise the sign-extension, shift and division edge cases.
+const EDGE_VALUES: [u32; 14] = [
+    0,
+    1,
+    2,
+    31,
+    32,
+    0x80,
+    0xff,
+    0x8000,
+    0xffff,
+    0x7fff_ffff,
+    0x8000_0000,
+    0x8000_0001,
+    0xffff_fffe,
+    0xffff_ffff,
+];
+
+fn r_type(funct7: u32, rs2: u32, rs1: u32, funct3: u32, rd: u32) -> u32 {
+    funct7 << 25 | rs2 << 20 | rs1 << 15 | funct3 << 12 | rd << 7 | 0b011_0011
+}
+
+fn i_type(imm: u32, rs1: u32, funct3: u32, rd: u32, opcode: u32) -> u32 {
+    (imm & 0xfff) << 20 | rs1 << 15 | funct3 << 12 | rd << 7 | opcode
+}
+
+fn s_type(imm: u32, rs2: u32, rs1: u32, funct3: u32) -> u32 {
+    (imm >> 5) << 25 | rs2 << 20 | rs1 << 15 | funct3 << 12 | (imm & 0x1f) << 7 | 0b010_0011
+}
+
+/// Encodes a forward branch by `imm` bytes, which must be less than 2^11.
+fn b_type(imm: u32, rs2: u32, rs1: u32, funct3: u32) -> u32 {
+    ((imm >> 5) & 0x3f) << 25
+        | rs2 << 20
+        | rs1 << 15
+        | funct3 << 12
+        | ((imm >> 1) & 0xf) << 8
+        | ((imm >> 11) & 1) << 7
+        | 0b110_0011
+}
+
+fn u_type(imm: u32, rd: u32, opcode: u32) -> u32 {
+    (imm & 0xffff_f000) | rd << 7 | opcode
+}
+
+/// Encodes a forward jump by `imm` bytes, which must be less than 2^11.
+fn j_type(imm: u32, rd: u32) -> u32 {
+    ((imm >> 1) & 0x3ff) << 21 | ((imm >> 11) & 1) << 20 | rd << 7 | 0b110_1111
+}
+
+/// Generates a random program of about `len` RV32IM instructions.
+///
+/// The registers are seeded with edge case values and control flow only moves forward, so that the
+/// program always terminates. `x30` and `x31` are reserved as the bases of jalrs and of loads and
+/// stores, which keeps them inside the program and the scratch memory.
+fn random_program(rng: &mut StdRng, len: usize) -> Program {
+    const LUI: u32 = 0b011_0111;
+    const AUIPC: u32 = 0b001_0111;
+    const OP_IMM: u32 = 0b001_0011;
+    const LOAD: u32 = 0b000_0011;
+    const JALR: u32 = 0b110_0111;
+
+    // The (funct7, funct3) of the RV32I register-register and RV32M instructions.
+    const OPS: [(u32, u32); 18] = [
+        (0, 0),
+        (0x20, 0),
+        (0, 1),
+        (0, 2),
+        (0, 3),
+        (0, 4),
+        (0, 5),
+        (0x20, 5),
+        (0, 6),
+        (0, 7),
+        (1, 0),
+        (1, 1),
+        (1, 2),
+        (1, 3),
+        (1, 4),
+        (1, 5),
+        (1, 6),
+        (1, 7),
+    ];
+
+    // Jalr targets are offsets from the base of the program, which must fit in an immediate.
+    assert!(len <= 500);
+
+    let mut code = vec![u_type(PC_BASE, 30, LUI), u_type(SCRATCH_ADDR, 31, LUI)];
+    for rd in 1..30 {
+        let value = if rng.gen_bool(0.75) {
+            *EDGE_VALUES.choose(rng).unwrap()
+        } else {
+            rng.gen()
+        };
+        code.push(u_type(value.wrapping_add(0x800), rd, LUI));
+        code.push(i_type(value, rd, 0, rd, OP_IMM));
+    }
+
+    while code.len() < len {
+        let rd = rng.gen_range(1..30);
+        let rs1 = rng.gen_range(0..32);
+        let rs2 = rng.gen_range(0..32);
+        let skip = 4 * rng.gen_range(1..=4);
+        match rng.gen_range(0..8) {
+            0 | 1 => {
+                let (funct7, funct3) = *OPS.choose(rng).unwrap();
+                code.push(r_type(funct7, rs2, rs1, funct3, rd));
+            }
+            2 => {
+                let funct3 = *[0, 2, 3, 4, 6, 7].choose(rng).unwrap();
+                code.push(i_type(rng.gen(), rs1, funct3, rd, OP_IMM));
+            }
+            3 => {
+                let (funct7, funct3) = *[(0, 1), (0, 5), (0x20, 5)].choose(rng).unwrap();
+                let imm = funct7 << 5 | rng.gen_range(0..32);
+                code.push(i_type(imm, rs1, funct3, rd, OP_IMM));
+            }
+            4 => {
+                let opcode = *[LUI, AUIPC].choose(rng).unwrap();
+                code.push(u_type(rng.gen(), rd, opcode));
+            }
+            5 => {
+                // lb, lh, lw, lbu and lhu, and sb, sh and sw, at aligned scratch offsets.
+                let (funct3, align) = *[(0, 1), (1, 2), (2, 4), (4, 1), (5, 2)]
+                    .choose(rng)
+                    .unwrap();
+                let offset = rng.gen_range(0..SCRATCH_SIZE / align) * align;
+                if rng.gen_bool(0.5) {
+                    code.push(i_type(offset, 31, funct3, rd, LOAD));
+                } else {
+                    code.push(s_type(offset, rs2, 31, funct3 & 0b11));
+                }
+            }
+            6 => {
+                let funct3 = *[0, 1, 4, 5, 6, 7].choose(rng).unwrap();
+                code.push(b_type(skip, rs2, rs1, funct3));
+            }
+            _ => {
+                if rng.gen_bool(0.5) {
+                    code.push(j_type(skip, rd));
+                } else {
+                    let target = code.len() as u32 * 4 + skip;
+                    code.push(i_type(target, 30, 0, rd, JALR));
+                }
+            }
+        }
+    }
+
+    let mut program = Program::new(transpile(&code), PC_BASE, PC_BASE);
+    program.memory_image = code
+        .iter()
+        .enumerate()
+        .map(|(i, &word)| (PC_BASE + i as u32 * 4, word))
+        .collect();
+    program
+}
+
+#[test]
+fn test_differential_fibonacci() {
+    run_lockstep(Program::from(FIBONACCI_ELF));
+}
+
+#[test]
+fn test_differential_random() {
+    for seed in 0..256 {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let program = random_program(&mut rng, 256);
+        let result = std::panic::catch_unwind(|| run_lockstep(program));
+        assert!(result.is_ok(), "random program with seed {} diverged", seed);
+    }
+}
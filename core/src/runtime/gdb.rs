@@ -0,0 +1,413 @@
+use std::collections::HashSet;
+use std::io::{self, BufRead, BufReader, ErrorKind, Read, Write};
+use std::net::{Ipv4Addr, TcpListener, TcpStream};
+
+use super::{Register, Runtime};
+
+/// The target description sent to the debugger, so that it picks the RV32 register layout.
+const TARGET_XML: &str = r#"<?xml version="1.0"?><target version="1.0"><architecture>riscv:rv32</architecture></target>"#;
+
+/// The number of registers reported to the debugger: `x0` to `x31` followed by `pc`.
+const NUM_GDB_REGISTERS: u32 = 33;
+
+/// The number of cycles executed between checks for an interrupt from the debugger.
+const INTERRUPT_CHECK_CYCLES: u64 = 1 << 16;
+
+/// The byte a debugger sends to interrupt a running target.
+const INTERRUPT: u8 = 0x03;
+
+/// The reason execution stopped and control returned to the debugger.
+enum StopReason {
+    /// A single step completed.
+    Step,
+    /// A software breakpoint was hit.
+    Breakpoint,
+    /// The debugger interrupted execution.
+    Interrupt,
+    /// The program finished with the given exit code.
+    Exited(u32),
+}
+
+/// A connection to a debugger speaking the GDB remote serial protocol.
+struct GdbConnection {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+}
+
+impl GdbConnection {
+    fn new(stream: TcpStream) -> io::Result<Self> {
+        Ok(Self {
+            reader: BufReader::new(stream.try_clone()?),
+            writer: stream,
+        })
+    }
+
+    /// Read the next packet, acknowledging it. Returns `None` once the debugger disconnects.
+    ///
+    /// An interrupt outside of a packet is returned as a packet containing only [`INTERRUPT`].
+    fn read_packet(&mut self) -> io::Result<Option<Vec<u8>>> {
+        loop {
+            // Skip everything up to the start of a packet, including acks from the debugger.
+            let mut byte = [0u8];
+            loop {
+                if self.reader.read(&mut byte)? == 0 {
+                    return Ok(None);
+                }
+                match byte[0] {
+                    b'$' => break,
+                    INTERRUPT => return Ok(Some(vec![INTERRUPT])),
+                    _ => continue,
+                }
+            }
+
+            let mut data = Vec::new();
+            if self.reader.read_until(b'#', &mut data)? == 0 {
+                return Ok(None);
+            }
+            data.pop();
+            let mut checksum = [0u8; 2];
+            self.reader.read_exact(&mut checksum)?;
+
+            let expected = std::str::from_utf8(&checksum)
+                .ok()
+                .and_then(|checksum| u8::from_str_radix(checksum, 16).ok());
+            if expected == Some(checksum_of(&data)) {
+                self.writer.write_all(b"+")?;
+                return Ok(Some(unescape(&data)));
+            }
+            // Ask the debugger to retransmit a corrupted packet.
+            self.writer.write_all(b"-")?;
+        }
+    }
+
+    fn write_packet(&mut self, data: &str) -> io::Result<()> {
+        write!(
+            self.writer,
+            "${}#{:02x}",
+            data,
+            checksum_of(data.as_bytes())
+        )?;
+        self.writer.flush()
+    }
+
+    /// Check, without blocking, whether the debugger has sent an interrupt.
+    fn poll_interrupt(&mut self) -> io::Result<bool> {
+        if self.reader.buffer().contains(&INTERRUPT) {
+            self.reader.consume(self.reader.buffer().len());
+            return Ok(true);
+        }
+        self.reader.get_ref().set_nonblocking(true)?;
+        let mut buf = [0u8; 64];
+        let result = self.reader.get_mut().read(&mut buf);
+        self.reader.get_ref().set_nonblocking(false)?;
+        match result {
+            Ok(n) => Ok(buf[..n].contains(&INTERRUPT)),
+            Err(e) if e.kind() == ErrorKind::WouldBlock => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+fn checksum_of(data: &[u8]) -> u8 {
+    data.iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte))
+}
+
+/// Undo the `}` escaping of `#`, `$`, `}` and `*` in packet data.
+fn unescape(data: &[u8]) -> Vec<u8> {
+    let mut unescaped = Vec::with_capacity(data.len());
+    let mut bytes = data.iter();
+    while let Some(&byte) = bytes.next() {
+        if byte == b'}' {
+            if let Some(&escaped) = bytes.next() {
+                unescaped.push(escaped ^ 0x20);
+            }
+        } else {
+            unescaped.push(byte);
+        }
+    }
+    unescaped
+}
+
+/// Encode a word as little-endian hex, the byte order GDB expects for RV32 registers.
+fn hex_word(word: u32) -> String {
+    word.to_le_bytes()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Parse a `addr,len` or `kind,addr,len` style list of hex numbers.
+fn parse_hex_args(args: &str) -> Option<Vec<u32>> {
+    args.split(',')
+        .map(|arg| u32::from_str_radix(arg, 16).ok())
+        .collect()
+}
+
+impl Runtime {
+    /// Runs the program under a GDB remote serial protocol server listening on localhost.
+    ///
+    /// The server accepts a single debugger (e.g. `target remote localhost:<port>` from
+    /// `riscv32-gdb`, or `gdb-remote <port>` from `lldb`), which can read registers and memory,
+    /// set software breakpoints, single-step and continue. Unconstrained blocks and syscalls
+    /// execute as a single step, and breakpoints inside unconstrained blocks are not reported, so
+    /// the debugger only observes the execution that is proven. If the debugger detaches, the
+    /// program runs to completion.
+    pub fn run_gdb(&mut self, port: u16) -> io::Result<()> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))?;
+        tracing::info!(
+            "waiting for a debugger on localhost:{}",
+            listener.local_addr()?.port()
+        );
+        self.serve_gdb(listener)
+    }
+
+    /// Runs the program under a GDB server which accepts a debugger from `listener`.
+    fn serve_gdb(&mut self, listener: TcpListener) -> io::Result<()> {
+        self.emit_events = true;
+        if self.state.global_clk == 0 {
+            // Load the memory image now, so that the debugger can read it before the first step.
+            self.initialize();
+        }
+
+        let (stream, addr) = listener.accept()?;
+        tracing::info!("debugger attached from {}", addr);
+        stream.set_nodelay(true)?;
+        let mut connection = GdbConnection::new(stream)?;
+
+        let mut breakpoints = HashSet::new();
+        let mut done = false;
+        while let Some(packet) = connection.read_packet()? {
+            let packet = String::from_utf8_lossy(&packet).into_owned();
+            let response = match packet.as_bytes().first() {
+                Some(&INTERRUPT) | Some(b'?') => self.gdb_stop_reply(StopReason::Interrupt, done),
+                Some(b'g') => (0..NUM_GDB_REGISTERS)
+                    .map(|i| hex_word(self.gdb_register(i)))
+                    .collect(),
+                Some(b'p') => match u32::from_str_radix(&packet[1..], 16) {
+                    Ok(i) if i < NUM_GDB_REGISTERS => hex_word(self.gdb_register(i)),
+                    _ => "E01".to_string(),
+                },
+                Some(b'm') => match parse_hex_args(&packet[1..]).as_deref() {
+                    Some(&[addr, len]) => (0..len)
+                        .map(|i| format!("{:02x}", self.byte(addr.wrapping_add(i))))
+                        .collect(),
+                    _ => "E01".to_string(),
+                },
+                Some(b'Z') | Some(b'z') => match parse_hex_args(&packet[1..]).as_deref() {
+                    // Software and hardware breakpoints are handled the same way.
+                    Some(&[kind, addr, _]) if kind <= 1 => {
+                        if packet.starts_with('Z') {
+                            breakpoints.insert(addr);
+                        } else {
+                            breakpoints.remove(&addr);
+                        }
+                        "OK".to_string()
+                    }
+                    _ => String::new(),
+                },
+                Some(b's') => {
+                    let reason = self.gdb_step(&mut done);
+                    self.gdb_stop_reply(reason, done)
+                }
+                Some(b'c') => {
+                    let reason = self.gdb_continue(&mut connection, &breakpoints, &mut done)?;
+                    self.gdb_stop_reply(reason, done)
+                }
+                Some(b'H') => "OK".to_string(),
+                Some(b'k') => return Ok(()),
+                Some(b'D') => {
+                    connection.write_packet("OK")?;
+                    break;
+                }
+                Some(b'q') => {
+                    if packet.starts_with("qSupported") {
+                        "PacketSize=4000;qXfer:features:read+;swbreak+".to_string()
+                    } else if let Some(range) =
+                        packet.strip_prefix("qXfer:features:read:target.xml:")
+                    {
+                        match parse_hex_args(range).as_deref() {
+                            Some(&[offset, len]) => {
+                                let start = (offset as usize).min(TARGET_XML.len());
+                                let end = (start + len as usize).min(TARGET_XML.len());
+                                let more = if end < TARGET_XML.len() { 'm' } else { 'l' };
+                                format!("{}{}", more, &TARGET_XML[start..end])
+                            }
+                            _ => "E01".to_string(),
+                        }
+                    } else if packet == "qAttached" {
+                        "1".to_string()
+                    } else if packet == "qC" {
+                        "QC1".to_string()
+                    } else if packet == "qfThreadInfo" {
+                        "m1".to_string()
+                    } else if packet == "qsThreadInfo" {
+                        "l".to_string()
+                    } else {
+                        String::new()
+                    }
+                }
+                _ => String::new(),
+            };
+            connection.write_packet(&response)?;
+        }
+
+        // The debugger detached or disconnected, so finish the program without it.
+        if !done {
+            while !self.execute() {}
+        }
+        Ok(())
+    }
+
+    /// The value of the register with the given GDB number.
+    fn gdb_register(&self, i: u32) -> u32 {
+        if i == 32 {
+            self.state.pc
+        } else {
+            self.register(Register::from_u32(i))
+        }
+    }
+
+    /// Executes one instruction, or a whole unconstrained block when the instruction enters one.
+    fn gdb_step(&mut self, done: &mut bool) -> StopReason {
+        if !*done {
+            // A batch of one cycle, since the cycles of unconstrained blocks are not counted.
+            let shard_batch_size = std::mem::replace(&mut self.shard_batch_size, 1);
+            *done = self.execute();
+            self.shard_batch_size = shard_batch_size;
+        }
+        if *done {
+            StopReason::Exited(self.register(Register::X10))
+        } else {
+            StopReason::Step
+        }
+    }
+
+    /// Executes until a breakpoint is hit, the debugger interrupts, or the program finishes.
+    fn gdb_continue(
+        &mut self,
+        connection: &mut GdbConnection,
+        breakpoints: &HashSet<u32>,
+        done: &mut bool,
+    ) -> io::Result<StopReason> {
+        let mut cycles = 0u64;
+        loop {
+            if let StopReason::Exited(code) = self.gdb_step(done) {
+                return Ok(StopReason::Exited(code));
+            }
+            if breakpoints.contains(&self.state.pc) {
+                return Ok(StopReason::Breakpoint);
+            }
+            cycles += 1;
+            if cycles % INTERRUPT_CHECK_CYCLES == 0 && connection.poll_interrupt()? {
+                return Ok(StopReason::Interrupt);
+            }
+        }
+    }
+
+    fn gdb_stop_reply(&self, reason: StopReason, done: bool) -> String {
+        match reason {
+            StopReason::Exited(code) => format!("W{:02x}", code & 0xff),
+            _ if done => format!("W{:02x}", self.register(Register::X10) & 0xff),
+            StopReason::Breakpoint => "T05swbreak:;".to_string(),
+            StopReason::Step | StopReason::Interrupt => "S05".to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::io::{Read, Write};
+    use std::net::{Ipv4Addr, TcpListener, TcpStream};
+    use std::thread;
+
+    use super::checksum_of;
+    use crate::runtime::tests::simple_program;
+    use crate::runtime::{Instruction, Opcode, Program, Register, Runtime, SyscallCode};
+
+    /// Sends a packet and returns the response.
+    fn request(stream: &mut TcpStream, data: &str) -> String {
+        write!(stream, "${}#{:02x}", data, checksum_of(data.as_bytes())).unwrap();
+        let mut response = Vec::new();
+        let mut byte = [0u8];
+        // Skip the ack and read up to the checksum.
+        loop {
+            stream.read_exact(&mut byte).unwrap();
+            if byte[0] == b'$' {
+                break;
+            }
+        }
+        loop {
+            stream.read_exact(&mut byte).unwrap();
+            if byte[0] == b'#' {
+                break;
+            }
+            response.push(byte[0]);
+        }
+        let mut checksum = [0u8; 2];
+        stream.read_exact(&mut checksum).unwrap();
+        stream.write_all(b"+").unwrap();
+        String::from_utf8(response).unwrap()
+    }
+
+    #[test]
+    fn test_gdb_step_and_breakpoint() {
+        // Let the OS pick a free port, so that tests running in parallel cannot collide.
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let program = simple_program();
+        let pc_start = program.pc_start;
+        let server = thread::spawn(move || {
+            let mut runtime = Runtime::new(program);
+            runtime.serve_gdb(listener).unwrap();
+            runtime.register(Register::X31)
+        });
+
+        let mut stream = TcpStream::connect((Ipv4Addr::LOCALHOST, port)).unwrap();
+        assert_eq!(request(&mut stream, "?"), "S05");
+        assert_eq!(request(&mut stream, "p20"), super::hex_word(pc_start));
+
+        // Step over the first instruction, which writes 5 to x29.
+        assert_eq!(request(&mut stream, "s"), "S05");
+        assert_eq!(request(&mut stream, "p1d"), super::hex_word(5));
+        assert_eq!(request(&mut stream, "p20"), super::hex_word(pc_start + 4));
+
+        // Stop at a breakpoint on the last instruction, before it executes.
+        let last = pc_start + 8;
+        assert_eq!(request(&mut stream, &format!("Z0,{:x},4", last)), "OK");
+        assert_eq!(request(&mut stream, "c"), "T05swbreak:;");
+        assert_eq!(request(&mut stream, "p20"), super::hex_word(last));
+        assert_eq!(request(&mut stream, "p1f"), super::hex_word(0));
+
+        // Run to completion.
+        assert_eq!(request(&mut stream, &format!("z0,{:x},4", last)), "OK");
+        assert_eq!(request(&mut stream, "c"), "W00");
+        assert_eq!(request(&mut stream, "D"), "OK");
+        assert_eq!(server.join().unwrap(), 42);
+    }
+
+    #[test]
+    fn test_gdb_fails_on_panic() {
+        // A program which halts with exit code 1.
+        let program = Program::new(
+            vec![
+                Instruction::new(Opcode::ADD, 5, 0, SyscallCode::HALT as u32, false, true),
+                Instruction::new(Opcode::ADD, 10, 0, 1, false, true),
+                Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+            ],
+            0,
+            0,
+        );
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = thread::spawn(move || {
+            let mut runtime = Runtime::new(program);
+            runtime.fail_on_panic = true;
+            runtime.serve_gdb(listener).unwrap();
+        });
+
+        // Detaching finishes the program, which must fail like `run` does.
+        let mut stream = TcpStream::connect((Ipv4Addr::LOCALHOST, port)).unwrap();
+        assert_eq!(request(&mut stream, "D"), "OK");
+        assert!(server.join().is_err());
+    }
+}
@@ -0,0 +1,96 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+pub use sp1_primitives::CommittedEnv;
+
+/// The environment the host provides to a guest program, which the guest reads through its
+/// `std::env` functions, `getrandom` and `sp1_zkvm::fs`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GuestEnv {
+    /// The environment variables of the guest.
+    pub vars: BTreeMap<String, String>,
+
+    /// The arguments of the guest, starting with the program name by convention.
+    pub args: Vec<String>,
+
+    /// Whether the guest commits `vars` and `args` to its public values on start-up, so that the
+    /// verifier knows which configuration the program ran with.
+    pub commit: bool,
+
+    /// The seed of the random number generator behind the guest's `getrandom`.
+    pub random_seed: Option<[u8; 32]>,
+
+    /// Whether the guest commits `random_seed` to its public values on start-up.
+    pub commit_random_seed: bool,
+
+    /// The read-only files of the guest, by path.
+    pub files: BTreeMap<String, Vec<u8>>,
+
+    /// Whether the guest commits the hash of each file to its public values on start-up. The guest
+    /// then checks each file it opens against its hash.
+    pub commit_file_hashes: bool,
+}
+
+/// A file opened by the guest.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpenFile {
+    pub path: String,
+
+    /// The position of the next byte read.
+    pub offset: u32,
+}
+
+impl GuestEnv {
+    /// The environment committed by the guest, or `None` if the host committed nothing, in which
+    /// case the public values of the program are left as they are.
+    pub fn committed(&self) -> Option<CommittedEnv> {
+        if !self.commit && !self.commit_random_seed && !self.commit_file_hashes {
+            return None;
+        }
+        let file_hashes = self.commit_file_hashes.then(|| {
+            self.files
+                .iter()
+                .map(|(path, contents)| (path.clone(), Sha256::digest(contents).into()))
+                .collect()
+        });
+        Some(CommittedEnv {
+            vars: self.commit.then(|| self.vars.clone()),
+            args: self.commit.then(|| self.args.clone()),
+            random_seed: self.random_seed.filter(|_| self.commit_random_seed),
+            file_hashes,
+        })
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn test_committed_env() {
+        let mut env = GuestEnv::default();
+        env.vars.insert("MODE".to_string(), "fast".to_string());
+        env.random_seed = Some([7; 32]);
+        env.files.insert("empty.txt".to_string(), vec![]);
+        assert_eq!(env.committed(), None);
+
+        env.commit_random_seed = true;
+        let committed = env.committed().unwrap();
+        assert_eq!(committed.vars, None);
+        assert_eq!(committed.random_seed, Some([7; 32]));
+        assert_eq!(committed.file_hashes, None);
+
+        env.commit = true;
+        env.commit_file_hashes = true;
+        let committed = env.committed().unwrap();
+        assert_eq!(committed.vars.unwrap()["MODE"], "fast");
+        assert_eq!(committed.args, Some(vec![]));
+        let empty_hash = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+        assert_eq!(
+            hex::encode(committed.file_hashes.unwrap()["empty.txt"]),
+            empty_hash
+        );
+    }
+}
@@ -0,0 +1,241 @@
+use std::fmt::{Display, Formatter};
+
+use super::{ExecutionState, Register, Runtime};
+
+/// The maximum number of frames reported in a guest backtrace.
+const MAX_BACKTRACE_FRAMES: usize = 64;
+
+/// The source location at which a guest program panicked.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PanicLocation {
+    pub file: String,
+    pub line: u32,
+    pub column: u32,
+}
+
+/// A frame of a guest backtrace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BacktraceFrame {
+    /// The pc of the frame: the halting instruction for the innermost frame, and the call
+    /// instruction for the others.
+    pub pc: u32,
+
+    /// The function containing `pc`, if the program has a symbol for it.
+    pub function: Option<String>,
+}
+
+/// A guest program which halted with a non-zero exit code, usually because it panicked.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GuestPanic {
+    pub exit_code: u32,
+
+    /// The message the guest wrote to stderr right before halting, as `sys_panic` does.
+    pub message: String,
+
+    /// The location of the panic, if the message contains one.
+    pub location: Option<PanicLocation>,
+
+    /// The call stack at the time the guest halted, innermost frame first.
+    pub backtrace: Vec<BacktraceFrame>,
+}
+
+impl Display for GuestPanic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "guest program halted with exit code {}", self.exit_code)?;
+        if !self.message.is_empty() {
+            write!(f, ": {}", self.message)?;
+        }
+        writeln!(f)?;
+        writeln!(f, "guest backtrace:")?;
+        for (i, frame) in self.backtrace.iter().enumerate() {
+            let function = frame.function.as_deref().unwrap_or("<unknown>");
+            writeln!(f, "{:>4}: 0x{:08x} - {}", i, frame.pc, function)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for GuestPanic {}
+
+/// Find the `file:line:column` location in a panic message, as written by the panic handler of
+/// the standard library (`panicked at src/main.rs:3:5:` or `panicked at 'msg', src/main.rs:3:5`).
+fn parse_location(message: &str) -> Option<PanicLocation> {
+    let (_, rest) = message.split_once("panicked at ")?;
+    let line = rest.lines().next()?;
+    line.split([' ', ',']).find_map(|token| {
+        let mut parts = token.trim_end_matches(':').rsplitn(3, ':');
+        let column = parts.next()?.parse().ok()?;
+        let line = parts.next()?.parse().ok()?;
+        let file = parts.next()?.to_string();
+        Some(PanicLocation { file, line, column })
+    })
+}
+
+impl Runtime {
+    /// Runs the program, returning the guest's panic as an error if it halts with a non-zero exit
+    /// code.
+    ///
+    /// If `fail_on_panic` is disabled, the panic is not an error and the program's exit code is
+    /// proven as usual.
+    pub fn try_run(&mut self) -> Result<(), GuestPanic> {
+        self.catch_panic(Self::run)
+    }
+
+    /// Like [Runtime::execute_state], but returns the guest's panic as an error if the program
+    /// halts with a non-zero exit code and `fail_on_panic` is enabled.
+    pub fn try_execute_state(&mut self) -> Result<(ExecutionState, bool), GuestPanic> {
+        self.catch_panic(Self::execute_state)
+    }
+
+    /// Executes `f` without panicking when the guest does, and returns the guest's panic instead
+    /// if `fail_on_panic` is enabled.
+    fn catch_panic<T>(&mut self, f: impl FnOnce(&mut Self) -> T) -> Result<T, GuestPanic> {
+        let fail_on_panic = std::mem::replace(&mut self.fail_on_panic, false);
+        let result = f(self);
+        self.fail_on_panic = fail_on_panic;
+        match &self.guest_panic {
+            Some(panic) if fail_on_panic => Err(panic.clone()),
+            _ => Ok(result),
+        }
+    }
+
+    /// Captures the panic of a guest halting with `exit_code` at the current pc.
+    pub(crate) fn capture_panic(&mut self, exit_code: u32) -> GuestPanic {
+        let message = self
+            .last_stderr_write
+            .take()
+            .map(|message| message.trim().to_string())
+            .unwrap_or_default();
+        let location = parse_location(&message);
+        GuestPanic {
+            exit_code,
+            message,
+            location,
+            backtrace: self.backtrace(),
+        }
+    }
+
+    /// Reconstructs the guest call stack from the frame pointer chain.
+    ///
+    /// Each frame saves its return address at `fp - 4` and its caller's frame pointer at `fp - 8`.
+    /// Guests compiled without frame pointers (`-C force-frame-pointers=yes`) only get their
+    /// innermost frame, since the chain stops at the first address outside of the program.
+    fn backtrace(&self) -> Vec<BacktraceFrame> {
+        let code_size = self.program.code_size();
+        let is_code = |pc: u32| pc.wrapping_sub(self.program.pc_base) < code_size;
+
+        let mut pcs = vec![self.state.pc];
+        let mut fp = self.register(Register::X8);
+        while pcs.len() < MAX_BACKTRACE_FRAMES && fp >= 8 && fp % 4 == 0 {
+            let return_addr = self.word(fp - 4);
+            let caller_fp = self.word(fp - 8);
+            if !is_code(return_addr) {
+                break;
+            }
+            pcs.push(self.program.previous_pc(return_addr));
+            // The stack grows down, so the caller's frame must be above this one.
+            if caller_fp <= fp {
+                break;
+            }
+            fp = caller_fp;
+        }
+
+        pcs.into_iter()
+            .map(|pc| BacktraceFrame {
+                pc,
+                function: self
+                    .program
+                    .symbols
+                    .lookup(pc)
+                    .map(|symbol| symbol.name.clone()),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::runtime::{Instruction, Opcode, Program, ShardingConfig, SyscallCode};
+    use crate::utils::{run_and_prove_runtime, BabyBearBlake3};
+
+    #[test]
+    fn test_parse_location() {
+        let location = PanicLocation {
+            file: "src/main.rs".to_string(),
+            line: 3,
+            column: 5,
+        };
+        assert_eq!(
+            parse_location("panicked at src/main.rs:3:5:\nboom"),
+            Some(location.clone())
+        );
+        assert_eq!(
+            parse_location("panicked at 'boom', src/main.rs:3:5"),
+            Some(location)
+        );
+        assert_eq!(parse_location("boom"), None);
+    }
+
+    #[test]
+    fn test_guest_panic() {
+        let message = b"panicked at src/main.rs:3:5:\nboom\n";
+        let (message_ptr, fp) = (0x1000, 0x2000);
+
+        // Store the message and a frame whose return address is the third instruction.
+        let mut instructions = vec![];
+        let mut store = |addr: u32, word: u32| {
+            instructions.extend([
+                Instruction::new(Opcode::ADD, 29, 0, word, false, true),
+                Instruction::new(Opcode::ADD, 30, 0, addr, false, true),
+                Instruction::new(Opcode::SW, 29, 30, 0, false, true),
+            ]);
+        };
+        for (i, chunk) in message.chunks(4).enumerate() {
+            let mut word = [0u8; 4];
+            word[..chunk.len()].copy_from_slice(chunk);
+            store(message_ptr + i as u32 * 4, u32::from_le_bytes(word));
+        }
+        store(fp - 4, 12);
+        store(fp - 8, 0);
+
+        // Write unrelated output and then the message to stderr, and halt with exit code 1.
+        instructions.extend([
+            Instruction::new(Opcode::ADD, 8, 0, fp, false, true),
+            Instruction::new(Opcode::ADD, 5, 0, SyscallCode::WRITE as u32, false, true),
+            Instruction::new(Opcode::ADD, 10, 0, 2, false, true),
+            Instruction::new(Opcode::ADD, 11, 0, message_ptr + 16, false, true),
+            Instruction::new(Opcode::ADD, 12, 0, 4, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+            Instruction::new(Opcode::ADD, 5, 0, SyscallCode::WRITE as u32, false, true),
+            Instruction::new(Opcode::ADD, 10, 0, 2, false, true),
+            Instruction::new(Opcode::ADD, 11, 0, message_ptr, false, true),
+            Instruction::new(Opcode::ADD, 12, 0, message.len() as u32, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+            Instruction::new(Opcode::ADD, 5, 0, SyscallCode::HALT as u32, false, true),
+            Instruction::new(Opcode::ADD, 10, 0, 1, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+        ]);
+        let halt_pc = (instructions.len() as u32 - 1) * 4;
+
+        let mut runtime = Runtime::new(Program::new(instructions.clone(), 0, 0));
+        let panic = runtime.try_run().unwrap_err();
+        assert_eq!(panic.exit_code, 1);
+        assert_eq!(panic.message, "panicked at src/main.rs:3:5:\nboom");
+        assert_eq!(panic.location.unwrap().line, 3);
+        let pcs = panic.backtrace.iter().map(|f| f.pc).collect::<Vec<_>>();
+        assert_eq!(pcs, vec![halt_pc, 8]);
+
+        // Proving returns the panic instead of a proof.
+        let runtime = Runtime::new(Program::new(instructions.clone(), 0, 0));
+        let config = BabyBearBlake3::new();
+        let result = run_and_prove_runtime(runtime, config, &ShardingConfig::default());
+        assert_eq!(result.err().unwrap().exit_code, 1);
+
+        // When opting in, the panic is recorded but the program still completes.
+        let mut runtime = Runtime::new(Program::new(instructions, 0, 0));
+        runtime.fail_on_panic = false;
+        runtime.try_run().unwrap();
+        assert_eq!(runtime.guest_panic.unwrap().exit_code, 1);
+    }
+}
@@ -12,6 +12,9 @@ pub struct Instruction {
     pub op_c: u32,
     pub imm_b: bool,
     pub imm_c: bool,
+
+    /// Whether the instruction was expanded from a 16-bit compressed instruction.
+    pub compressed: bool,
 }
 
 impl Instruction {
@@ -24,6 +27,17 @@ impl Instruction {
             op_c,
             imm_b,
             imm_c,
+            compressed: false,
+        }
+    }
+
+    /// The size of the instruction in bytes, which is the offset to the next instruction.
+    #[inline(always)]
+    pub fn size(&self) -> u32 {
+        if self.compressed {
+            2
+        } else {
+            4
         }
     }
 
@@ -6,7 +6,7 @@ use crate::utils::BabyBearPoseidon2Inner;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 
-use super::Runtime;
+use super::{GuestEnv, Runtime};
 
 impl Read for Runtime {
     fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
@@ -40,6 +40,10 @@ impl Runtime {
         self.state.proof_stream.push((proof, vk));
     }
 
+    pub fn write_env(&mut self, env: GuestEnv) {
+        self.state.env = env;
+    }
+
     pub fn read_public_values<T: DeserializeOwned>(&mut self) -> T {
         let result = bincode::deserialize_from::<_, T>(self);
         result.unwrap()
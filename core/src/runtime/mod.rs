@@ -1,7 +1,14 @@
+mod coverage;
+#[cfg(test)]
+mod differential;
+mod gdb;
+mod guest_env;
+mod guest_panic;
 mod instruction;
 mod io;
 mod memory;
 mod opcode;
+mod profiler;
 mod program;
 mod record;
 mod register;
@@ -10,9 +17,13 @@ mod syscall;
 #[macro_use]
 mod utils;
 
+pub use coverage::*;
+pub use guest_env::*;
+pub use guest_panic::*;
 pub use instruction::*;
 pub use memory::*;
 pub use opcode::*;
+pub use profiler::*;
 pub use program::*;
 pub use record::*;
 pub use register::*;
@@ -70,9 +81,25 @@ pub struct Runtime {
     /// A buffer for writing trace events to a file.
     pub trace_buf: Option<BufWriter<File>>,
 
-    /// Whether the runtime should fail on panic or not.
+    /// The profiler which attributes cycles to the guest's functions, if enabled.
+    pub profiler: Option<Profiler>,
+
+    /// The recorder of which guest instructions are executed, if coverage is enabled.
+    pub coverage: Option<Coverage>,
+
+    /// Whether the runtime should fail on panic or not. Enabled by default.
+    ///
+    /// When disabled, a guest which halts with a non-zero exit code is executed (and proven) as
+    /// usual, and its panic is only recorded in `guest_panic`.
     pub fail_on_panic: bool,
 
+    /// The panic of the guest, if it halted with a non-zero exit code.
+    pub guest_panic: Option<GuestPanic>,
+
+    /// The last write to stderr, if no other syscall has been made since. `sys_panic` writes the
+    /// panic message to stderr and halts right after, so this is the message of a panic.
+    pub(crate) last_stderr_write: Option<String>,
+
     /// Whether the runtime is in constrained mode or not.
     /// In unconstrained mode, any events, clock, register, or memory changes are reset after leaving
     /// the unconstrained block. The only thing preserved is writes to the input stream.
@@ -82,9 +109,10 @@ pub struct Runtime {
 
     pub syscall_map: HashMap<SyscallCode, Rc<dyn Syscall>>,
 
-    pub max_syscall_cycles: u32,
-
     pub emit_events: bool,
+
+    /// Whether the memory image has been loaded, which a debugger may do before the first cycle.
+    initialized: bool,
 }
 
 impl Runtime {
@@ -108,12 +136,6 @@ impl Runtime {
         };
 
         let syscall_map = default_syscall_map();
-        // Determine the maximum number of cycles for any syscall.
-        let max_syscall_cycles = syscall_map
-            .values()
-            .map(|syscall| syscall.num_extra_cycles())
-            .max()
-            .unwrap_or(0);
 
         let shard_size = env::shard_size() as u32;
 
@@ -127,24 +149,34 @@ impl Runtime {
             cycle_tracker: HashMap::new(),
             io_buf: HashMap::new(),
             trace_buf,
+            profiler: None,
+            coverage: None,
             fail_on_panic: true,
+            guest_panic: None,
+            last_stderr_write: None,
             unconstrained: false,
             unconstrained_state: ForkState::default(),
             syscall_map,
             emit_events: true,
-            max_syscall_cycles,
+            initialized: false,
         }
     }
 
     /// Recover runtime state from a program and existing execution state.
     pub fn recover(program: Program, state: ExecutionState) -> Self {
         let mut runtime = Self::new(program);
-        runtime.state = state;
-        let index: u32 = (runtime.state.global_clk / (runtime.shard_size / 4) as u64)
+        runtime.restore(state);
+        runtime
+    }
+
+    /// Resume from an existing execution state, which must have been saved by a runtime with the
+    /// same shard size.
+    pub fn restore(&mut self, state: ExecutionState) {
+        self.state = state;
+        let index: u32 = (self.state.global_clk / (self.shard_size / 4) as u64)
             .try_into()
             .unwrap();
-        runtime.record.index = index;
-        runtime
+        self.record.index = index;
     }
 
     /// Get the current values of the registers.
@@ -499,8 +531,13 @@ impl Runtime {
 
     /// Fetch the instruction at the current program counter.
     fn fetch(&self) -> Instruction {
-        let idx = ((self.state.pc - self.program.pc_base) / 4) as usize;
-        self.program.instructions[idx]
+        match self.program.instruction_index(self.state.pc) {
+            Some(idx) => self.program.instructions[idx],
+            None => panic!(
+                "invalid pc 0x{:08x}: no instruction starts at this address",
+                self.state.pc
+            ),
+        }
     }
 
     /// Execute the given instruction over the current state of the runtime.
@@ -509,7 +546,7 @@ impl Runtime {
         let mut clk = self.state.clk;
         let mut exit_code = 0u32;
 
-        let mut next_pc = self.state.pc.wrapping_add(4);
+        let mut next_pc = self.state.pc.wrapping_add(instruction.size());
 
         let rd: Register;
         let (a, b, c): (u32, u32, u32);
@@ -691,14 +728,14 @@ impl Runtime {
             Opcode::JAL => {
                 let (rd, imm) = instruction.j_type();
                 (b, c) = (imm, 0);
-                a = self.state.pc + 4;
+                a = self.state.pc + instruction.size();
                 self.rw(rd, a);
                 next_pc = self.state.pc.wrapping_add(imm);
             }
             Opcode::JALR => {
                 let (rd, rs1, imm) = instruction.i_type();
                 (b, c) = (self.rr(rs1, MemoryAccessPosition::B), imm);
-                a = self.state.pc + 4;
+                a = self.state.pc + instruction.size();
                 self.rw(rd, a);
                 next_pc = b.wrapping_add(c);
             }
@@ -720,6 +757,9 @@ impl Runtime {
                 c = self.rr(Register::X11, MemoryAccessPosition::C);
                 b = self.rr(Register::X10, MemoryAccessPosition::B);
                 let syscall = SyscallCode::from_u32(syscall_id);
+                if syscall != SyscallCode::HALT {
+                    self.last_stderr_write = None;
+                }
 
                 let syscall_impl = self.get_syscall(syscall).cloned();
                 let mut precompile_rt = SyscallContext::new(self);
@@ -854,21 +894,49 @@ impl Runtime {
         // Log the current state of the runtime.
         self.log(&instruction);
 
+        // Capture the pc and syscall id for the profiler before the instruction updates them.
+        let profiled = match self.profiler {
+            Some(_) if !self.unconstrained => Some((self.state.pc, self.register(Register::X5))),
+            _ => None,
+        };
+
         // Execute the instruction.
         self.execute_instruction(instruction);
 
+        if let Some((pc, syscall_id)) = profiled {
+            let syscall = instruction.is_ecall_instruction().then(|| {
+                let code = SyscallCode::from_u32(syscall_id);
+                let extra_cycles = self.syscall_map[&code].num_extra_cycles();
+                (code, extra_cycles)
+            });
+            let next_pc = self.state.pc;
+            if let Some(profiler) = self.profiler.as_mut() {
+                profiler.record(pc, &instruction, next_pc, syscall);
+            }
+        }
+
         // Increment the clock.
         self.state.global_clk += 1;
 
-        // If there's not enough cycles left for another instruction, move to the next shard.
-        // We multiply by 4 because clk is incremented by 4 for each normal instruction.
-        if !self.unconstrained && self.max_syscall_cycles + self.state.clk >= self.shard_size {
+        // If there's not enough cycles left for the next instruction, move to the next shard.
+        if !self.unconstrained && self.state.clk + self.next_extra_cycles() >= self.shard_size {
             self.state.current_shard += 1;
             self.state.clk = 0;
         }
 
-        self.state.pc.wrapping_sub(self.program.pc_base)
-            >= (self.program.instructions.len() * 4) as u32
+        self.state.pc.wrapping_sub(self.program.pc_base) >= self.program.code_size()
+    }
+
+    /// The number of extra cycles that the instruction at the current pc takes, which is only
+    /// nonzero for syscalls. A syscall code encodes its number of cycles, which the CPU table
+    /// constrains, so an unknown syscall reserves the cycles of its encoding until it fails.
+    fn next_extra_cycles(&self) -> u32 {
+        match self.program.instruction_index(self.state.pc) {
+            Some(idx) if self.program.instructions[idx].is_ecall_instruction() => {
+                self.register(Register::X5) >> 16
+            }
+            _ => 0,
+        }
     }
 
     /// Execute up to `self.shard_batch_size` cycles, returning the events emitted and whether the program ended.
@@ -887,6 +955,10 @@ impl Runtime {
     }
 
     fn initialize(&mut self) {
+        if self.initialized {
+            return;
+        }
+        self.initialized = true;
         self.state.clk = 0;
 
         tracing::info!("loading memory image");
@@ -934,12 +1006,23 @@ impl Runtime {
         }
 
         if done {
-            self.postprocess();
+            self.finish();
         }
 
         done
     }
 
+    /// Completes a program which has halted, panicking if the guest did and `fail_on_panic` is
+    /// enabled. Every way of running the program to completion must go through this.
+    fn finish(&mut self) {
+        self.postprocess();
+        if self.fail_on_panic {
+            if let Some(panic) = &self.guest_panic {
+                panic!("{}", panic);
+            }
+        }
+    }
+
     fn postprocess(&mut self) {
         tracing::info!(
             "finished execution clk = {} pc = 0x{:x?}",
@@ -1041,7 +1124,7 @@ pub mod tests {
         utils::tests::{FIBONACCI_ELF, SSZ_WITHDRAWALS_ELF},
     };
 
-    use super::{Instruction, Opcode, Program, Runtime};
+    use super::{Instruction, Opcode, Program, Runtime, SyscallCode};
 
     pub fn simple_program() -> Program {
         let instructions = vec![
@@ -1052,6 +1135,41 @@ pub mod tests {
         Program::new(instructions, 0, 0)
     }
 
+    /// A program mixing compressed and 32-bit instructions, which sums 5 + 4 + 3 + 2 + 1 in a
+    /// function.
+    pub fn compressed_program() -> Program {
+        let c = |instruction: Instruction| Instruction {
+            compressed: true,
+            ..instruction
+        };
+        let instructions = vec![
+            //  0: c.li a0, 5
+            c(Instruction::new(Opcode::ADD, 10, 0, 5, false, true)),
+            //  2: addi a1, x0, 0
+            Instruction::new(Opcode::ADD, 11, 0, 0, false, true),
+            //  6: c.jal 10
+            c(Instruction::new(Opcode::JAL, 1, 4, 0, true, true)),
+            //  8: c.j 20
+            c(Instruction::new(Opcode::JAL, 0, 12, 0, true, true)),
+            // 10: c.add a1, a0
+            c(Instruction::new(Opcode::ADD, 11, 11, 10, false, false)),
+            // 12: addi a0, a0, -1
+            Instruction::new(Opcode::ADD, 10, 10, 0xffffffff, false, true),
+            // 16: c.bnez a0, 10
+            c(Instruction::new(
+                Opcode::BNE,
+                10,
+                0,
+                0xfffffffa,
+                false,
+                true,
+            )),
+            // 18: c.jr ra
+            c(Instruction::new(Opcode::JALR, 0, 1, 0, false, true)),
+        ];
+        Program::new(instructions, 0, 0)
+    }
+
     pub fn fibonacci_program() -> Program {
         Program::from(FIBONACCI_ELF)
     }
@@ -1068,6 +1186,33 @@ pub mod tests {
         assert_eq!(runtime.register(Register::X31), 42);
     }
 
+    #[test]
+    fn test_shard_reserves_syscall_cycles() {
+        // With shards of 16 rows, the 48 extra cycles of the ecall don't fit after the 14
+        // instructions before it, so it starts the second shard. Without it, the first shard would
+        // hold 16 instructions.
+        let mut instructions = vec![
+            Instruction::new(
+                Opcode::ADD,
+                5,
+                0,
+                SyscallCode::SHA_EXTEND as u32,
+                false,
+                true,
+            ),
+            Instruction::new(Opcode::ADD, 10, 0, 1000, false, true),
+        ];
+        instructions.extend((0..12).map(|_| Instruction::new(Opcode::ADD, 29, 0, 1, false, true)));
+        instructions.push(Instruction::new(Opcode::ECALL, 5, 10, 11, false, false));
+        let mut runtime = Runtime::new(Program::new(instructions, 0, 0));
+        runtime.shard_size = 16 * 4;
+        runtime.run();
+
+        let events = &runtime.record.cpu_events;
+        assert!(events[..14].iter().all(|event| event.shard == 1));
+        assert_eq!((events[14].shard, events[14].clk), (2, 0));
+    }
+
     #[test]
     fn test_add() {
         // main:
@@ -1390,6 +1535,38 @@ pub mod tests {
         assert_eq!(runtime.register(Register::X31), 0);
     }
 
+    #[test]
+    fn test_compressed() {
+        let program = compressed_program();
+        assert_eq!(program.code_size(), 20);
+        assert_eq!(
+            program.instruction_pcs().collect::<Vec<_>>(),
+            vec![0, 2, 6, 8, 10, 12, 16, 18]
+        );
+        let mut runtime = Runtime::new(program);
+        runtime.run();
+        assert_eq!(runtime.register(Register::X11), 15);
+        assert_eq!(runtime.register(Register::X1), 8);
+        assert_eq!(runtime.state.pc, 20);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid pc 0x00000004")]
+    fn test_jump_into_instruction() {
+        let c = |instruction: Instruction| Instruction {
+            compressed: true,
+            ..instruction
+        };
+        let instructions = vec![
+            // 0: c.j 4, which is the upper halfword of the next instruction.
+            c(Instruction::new(Opcode::JAL, 0, 4, 0, true, true)),
+            // 2: addi a0, x0, 1
+            Instruction::new(Opcode::ADD, 10, 0, 1, false, true),
+        ];
+        let mut runtime = Runtime::new(Program::new(instructions, 0, 0));
+        runtime.run();
+    }
+
     #[test]
     fn test_jalr() {
         //   addi x11, x11, 100
@@ -0,0 +1,447 @@
+use std::collections::HashMap;
+use std::io::{self, Write};
+
+use addr2line::gimli::{EndianRcSlice, RunTimeEndian};
+use addr2line::object;
+use elf::endian::LittleEndian;
+use elf::ElfBytes;
+
+use super::{Instruction, Opcode, Register, SyscallCode};
+use crate::disassembler::SymbolTable;
+
+/// The name used for code which is not covered by any symbol of the ELF.
+const UNKNOWN_FUNCTION: &str = "[unknown]";
+
+/// A node of the call tree, identified by its parent, the call site and the called function.
+#[derive(Debug, Clone)]
+struct CallNode {
+    parent: Option<usize>,
+
+    /// The pc of the instruction which called into this node.
+    call_site: u32,
+
+    /// The index of the called function in the symbol table.
+    function: Option<usize>,
+
+    /// The cycles spent in this node, keyed by pc.
+    cycles: HashMap<u32, u64>,
+
+    /// The syscalls made directly from this node.
+    syscalls: HashMap<SyscallCode, u64>,
+
+    children: HashMap<(u32, Option<usize>), usize>,
+}
+
+/// An active frame of the reconstructed guest call stack.
+#[derive(Debug, Clone, Copy)]
+struct Frame {
+    node: usize,
+
+    /// The address the frame returns to, or `None` for the outermost frame.
+    return_addr: Option<u32>,
+}
+
+/// The cycles attributed to a guest function.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionCycles {
+    pub name: String,
+
+    /// The number of times the function was called.
+    pub calls: u64,
+
+    /// The cycles spent in the function itself.
+    pub self_cycles: u64,
+
+    /// The cycles spent in the function and everything it called.
+    pub total_cycles: u64,
+}
+
+/// The cycles attributed to a syscall.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SyscallCycles {
+    pub code: SyscallCode,
+
+    /// The number of times the syscall was invoked.
+    pub calls: u64,
+
+    /// The extra cycles the syscall adds to the clock on top of its `ECALL` instruction.
+    pub extra_cycles: u64,
+}
+
+/// A profiler which attributes the cycles of a guest program to the functions of its ELF.
+///
+/// Program counters are resolved against the ELF's symbol table, and against its DWARF info to
+/// recover inlined frames when the ELF has debug info. Call stacks are reconstructed from `JAL`
+/// and `JALR` instructions which link through `ra`, and returns are detected from `JALR` through
+/// `ra` without linking.
+pub struct Profiler {
+    symbols: SymbolTable,
+    dwarf: Option<addr2line::Context<EndianRcSlice<RunTimeEndian>>>,
+    inlined: HashMap<u32, Vec<String>>,
+    nodes: Vec<CallNode>,
+    stack: Vec<Frame>,
+    calls: HashMap<Option<usize>, u64>,
+    syscalls: HashMap<SyscallCode, SyscallCycles>,
+    total_cycles: u64,
+}
+
+impl Profiler {
+    /// Create a profiler for the program of the given ELF.
+    pub fn new(elf_bytes: &[u8]) -> Self {
+        let elf = ElfBytes::<LittleEndian>::minimal_parse(elf_bytes).expect("failed to parse elf");
+
+        let symbols = SymbolTable::parse(&elf);
+
+        // Debug info is optional, so an ELF without it only loses the inlined frames.
+        let dwarf = object::File::parse(elf_bytes)
+            .ok()
+            .and_then(|file| addr2line::Context::new(&file).ok());
+
+        Self {
+            symbols,
+            dwarf,
+            inlined: HashMap::new(),
+            nodes: Vec::new(),
+            stack: Vec::new(),
+            calls: HashMap::new(),
+            syscalls: HashMap::new(),
+            total_cycles: 0,
+        }
+    }
+
+    /// Record the execution of an instruction at `pc`, which moved the program to `next_pc`.
+    ///
+    /// For an `ECALL`, `syscall` holds the invoked syscall and its number of extra cycles.
+    pub(crate) fn record(
+        &mut self,
+        pc: u32,
+        instruction: &Instruction,
+        next_pc: u32,
+        syscall: Option<(SyscallCode, u32)>,
+    ) {
+        if self.stack.is_empty() {
+            let function = self.symbols.index_of(pc);
+            let node = self.node(None, pc, function);
+            *self.calls.entry(function).or_default() += 1;
+            self.stack.push(Frame {
+                node,
+                return_addr: None,
+            });
+        }
+
+        let frame = *self.stack.last().unwrap();
+        self.total_cycles += 1;
+        match syscall {
+            Some((code, extra_cycles)) => {
+                *self.nodes[frame.node].syscalls.entry(code).or_default() += 1;
+                let entry = self.syscalls.entry(code).or_insert(SyscallCycles {
+                    code,
+                    calls: 0,
+                    extra_cycles: 0,
+                });
+                entry.calls += 1;
+                entry.extra_cycles += extra_cycles as u64;
+            }
+            None => *self.nodes[frame.node].cycles.entry(pc).or_default() += 1,
+        }
+
+        let (rd, rs1) = match instruction.opcode {
+            Opcode::JAL => (instruction.j_type().0, None),
+            Opcode::JALR => {
+                let (rd, rs1, _) = instruction.i_type();
+                (rd, Some(rs1))
+            }
+            _ => return,
+        };
+
+        if rd == Register::X1 {
+            // A call: push a frame which returns to the instruction after the jump.
+            let function = self.symbols.index_of(next_pc);
+            let node = self.node(Some(frame.node), pc, function);
+            *self.calls.entry(function).or_default() += 1;
+            self.stack.push(Frame {
+                node,
+                return_addr: Some(pc + instruction.size()),
+            });
+        } else if rd == Register::X0 && rs1 == Some(Register::X1) {
+            // A return: pop every frame up to the one returning to `next_pc`, which also unwinds
+            // frames that were left without a return (e.g. by a longjmp-style jump).
+            if let Some(depth) = self
+                .stack
+                .iter()
+                .rposition(|frame| frame.return_addr == Some(next_pc))
+            {
+                self.stack.truncate(depth);
+            }
+        } else if rd == Register::X0 && self.symbols.is_function_start(next_pc) {
+            // A tail call: the callee replaces the current frame and returns to its caller.
+            let function = self.symbols.index_of(next_pc);
+            let current = &self.nodes[frame.node];
+            if current.function != function {
+                let (parent, call_site) = (current.parent, current.call_site);
+                let node = self.node(parent, call_site, function);
+                *self.calls.entry(function).or_default() += 1;
+                *self.stack.last_mut().unwrap() = Frame {
+                    node,
+                    return_addr: frame.return_addr,
+                };
+            }
+        }
+    }
+
+    /// The total number of cycles recorded.
+    pub fn total_cycles(&self) -> u64 {
+        self.total_cycles
+    }
+
+    /// The cycles attributed to each function, sorted by decreasing total cycles.
+    pub fn function_cycles(&self) -> Vec<FunctionCycles> {
+        // Children are always created after their parents, so walking the nodes backwards
+        // accumulates every subtree before its root is visited.
+        let mut subtree_cycles = self
+            .nodes
+            .iter()
+            .map(|node| node.cycles.values().sum::<u64>() + node.syscalls.values().sum::<u64>())
+            .collect::<Vec<_>>();
+        let self_cycles = subtree_cycles.clone();
+        for (i, node) in self.nodes.iter().enumerate().rev() {
+            if let Some(parent) = node.parent {
+                subtree_cycles[parent] += subtree_cycles[i];
+            }
+        }
+
+        let mut functions: HashMap<Option<usize>, FunctionCycles> = HashMap::new();
+        for (i, node) in self.nodes.iter().enumerate() {
+            let entry = functions
+                .entry(node.function)
+                .or_insert_with(|| FunctionCycles {
+                    name: self.function_name(node.function).to_string(),
+                    calls: self.calls.get(&node.function).copied().unwrap_or_default(),
+                    self_cycles: 0,
+                    total_cycles: 0,
+                });
+            entry.self_cycles += self_cycles[i];
+
+            // Only count the outermost frame of a recursive function towards its total.
+            let mut ancestor = node.parent;
+            let mut is_recursive = false;
+            while let Some(parent) = ancestor {
+                if self.nodes[parent].function == node.function {
+                    is_recursive = true;
+                    break;
+                }
+                ancestor = self.nodes[parent].parent;
+            }
+            if !is_recursive {
+                entry.total_cycles += subtree_cycles[i];
+            }
+        }
+
+        let mut functions = functions.into_values().collect::<Vec<_>>();
+        functions.sort_by(|a, b| {
+            b.total_cycles
+                .cmp(&a.total_cycles)
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        functions
+    }
+
+    /// The cycles attributed to each syscall, sorted by decreasing number of calls.
+    pub fn syscall_cycles(&self) -> Vec<SyscallCycles> {
+        let mut syscalls = self.syscalls.values().copied().collect::<Vec<_>>();
+        syscalls.sort_by_key(|syscall| (std::cmp::Reverse(syscall.calls), syscall.code as u32));
+        syscalls
+    }
+
+    /// Write the recorded cycles as collapsed stacks, one `frame;frame;... cycles` line per stack.
+    ///
+    /// This is the input format of flamegraph tools such as `inferno-flamegraph` and
+    /// `flamegraph.pl`. Syscalls appear as `[syscall NAME]` frames under their caller.
+    pub fn write_collapsed<W: Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        let mut stacks: HashMap<String, u64> = HashMap::new();
+        for i in 0..self.nodes.len() {
+            // Collect the frames of the path to this node, including the frames inlined at each
+            // call site.
+            let mut path = Vec::new();
+            let mut ancestor = Some(i);
+            while let Some(node) = ancestor {
+                path.push(node);
+                ancestor = self.nodes[node].parent;
+            }
+            path.reverse();
+
+            let mut prefix = Vec::new();
+            for (depth, &node) in path.iter().enumerate() {
+                prefix.push(self.function_name(self.nodes[node].function).to_string());
+                if let Some(&child) = path.get(depth + 1) {
+                    let call_site = self.nodes[child].call_site;
+                    prefix.extend(self.inlined_frames(call_site).iter().cloned());
+                }
+            }
+
+            let node = &self.nodes[i];
+            let pcs = node
+                .cycles
+                .iter()
+                .map(|(&pc, &n)| (pc, n))
+                .collect::<Vec<_>>();
+            let syscalls = node
+                .syscalls
+                .iter()
+                .map(|(&code, &n)| (code, n))
+                .collect::<Vec<_>>();
+            for (pc, cycles) in pcs {
+                let mut frames = prefix.clone();
+                frames.extend(self.inlined_frames(pc).iter().cloned());
+                *stacks.entry(frames.join(";")).or_default() += cycles;
+            }
+            for (code, cycles) in syscalls {
+                let mut frames = prefix.clone();
+                frames.push(format!("[syscall {:?}]", code));
+                *stacks.entry(frames.join(";")).or_default() += cycles;
+            }
+        }
+
+        let mut stacks = stacks.into_iter().collect::<Vec<_>>();
+        stacks.sort();
+        for (stack, cycles) in stacks {
+            writeln!(writer, "{} {}", stack, cycles)?;
+        }
+        Ok(())
+    }
+
+    /// Write a table of the cycles spent in each function and syscall.
+    pub fn write_report<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writeln!(writer, "total cycles: {}", self.total_cycles)?;
+        writeln!(writer)?;
+        writeln!(
+            writer,
+            "{:>12} {:>12} {:>8}  function",
+            "total", "self", "calls"
+        )?;
+        for function in self.function_cycles() {
+            writeln!(
+                writer,
+                "{:>12} {:>12} {:>8}  {}",
+                function.total_cycles, function.self_cycles, function.calls, function.name
+            )?;
+        }
+
+        let syscalls = self.syscall_cycles();
+        if !syscalls.is_empty() {
+            writeln!(writer)?;
+            writeln!(writer, "{:>12} {:>8}  syscall", "extra", "calls")?;
+            for syscall in syscalls {
+                writeln!(
+                    writer,
+                    "{:>12} {:>8}  {:?}",
+                    syscall.extra_cycles, syscall.calls, syscall.code
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Get or create the child of `parent` for a call to `function` from `call_site`.
+    fn node(&mut self, parent: Option<usize>, call_site: u32, function: Option<usize>) -> usize {
+        if let Some(parent) = parent {
+            if let Some(&child) = self.nodes[parent].children.get(&(call_site, function)) {
+                return child;
+            }
+        } else if let Some(root) = self
+            .nodes
+            .iter()
+            .position(|node| node.parent.is_none() && node.function == function)
+        {
+            return root;
+        }
+
+        let index = self.nodes.len();
+        self.nodes.push(CallNode {
+            parent,
+            call_site,
+            function,
+            cycles: HashMap::new(),
+            syscalls: HashMap::new(),
+            children: HashMap::new(),
+        });
+        if let Some(parent) = parent {
+            self.nodes[parent]
+                .children
+                .insert((call_site, function), index);
+        }
+        index
+    }
+
+    fn function_name(&self, function: Option<usize>) -> &str {
+        function.map_or(UNKNOWN_FUNCTION, |index| &self.symbols.get(index).name)
+    }
+
+    /// The functions inlined at `pc`, from the outermost to the innermost.
+    fn inlined_frames(&mut self, pc: u32) -> &[String] {
+        let dwarf = &self.dwarf;
+        self.inlined.entry(pc).or_insert_with(|| {
+            let mut names = Vec::new();
+            if let Some(dwarf) = dwarf {
+                if let Ok(mut frames) = dwarf.find_frames(pc as u64).skip_all_loads() {
+                    while let Ok(Some(frame)) = frames.next() {
+                        if let Some(Ok(name)) = frame.function.as_ref().map(|f| f.demangle()) {
+                            names.push(name.into_owned());
+                        }
+                    }
+                }
+            }
+            // The frames are innermost first and the last one is the symbol's own function.
+            names.pop();
+            names.reverse();
+            names
+        })
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::runtime::{Program, Runtime};
+    use crate::utils::tests::{FIBONACCI_ELF, SHA_EXTEND_ELF};
+
+    fn profile(elf: &[u8]) -> (Profiler, u64) {
+        let mut runtime = Runtime::new(Program::from(elf));
+        runtime.profiler = Some(Profiler::new(elf));
+        runtime.run();
+        (runtime.profiler.take().unwrap(), runtime.state.global_clk)
+    }
+
+    #[test]
+    fn test_profile_fibonacci() {
+        let (mut profiler, cycles) = profile(FIBONACCI_ELF);
+        assert_eq!(profiler.total_cycles(), cycles);
+
+        let functions = profiler.function_cycles();
+        assert_eq!(functions.iter().map(|f| f.self_cycles).sum::<u64>(), cycles);
+        assert!(functions
+            .iter()
+            .any(|f| f.name == "main" || f.name.ends_with("::main")));
+
+        let mut collapsed = Vec::new();
+        profiler.write_collapsed(&mut collapsed).unwrap();
+        let collapsed_cycles = String::from_utf8(collapsed)
+            .unwrap()
+            .lines()
+            .map(|line| line.rsplit_once(' ').unwrap().1.parse::<u64>().unwrap())
+            .sum::<u64>();
+        assert_eq!(collapsed_cycles, cycles);
+    }
+
+    #[test]
+    fn test_profile_syscalls() {
+        let (profiler, _) = profile(SHA_EXTEND_ELF);
+        let sha_extend = profiler
+            .syscall_cycles()
+            .into_iter()
+            .find(|syscall| syscall.code == SyscallCode::SHA_EXTEND)
+            .unwrap();
+        assert_eq!(sha_extend.calls, 3);
+        assert_eq!(sha_extend.extra_cycles, 3 * 48);
+    }
+}
@@ -2,6 +2,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
 use super::Instruction;
+use crate::disassembler::SymbolTable;
 
 /// A program that can be executed by the VM.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -17,4 +18,88 @@ pub struct Program {
 
     /// The initial memory image, useful for global constants.
     pub memory_image: BTreeMap<u32, u32>,
+
+    /// The function symbols of the program, used to symbolize guest panics.
+    #[serde(skip)]
+    pub symbols: SymbolTable,
+
+    /// The index of the instruction at each halfword of the program, or `u32::MAX` for the upper
+    /// halfword of a 32-bit instruction.
+    ///
+    /// This is only needed when the program has compressed instructions. Otherwise it is empty and
+    /// the instruction at `pc` has index `(pc - pc_base) / 4`.
+    #[serde(default)]
+    pub pc_index: Vec<u32>,
+}
+
+impl Program {
+    /// Builds the `pc_index` of a program with the given instructions.
+    pub(crate) fn index_instructions(instructions: &[Instruction]) -> Vec<u32> {
+        if !instructions
+            .iter()
+            .any(|instruction| instruction.compressed)
+        {
+            return Vec::new();
+        }
+        let mut pc_index = Vec::new();
+        for (i, instruction) in instructions.iter().enumerate() {
+            pc_index.push(i as u32);
+            if !instruction.compressed {
+                pc_index.push(u32::MAX);
+            }
+        }
+        pc_index
+    }
+
+    /// The index of the instruction at `pc`, or `None` if no instruction starts at `pc`, e.g. when
+    /// it is misaligned or points into the upper halfword of a 32-bit instruction.
+    #[inline]
+    pub fn instruction_index(&self, pc: u32) -> Option<usize> {
+        let offset = pc.wrapping_sub(self.pc_base);
+        let index = if self.pc_index.is_empty() {
+            if offset % 4 != 0 {
+                return None;
+            }
+            offset / 4
+        } else {
+            if offset % 2 != 0 {
+                return None;
+            }
+            *self.pc_index.get((offset / 2) as usize)?
+        };
+        (index != u32::MAX && (index as usize) < self.instructions.len()).then_some(index as usize)
+    }
+
+    /// The size of the program's code in bytes.
+    #[inline]
+    pub fn code_size(&self) -> u32 {
+        if self.pc_index.is_empty() {
+            self.instructions.len() as u32 * 4
+        } else {
+            self.pc_index.len() as u32 * 2
+        }
+    }
+
+    /// The pc of the instruction before the one at `pc`.
+    pub fn previous_pc(&self, pc: u32) -> u32 {
+        let pc = pc.wrapping_sub(2);
+        match self
+            .pc_index
+            .get((pc.wrapping_sub(self.pc_base) / 2) as usize)
+        {
+            Some(&index) if index != u32::MAX => pc,
+            _ => pc.wrapping_sub(2),
+        }
+    }
+
+    /// The pc of each instruction, in order.
+    pub fn instruction_pcs(&self) -> impl Iterator<Item = u32> + '_ {
+        self.instructions
+            .iter()
+            .scan(self.pc_base, |pc, instruction| {
+                let instruction_pc = *pc;
+                *pc += instruction.size();
+                Some(instruction_pc)
+            })
+    }
 }
@@ -9,6 +9,7 @@ use serde::{Deserialize, Serialize};
 
 use super::program::Program;
 use super::Opcode;
+use super::SyscallCode;
 use crate::air::PublicValues;
 use crate::alu::AluEvent;
 use crate::bytes::{ByteLookupEvent, ByteOpcode};
@@ -16,12 +17,20 @@ use crate::cpu::CpuEvent;
 use crate::runtime::MemoryInitializeFinalizeEvent;
 use crate::runtime::MemoryRecordEnum;
 use crate::stark::MachineRecord;
+use crate::syscall::precompiles::bigint::BigIntMulModEvent;
+use crate::syscall::precompiles::blake2b::Blake2bCompressEvent;
 use crate::syscall::precompiles::blake3::Blake3CompressInnerEvent;
-use crate::syscall::precompiles::edwards::EdDecompressEvent;
+use crate::syscall::precompiles::edwards::{EdCompressEvent, EdDecompressEvent, EdScalarMulEvent};
+use crate::syscall::precompiles::fptower::Fp2Event;
 use crate::syscall::precompiles::k256::K256DecompressEvent;
-use crate::syscall::precompiles::keccak256::KeccakPermuteEvent;
+use crate::syscall::precompiles::keccak256::{Keccak256HashEvent, KeccakPermuteEvent};
+use crate::syscall::precompiles::memcpy::MemCopyEvent;
+use crate::syscall::precompiles::poseidon2::Poseidon2PermuteEvent;
+use crate::syscall::precompiles::ripemd160::Ripemd160CompressEvent;
 use crate::syscall::precompiles::sha256::{ShaCompressEvent, ShaExtendEvent};
-use crate::syscall::precompiles::{ECAddEvent, ECDoubleEvent};
+use crate::syscall::precompiles::sha512::{Sha512CompressEvent, Sha512ExtendEvent};
+use crate::syscall::precompiles::uint256::Uint256MulModEvent;
+use crate::syscall::precompiles::{ECAddEvent, ECDecompressEvent, ECDoubleEvent, ECMsmEvent};
 use crate::utils::env;
 
 /// A record of the execution of a program. Contains event data for everything that happened during
@@ -75,13 +84,43 @@ pub struct ExecutionRecord {
 
     pub ed_decompress_events: Vec<EdDecompressEvent>,
 
-    pub secp256k1_add_events: Vec<ECAddEvent>,
+    pub ed_scalar_mul_events: Vec<EdScalarMulEvent>,
 
-    pub secp256k1_double_events: Vec<ECDoubleEvent>,
+    pub ed_compress_events: Vec<EdCompressEvent>,
 
-    pub bn254_add_events: Vec<ECAddEvent>,
+    pub ripemd160_compress_events: Vec<Ripemd160CompressEvent>,
 
-    pub bn254_double_events: Vec<ECDoubleEvent>,
+    pub bigint_mulmod_events: Vec<BigIntMulModEvent>,
+
+    /// The add events of each short Weierstrass curve, keyed by the curve's add syscall.
+    pub weierstrass_add_events: BTreeMap<SyscallCode, Vec<ECAddEvent>>,
+
+    /// The double events of each short Weierstrass curve, keyed by the curve's double syscall.
+    pub weierstrass_double_events: BTreeMap<SyscallCode, Vec<ECDoubleEvent>>,
+
+    /// The decompress events of each short Weierstrass curve, keyed by the curve's decompress
+    /// syscall.
+    pub weierstrass_decompress_events: BTreeMap<SyscallCode, Vec<ECDecompressEvent>>,
+
+    /// The MSM events of each short Weierstrass curve, keyed by the curve's MSM syscall.
+    pub weierstrass_msm_events: BTreeMap<SyscallCode, Vec<ECMsmEvent>>,
+
+    /// The Fp2 events of each base field and operation, keyed by their syscall.
+    pub fp2_events: BTreeMap<SyscallCode, Vec<Fp2Event>>,
+
+    pub uint256_mulmod_events: Vec<Uint256MulModEvent>,
+
+    pub sha512_extend_events: Vec<Sha512ExtendEvent>,
+
+    pub sha512_compress_events: Vec<Sha512CompressEvent>,
+
+    pub blake2b_compress_events: Vec<Blake2bCompressEvent>,
+
+    pub poseidon2_permute_events: Vec<Poseidon2PermuteEvent>,
+
+    pub keccak256_hash_events: Vec<Keccak256HashEvent>,
+
+    pub memcpy_events: Vec<MemCopyEvent>,
 
     pub k256_decompress_events: Vec<K256DecompressEvent>,
 
@@ -109,21 +148,13 @@ pub struct ShardingConfig {
     pub lt_len: usize,
     pub field_len: usize,
     pub keccak_len: usize,
-    pub secp256k1_add_len: usize,
-    pub secp256k1_double_len: usize,
-    pub bn254_add_len: usize,
-    pub bn254_double_len: usize,
+    pub weierstrass_add_len: usize,
+    pub weierstrass_double_len: usize,
 }
 
 impl ShardingConfig {
-    pub const fn shard_size(&self) -> usize {
-        self.shard_size
-    }
-}
-
-impl Default for ShardingConfig {
-    fn default() -> Self {
-        let shard_size = env::shard_size();
+    /// A config with shards of `shard_size` cycles, in which each table has as many rows.
+    pub fn new(shard_size: usize) -> Self {
         Self {
             shard_size,
             add_len: shard_size,
@@ -136,12 +167,20 @@ impl Default for ShardingConfig {
             shift_right_len: shard_size,
             field_len: shard_size * 4,
             keccak_len: shard_size,
-            secp256k1_add_len: shard_size,
-            secp256k1_double_len: shard_size,
-            bn254_add_len: shard_size,
-            bn254_double_len: shard_size,
+            weierstrass_add_len: shard_size,
+            weierstrass_double_len: shard_size,
         }
     }
+
+    pub const fn shard_size(&self) -> usize {
+        self.shard_size
+    }
+}
+
+impl Default for ShardingConfig {
+    fn default() -> Self {
+        Self::new(env::shard_size())
+    }
 }
 
 impl MachineRecord for ExecutionRecord {
@@ -190,18 +229,51 @@ impl MachineRecord for ExecutionRecord {
             self.ed_decompress_events.len(),
         );
         stats.insert(
-            "secp256k1_add_events".to_string(),
-            self.secp256k1_add_events.len(),
+            "ed_scalar_mul_events".to_string(),
+            self.ed_scalar_mul_events.len(),
+        );
+        stats.insert(
+            "ed_compress_events".to_string(),
+            self.ed_compress_events.len(),
+        );
+        stats.insert(
+            "ripemd160_compress_events".to_string(),
+            self.ripemd160_compress_events.len(),
+        );
+        stats.insert(
+            "bigint_mulmod_events".to_string(),
+            self.bigint_mulmod_events.len(),
+        );
+        insert_event_stats(&mut stats, &self.weierstrass_add_events);
+        insert_event_stats(&mut stats, &self.weierstrass_double_events);
+        insert_event_stats(&mut stats, &self.weierstrass_decompress_events);
+        insert_event_stats(&mut stats, &self.weierstrass_msm_events);
+        insert_event_stats(&mut stats, &self.fp2_events);
+        stats.insert(
+            "uint256_mulmod_events".to_string(),
+            self.uint256_mulmod_events.len(),
+        );
+        stats.insert(
+            "sha512_extend_events".to_string(),
+            self.sha512_extend_events.len(),
+        );
+        stats.insert(
+            "sha512_compress_events".to_string(),
+            self.sha512_compress_events.len(),
+        );
+        stats.insert(
+            "blake2b_compress_events".to_string(),
+            self.blake2b_compress_events.len(),
         );
         stats.insert(
-            "secp256k1_double_events".to_string(),
-            self.secp256k1_double_events.len(),
+            "poseidon2_permute_events".to_string(),
+            self.poseidon2_permute_events.len(),
         );
-        stats.insert("bn254_add_events".to_string(), self.bn254_add_events.len());
         stats.insert(
-            "bn254_double_events".to_string(),
-            self.bn254_double_events.len(),
+            "keccak256_hash_events".to_string(),
+            self.keccak256_hash_events.len(),
         );
+        stats.insert("memcpy_events".to_string(), self.memcpy_events.len());
         stats.insert(
             "k256_decompress_events".to_string(),
             self.k256_decompress_events.len(),
@@ -232,13 +304,44 @@ impl MachineRecord for ExecutionRecord {
         self.ed_add_events.append(&mut other.ed_add_events);
         self.ed_decompress_events
             .append(&mut other.ed_decompress_events);
-        self.secp256k1_add_events
-            .append(&mut other.secp256k1_add_events);
-        self.secp256k1_double_events
-            .append(&mut other.secp256k1_double_events);
-        self.bn254_add_events.append(&mut other.bn254_add_events);
-        self.bn254_double_events
-            .append(&mut other.bn254_double_events);
+        self.ed_scalar_mul_events
+            .append(&mut other.ed_scalar_mul_events);
+        self.ed_compress_events
+            .append(&mut other.ed_compress_events);
+        self.ripemd160_compress_events
+            .append(&mut other.ripemd160_compress_events);
+        self.bigint_mulmod_events
+            .append(&mut other.bigint_mulmod_events);
+        append_events(
+            &mut self.weierstrass_add_events,
+            &mut other.weierstrass_add_events,
+        );
+        append_events(
+            &mut self.weierstrass_double_events,
+            &mut other.weierstrass_double_events,
+        );
+        append_events(
+            &mut self.weierstrass_decompress_events,
+            &mut other.weierstrass_decompress_events,
+        );
+        append_events(
+            &mut self.weierstrass_msm_events,
+            &mut other.weierstrass_msm_events,
+        );
+        append_events(&mut self.fp2_events, &mut other.fp2_events);
+        self.uint256_mulmod_events
+            .append(&mut other.uint256_mulmod_events);
+        self.sha512_extend_events
+            .append(&mut other.sha512_extend_events);
+        self.sha512_compress_events
+            .append(&mut other.sha512_compress_events);
+        self.blake2b_compress_events
+            .append(&mut other.blake2b_compress_events);
+        self.poseidon2_permute_events
+            .append(&mut other.poseidon2_permute_events);
+        self.keccak256_hash_events
+            .append(&mut other.keccak256_hash_events);
+        self.memcpy_events.append(&mut other.memcpy_events);
         self.k256_decompress_events
             .append(&mut other.k256_decompress_events);
         self.blake3_compress_inner_events
@@ -400,42 +503,28 @@ impl MachineRecord for ExecutionRecord {
             shard.keccak_permute_events.extend_from_slice(keccak_chunk);
         }
 
-        // secp256k1 curve add events.
-        for (secp256k1_add_chunk, shard) in take(&mut self.secp256k1_add_events)
-            .chunks_mut(config.secp256k1_add_len)
-            .zip(shards.iter_mut())
-        {
-            shard
-                .secp256k1_add_events
-                .extend_from_slice(secp256k1_add_chunk);
-        }
-
-        // secp256k1 curve double events.
-        for (secp256k1_double_chunk, shard) in take(&mut self.secp256k1_double_events)
-            .chunks_mut(config.secp256k1_double_len)
-            .zip(shards.iter_mut())
-        {
-            shard
-                .secp256k1_double_events
-                .extend_from_slice(secp256k1_double_chunk);
-        }
-
-        // bn254 curve add events.
-        for (bn254_add_chunk, shard) in take(&mut self.bn254_add_events)
-            .chunks_mut(config.bn254_add_len)
-            .zip(shards.iter_mut())
-        {
-            shard.bn254_add_events.extend_from_slice(bn254_add_chunk);
+        // Short Weierstrass curve add events.
+        for (code, events) in take(&mut self.weierstrass_add_events) {
+            for (add_chunk, shard) in events
+                .chunks(config.weierstrass_add_len)
+                .zip(shards.iter_mut())
+            {
+                shard
+                    .weierstrass_add_events
+                    .insert(code, add_chunk.to_vec());
+            }
         }
 
-        // bn254 curve double events.
-        for (bn254_double_chunk, shard) in take(&mut self.bn254_double_events)
-            .chunks_mut(config.bn254_double_len)
-            .zip(shards.iter_mut())
-        {
-            shard
-                .bn254_double_events
-                .extend_from_slice(bn254_double_chunk);
+        // Short Weierstrass curve double events.
+        for (code, events) in take(&mut self.weierstrass_double_events) {
+            for (double_chunk, shard) in events
+                .chunks(config.weierstrass_double_len)
+                .zip(shards.iter_mut())
+            {
+                shard
+                    .weierstrass_double_events
+                    .insert(code, double_chunk.to_vec());
+            }
         }
 
         // Put the precompile events in the first shard.
@@ -453,9 +542,52 @@ impl MachineRecord for ExecutionRecord {
         // Edwards curve decompress events.
         first.ed_decompress_events = std::mem::take(&mut self.ed_decompress_events);
 
+        // Edwards curve scalar multiplication events.
+        first.ed_scalar_mul_events = std::mem::take(&mut self.ed_scalar_mul_events);
+
+        // Edwards curve compress events.
+        first.ed_compress_events = std::mem::take(&mut self.ed_compress_events);
+
+        // RIPEMD-160 compress events.
+        first.ripemd160_compress_events = std::mem::take(&mut self.ripemd160_compress_events);
+
+        // BigInt mulmod events.
+        first.bigint_mulmod_events = std::mem::take(&mut self.bigint_mulmod_events);
+
         // K256 curve decompress events.
         first.k256_decompress_events = std::mem::take(&mut self.k256_decompress_events);
 
+        // Short Weierstrass curve decompress events.
+        first.weierstrass_decompress_events =
+            std::mem::take(&mut self.weierstrass_decompress_events);
+
+        // Fp2 events.
+        first.fp2_events = std::mem::take(&mut self.fp2_events);
+
+        // Uint256 mulmod events.
+        first.uint256_mulmod_events = std::mem::take(&mut self.uint256_mulmod_events);
+
+        // SHA-512 extend events.
+        first.sha512_extend_events = std::mem::take(&mut self.sha512_extend_events);
+
+        // SHA-512 compress events.
+        first.sha512_compress_events = std::mem::take(&mut self.sha512_compress_events);
+
+        // Blake2b compress events.
+        first.blake2b_compress_events = std::mem::take(&mut self.blake2b_compress_events);
+
+        // Poseidon2 permute events.
+        first.poseidon2_permute_events = std::mem::take(&mut self.poseidon2_permute_events);
+
+        // Keccak256 hash events.
+        first.keccak256_hash_events = std::mem::take(&mut self.keccak256_hash_events);
+
+        // Short Weierstrass curve msm events.
+        first.weierstrass_msm_events = std::mem::take(&mut self.weierstrass_msm_events);
+
+        // Memory copy events.
+        first.memcpy_events = std::mem::take(&mut self.memcpy_events);
+
         // Blake3 compress events .
         first.blake3_compress_inner_events = std::mem::take(&mut self.blake3_compress_inner_events);
 
@@ -603,6 +735,26 @@ impl ExecutionRecord {
     }
 }
 
+/// Appends the events of each syscall in `other` to those of the same syscall in `events`.
+fn append_events<E>(
+    events: &mut BTreeMap<SyscallCode, Vec<E>>,
+    other: &mut BTreeMap<SyscallCode, Vec<E>>,
+) {
+    for (code, mut other_events) in take(other) {
+        events.entry(code).or_default().append(&mut other_events);
+    }
+}
+
+/// Inserts the number of events of each syscall in `events` into `stats`, named after the syscall.
+fn insert_event_stats<E>(
+    stats: &mut HashMap<String, usize>,
+    events: &BTreeMap<SyscallCode, Vec<E>>,
+) {
+    for (code, events) in events.iter() {
+        stats.insert(format!("{:?}_events", code).to_lowercase(), events.len());
+    }
+}
+
 #[derive(Debug, Copy, Clone, Default)]
 pub struct MemoryAccessRecord {
     pub a: Option<MemoryRecordEnum>,
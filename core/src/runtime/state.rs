@@ -9,7 +9,7 @@ use crate::{
     utils::BabyBearPoseidon2Inner,
 };
 
-use super::{ExecutionRecord, MemoryAccessRecord, MemoryRecord};
+use super::{ExecutionRecord, GuestEnv, MemoryAccessRecord, MemoryRecord, OpenFile};
 
 /// Holds data describing the current state of a program's execution.
 #[serde_as]
@@ -43,7 +43,6 @@ pub struct ExecutionState {
     pub input_stream_ptr: usize,
 
     /// A stream of proofs inputted to the program.
-    #[serde(skip)] // TODO: fix serialization for VerifyingKey
     pub proof_stream: Vec<(
         Proof<BabyBearPoseidon2Inner>,
         VerifyingKey<BabyBearPoseidon2Inner>,
@@ -52,6 +51,12 @@ pub struct ExecutionState {
     /// A ptr to the current position in the proof stream, incremented after verifying a proof.
     pub proof_stream_ptr: usize,
 
+    /// The environment variables, arguments, random seed and files provided to the program.
+    pub env: GuestEnv,
+
+    /// The files opened by the program, indexed by their file descriptor.
+    pub open_files: Vec<OpenFile>,
+
     /// A stream of public values from the program (global to entire program).
     pub public_values_stream: Vec<u8>,
 
@@ -75,6 +80,8 @@ impl ExecutionState {
             public_values_stream_ptr: 0,
             proof_stream: Vec::new(),
             proof_stream_ptr: 0,
+            env: GuestEnv::default(),
+            open_files: Vec::new(),
         }
     }
 }
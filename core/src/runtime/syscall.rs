@@ -1,122 +1,290 @@
 use std::collections::HashMap;
 use std::rc::Rc;
 
+use serde::{Deserialize, Serialize};
 use strum_macros::EnumIter;
 
 use crate::runtime::{Register, Runtime};
+use crate::syscall::precompiles::bigint::BigIntMulModChip;
+use crate::syscall::precompiles::blake2b::Blake2bCompressChip;
 use crate::syscall::precompiles::edwards::EdAddAssignChip;
+use crate::syscall::precompiles::edwards::EdCompressChip;
 use crate::syscall::precompiles::edwards::EdDecompressChip;
+use crate::syscall::precompiles::edwards::EdScalarMulChip;
+use crate::syscall::precompiles::fptower::{Fp2AddSubAssignChip, Fp2MulAssignChip, Fp2Operation};
 use crate::syscall::precompiles::k256::K256DecompressChip;
-use crate::syscall::precompiles::keccak256::KeccakPermuteChip;
+use crate::syscall::precompiles::keccak256::{Keccak256HashChip, KeccakPermuteChip};
+use crate::syscall::precompiles::memcpy::MemCopyChip;
+use crate::syscall::precompiles::poseidon2::Poseidon2PermuteChip;
+use crate::syscall::precompiles::ripemd160::Ripemd160CompressChip;
 use crate::syscall::precompiles::sha256::{ShaCompressChip, ShaExtendChip};
-use crate::syscall::precompiles::weierstrass::WeierstrassAddAssignChip;
-use crate::syscall::precompiles::weierstrass::WeierstrassDoubleAssignChip;
+use crate::syscall::precompiles::sha512::{Sha512CompressChip, Sha512ExtendChip};
+use crate::syscall::precompiles::uint256::Uint256MulModChip;
+use crate::syscall::precompiles::weierstrass::insert_weierstrass_syscalls;
+use crate::syscall::precompiles::weierstrass::weierstrass_curves;
 use crate::syscall::{
-    SyscallCommit, SyscallCommitDeferred, SyscallEnterUnconstrained, SyscallExitUnconstrained,
-    SyscallHalt, SyscallHintLen, SyscallHintRead, SyscallVerifySP1Proof, SyscallWrite,
+    SyscallArgc, SyscallArgv, SyscallCommit, SyscallCommitDeferred, SyscallCommittedEnv,
+    SyscallEnterUnconstrained, SyscallExitUnconstrained, SyscallFsOpen, SyscallFsRead,
+    SyscallFsSeek, SyscallGetenv, SyscallHalt, SyscallHintLen, SyscallHintRead, SyscallRandomSeed,
+    SyscallVerifySP1Proof, SyscallWrite,
 };
 use crate::utils::ec::edwards::ed25519::{Ed25519, Ed25519Parameters};
-use crate::utils::ec::weierstrass::{bn254::Bn254, secp256k1::Secp256k1};
+use crate::utils::ec::weierstrass::{bls12381::Bls12381BaseField, bn254::Bn254BaseField};
 use crate::{runtime::ExecutionRecord, runtime::MemoryReadRecord, runtime::MemoryWriteRecord};
 
-/// A system call is invoked by the the `ecall` instruction with a specific value in register t0.
-/// The syscall number is a 32-bit integer, with the following layout (in litte-endian format)
-/// - The first byte is the syscall id.
-/// - The second byte is 0/1 depending on whether the syscall has a separate table. This is used
-/// in the CPU table to determine whether to lookup the syscall using the syscall interaction.
-/// - The third byte is the number of additional cycles the syscall uses.
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, EnumIter)]
-#[allow(non_camel_case_types)]
-pub enum SyscallCode {
-    /// Halts the program.
-    HALT = 0x00_00_00_00,
+/// Defines [`SyscallCode`], with the syscalls of the curves of [`weierstrass_curves`].
+macro_rules! define_syscall_code {
+    ($(
+        $params:ident {
+            name: $name:literal,
+            add: $add_variant:ident($add:ident = $add_code:literal),
+            double: $double_variant:ident($double:ident = $double_code:literal),
+            $(decompress: $decompress_variant:ident(
+                $decompress:ident = $decompress_code:literal,
+                $sqrt:ident
+            ),)?
+            $(msm: $msm_variant:ident($msm:ident = $msm_code:literal),)?
+        }
+    )*) => {
+        /// A system call is invoked by the the `ecall` instruction with a specific value in
+        /// register t0. The syscall number is a 32-bit integer, with the following layout (in
+        /// litte-endian format)
+        /// - The first byte is the syscall id.
+        /// - The second byte is 0/1 depending on whether the syscall has a separate table. This is
+        /// used in the CPU table to determine whether to lookup the syscall using the syscall
+        /// interaction.
+        /// - The third and fourth bytes are the number of additional cycles the syscall uses, as a
+        /// little-endian u16. The fourth byte used to be reserved for whether the syscall halts,
+        /// which the CPU table never read since it identifies `HALT` by its id, so it was zero for
+        /// every syscall and their codes are unchanged. Only the MSM syscalls take more than 255
+        /// cycles.
+        ///
+        /// The number of cycles is fixed for each syscall, so a syscall whose work depends on its
+        /// input reserves the cycles of its largest input. For instance, an MSM reserves the cycles
+        /// of `MAX_MSM_POINTS` points whatever the number of points. The runtime moves to the next
+        /// shard before a syscall whose cycles don't fit in the current one, so only the shards
+        /// that contain an MSM give up rows for it.
+        ///
+        /// The add and double syscalls of the short Weierstrass curves are listed in
+        /// [`weierstrass_curves`].
+        #[derive(
+            Debug,
+            Copy,
+            Clone,
+            PartialEq,
+            Eq,
+            PartialOrd,
+            Ord,
+            Hash,
+            EnumIter,
+            Serialize,
+            Deserialize,
+        )]
+        #[allow(non_camel_case_types)]
+        pub enum SyscallCode {
+            /// Halts the program.
+            HALT = 0x00_00_00_00,
+
+            /// Write to the output buffer.
+            WRITE = 0x00_00_00_02,
 
-    /// Write to the output buffer.
-    WRITE = 0x00_00_00_02,
+            /// Enter unconstrained block.
+            ENTER_UNCONSTRAINED = 0x00_00_00_03,
 
-    /// Enter unconstrained block.
-    ENTER_UNCONSTRAINED = 0x00_00_00_03,
+            /// Exit unconstrained block.
+            EXIT_UNCONSTRAINED = 0x00_00_00_04,
 
-    /// Exit unconstrained block.
-    EXIT_UNCONSTRAINED = 0x00_00_00_04,
+            /// Executes the `SHA_EXTEND` precompile.
+            SHA_EXTEND = 0x00_30_01_05,
 
-    /// Executes the `SHA_EXTEND` precompile.
-    SHA_EXTEND = 0x00_30_01_05,
+            /// Executes the `SHA_COMPRESS` precompile.
+            SHA_COMPRESS = 0x00_01_01_06,
 
-    /// Executes the `SHA_COMPRESS` precompile.
-    SHA_COMPRESS = 0x00_01_01_06,
+            /// Executes the `ED_ADD` precompile.
+            ED_ADD = 0x00_01_01_07,
 
-    /// Executes the `ED_ADD` precompile.
-    ED_ADD = 0x00_01_01_07,
+            /// Executes the `ED_DECOMPRESS` precompile.
+            ED_DECOMPRESS = 0x00_00_01_08,
 
-    /// Executes the `ED_DECOMPRESS` precompile.
-    ED_DECOMPRESS = 0x00_00_01_08,
+            /// Executes the `KECCAK_PERMUTE` precompile.
+            KECCAK_PERMUTE = 0x00_01_01_09,
 
-    /// Executes the `KECCAK_PERMUTE` precompile.
-    KECCAK_PERMUTE = 0x00_01_01_09,
+            /// Executes the `SECP256K1_DECOMPRESS` precompile.
+            SECP256K1_DECOMPRESS = 0x00_00_01_0C,
 
-    /// Executes the `SECP256K1_ADD` precompile.
-    SECP256K1_ADD = 0x00_01_01_0A,
+            /// Executes the `BLAKE3_COMPRESS_INNER` precompile.
+            BLAKE3_COMPRESS_INNER = 0x00_38_01_0D,
 
-    /// Executes the `SECP256K1_DOUBLE` precompile.
-    SECP256K1_DOUBLE = 0x00_00_01_0B,
+            /// Executes the `COMMIT` precompile.
+            COMMIT = 0x00_00_00_10,
 
-    /// Executes the `SECP256K1_DECOMPRESS` precompile.
-    SECP256K1_DECOMPRESS = 0x00_00_01_0C,
+            /// Executes the `BN254_FP2_ADD` precompile.
+            BN254_FP2_ADD = 0x00_01_01_17,
 
-    /// Executes the `BLAKE3_COMPRESS_INNER` precompile.
-    BLAKE3_COMPRESS_INNER = 0x00_38_01_0D,
+            /// Executes the `BN254_FP2_SUB` precompile.
+            BN254_FP2_SUB = 0x00_01_01_18,
 
-    /// Executes the `BN254_ADD` precompile.
-    BN254_ADD = 0x00_01_01_0E,
+            /// Executes the `BN254_FP2_MUL` precompile.
+            BN254_FP2_MUL = 0x00_01_01_19,
 
-    /// Executes the `BN254_DOUBLE` precompile.
-    BN254_DOUBLE = 0x00_00_01_0F,
+            /// Executes the `COMMIT_DEFERRED_PROOFS` precompile.
+            COMMIT_DEFERRED_PROOFS = 0x00_00_00_1A,
 
-    /// Executes the `COMMIT` precompile.
-    COMMIT = 0x00_00_00_10,
+            /// Executes the `VERIFY_SP1_PROOF` precompile.
+            VERIFY_SP1_PROOF = 0x00_00_00_1B,
 
-    /// Executes the `COMMIT_DEFERRED_PROOFS` precompile.
-    COMMIT_DEFERRED_PROOFS = 0x00_00_00_1A,
+            /// Executes the `BLS12381_FP2_ADD` precompile.
+            BLS12381_FP2_ADD = 0x00_01_01_1C,
 
-    /// Executes the `VERIFY_SP1_PROOF` precompile.
-    VERIFY_SP1_PROOF = 0x00_00_00_1B,
+            /// Executes the `BLS12381_FP2_SUB` precompile.
+            BLS12381_FP2_SUB = 0x00_01_01_1D,
 
-    /// Executes the `HINT_LEN` precompile.
-    HINT_LEN = 0x00_00_00_F0,
+            /// Executes the `BLS12381_FP2_MUL` precompile.
+            BLS12381_FP2_MUL = 0x00_01_01_1E,
 
-    /// Executes the `HINT_READ` precompile.
-    HINT_READ = 0x00_00_00_F1,
-}
+            /// Executes the `UINT256_MULMOD` precompile.
+            UINT256_MULMOD = 0x00_01_01_1F,
 
-impl SyscallCode {
-    /// Create a syscall from a u32.
-    pub fn from_u32(value: u32) -> Self {
-        match value {
-            0x00_00_00_00 => SyscallCode::HALT,
-            0x00_00_00_02 => SyscallCode::WRITE,
-            0x00_00_00_03 => SyscallCode::ENTER_UNCONSTRAINED,
-            0x00_00_00_04 => SyscallCode::EXIT_UNCONSTRAINED,
-            0x00_30_01_05 => SyscallCode::SHA_EXTEND,
-            0x00_01_01_06 => SyscallCode::SHA_COMPRESS,
-            0x00_01_01_07 => SyscallCode::ED_ADD,
-            0x00_00_01_08 => SyscallCode::ED_DECOMPRESS,
-            0x00_01_01_09 => SyscallCode::KECCAK_PERMUTE,
-            0x00_01_01_0A => SyscallCode::SECP256K1_ADD,
-            0x00_00_01_0B => SyscallCode::SECP256K1_DOUBLE,
-            0x00_00_01_0C => SyscallCode::SECP256K1_DECOMPRESS,
-            0x00_38_01_0D => SyscallCode::BLAKE3_COMPRESS_INNER,
-            0x00_01_01_0E => SyscallCode::BN254_ADD,
-            0x00_00_01_0F => SyscallCode::BN254_DOUBLE,
-            0x00_00_00_10 => SyscallCode::COMMIT,
-            0x00_00_00_1A => SyscallCode::COMMIT_DEFERRED_PROOFS,
-            0x00_00_00_1B => SyscallCode::VERIFY_SP1_PROOF,
-            0x00_00_00_F0 => SyscallCode::HINT_LEN,
-            0x00_00_00_F1 => SyscallCode::HINT_READ,
-            _ => panic!("invalid syscall number: {}", value),
+            /// Executes the `SHA512_EXTEND` precompile.
+            SHA512_EXTEND = 0x00_40_01_20,
+
+            /// Executes the `SHA512_COMPRESS` precompile.
+            SHA512_COMPRESS = 0x00_01_01_21,
+
+            /// Executes the `BLAKE2B_COMPRESS` precompile.
+            BLAKE2B_COMPRESS = 0x00_01_01_22,
+
+            /// Executes the `POSEIDON2_PERMUTE` precompile.
+            POSEIDON2_PERMUTE = 0x00_01_01_23,
+
+            /// Executes the `KECCAK256_HASH` precompile.
+            KECCAK256_HASH = 0x00_01_01_24,
+
+            /// Executes the `MEMCPY` precompile.
+            MEMCPY = 0x00_01_01_27,
+
+            /// Executes the `ED_SCALAR_MUL` precompile.
+            ED_SCALAR_MUL = 0x00_80_01_28,
+
+            /// Executes the `ED_COMPRESS` precompile.
+            ED_COMPRESS = 0x00_00_01_29,
+
+            /// Executes the `RIPEMD160_COMPRESS` precompile.
+            RIPEMD160_COMPRESS = 0x00_01_01_2A,
+
+            /// Executes the `BIGINT_MULMOD` precompile.
+            BIGINT_MULMOD = 0x00_01_01_2B,
+
+            /// Executes the `HINT_LEN` precompile.
+            HINT_LEN = 0x00_00_00_F0,
+
+            /// Executes the `HINT_READ` precompile.
+            HINT_READ = 0x00_00_00_F1,
+
+            /// Looks up an environment variable of the guest.
+            GETENV = 0x00_00_00_F2,
+
+            /// Returns the number of arguments of the guest.
+            ARGC = 0x00_00_00_F3,
+
+            /// Looks up an argument of the guest.
+            ARGV = 0x00_00_00_F4,
+
+            /// Returns the environment which the host asked the guest to commit.
+            COMMITTED_ENV = 0x00_00_00_F5,
+
+            /// Returns the seed of the guest's random number generator.
+            RANDOM_SEED = 0x00_00_00_F6,
+
+            /// Opens a file of the guest filesystem.
+            FS_OPEN = 0x00_00_00_F7,
+
+            /// Reads from a file of the guest filesystem.
+            FS_READ = 0x00_00_00_F8,
+
+            /// Seeks in a file of the guest filesystem.
+            FS_SEEK = 0x00_00_00_F9,
+
+            $(
+                #[doc = concat!("Executes the `", stringify!($add), "` precompile.")]
+                $add = $add_code,
+
+                #[doc = concat!("Executes the `", stringify!($double), "` precompile.")]
+                $double = $double_code,
+
+                $(
+                    #[doc = concat!("Executes the `", stringify!($decompress), "` precompile.")]
+                    $decompress = $decompress_code,
+                )?
+
+                $(
+                    #[doc = concat!("Executes the `", stringify!($msm), "` precompile.")]
+                    $msm = $msm_code,
+                )?
+            )*
         }
-    }
 
+        impl SyscallCode {
+            /// Create a syscall from a u32.
+            pub fn from_u32(value: u32) -> Self {
+                match value {
+                    0x00_00_00_00 => SyscallCode::HALT,
+                    0x00_00_00_02 => SyscallCode::WRITE,
+                    0x00_00_00_03 => SyscallCode::ENTER_UNCONSTRAINED,
+                    0x00_00_00_04 => SyscallCode::EXIT_UNCONSTRAINED,
+                    0x00_30_01_05 => SyscallCode::SHA_EXTEND,
+                    0x00_01_01_06 => SyscallCode::SHA_COMPRESS,
+                    0x00_01_01_07 => SyscallCode::ED_ADD,
+                    0x00_00_01_08 => SyscallCode::ED_DECOMPRESS,
+                    0x00_01_01_09 => SyscallCode::KECCAK_PERMUTE,
+                    0x00_00_01_0C => SyscallCode::SECP256K1_DECOMPRESS,
+                    0x00_38_01_0D => SyscallCode::BLAKE3_COMPRESS_INNER,
+                    0x00_00_00_10 => SyscallCode::COMMIT,
+                    0x00_01_01_17 => SyscallCode::BN254_FP2_ADD,
+                    0x00_01_01_18 => SyscallCode::BN254_FP2_SUB,
+                    0x00_01_01_19 => SyscallCode::BN254_FP2_MUL,
+                    0x00_00_00_1A => SyscallCode::COMMIT_DEFERRED_PROOFS,
+                    0x00_00_00_1B => SyscallCode::VERIFY_SP1_PROOF,
+                    0x00_01_01_1C => SyscallCode::BLS12381_FP2_ADD,
+                    0x00_01_01_1D => SyscallCode::BLS12381_FP2_SUB,
+                    0x00_01_01_1E => SyscallCode::BLS12381_FP2_MUL,
+                    0x00_01_01_1F => SyscallCode::UINT256_MULMOD,
+                    0x00_40_01_20 => SyscallCode::SHA512_EXTEND,
+                    0x00_01_01_21 => SyscallCode::SHA512_COMPRESS,
+                    0x00_01_01_22 => SyscallCode::BLAKE2B_COMPRESS,
+                    0x00_01_01_23 => SyscallCode::POSEIDON2_PERMUTE,
+                    0x00_01_01_24 => SyscallCode::KECCAK256_HASH,
+                    0x00_01_01_27 => SyscallCode::MEMCPY,
+                    0x00_80_01_28 => SyscallCode::ED_SCALAR_MUL,
+                    0x00_00_01_29 => SyscallCode::ED_COMPRESS,
+                    0x00_01_01_2A => SyscallCode::RIPEMD160_COMPRESS,
+                    0x00_01_01_2B => SyscallCode::BIGINT_MULMOD,
+                    0x00_00_00_F0 => SyscallCode::HINT_LEN,
+                    0x00_00_00_F1 => SyscallCode::HINT_READ,
+                    0x00_00_00_F2 => SyscallCode::GETENV,
+                    0x00_00_00_F3 => SyscallCode::ARGC,
+                    0x00_00_00_F4 => SyscallCode::ARGV,
+                    0x00_00_00_F5 => SyscallCode::COMMITTED_ENV,
+                    0x00_00_00_F6 => SyscallCode::RANDOM_SEED,
+                    0x00_00_00_F7 => SyscallCode::FS_OPEN,
+                    0x00_00_00_F8 => SyscallCode::FS_READ,
+                    0x00_00_00_F9 => SyscallCode::FS_SEEK,
+                    $(
+                        $add_code => SyscallCode::$add,
+                        $double_code => SyscallCode::$double,
+                        $($decompress_code => SyscallCode::$decompress,)?
+                        $($msm_code => SyscallCode::$msm,)?
+                    )*
+                    _ => panic!("invalid syscall number: {}", value),
+                }
+            }
+        }
+    };
+}
+
+weierstrass_curves!(define_syscall_code);
+
+impl SyscallCode {
     pub fn syscall_id(&self) -> u32 {
         (*self as u32).to_le_bytes()[0].into()
     }
@@ -126,11 +294,8 @@ impl SyscallCode {
     }
 
     pub fn num_cycles(&self) -> u32 {
-        (*self as u32).to_le_bytes()[2].into()
-    }
-
-    pub fn is_halt(&self) -> u32 {
-        (*self as u32).to_le_bytes()[3].into()
+        let bytes = (*self as u32).to_le_bytes();
+        u16::from_le_bytes([bytes[2], bytes[3]]).into()
     }
 }
 
@@ -255,30 +420,82 @@ pub fn default_syscall_map() -> HashMap<SyscallCode, Rc<dyn Syscall>> {
         Rc::new(EdDecompressChip::<Ed25519Parameters>::new()),
     );
     syscall_map.insert(
-        SyscallCode::KECCAK_PERMUTE,
-        Rc::new(KeccakPermuteChip::new()),
+        SyscallCode::ED_SCALAR_MUL,
+        Rc::new(EdScalarMulChip::<Ed25519>::new()),
     );
     syscall_map.insert(
-        SyscallCode::SECP256K1_ADD,
-        Rc::new(WeierstrassAddAssignChip::<Secp256k1>::new()),
+        SyscallCode::ED_COMPRESS,
+        Rc::new(EdCompressChip::<Ed25519Parameters>::new()),
     );
     syscall_map.insert(
-        SyscallCode::SECP256K1_DOUBLE,
-        Rc::new(WeierstrassDoubleAssignChip::<Secp256k1>::new()),
+        SyscallCode::RIPEMD160_COMPRESS,
+        Rc::new(Ripemd160CompressChip::new()),
     );
+    syscall_map.insert(SyscallCode::BIGINT_MULMOD, Rc::new(BigIntMulModChip::new()));
+    syscall_map.insert(
+        SyscallCode::KECCAK_PERMUTE,
+        Rc::new(KeccakPermuteChip::new()),
+    );
+    insert_weierstrass_syscalls(&mut syscall_map);
     syscall_map.insert(SyscallCode::SHA_COMPRESS, Rc::new(ShaCompressChip::new()));
     syscall_map.insert(
         SyscallCode::SECP256K1_DECOMPRESS,
         Rc::new(K256DecompressChip::new()),
     );
     syscall_map.insert(
-        SyscallCode::BN254_ADD,
-        Rc::new(WeierstrassAddAssignChip::<Bn254>::new()),
+        SyscallCode::BN254_FP2_ADD,
+        Rc::new(Fp2AddSubAssignChip::<Bn254BaseField>::new(
+            Fp2Operation::Add,
+        )),
+    );
+    syscall_map.insert(
+        SyscallCode::BN254_FP2_SUB,
+        Rc::new(Fp2AddSubAssignChip::<Bn254BaseField>::new(
+            Fp2Operation::Sub,
+        )),
     );
     syscall_map.insert(
-        SyscallCode::BN254_DOUBLE,
-        Rc::new(WeierstrassDoubleAssignChip::<Bn254>::new()),
+        SyscallCode::BN254_FP2_MUL,
+        Rc::new(Fp2MulAssignChip::<Bn254BaseField>::new()),
     );
+    syscall_map.insert(
+        SyscallCode::BLS12381_FP2_ADD,
+        Rc::new(Fp2AddSubAssignChip::<Bls12381BaseField>::new(
+            Fp2Operation::Add,
+        )),
+    );
+    syscall_map.insert(
+        SyscallCode::BLS12381_FP2_SUB,
+        Rc::new(Fp2AddSubAssignChip::<Bls12381BaseField>::new(
+            Fp2Operation::Sub,
+        )),
+    );
+    syscall_map.insert(
+        SyscallCode::BLS12381_FP2_MUL,
+        Rc::new(Fp2MulAssignChip::<Bls12381BaseField>::new()),
+    );
+    syscall_map.insert(
+        SyscallCode::UINT256_MULMOD,
+        Rc::new(Uint256MulModChip::new()),
+    );
+    syscall_map.insert(SyscallCode::SHA512_EXTEND, Rc::new(Sha512ExtendChip::new()));
+    syscall_map.insert(
+        SyscallCode::SHA512_COMPRESS,
+        Rc::new(Sha512CompressChip::new()),
+    );
+    syscall_map.insert(
+        SyscallCode::BLAKE2B_COMPRESS,
+        Rc::new(Blake2bCompressChip::new()),
+    );
+    syscall_map.insert(
+        SyscallCode::POSEIDON2_PERMUTE,
+        Rc::new(Poseidon2PermuteChip::new()),
+    );
+    syscall_map.insert(
+        SyscallCode::KECCAK256_HASH,
+        Rc::new(Keccak256HashChip::new()),
+    );
+    syscall_map.insert(SyscallCode::MEMCPY, Rc::new(MemCopyChip::new()));
     syscall_map.insert(
         SyscallCode::ENTER_UNCONSTRAINED,
         Rc::new(SyscallEnterUnconstrained::new()),
@@ -299,6 +516,17 @@ pub fn default_syscall_map() -> HashMap<SyscallCode, Rc<dyn Syscall>> {
     );
     syscall_map.insert(SyscallCode::HINT_LEN, Rc::new(SyscallHintLen::new()));
     syscall_map.insert(SyscallCode::HINT_READ, Rc::new(SyscallHintRead::new()));
+    syscall_map.insert(SyscallCode::GETENV, Rc::new(SyscallGetenv::new()));
+    syscall_map.insert(SyscallCode::ARGC, Rc::new(SyscallArgc::new()));
+    syscall_map.insert(SyscallCode::ARGV, Rc::new(SyscallArgv::new()));
+    syscall_map.insert(
+        SyscallCode::COMMITTED_ENV,
+        Rc::new(SyscallCommittedEnv::new()),
+    );
+    syscall_map.insert(SyscallCode::RANDOM_SEED, Rc::new(SyscallRandomSeed::new()));
+    syscall_map.insert(SyscallCode::FS_OPEN, Rc::new(SyscallFsOpen::new()));
+    syscall_map.insert(SyscallCode::FS_READ, Rc::new(SyscallFsRead::new()));
+    syscall_map.insert(SyscallCode::FS_SEEK, Rc::new(SyscallFsSeek::new()));
 
     syscall_map
 }
@@ -371,6 +599,77 @@ mod tests {
                 SyscallCode::SECP256K1_DECOMPRESS => {
                     assert_eq!(code as u32, sp1_zkvm::syscalls::SECP256K1_DECOMPRESS)
                 }
+                SyscallCode::SECP256R1_ADD => {
+                    assert_eq!(code as u32, sp1_zkvm::syscalls::SECP256R1_ADD)
+                }
+                SyscallCode::SECP256R1_DOUBLE => {
+                    assert_eq!(code as u32, sp1_zkvm::syscalls::SECP256R1_DOUBLE)
+                }
+                SyscallCode::SECP256R1_DECOMPRESS => {
+                    assert_eq!(code as u32, sp1_zkvm::syscalls::SECP256R1_DECOMPRESS)
+                }
+                SyscallCode::BLS12381_ADD => {
+                    assert_eq!(code as u32, sp1_zkvm::syscalls::BLS12381_ADD)
+                }
+                SyscallCode::BLS12381_DOUBLE => {
+                    assert_eq!(code as u32, sp1_zkvm::syscalls::BLS12381_DOUBLE)
+                }
+                SyscallCode::BLS12381_DECOMPRESS => {
+                    assert_eq!(code as u32, sp1_zkvm::syscalls::BLS12381_DECOMPRESS)
+                }
+                SyscallCode::BN254_FP2_ADD => {
+                    assert_eq!(code as u32, sp1_zkvm::syscalls::BN254_FP2_ADD)
+                }
+                SyscallCode::BN254_FP2_SUB => {
+                    assert_eq!(code as u32, sp1_zkvm::syscalls::BN254_FP2_SUB)
+                }
+                SyscallCode::BN254_FP2_MUL => {
+                    assert_eq!(code as u32, sp1_zkvm::syscalls::BN254_FP2_MUL)
+                }
+                SyscallCode::BLS12381_FP2_ADD => {
+                    assert_eq!(code as u32, sp1_zkvm::syscalls::BLS12381_FP2_ADD)
+                }
+                SyscallCode::BLS12381_FP2_SUB => {
+                    assert_eq!(code as u32, sp1_zkvm::syscalls::BLS12381_FP2_SUB)
+                }
+                SyscallCode::BLS12381_FP2_MUL => {
+                    assert_eq!(code as u32, sp1_zkvm::syscalls::BLS12381_FP2_MUL)
+                }
+                SyscallCode::UINT256_MULMOD => {
+                    assert_eq!(code as u32, sp1_zkvm::syscalls::UINT256_MULMOD)
+                }
+                SyscallCode::SHA512_EXTEND => {
+                    assert_eq!(code as u32, sp1_zkvm::syscalls::SHA512_EXTEND)
+                }
+                SyscallCode::SHA512_COMPRESS => {
+                    assert_eq!(code as u32, sp1_zkvm::syscalls::SHA512_COMPRESS)
+                }
+                SyscallCode::BLAKE2B_COMPRESS => {
+                    assert_eq!(code as u32, sp1_zkvm::syscalls::BLAKE2B_COMPRESS)
+                }
+                SyscallCode::POSEIDON2_PERMUTE => {
+                    assert_eq!(code as u32, sp1_zkvm::syscalls::POSEIDON2_PERMUTE)
+                }
+                SyscallCode::KECCAK256_HASH => {
+                    assert_eq!(code as u32, sp1_zkvm::syscalls::KECCAK256_HASH)
+                }
+                SyscallCode::SECP256K1_MSM => {
+                    assert_eq!(code as u32, sp1_zkvm::syscalls::SECP256K1_MSM)
+                }
+                SyscallCode::BN254_MSM => assert_eq!(code as u32, sp1_zkvm::syscalls::BN254_MSM),
+                SyscallCode::MEMCPY => assert_eq!(code as u32, sp1_zkvm::syscalls::MEMCPY),
+                SyscallCode::ED_SCALAR_MUL => {
+                    assert_eq!(code as u32, sp1_zkvm::syscalls::ED_SCALAR_MUL)
+                }
+                SyscallCode::ED_COMPRESS => {
+                    assert_eq!(code as u32, sp1_zkvm::syscalls::ED_COMPRESS)
+                }
+                SyscallCode::RIPEMD160_COMPRESS => {
+                    assert_eq!(code as u32, sp1_zkvm::syscalls::RIPEMD160_COMPRESS)
+                }
+                SyscallCode::BIGINT_MULMOD => {
+                    assert_eq!(code as u32, sp1_zkvm::syscalls::BIGINT_MULMOD)
+                }
                 SyscallCode::BN254_ADD => assert_eq!(code as u32, sp1_zkvm::syscalls::BN254_ADD),
                 SyscallCode::BN254_DOUBLE => {
                     assert_eq!(code as u32, sp1_zkvm::syscalls::BN254_DOUBLE)
@@ -384,6 +683,18 @@ mod tests {
                 }
                 SyscallCode::HINT_LEN => assert_eq!(code as u32, sp1_zkvm::syscalls::HINT_LEN),
                 SyscallCode::HINT_READ => assert_eq!(code as u32, sp1_zkvm::syscalls::HINT_READ),
+                SyscallCode::GETENV => assert_eq!(code as u32, sp1_zkvm::syscalls::GETENV),
+                SyscallCode::ARGC => assert_eq!(code as u32, sp1_zkvm::syscalls::ARGC),
+                SyscallCode::ARGV => assert_eq!(code as u32, sp1_zkvm::syscalls::ARGV),
+                SyscallCode::COMMITTED_ENV => {
+                    assert_eq!(code as u32, sp1_zkvm::syscalls::COMMITTED_ENV)
+                }
+                SyscallCode::RANDOM_SEED => {
+                    assert_eq!(code as u32, sp1_zkvm::syscalls::RANDOM_SEED)
+                }
+                SyscallCode::FS_OPEN => assert_eq!(code as u32, sp1_zkvm::syscalls::FS_OPEN),
+                SyscallCode::FS_READ => assert_eq!(code as u32, sp1_zkvm::syscalls::FS_READ),
+                SyscallCode::FS_SEEK => assert_eq!(code as u32, sp1_zkvm::syscalls::FS_SEEK),
             }
         }
     }
@@ -40,6 +40,11 @@ impl Runtime {
             }
         }
 
+        // Record the executed instruction for the coverage report.
+        if let Some(ref mut coverage) = self.coverage {
+            coverage.record(self.state.pc);
+        }
+
         // If RUST_LOG is set to "trace", then log the current state of the runtime every cycle.
         let width = 12;
         log::trace!(
@@ -3,6 +3,8 @@ pub use crate::air::SP1AirBuilder;
 use crate::air::{MachineAir, SP1_PROOF_NUM_PV_ELTS};
 use crate::memory::{MemoryChipType, MemoryProgramChip};
 use crate::stark::Chip;
+use crate::syscall::precompiles::fptower::Fp2Operation;
+use crate::syscall::precompiles::weierstrass::weierstrass_curves;
 use crate::StarkGenericConfig;
 use p3_field::PrimeField32;
 pub use riscv_chips::*;
@@ -20,79 +22,190 @@ pub(crate) mod riscv_chips {
     pub use crate::cpu::CpuChip;
     pub use crate::memory::MemoryChip;
     pub use crate::program::ProgramChip;
+    pub use crate::syscall::precompiles::bigint::BigIntMulModChip;
+    pub use crate::syscall::precompiles::blake2b::Blake2bCompressChip;
     pub use crate::syscall::precompiles::blake3::Blake3CompressInnerChip;
     pub use crate::syscall::precompiles::edwards::EdAddAssignChip;
+    pub use crate::syscall::precompiles::edwards::EdCompressChip;
     pub use crate::syscall::precompiles::edwards::EdDecompressChip;
+    pub use crate::syscall::precompiles::edwards::EdScalarMulChip;
+    pub use crate::syscall::precompiles::fptower::Fp2AddSubAssignChip;
+    pub use crate::syscall::precompiles::fptower::Fp2MulAssignChip;
     pub use crate::syscall::precompiles::k256::K256DecompressChip;
+    pub use crate::syscall::precompiles::keccak256::Keccak256HashChip;
     pub use crate::syscall::precompiles::keccak256::KeccakPermuteChip;
+    pub use crate::syscall::precompiles::memcpy::MemCopyChip;
+    pub use crate::syscall::precompiles::poseidon2::Poseidon2PermuteChip;
+    pub use crate::syscall::precompiles::ripemd160::Ripemd160CompressChip;
     pub use crate::syscall::precompiles::sha256::ShaCompressChip;
     pub use crate::syscall::precompiles::sha256::ShaExtendChip;
+    pub use crate::syscall::precompiles::sha512::Sha512CompressChip;
+    pub use crate::syscall::precompiles::sha512::Sha512ExtendChip;
+    pub use crate::syscall::precompiles::uint256::Uint256MulModChip;
     pub use crate::syscall::precompiles::weierstrass::WeierstrassAddAssignChip;
+    pub use crate::syscall::precompiles::weierstrass::WeierstrassDecompressChip;
     pub use crate::syscall::precompiles::weierstrass::WeierstrassDoubleAssignChip;
+    pub use crate::syscall::precompiles::weierstrass::WeierstrassMsmChip;
     pub use crate::utils::ec::edwards::ed25519::Ed25519Parameters;
     pub use crate::utils::ec::edwards::EdwardsCurve;
-    pub use crate::utils::ec::weierstrass::bn254::Bn254Parameters;
+    pub use crate::utils::ec::weierstrass::bls12381::{Bls12381BaseField, Bls12381Parameters};
+    pub use crate::utils::ec::weierstrass::bn254::{Bn254BaseField, Bn254Parameters};
     pub use crate::utils::ec::weierstrass::secp256k1::Secp256k1Parameters;
+    pub use crate::utils::ec::weierstrass::secp256r1::Secp256r1Parameters;
     pub use crate::utils::ec::weierstrass::SwCurve;
 }
 
-/// An AIR for encoding RISC-V execution.
-///
-/// This enum contains all the different AIRs that are used in the Sp1 RISC-V IOP. Each variant is
-/// a different AIR that is used to encode a different part of the RISC-V execution, and the
-/// different AIR variants have a joint lookup argument.
-#[derive(MachineAir)]
-pub enum RiscvAir<F: PrimeField32> {
-    /// An AIR that containts a preprocessed program table and a lookup for the instructions.
-    Program(ProgramChip),
-    /// An AIR for the RISC-V CPU. Each row represents a cpu cycle.
-    Cpu(CpuChip),
-    /// An AIR for the RISC-V Add and SUB instruction.
-    Add(AddSubChip),
-    /// An AIR for RISC-V Bitwise instructions.
-    Bitwise(BitwiseChip),
-    /// An AIR for RISC-V Mul instruction.
-    Mul(MulChip),
-    /// An AIR for RISC-V Div and Rem instructions.
-    DivRem(DivRemChip),
-    /// An AIR for RISC-V Lt instruction.
-    Lt(LtChip),
-    /// An AIR for RISC-V SLL instruction.
-    ShiftLeft(ShiftLeft),
-    /// An AIR for RISC-V SRL and SRA instruction.
-    ShiftRight(ShiftRightChip),
-    /// A lookup table for byte operations.
-    ByteLookup(ByteChip<F>),
-    /// A table for initializing the memory state.
-    MemoryInit(MemoryChip),
-    /// A table for finalizing the memory state.
-    MemoryFinal(MemoryChip),
-    /// A table for initializing the program memory.
-    ProgramMemory(MemoryProgramChip),
-    /// A precompile for sha256 extend.
-    Sha256Extend(ShaExtendChip),
-    /// A precompile for sha256 compress.
-    Sha256Compress(ShaCompressChip),
-    /// A precompile for addition on the Elliptic curve ed25519.
-    Ed25519Add(EdAddAssignChip<EdwardsCurve<Ed25519Parameters>>),
-    /// A precompile for decompressing a point on the Edwards curve ed25519.
-    Ed25519Decompress(EdDecompressChip<Ed25519Parameters>),
-    /// A precompile for decompressing a point on the K256 curve.
-    K256Decompress(K256DecompressChip),
-    /// A precompile for addition on the Elliptic curve secp256k1.
-    Secp256k1Add(WeierstrassAddAssignChip<SwCurve<Secp256k1Parameters>>),
-    /// A precompile for doubling a point on the Elliptic curve secp256k1.
-    Secp256k1Double(WeierstrassDoubleAssignChip<SwCurve<Secp256k1Parameters>>),
-    /// A precompile for the Keccak permutation.
-    KeccakP(KeccakPermuteChip),
-    /// A precompile for the Blake3 compression function. (Disabled by default.)
-    Blake3Compress(Blake3CompressInnerChip),
-    /// A precompile for addition on the Elliptic curve bn254.
-    Bn254Add(WeierstrassAddAssignChip<SwCurve<Bn254Parameters>>),
-    /// A precompile for doubling a point on the Elliptic curve bn254.
-    Bn254Double(WeierstrassDoubleAssignChip<SwCurve<Bn254Parameters>>),
+/// Defines [`RiscvAir`], with the chips of the curves of [`weierstrass_curves`].
+macro_rules! define_riscv_air {
+    ($(
+        $params:ident {
+            name: $name:literal,
+            add: $add_variant:ident($add:ident = $add_code:literal),
+            double: $double_variant:ident($double:ident = $double_code:literal),
+            $(decompress: $decompress_variant:ident(
+                $decompress:ident = $decompress_code:literal,
+                $sqrt:ident
+            ),)?
+            $(msm: $msm_variant:ident($msm:ident = $msm_code:literal),)?
+        }
+    )*) => {
+        /// An AIR for encoding RISC-V execution.
+        ///
+        /// This enum contains all the different AIRs that are used in the Sp1 RISC-V IOP. Each
+        /// variant is a different AIR that is used to encode a different part of the RISC-V
+        /// execution, and the different AIR variants have a joint lookup argument.
+        ///
+        /// The chips of the short Weierstrass curves are listed in [`weierstrass_curves`].
+        #[derive(MachineAir)]
+        pub enum RiscvAir<F: PrimeField32> {
+            /// An AIR that containts a preprocessed program table and a lookup for the
+            /// instructions.
+            Program(ProgramChip),
+            /// An AIR for the RISC-V CPU. Each row represents a cpu cycle.
+            Cpu(CpuChip),
+            /// An AIR for the RISC-V Add and SUB instruction.
+            Add(AddSubChip),
+            /// An AIR for RISC-V Bitwise instructions.
+            Bitwise(BitwiseChip),
+            /// An AIR for RISC-V Mul instruction.
+            Mul(MulChip),
+            /// An AIR for RISC-V Div and Rem instructions.
+            DivRem(DivRemChip),
+            /// An AIR for RISC-V Lt instruction.
+            Lt(LtChip),
+            /// An AIR for RISC-V SLL instruction.
+            ShiftLeft(ShiftLeft),
+            /// An AIR for RISC-V SRL and SRA instruction.
+            ShiftRight(ShiftRightChip),
+            /// A lookup table for byte operations.
+            ByteLookup(ByteChip<F>),
+            /// A table for initializing the memory state.
+            MemoryInit(MemoryChip),
+            /// A table for finalizing the memory state.
+            MemoryFinal(MemoryChip),
+            /// A table for initializing the program memory.
+            ProgramMemory(MemoryProgramChip),
+            /// A precompile for sha256 extend.
+            Sha256Extend(ShaExtendChip),
+            /// A precompile for sha256 compress.
+            Sha256Compress(ShaCompressChip),
+            /// A precompile for addition on the Elliptic curve ed25519.
+            Ed25519Add(EdAddAssignChip<EdwardsCurve<Ed25519Parameters>>),
+            /// A precompile for decompressing a point on the Edwards curve ed25519.
+            Ed25519Decompress(EdDecompressChip<Ed25519Parameters>),
+            /// A precompile for decompressing a point on the K256 curve.
+            K256Decompress(K256DecompressChip),
+            /// A precompile for the Keccak permutation.
+            KeccakP(KeccakPermuteChip),
+            /// A precompile for the Blake3 compression function. (Disabled by default.)
+            Blake3Compress(Blake3CompressInnerChip),
+            /// A precompile for addition in the quadratic extension of the bn254 base field.
+            Bn254Fp2Add(Fp2AddSubAssignChip<Bn254BaseField>),
+            /// A precompile for subtraction in the quadratic extension of the bn254 base field.
+            Bn254Fp2Sub(Fp2AddSubAssignChip<Bn254BaseField>),
+            /// A precompile for multiplication in the quadratic extension of the bn254 base field.
+            Bn254Fp2Mul(Fp2MulAssignChip<Bn254BaseField>),
+            /// A precompile for addition in the quadratic extension of the bls12_381 base field.
+            Bls12381Fp2Add(Fp2AddSubAssignChip<Bls12381BaseField>),
+            /// A precompile for subtraction in the quadratic extension of the bls12_381 base field.
+            Bls12381Fp2Sub(Fp2AddSubAssignChip<Bls12381BaseField>),
+            /// A precompile for multiplication in the quadratic extension of the bls12_381 base
+            /// field.
+            Bls12381Fp2Mul(Fp2MulAssignChip<Bls12381BaseField>),
+            /// A precompile for multiplication of 256-bit integers modulo a caller-supplied
+            /// modulus.
+            Uint256MulMod(Uint256MulModChip),
+            /// A precompile for sha512 extend.
+            Sha512Extend(Sha512ExtendChip),
+            /// A precompile for sha512 compress.
+            Sha512Compress(Sha512CompressChip),
+            /// A precompile for the Blake2b compression function.
+            Blake2bCompress(Blake2bCompressChip),
+            /// A precompile for the Poseidon2 permutation over BabyBear.
+            Poseidon2Permute(Poseidon2PermuteChip),
+            /// A precompile for the Keccak-256 hash of an input in memory.
+            Keccak256Hash(Keccak256HashChip),
+            /// A precompile for copying word-aligned memory.
+            MemCopy(MemCopyChip),
+            /// A precompile for scalar multiplication on the Edwards curve ed25519.
+            Ed25519ScalarMul(EdScalarMulChip<EdwardsCurve<Ed25519Parameters>>),
+            /// A precompile for compressing a point on the Edwards curve ed25519.
+            Ed25519Compress(EdCompressChip<Ed25519Parameters>),
+            /// A precompile for the RIPEMD-160 compression function.
+            Ripemd160Compress(Ripemd160CompressChip),
+            /// A precompile for multiplication modulo an integer of up to 4096 bits.
+            BigIntMulMod(BigIntMulModChip),
+
+            $(
+                #[doc = concat!("A precompile for addition on the Elliptic curve ", $name, ".")]
+                $add_variant(WeierstrassAddAssignChip<SwCurve<$params>>),
+                #[doc = concat!(
+                    "A precompile for doubling a point on the Elliptic curve ", $name, "."
+                )]
+                $double_variant(WeierstrassDoubleAssignChip<SwCurve<$params>>),
+                $(
+                    #[doc = concat!(
+                        "A precompile for decompressing a point on the Elliptic curve ", $name, "."
+                    )]
+                    $decompress_variant(WeierstrassDecompressChip<SwCurve<$params>>),
+                )?
+                $(
+                    #[doc = concat!(
+                        "A precompile for multi-scalar multiplication on the Elliptic curve ",
+                        $name,
+                        "."
+                    )]
+                    $msm_variant(WeierstrassMsmChip<SwCurve<$params>>),
+                )?
+            )*
+        }
+
+        impl<F: PrimeField32> RiscvAir<F> {
+            /// Get the AIRs of the short Weierstrass curves.
+            fn get_weierstrass() -> Vec<Self> {
+                vec![
+                    $(
+                        RiscvAir::$add_variant(WeierstrassAddAssignChip::<SwCurve<$params>>::new()),
+                        RiscvAir::$double_variant(
+                            WeierstrassDoubleAssignChip::<SwCurve<$params>>::new(),
+                        ),
+                        $(
+                            RiscvAir::$decompress_variant(
+                                WeierstrassDecompressChip::<SwCurve<$params>>::new(),
+                            ),
+                        )?
+                        $(
+                            RiscvAir::$msm_variant(WeierstrassMsmChip::<SwCurve<$params>>::new()),
+                        )?
+                    )*
+                ]
+            }
+        }
+    };
 }
 
+weierstrass_curves!(define_riscv_air);
+
 impl<F: PrimeField32> RiscvAir<F> {
     pub fn machine<SC: StarkGenericConfig<Val = F>>(config: SC) -> MachineStark<SC, Self> {
         let chips = Self::get_all()
@@ -121,17 +234,43 @@ impl<F: PrimeField32> RiscvAir<F> {
         chips.push(RiscvAir::Ed25519Decompress(ed_decompress));
         let k256_decompress = K256DecompressChip::default();
         chips.push(RiscvAir::K256Decompress(k256_decompress));
-        let secp256k1_add_assign = WeierstrassAddAssignChip::<SwCurve<Secp256k1Parameters>>::new();
-        chips.push(RiscvAir::Secp256k1Add(secp256k1_add_assign));
-        let secp256k1_double_assign =
-            WeierstrassDoubleAssignChip::<SwCurve<Secp256k1Parameters>>::new();
-        chips.push(RiscvAir::Secp256k1Double(secp256k1_double_assign));
+        chips.extend(Self::get_weierstrass());
         let keccak_permute = KeccakPermuteChip::new();
         chips.push(RiscvAir::KeccakP(keccak_permute));
-        let bn254_add_assign = WeierstrassAddAssignChip::<SwCurve<Bn254Parameters>>::new();
-        chips.push(RiscvAir::Bn254Add(bn254_add_assign));
-        let bn254_double_assign = WeierstrassDoubleAssignChip::<SwCurve<Bn254Parameters>>::new();
-        chips.push(RiscvAir::Bn254Double(bn254_double_assign));
+        let bn254_fp2_add = Fp2AddSubAssignChip::<Bn254BaseField>::new(Fp2Operation::Add);
+        chips.push(RiscvAir::Bn254Fp2Add(bn254_fp2_add));
+        let bn254_fp2_sub = Fp2AddSubAssignChip::<Bn254BaseField>::new(Fp2Operation::Sub);
+        chips.push(RiscvAir::Bn254Fp2Sub(bn254_fp2_sub));
+        let bn254_fp2_mul = Fp2MulAssignChip::<Bn254BaseField>::new();
+        chips.push(RiscvAir::Bn254Fp2Mul(bn254_fp2_mul));
+        let bls12381_fp2_add = Fp2AddSubAssignChip::<Bls12381BaseField>::new(Fp2Operation::Add);
+        chips.push(RiscvAir::Bls12381Fp2Add(bls12381_fp2_add));
+        let bls12381_fp2_sub = Fp2AddSubAssignChip::<Bls12381BaseField>::new(Fp2Operation::Sub);
+        chips.push(RiscvAir::Bls12381Fp2Sub(bls12381_fp2_sub));
+        let bls12381_fp2_mul = Fp2MulAssignChip::<Bls12381BaseField>::new();
+        chips.push(RiscvAir::Bls12381Fp2Mul(bls12381_fp2_mul));
+        let uint256_mulmod = Uint256MulModChip::new();
+        chips.push(RiscvAir::Uint256MulMod(uint256_mulmod));
+        let sha512_extend = Sha512ExtendChip::new();
+        chips.push(RiscvAir::Sha512Extend(sha512_extend));
+        let sha512_compress = Sha512CompressChip::new();
+        chips.push(RiscvAir::Sha512Compress(sha512_compress));
+        let blake2b_compress = Blake2bCompressChip::new();
+        chips.push(RiscvAir::Blake2bCompress(blake2b_compress));
+        let poseidon2_permute = Poseidon2PermuteChip::new();
+        chips.push(RiscvAir::Poseidon2Permute(poseidon2_permute));
+        let keccak256_hash = Keccak256HashChip::new();
+        chips.push(RiscvAir::Keccak256Hash(keccak256_hash));
+        let memcpy = MemCopyChip::new();
+        chips.push(RiscvAir::MemCopy(memcpy));
+        let ed_scalar_mul = EdScalarMulChip::<EdwardsCurve<Ed25519Parameters>>::new();
+        chips.push(RiscvAir::Ed25519ScalarMul(ed_scalar_mul));
+        let ed_compress = EdCompressChip::<Ed25519Parameters>::new();
+        chips.push(RiscvAir::Ed25519Compress(ed_compress));
+        let ripemd160_compress = Ripemd160CompressChip::new();
+        chips.push(RiscvAir::Ripemd160Compress(ripemd160_compress));
+        let bigint_mulmod = BigIntMulModChip::new();
+        chips.push(RiscvAir::BigIntMulMod(bigint_mulmod));
         let add = AddSubChip::default();
         chips.push(RiscvAir::Add(add));
         let bitwise = BitwiseChip::default();
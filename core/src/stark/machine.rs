@@ -14,6 +14,10 @@ use p3_matrix::dense::RowMajorMatrix;
 use p3_matrix::Dimensions;
 use p3_matrix::Matrix;
 use p3_maybe_rayon::prelude::*;
+use serde::Deserialize;
+use serde::Deserializer;
+use serde::Serialize;
+use serde::Serializer;
 
 use super::debug_constraints;
 use super::Dom;
@@ -80,6 +84,55 @@ impl<SC: StarkGenericConfig> Debug for VerifyingKey<SC> {
     }
 }
 
+/// The serialized form of a [`VerifyingKey`].
+///
+/// The preprocessed trace domains are not stored: they are always the natural domains for the
+/// trace heights, so they are recomputed from the config's PCS when deserializing.
+#[derive(Serialize, Deserialize)]
+#[serde(bound = "")]
+struct SerializedVerifyingKey<SC: StarkGenericConfig> {
+    commit: Com<SC>,
+    /// The name, width and height of each preprocessed trace.
+    chip_information: Vec<(String, usize, usize)>,
+    chip_ordering: HashMap<String, usize>,
+}
+
+impl<SC: StarkGenericConfig> Serialize for VerifyingKey<SC> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        SerializedVerifyingKey::<SC> {
+            commit: self.commit.clone(),
+            chip_information: self
+                .chip_information
+                .iter()
+                .map(|(name, _, dims)| (name.clone(), dims.width, dims.height))
+                .collect(),
+            chip_ordering: self.chip_ordering.clone(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, SC: StarkGenericConfig + Default> Deserialize<'de> for VerifyingKey<SC> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let serialized = SerializedVerifyingKey::<SC>::deserialize(deserializer)?;
+        let config = SC::default();
+        let pcs = config.pcs();
+        let chip_information = serialized
+            .chip_information
+            .into_iter()
+            .map(|(name, width, height)| {
+                let domain = pcs.natural_domain_for_degree(height);
+                (name, domain, Dimensions { width, height })
+            })
+            .collect();
+        Ok(VerifyingKey {
+            commit: serialized.commit,
+            chip_information,
+            chip_ordering: serialized.chip_ordering,
+        })
+    }
+}
+
 impl<SC: StarkGenericConfig, A: MachineAir<Val<SC>>> MachineStark<SC, A> {
     /// Get an array containing a `ChipRef` for all the chips of this RISC-V STARK machine.
     pub fn chips(&self) -> &[MachineChip<SC, A>] {
@@ -0,0 +1,205 @@
+use crate::runtime::{Syscall, SyscallContext};
+
+/// Pushes `value` to the front of the unread hint input stream, so that it is the next slice read
+/// by the guest.
+pub(crate) fn push_hint(ctx: &mut SyscallContext, value: Vec<u8>) -> u32 {
+    let len = value.len() as u32;
+    let ptr = ctx.rt.state.input_stream_ptr;
+    ctx.rt.state.input_stream.insert(ptr, value);
+    len
+}
+
+pub struct SyscallGetenv;
+
+/// SyscallGetenv looks up the environment variable whose name is the `len` bytes at `ptr`. If it is
+/// set, its value is pushed to the hint input stream and its length is returned, and otherwise
+/// `u32::MAX` is returned.
+impl SyscallGetenv {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Syscall for SyscallGetenv {
+    fn execute(&self, ctx: &mut SyscallContext, ptr: u32, len: u32) -> Option<u32> {
+        let name = (0..len).map(|i| ctx.rt.byte(ptr + i)).collect::<Vec<u8>>();
+        let value = String::from_utf8(name)
+            .ok()
+            .and_then(|name| ctx.rt.state.env.vars.get(&name).cloned());
+        match value {
+            Some(value) => Some(push_hint(ctx, value.into_bytes())),
+            None => Some(u32::MAX),
+        }
+    }
+}
+
+pub struct SyscallArgc;
+
+/// SyscallArgc returns the number of arguments of the guest.
+impl SyscallArgc {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Syscall for SyscallArgc {
+    fn execute(&self, ctx: &mut SyscallContext, _arg1: u32, _arg2: u32) -> Option<u32> {
+        Some(ctx.rt.state.env.args.len() as u32)
+    }
+}
+
+pub struct SyscallArgv;
+
+/// SyscallArgv pushes the argument at `index` to the hint input stream and returns its length.
+impl SyscallArgv {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Syscall for SyscallArgv {
+    fn execute(&self, ctx: &mut SyscallContext, index: u32, _arg2: u32) -> Option<u32> {
+        let arg = ctx
+            .rt
+            .state
+            .env
+            .args
+            .get(index as usize)
+            .unwrap_or_else(|| panic!("guest argument {} out of range", index))
+            .clone();
+        Some(push_hint(ctx, arg.into_bytes()))
+    }
+}
+
+pub struct SyscallRandomSeed;
+
+/// SyscallRandomSeed pushes the seed of the guest's random number generator to the hint input
+/// stream and returns its length, or returns `u32::MAX` if the host did not provide one.
+impl SyscallRandomSeed {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Syscall for SyscallRandomSeed {
+    fn execute(&self, ctx: &mut SyscallContext, _arg1: u32, _arg2: u32) -> Option<u32> {
+        match ctx.rt.state.env.random_seed {
+            Some(seed) => Some(push_hint(ctx, seed.to_vec())),
+            None => Some(u32::MAX),
+        }
+    }
+}
+
+pub struct SyscallCommittedEnv;
+
+/// SyscallCommittedEnv pushes the serialized environment which the host asked the guest to commit
+/// to the hint input stream and returns its length, or returns `u32::MAX` if the host committed
+/// nothing.
+impl SyscallCommittedEnv {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Syscall for SyscallCommittedEnv {
+    fn execute(&self, ctx: &mut SyscallContext, _arg1: u32, _arg2: u32) -> Option<u32> {
+        match ctx.rt.state.env.committed() {
+            Some(committed) => {
+                let bytes = bincode::serialize(&committed).expect("serialization failed");
+                Some(push_hint(ctx, bytes))
+            }
+            None => Some(u32::MAX),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::runtime::{GuestEnv, Instruction, Opcode, Program, Register, Runtime, SyscallCode};
+
+    /// A program which stores `name` at address 0x1000 and calls `GETENV`, `ARGC` and `ARGV` with
+    /// index 1, in that order.
+    fn env_program(name: &[u8]) -> Program {
+        let mut instructions = vec![];
+        for (i, chunk) in name.chunks(4).enumerate() {
+            let mut word = [0u8; 4];
+            word[..chunk.len()].copy_from_slice(chunk);
+            instructions.extend([
+                Instruction::new(Opcode::ADD, 29, 0, u32::from_le_bytes(word), false, true),
+                Instruction::new(Opcode::ADD, 30, 0, 0x1000 + i as u32 * 4, false, true),
+                Instruction::new(Opcode::SW, 29, 30, 0, false, true),
+            ]);
+        }
+        instructions.extend([
+            Instruction::new(Opcode::ADD, 5, 0, SyscallCode::GETENV as u32, false, true),
+            Instruction::new(Opcode::ADD, 10, 0, 0x1000, false, true),
+            Instruction::new(Opcode::ADD, 11, 0, name.len() as u32, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+            Instruction::new(Opcode::ADD, 20, 5, 0, false, true),
+            Instruction::new(Opcode::ADD, 5, 0, SyscallCode::ARGC as u32, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+            Instruction::new(Opcode::ADD, 21, 5, 0, false, true),
+            Instruction::new(Opcode::ADD, 5, 0, SyscallCode::ARGV as u32, false, true),
+            Instruction::new(Opcode::ADD, 10, 0, 1, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+        ]);
+        Program::new(instructions, 0, 0)
+    }
+
+    #[test]
+    fn test_env_syscalls() {
+        let mut env = GuestEnv::default();
+        env.vars.insert("MODE".to_string(), "fast".to_string());
+        env.args = vec!["guest".to_string(), "--verbose".to_string()];
+
+        let mut runtime = Runtime::new(env_program(b"MODE"));
+        runtime.write_stdin_slice(b"input");
+        runtime.write_env(env.clone());
+        runtime.run();
+        assert_eq!(runtime.register(Register::X20), 4);
+        assert_eq!(runtime.register(Register::X21), 2);
+        assert_eq!(runtime.register(Register::X5), 9);
+        let hints = [b"--verbose".to_vec(), b"fast".to_vec(), b"input".to_vec()];
+        assert_eq!(runtime.state.input_stream, hints);
+
+        // Unset variables are reported as missing rather than empty.
+        let mut runtime = Runtime::new(env_program(b"THREADS"));
+        runtime.write_env(env);
+        runtime.run();
+        assert_eq!(runtime.register(Register::X20), u32::MAX);
+        assert_eq!(runtime.state.input_stream, [b"--verbose".to_vec()]);
+    }
+
+    #[test]
+    fn test_random_seed_syscall() {
+        let program = Program::new(
+            vec![
+                Instruction::new(
+                    Opcode::ADD,
+                    5,
+                    0,
+                    SyscallCode::RANDOM_SEED as u32,
+                    false,
+                    true,
+                ),
+                Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+            ],
+            0,
+            0,
+        );
+
+        let mut runtime = Runtime::new(program.clone());
+        runtime.run();
+        assert_eq!(runtime.register(Register::X5), u32::MAX);
+        assert!(runtime.state.input_stream.is_empty());
+
+        let mut runtime = Runtime::new(program);
+        runtime.write_env(GuestEnv {
+            random_seed: Some([7; 32]),
+            ..Default::default()
+        });
+        runtime.run();
+        assert_eq!(runtime.register(Register::X5), 32);
+        assert_eq!(runtime.state.input_stream, [vec![7; 32]]);
+    }
+}
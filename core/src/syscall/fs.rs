@@ -0,0 +1,187 @@
+use super::env::push_hint;
+use crate::runtime::{OpenFile, Register, Syscall, SyscallContext};
+
+/// Returns the open file with descriptor `fd` and its contents.
+fn open_file<'a>(ctx: &'a mut SyscallContext, fd: u32) -> (&'a mut OpenFile, &'a [u8]) {
+    let state = &mut ctx.rt.state;
+    let file = state
+        .open_files
+        .get_mut(fd as usize)
+        .unwrap_or_else(|| panic!("invalid file descriptor {}", fd));
+    (file, &state.env.files[&file.path])
+}
+
+pub struct SyscallFsOpen;
+
+/// SyscallFsOpen opens the file whose path is the `len` bytes at `ptr`, returning its file
+/// descriptor, or `u32::MAX` if there is no such file.
+impl SyscallFsOpen {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Syscall for SyscallFsOpen {
+    fn execute(&self, ctx: &mut SyscallContext, ptr: u32, len: u32) -> Option<u32> {
+        let path = (0..len).map(|i| ctx.rt.byte(ptr + i)).collect::<Vec<u8>>();
+        let state = &mut ctx.rt.state;
+        match String::from_utf8(path) {
+            Ok(path) if state.env.files.contains_key(&path) => {
+                state.open_files.push(OpenFile { path, offset: 0 });
+                Some(state.open_files.len() as u32 - 1)
+            }
+            _ => Some(u32::MAX),
+        }
+    }
+}
+
+pub struct SyscallFsRead;
+
+/// SyscallFsRead reads up to `len` bytes of the file `fd` from its current offset. The bytes are
+/// pushed to the hint input stream and their number is returned, which is 0 at the end of the
+/// file.
+impl SyscallFsRead {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Syscall for SyscallFsRead {
+    fn execute(&self, ctx: &mut SyscallContext, fd: u32, len: u32) -> Option<u32> {
+        let (file, contents) = open_file(ctx, fd);
+        let start = (file.offset as usize).min(contents.len());
+        let end = start + (len as usize).min(contents.len() - start);
+        let bytes = contents[start..end].to_vec();
+        file.offset += bytes.len() as u32;
+        Some(push_hint(ctx, bytes))
+    }
+}
+
+pub struct SyscallFsSeek;
+
+/// SyscallFsSeek moves the offset of the file `fd` by `offset` from its start, its current offset
+/// or its end when register a2 is 0, 1 or 2, like `lseek`. It returns the new offset, or `u32::MAX`
+/// if it would be out of range, in which case the offset is left as it is.
+impl SyscallFsSeek {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Syscall for SyscallFsSeek {
+    fn execute(&self, ctx: &mut SyscallContext, fd: u32, offset: u32) -> Option<u32> {
+        let whence = ctx.rt.register(Register::X12);
+        let (file, contents) = open_file(ctx, fd);
+        let base = match whence {
+            0 => 0,
+            1 => file.offset as i64,
+            2 => contents.len() as i64,
+            _ => return Some(u32::MAX),
+        };
+        // Seeks from the start take an unsigned offset, and the others a signed one.
+        let offset = if whence == 0 {
+            offset as i64
+        } else {
+            offset as i32 as i64
+        };
+        match u32::try_from(base + offset) {
+            Ok(new_offset) if new_offset != u32::MAX => {
+                file.offset = new_offset;
+                Some(new_offset)
+            }
+            _ => Some(u32::MAX),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::runtime::{GuestEnv, Instruction, Opcode, Program, Register, Runtime, SyscallCode};
+
+    /// Stores `path` at address 0x1000 and opens it, keeping its file descriptor in `x20`.
+    fn open(path: &[u8]) -> Vec<Instruction> {
+        let mut instructions = vec![];
+        for (i, chunk) in path.chunks(4).enumerate() {
+            let mut word = [0u8; 4];
+            word[..chunk.len()].copy_from_slice(chunk);
+            instructions.extend([
+                Instruction::new(Opcode::ADD, 29, 0, u32::from_le_bytes(word), false, true),
+                Instruction::new(Opcode::ADD, 30, 0, 0x1000 + i as u32 * 4, false, true),
+                Instruction::new(Opcode::SW, 29, 30, 0, false, true),
+            ]);
+        }
+        instructions.extend([
+            Instruction::new(Opcode::ADD, 5, 0, SyscallCode::FS_OPEN as u32, false, true),
+            Instruction::new(Opcode::ADD, 10, 0, 0x1000, false, true),
+            Instruction::new(Opcode::ADD, 11, 0, path.len() as u32, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+            Instruction::new(Opcode::ADD, 20, 5, 0, false, true),
+        ]);
+        instructions
+    }
+
+    /// Reads up to `len` bytes of the file opened by [open].
+    fn read(len: u32) -> Vec<Instruction> {
+        vec![
+            Instruction::new(Opcode::ADD, 5, 0, SyscallCode::FS_READ as u32, false, true),
+            Instruction::new(Opcode::ADD, 10, 20, 0, false, true),
+            Instruction::new(Opcode::ADD, 11, 0, len, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+        ]
+    }
+
+    /// Seeks the file opened by [open], keeping the new offset in `x21`.
+    fn seek(offset: u32, whence: u32) -> Vec<Instruction> {
+        vec![
+            Instruction::new(Opcode::ADD, 5, 0, SyscallCode::FS_SEEK as u32, false, true),
+            Instruction::new(Opcode::ADD, 10, 20, 0, false, true),
+            Instruction::new(Opcode::ADD, 11, 0, offset, false, true),
+            Instruction::new(Opcode::ADD, 12, 0, whence, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+            Instruction::new(Opcode::ADD, 21, 5, 0, false, true),
+        ]
+    }
+
+    fn run(instructions: Vec<Instruction>) -> Runtime {
+        let mut env = GuestEnv::default();
+        env.files
+            .insert("config.toml".to_string(), b"mode = \"fast\"".to_vec());
+        let mut runtime = Runtime::new(Program::new(instructions, 0, 0));
+        runtime.write_env(env);
+        runtime.run();
+        runtime
+    }
+
+    #[test]
+    fn test_fs_syscalls() {
+        let runtime = run([
+            open(b"config.toml"),
+            read(4),
+            seek(-5i32 as u32, 2),
+            read(64),
+        ]
+        .concat());
+        assert_eq!(runtime.register(Register::X20), 0);
+        assert_eq!(runtime.register(Register::X21), 8);
+        assert_eq!(runtime.register(Register::X5), 5);
+        let hints = [b"fast\"".to_vec(), b"mode".to_vec()];
+        assert_eq!(runtime.state.input_stream, hints);
+        assert_eq!(runtime.state.open_files[0].offset, 13);
+
+        // Reads past the end of the file are empty, and seeks before its start fail.
+        let runtime = run([
+            open(b"config.toml"),
+            seek(20, 0),
+            read(4),
+            seek(-21i32 as u32, 1),
+        ]
+        .concat());
+        assert_eq!(runtime.register(Register::X21), u32::MAX);
+        assert_eq!(runtime.state.input_stream, [Vec::<u8>::new()]);
+        assert_eq!(runtime.state.open_files[0].offset, 20);
+
+        let runtime = run(open(b"missing.toml"));
+        assert_eq!(runtime.register(Register::X20), u32::MAX);
+        assert!(runtime.state.open_files.is_empty());
+    }
+}
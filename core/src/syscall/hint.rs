@@ -96,6 +96,6 @@ mod tests {
         let program = Program::from(HINT_IO_ELF);
 
         let config = BabyBearPoseidon2::new();
-        run_and_prove(program, &stdin.buffer, config);
+        run_and_prove(program, &stdin.buffer, config).unwrap();
     }
 }
@@ -1,4 +1,6 @@
 mod commit;
+mod env;
+mod fs;
 mod halt;
 mod hint;
 pub mod precompiles;
@@ -7,6 +9,8 @@ mod verify;
 mod write;
 
 pub use commit::*;
+pub use env::*;
+pub use fs::*;
 pub use halt::*;
 pub use hint::*;
 pub use unconstrained::*;
@@ -0,0 +1,587 @@
+use core::borrow::{Borrow, BorrowMut};
+use core::mem::size_of;
+
+use num::{BigUint, One, Zero};
+use p3_air::AirBuilder;
+use p3_air::{Air, BaseAir};
+use p3_field::AbstractField;
+use p3_field::PrimeField32;
+use p3_matrix::dense::RowMajorMatrix;
+use p3_matrix::Matrix;
+use sp1_derive::AlignedBorrow;
+
+use super::{
+    bigint_to_words_le, BigIntMulModEvent, MAX_WORDS_BIGINT, NUM_LIMBS_BIGINT,
+    NUM_WITNESS_LIMBS_BIGINT, WITNESS_OFFSET_BIGINT,
+};
+use crate::air::MachineAir;
+use crate::air::Polynomial;
+use crate::air::{BaseAirBuilder, SP1AirBuilder, Word, WordAirBuilder};
+use crate::memory::MemoryCols;
+use crate::memory::MemoryReadCols;
+use crate::memory::MemoryWriteCols;
+use crate::operations::field::params::NB_BITS_PER_LIMB;
+use crate::operations::field::util::compute_root_quotient_and_shift;
+use crate::runtime::ExecutionRecord;
+use crate::runtime::Program;
+use crate::runtime::Syscall;
+use crate::runtime::SyscallCode;
+use crate::syscall::precompiles::SyscallContext;
+use crate::utils::pad_rows;
+
+pub const NUM_BIGINT_MULMOD_COLS: usize = size_of::<BigIntMulModCols<u8>>();
+
+/// A set of columns to compute `x * y mod modulus` for integers of `n` words, where `n` is at most
+/// `MAX_WORDS_BIGINT` and is read from memory.
+///
+/// The operands are laid out as `MAX_WORDS_BIGINT` word polynomials in the byte limbs, whose words
+/// past `n` are zero. `x` is first reduced, so that the quotient of `x * y` by the modulus fits in
+/// `n` words for any `x` and `y`.
+#[derive(Debug, Clone, AlignedBorrow)]
+#[repr(C)]
+pub struct BigIntMulModCols<T> {
+    pub is_real: T,
+    pub shard: T,
+    pub clk: T,
+    pub x_ptr: T,
+    pub y_ptr: T,
+
+    /// `is_active[i]` is set iff `i < n`, so exactly the first `n` words are accessed.
+    pub is_active: [T; MAX_WORDS_BIGINT],
+
+    /// The read of `n`, which is the first word at `y_ptr`.
+    pub num_words_access: MemoryReadCols<T>,
+
+    pub x_access: [MemoryWriteCols<T>; MAX_WORDS_BIGINT],
+    pub y_access: [MemoryReadCols<T>; MAX_WORDS_BIGINT],
+    pub modulus_access: [MemoryReadCols<T>; MAX_WORDS_BIGINT],
+
+    /// The byte limbs of `x mod modulus`.
+    pub(crate) x_reduced: [T; NUM_LIMBS_BIGINT],
+
+    /// The check of `x * 1 = quotient * modulus + x_reduced`.
+    pub(crate) x_reduction: BigIntQuotientCols<T>,
+
+    /// The check of `x_reduced * y = quotient * modulus + result`.
+    pub(crate) product: BigIntQuotientCols<T>,
+}
+
+/// A set of columns to check `a * b = quotient * modulus + result` for byte-limb polynomials, as
+/// `a * b - quotient * modulus - result = (X - 2^8) * witness`. The quotient and the witness are
+/// range checked.
+#[derive(Debug, Clone, AlignedBorrow)]
+#[repr(C)]
+pub struct BigIntQuotientCols<T> {
+    /// The byte limbs of `a * b / modulus`.
+    pub(crate) quotient: [T; NUM_LIMBS_BIGINT],
+
+    /// The shifted witness coefficients, split into their low 16 bits and their high 8 bits.
+    pub(crate) witness_low: [T; NUM_WITNESS_LIMBS_BIGINT],
+    pub(crate) witness_high: [T; NUM_WITNESS_LIMBS_BIGINT],
+}
+
+#[derive(Default)]
+pub struct BigIntMulModChip;
+
+impl BigIntMulModChip {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Syscall for BigIntMulModChip {
+    fn execute(&self, rt: &mut SyscallContext, arg1: u32, arg2: u32) -> Option<u32> {
+        let start_clk = rt.clk;
+        let x_ptr = arg1;
+        if x_ptr % 4 != 0 {
+            panic!();
+        }
+        let y_ptr = arg2;
+        if y_ptr % 4 != 0 {
+            panic!();
+        }
+
+        let (num_words_memory_record, num_words) = rt.mr(y_ptr);
+        let num_words = num_words as usize;
+        assert!(
+            (1..=MAX_WORDS_BIGINT).contains(&num_words),
+            "bigint_mulmod: the number of words must be between 1 and {}",
+            MAX_WORDS_BIGINT
+        );
+
+        let x = rt.slice_unsafe(x_ptr, num_words);
+        let (y_memory_records, y) = rt.mr_slice(y_ptr + 4, num_words);
+        let (modulus_memory_records, modulus) =
+            rt.mr_slice(y_ptr + 4 * (num_words as u32 + 1), num_words);
+        // When we write to x, we want the clk to be incremented because x and y could be the same.
+        rt.clk += 1;
+
+        let modulus_big = BigUint::from_slice(&modulus);
+        assert!(
+            !modulus_big.is_zero(),
+            "bigint_mulmod: the modulus must be nonzero"
+        );
+        let x_reduced = BigUint::from_slice(&x) % &modulus_big;
+        let product = x_reduced * BigUint::from_slice(&y);
+        let result = bigint_to_words_le(&(product % &modulus_big), num_words);
+        let x_memory_records = rt.mw_slice(x_ptr, &result);
+
+        let shard = rt.current_shard();
+        rt.record_mut()
+            .bigint_mulmod_events
+            .push(BigIntMulModEvent {
+                shard,
+                clk: start_clk,
+                x_ptr,
+                x,
+                y_ptr,
+                y,
+                modulus,
+                num_words_memory_record,
+                x_memory_records,
+                y_memory_records,
+                modulus_memory_records,
+            });
+
+        None
+    }
+
+    fn num_extra_cycles(&self) -> u32 {
+        1
+    }
+}
+
+/// Returns the `NUM_LIMBS_BIGINT` little-endian byte limbs of an integer.
+fn bigint_limbs(x: &BigUint) -> Vec<u8> {
+    let mut bytes = x.to_bytes_le();
+    bytes.resize(NUM_LIMBS_BIGINT, 0u8);
+    bytes
+}
+
+impl<F: PrimeField32> BigIntQuotientCols<F> {
+    /// Populates the columns for `a * b = quotient * modulus + result`, and adds the range checks of
+    /// the quotient, the result and the witness to `output`. Returns the result.
+    fn populate(
+        &mut self,
+        output: &mut ExecutionRecord,
+        shard: u32,
+        a: &BigUint,
+        b: &BigUint,
+        modulus: &BigUint,
+    ) -> BigUint {
+        let product = a * b;
+        let result = &product % modulus;
+        let quotient = (&product - &result) / modulus;
+
+        let quotient_limbs = bigint_limbs(&quotient);
+        let result_limbs = bigint_limbs(&result);
+        let to_polynomial = |limbs: &[u8]| -> Polynomial<F> {
+            limbs
+                .iter()
+                .map(|&limb| F::from_canonical_u8(limb))
+                .collect()
+        };
+        let p_vanishing = &(&to_polynomial(&bigint_limbs(a)) * &to_polynomial(&bigint_limbs(b)))
+            - &(&to_polynomial(&quotient_limbs) * &to_polynomial(&bigint_limbs(modulus)));
+        let p_vanishing = p_vanishing - &to_polynomial(&result_limbs);
+        let p_witness = compute_root_quotient_and_shift(
+            &p_vanishing,
+            WITNESS_OFFSET_BIGINT,
+            NB_BITS_PER_LIMB as u32,
+        );
+
+        let mut witness_low = Vec::with_capacity(NUM_WITNESS_LIMBS_BIGINT);
+        let mut witness_high = Vec::with_capacity(NUM_WITNESS_LIMBS_BIGINT);
+        for (i, coefficient) in p_witness.iter().enumerate() {
+            let coefficient = coefficient.as_canonical_u32();
+            self.witness_low[i] = F::from_canonical_u32(coefficient & 0xffff);
+            self.witness_high[i] = F::from_canonical_u32(coefficient >> 16);
+            witness_low.push(coefficient & 0xffff);
+            witness_high.push((coefficient >> 16) as u8);
+        }
+        for i in 0..NUM_LIMBS_BIGINT {
+            self.quotient[i] = F::from_canonical_u8(quotient_limbs[i]);
+        }
+
+        output.add_u8_range_checks(shard, &quotient_limbs);
+        output.add_u8_range_checks(shard, &result_limbs);
+        output.add_u16_range_checks(shard, &witness_low);
+        output.add_u8_range_checks(shard, &witness_high);
+
+        result
+    }
+
+    /// Populates a padding row, in which all the operands are zero, so the shifted witness is the
+    /// offset.
+    fn populate_padding(&mut self) {
+        for i in 0..NUM_WITNESS_LIMBS_BIGINT {
+            self.witness_low[i] = F::from_canonical_usize(WITNESS_OFFSET_BIGINT & 0xffff);
+            self.witness_high[i] = F::from_canonical_usize(WITNESS_OFFSET_BIGINT >> 16);
+        }
+    }
+}
+
+impl<V: Copy> BigIntQuotientCols<V> {
+    /// Evaluates `a * b = quotient * modulus + result`, and range checks the quotient, the result
+    /// and the witness.
+    #[allow(clippy::too_many_arguments)]
+    fn eval<AB: SP1AirBuilder<Var = V>>(
+        &self,
+        builder: &mut AB,
+        p_a: &Polynomial<AB::Expr>,
+        p_b: &Polynomial<AB::Expr>,
+        p_modulus: &Polynomial<AB::Expr>,
+        result: &[V],
+        shard: V,
+        is_real: V,
+    ) where
+        V: Into<AB::Expr>,
+    {
+        let p_result: Polynomial<AB::Expr> = result.iter().map(|&limb| limb.into()).collect();
+        let p_quotient: Polynomial<AB::Expr> = self.quotient.iter().into();
+        let p_vanishing = &(p_a * p_b) - &(&p_quotient * p_modulus);
+        let p_vanishing = p_vanishing - &p_result;
+
+        let limb_base = AB::F::from_canonical_u32(1 << NB_BITS_PER_LIMB);
+        let offset = AB::F::from_canonical_usize(WITNESS_OFFSET_BIGINT);
+        let p_witness: Polynomial<AB::Expr> = self
+            .witness_low
+            .iter()
+            .zip(self.witness_high.iter())
+            .map(|(&low, &high)| {
+                let (low, high): (AB::Expr, AB::Expr) = (low.into(), high.into());
+                low + high * AB::F::from_canonical_u32(1 << 16) - AB::Expr::from(offset)
+            })
+            .collect();
+        let root_monomial = Polynomial::new(vec![-AB::Expr::from(limb_base), AB::Expr::one()]);
+        let constraints = p_vanishing - &(&p_witness * &root_monomial);
+        for constraint in constraints.as_coefficients() {
+            builder.assert_zero(constraint);
+        }
+
+        builder.slice_range_check_u8(&self.quotient, shard, is_real);
+        builder.slice_range_check_u8(result, shard, is_real);
+        builder.slice_range_check_u16(&self.witness_low, shard, is_real);
+        builder.slice_range_check_u8(&self.witness_high, shard, is_real);
+    }
+}
+
+impl<F: PrimeField32> MachineAir<F> for BigIntMulModChip {
+    type Record = ExecutionRecord;
+    type Program = Program;
+
+    fn name(&self) -> String {
+        "BigIntMulMod".to_string()
+    }
+
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        output: &mut ExecutionRecord,
+    ) -> RowMajorMatrix<F> {
+        let mut rows = Vec::new();
+
+        let mut new_byte_lookup_events = Vec::new();
+
+        for event in input.bigint_mulmod_events.iter() {
+            let mut row = [F::zero(); NUM_BIGINT_MULMOD_COLS];
+            let cols: &mut BigIntMulModCols<F> = row.as_mut_slice().borrow_mut();
+            let num_words = event.x.len();
+
+            // Populate basic columns.
+            cols.is_real = F::one();
+            cols.shard = F::from_canonical_u32(event.shard);
+            cols.clk = F::from_canonical_u32(event.clk);
+            cols.x_ptr = F::from_canonical_u32(event.x_ptr);
+            cols.y_ptr = F::from_canonical_u32(event.y_ptr);
+            for i in 0..num_words {
+                cols.is_active[i] = F::one();
+            }
+
+            // Populate the memory access columns.
+            cols.num_words_access
+                .populate(event.num_words_memory_record, &mut new_byte_lookup_events);
+            for i in 0..num_words {
+                cols.y_access[i].populate(event.y_memory_records[i], &mut new_byte_lookup_events);
+                cols.modulus_access[i]
+                    .populate(event.modulus_memory_records[i], &mut new_byte_lookup_events);
+                cols.x_access[i].populate(event.x_memory_records[i], &mut new_byte_lookup_events);
+            }
+
+            // Reduce `x`, and then multiply it by `y`.
+            let modulus = BigUint::from_slice(&event.modulus);
+            let x_reduced = cols.x_reduction.populate(
+                output,
+                event.shard,
+                &BigUint::from_slice(&event.x),
+                &BigUint::one(),
+                &modulus,
+            );
+            for (i, limb) in bigint_limbs(&x_reduced).into_iter().enumerate() {
+                cols.x_reduced[i] = F::from_canonical_u8(limb);
+            }
+            cols.product.populate(
+                output,
+                event.shard,
+                &x_reduced,
+                &BigUint::from_slice(&event.y),
+                &modulus,
+            );
+
+            rows.push(row);
+        }
+        output.add_byte_lookup_events(new_byte_lookup_events);
+
+        pad_rows(&mut rows, || {
+            let mut row = [F::zero(); NUM_BIGINT_MULMOD_COLS];
+            let cols: &mut BigIntMulModCols<F> = row.as_mut_slice().borrow_mut();
+            cols.x_reduction.populate_padding();
+            cols.product.populate_padding();
+            row
+        });
+
+        // Convert the trace to a row major matrix.
+        RowMajorMatrix::new(
+            rows.into_iter().flatten().collect::<Vec<_>>(),
+            NUM_BIGINT_MULMOD_COLS,
+        )
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        !shard.bigint_mulmod_events.is_empty()
+    }
+}
+
+impl<F> BaseAir<F> for BigIntMulModChip {
+    fn width(&self) -> usize {
+        NUM_BIGINT_MULMOD_COLS
+    }
+}
+
+impl<AB> Air<AB> for BigIntMulModChip
+where
+    AB: SP1AirBuilder,
+{
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let row = main.row_slice(0);
+        let row: &BigIntMulModCols<AB::Var> = (*row).borrow();
+
+        builder.assert_bool(row.is_real);
+
+        // The active words are a prefix of the operands, whose length is the number of words read
+        // from memory. The first word is always active.
+        builder.assert_eq(row.is_active[0], row.is_real);
+        let mut num_words = AB::Expr::zero();
+        for i in 0..MAX_WORDS_BIGINT {
+            builder.assert_bool(row.is_active[i]);
+            if i + 1 < MAX_WORDS_BIGINT {
+                builder
+                    .when(row.is_active[i + 1])
+                    .assert_one(row.is_active[i]);
+            }
+            num_words += row.is_active[i].into();
+        }
+        let num_words_value = row.num_words_access.value();
+        builder
+            .when(row.is_real)
+            .assert_eq(num_words_value[0], num_words.clone());
+        for i in 1..4 {
+            builder.when(row.is_real).assert_zero(num_words_value[i]);
+        }
+
+        // The inactive words of the operands and of the result are zero.
+        for i in 0..MAX_WORDS_BIGINT {
+            builder
+                .when_not(row.is_active[i])
+                .assert_word_zero(*row.x_access[i].prev_value());
+            builder
+                .when_not(row.is_active[i])
+                .assert_word_zero(*row.x_access[i].value());
+            builder
+                .when_not(row.is_active[i])
+                .assert_word_zero(*row.y_access[i].value());
+            builder
+                .when_not(row.is_active[i])
+                .assert_word_zero(*row.modulus_access[i].value());
+        }
+
+        // Check `x = quotient * modulus + x_reduced` and `x_reduced * y = quotient * modulus + result`.
+        let limbs = |words: Vec<Word<AB::Var>>| -> Polynomial<AB::Expr> {
+            words
+                .into_iter()
+                .flat_map(|word| word.0)
+                .map(Into::into)
+                .collect()
+        };
+        let p_x = limbs(row.x_access.iter().map(|a| *a.prev_value()).collect());
+        let p_y = limbs(row.y_access.iter().map(|a| *a.value()).collect());
+        let p_modulus = limbs(row.modulus_access.iter().map(|a| *a.value()).collect());
+        let p_one: Polynomial<AB::Expr> = std::iter::once(AB::Expr::one()).collect();
+        let p_x_reduced: Polynomial<AB::Expr> = row.x_reduced.iter().into();
+        row.x_reduction.eval::<AB>(
+            builder,
+            &p_x,
+            &p_one,
+            &p_modulus,
+            &row.x_reduced,
+            row.shard,
+            row.is_real,
+        );
+        let result_limbs = row
+            .x_access
+            .iter()
+            .flat_map(|access| access.value().0)
+            .collect::<Vec<_>>();
+        row.product.eval::<AB>(
+            builder,
+            &p_x_reduced,
+            &p_y,
+            &p_modulus,
+            &result_limbs,
+            row.shard,
+            row.is_real,
+        );
+
+        // Read `n` and the active words of `y` and the modulus, and write the result over `x`.
+        builder.eval_memory_access(
+            row.shard,
+            row.clk,
+            row.y_ptr,
+            &row.num_words_access,
+            row.is_real,
+        );
+        for i in 0..MAX_WORDS_BIGINT {
+            builder.eval_memory_access(
+                row.shard,
+                row.clk,
+                row.y_ptr + AB::F::from_canonical_usize(4 * (i + 1)),
+                &row.y_access[i],
+                row.is_active[i],
+            );
+            builder.eval_memory_access(
+                row.shard,
+                row.clk,
+                row.y_ptr
+                    + num_words.clone() * AB::F::from_canonical_u32(4)
+                    + AB::F::from_canonical_usize(4 * (i + 1)),
+                &row.modulus_access[i],
+                row.is_active[i],
+            );
+            builder.eval_memory_access(
+                row.shard,
+                row.clk + AB::F::one(), // We write x at +1 since x, y could be the same.
+                row.x_ptr + AB::F::from_canonical_usize(4 * i),
+                &row.x_access[i],
+                row.is_active[i],
+            );
+        }
+
+        builder.receive_syscall(
+            row.shard,
+            row.clk,
+            AB::F::from_canonical_u32(SyscallCode::BIGINT_MULMOD.syscall_id()),
+            row.x_ptr,
+            row.y_ptr,
+            row.is_real,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::bigint::RandBigInt;
+    use num::{BigUint, One};
+
+    use crate::{
+        runtime::{Instruction, Opcode, Program, Runtime, SyscallCode},
+        syscall::precompiles::bigint::bigint_to_words_le,
+        utils::{run_test, setup_logger},
+    };
+
+    const X_PTR: u32 = 1000;
+    const ARGS_PTR: u32 = 4000;
+
+    /// Writes `x` and the arguments, which are the number of words followed by `y` and the
+    /// modulus, and multiplies `x` by `y` modulo the modulus in place.
+    pub fn bigint_mulmod_program(x: &[u32], args: &[u32]) -> Program {
+        let mut instructions = vec![];
+        for (ptr, words) in [(X_PTR, x), (ARGS_PTR, args)] {
+            for (i, word) in words.iter().enumerate() {
+                instructions.extend(vec![
+                    Instruction::new(Opcode::ADD, 29, 0, *word, false, true),
+                    Instruction::new(Opcode::ADD, 30, 0, ptr + i as u32 * 4, false, true),
+                    Instruction::new(Opcode::SW, 29, 30, 0, false, true),
+                ]);
+            }
+        }
+        instructions.extend(vec![
+            Instruction::new(
+                Opcode::ADD,
+                5,
+                0,
+                SyscallCode::BIGINT_MULMOD as u32,
+                false,
+                true,
+            ),
+            Instruction::new(Opcode::ADD, 10, 0, X_PTR, false, true),
+            Instruction::new(Opcode::ADD, 11, 0, ARGS_PTR, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+        ]);
+        Program::new(instructions, 0, 0)
+    }
+
+    fn test_bigint_mulmod(x: &BigUint, y: &BigUint, modulus: &BigUint, num_words: usize) {
+        setup_logger();
+        let mut args = vec![num_words as u32];
+        args.extend(bigint_to_words_le(y, num_words));
+        args.extend(bigint_to_words_le(modulus, num_words));
+
+        let program = bigint_mulmod_program(&bigint_to_words_le(x, num_words), &args);
+        let mut runtime = Runtime::new(program.clone());
+        runtime.run();
+        let result = (0..num_words as u32)
+            .map(|i| runtime.word(X_PTR + i * 4))
+            .collect::<Vec<_>>();
+
+        let expected = (x * y) % modulus;
+        assert_eq!(result, bigint_to_words_le(&expected, num_words));
+        run_test(program).unwrap();
+    }
+
+    #[test]
+    fn test_bigint_mulmod_2048() {
+        let mut rng = rand::thread_rng();
+        let modulus = rng.gen_biguint(2048) | BigUint::one();
+        let x = rng.gen_biguint_below(&modulus);
+        let y = rng.gen_biguint_below(&modulus);
+        test_bigint_mulmod(&x, &y, &modulus, 64);
+    }
+
+    #[test]
+    fn test_bigint_mulmod_4096() {
+        let mut rng = rand::thread_rng();
+        let modulus = (BigUint::one() << 4096) - 1u32;
+        let x = rng.gen_biguint_below(&modulus);
+        let y = rng.gen_biguint(4096);
+        test_bigint_mulmod(&x, &y, &modulus, 128);
+    }
+
+    #[test]
+    fn test_bigint_mulmod_unreduced() {
+        // Neither operand is reduced, so `x * y / modulus` does not fit in 64 words.
+        let mut rng = rand::thread_rng();
+        let modulus = rng.gen_biguint(100) | BigUint::one();
+        let x = (BigUint::one() << 2048) - 1u32;
+        let y = rng.gen_biguint(2048);
+        test_bigint_mulmod(&x, &y, &modulus, 64);
+    }
+
+    #[test]
+    fn test_bigint_mulmod_single_word() {
+        let modulus = BigUint::from(1_000_000_007u32);
+        let x = BigUint::from(999_999_999u32);
+        let y = BigUint::from(u32::MAX);
+        test_bigint_mulmod(&x, &y, &modulus, 1);
+    }
+}
@@ -0,0 +1,50 @@
+mod air;
+
+pub use air::*;
+
+use num::BigUint;
+use serde::{Deserialize, Serialize};
+
+use crate::runtime::{MemoryReadRecord, MemoryWriteRecord};
+
+/// The largest number of words of the operands, which makes for 4096-bit integers.
+pub const MAX_WORDS_BIGINT: usize = 128;
+
+/// The number of byte limbs of an operand of `MAX_WORDS_BIGINT` words.
+pub(crate) const NUM_LIMBS_BIGINT: usize = 4 * MAX_WORDS_BIGINT;
+
+/// The number of limbs of the witness polynomial, whose degree is one less than that of
+/// `x * y - quotient * modulus - result`.
+pub(crate) const NUM_WITNESS_LIMBS_BIGINT: usize = 2 * NUM_LIMBS_BIGINT - 2;
+
+/// The witness coefficients are sums of up to `NUM_LIMBS_BIGINT` products of bytes divided by
+/// `2^8`, so they are less than `2^18` in absolute value. The shifted coefficients take 21 bits,
+/// which are stored as a u16 and a u8.
+pub(crate) const WITNESS_OFFSET_BIGINT: usize = 1usize << 20;
+
+/// BigInt mulmod event.
+///
+/// The syscall reads the number of words `n` followed by `y || modulus` from `y_ptr`, and
+/// overwrites the `n` words of `x` with `x * y mod modulus`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BigIntMulModEvent {
+    pub shard: u32,
+    pub clk: u32,
+    pub x_ptr: u32,
+    pub x: Vec<u32>,
+    pub y_ptr: u32,
+    pub y: Vec<u32>,
+    pub modulus: Vec<u32>,
+    pub num_words_memory_record: MemoryReadRecord,
+    pub x_memory_records: Vec<MemoryWriteRecord>,
+    pub y_memory_records: Vec<MemoryReadRecord>,
+    pub modulus_memory_records: Vec<MemoryReadRecord>,
+}
+
+/// Serializes an integer as `num_words` little-endian words.
+pub fn bigint_to_words_le(x: &BigUint, num_words: usize) -> Vec<u32> {
+    let mut words = x.to_u32_digits();
+    debug_assert!(words.len() <= num_words);
+    words.resize(num_words, 0);
+    words
+}
@@ -0,0 +1,358 @@
+use core::borrow::Borrow;
+
+use p3_air::{Air, AirBuilder, BaseAir};
+use p3_field::AbstractField;
+use p3_matrix::Matrix;
+
+use super::columns::{Blake2bCompressCols, NUM_BLAKE2B_COMPRESS_COLS};
+use super::g::Blake2bGOperation;
+use super::{
+    Blake2bCompressChip, BLAKE2B_IV, F_OFFSET, G_INDEX, MSG_OFFSET, MSG_SIZE, OPERATION_COUNT,
+    SIGMA, SIGMA_COUNT, STATE_SIZE, T_OFFSET,
+};
+use crate::air::{BaseAirBuilder, SP1AirBuilder};
+use crate::bytes::ByteOpcode;
+use crate::memory::MemoryCols;
+use crate::operations::uint64::{u64_from_words, u64_to_bytes, XorU64Operation, U64_SIZE};
+use crate::operations::IsZeroOperation;
+use crate::runtime::SyscallCode;
+
+impl<F> BaseAir<F> for Blake2bCompressChip {
+    fn width(&self) -> usize {
+        NUM_BLAKE2B_COMPRESS_COLS
+    }
+}
+
+impl<AB> Air<AB> for Blake2bCompressChip
+where
+    AB: SP1AirBuilder,
+{
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let (local, next) = (main.row_slice(0), main.row_slice(1));
+        let local: &Blake2bCompressCols<AB::Var> = (*local).borrow();
+        let next: &Blake2bCompressCols<AB::Var> = (*next).borrow();
+
+        self.constrain_control_flow_flags(builder, local, next);
+
+        self.constrain_memory(builder, local);
+
+        self.constrain_init(builder, local);
+
+        self.constrain_round(builder, local, next);
+
+        self.constrain_final(builder, local);
+
+        builder.receive_syscall(
+            local.shard,
+            local.clk,
+            AB::F::from_canonical_u32(SyscallCode::BLAKE2B_COMPRESS.syscall_id()),
+            local.h_ptr,
+            local.input_ptr,
+            local.is_first,
+        );
+    }
+}
+
+impl Blake2bCompressChip {
+    /// Constrains the control flow flags such as the row index and the message permutation index.
+    fn constrain_control_flow_flags<AB: SP1AirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &Blake2bCompressCols<AB::Var>,
+        next: &Blake2bCompressCols<AB::Var>,
+    ) {
+        builder.assert_bool(local.is_real);
+        builder.assert_bool(local.is_first);
+
+        // The final row is the one where `round == rounds`, and every other real row is a round.
+        IsZeroOperation::<AB::F>::eval(
+            builder,
+            local.rounds - local.round,
+            local.rounds_left,
+            local.is_real.into(),
+        );
+        builder.assert_eq(local.is_final, local.rounds_left.result * local.is_real);
+        builder.assert_eq(local.is_round, local.is_real - local.is_final);
+
+        // A syscall starts on the first real row and after each final row.
+        builder
+            .when_first_row()
+            .assert_eq(local.is_first, local.is_real);
+        builder
+            .when_transition()
+            .assert_eq(next.is_first, local.is_final * next.is_real);
+        builder.when(local.is_first).assert_zero(local.round);
+        builder.when(local.is_first).assert_one(local.is_real);
+
+        // A round is always followed by another row of the same syscall, and padding is only
+        // followed by padding.
+        builder
+            .when_transition()
+            .when(local.is_round)
+            .assert_one(next.is_real);
+        builder
+            .when_transition()
+            .when_not(local.is_real)
+            .assert_zero(next.is_real);
+
+        // Assert that the table ends in nonreal columns, so that every syscall reaches its final
+        // row. The table is padded with at least one row to guarantee this.
+        builder.when_last_row().assert_zero(local.is_real);
+
+        // Copy over the inputs until the final row.
+        builder
+            .when_transition()
+            .when(local.is_round)
+            .assert_eq(local.shard, next.shard);
+        builder
+            .when_transition()
+            .when(local.is_round)
+            .assert_eq(local.clk, next.clk);
+        builder
+            .when_transition()
+            .when(local.is_round)
+            .assert_eq(local.h_ptr, next.h_ptr);
+        builder
+            .when_transition()
+            .when(local.is_round)
+            .assert_eq(local.input_ptr, next.input_ptr);
+        builder
+            .when_transition()
+            .when(local.is_round)
+            .assert_eq(local.rounds, next.rounds);
+        builder
+            .when_transition()
+            .when(local.is_round)
+            .assert_eq(local.round + AB::Expr::one(), next.round);
+        for i in 0..STATE_SIZE {
+            builder
+                .when_transition()
+                .when(local.is_round)
+                .assert_all_eq(local.h[i], next.h[i]);
+        }
+        for i in 0..MSG_SIZE {
+            builder
+                .when_transition()
+                .when(local.is_round)
+                .assert_all_eq(local.m[i], next.m[i]);
+        }
+
+        // Verify that exactly one of the message permutation flags is set, that the first row uses
+        // the first permutation, and that each round moves on to the next permutation.
+        let mut sigma_sum = AB::Expr::zero();
+        for i in 0..SIGMA_COUNT {
+            builder.assert_bool(local.is_sigma_index_n[i]);
+            sigma_sum += local.is_sigma_index_n[i].into();
+        }
+        builder.when(local.is_real).assert_one(sigma_sum);
+        builder
+            .when(local.is_first)
+            .assert_one(local.is_sigma_index_n[0]);
+        for i in 0..SIGMA_COUNT {
+            builder.when_transition().when(local.is_round).assert_eq(
+                local.is_sigma_index_n[i],
+                next.is_sigma_index_n[(i + 1) % SIGMA_COUNT],
+            );
+        }
+    }
+
+    /// Constrains the memory accesses: the inputs are read in the first row and the new state is
+    /// written in the final row.
+    fn constrain_memory<AB: SP1AirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &Blake2bCompressCols<AB::Var>,
+    ) {
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.clk.into(),
+            local.h_ptr,
+            &local.h_reads,
+            local.is_first,
+        );
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.clk.into(),
+            local.input_ptr,
+            &local.input_reads,
+            local.is_first,
+        );
+        // We write h at +1 since it was already read at clk.
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.clk + AB::F::one(),
+            local.h_ptr,
+            &local.h_writes,
+            local.is_final,
+        );
+    }
+
+    /// Constrains the inputs read in the first row and the initial working vector.
+    fn constrain_init<AB: SP1AirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &Blake2bCompressCols<AB::Var>,
+    ) {
+        let is_first = local.is_first;
+
+        // The state and the message are the values read from memory.
+        for i in 0..STATE_SIZE {
+            let h_i = u64_from_words(
+                local.h_reads[2 * i].value(),
+                local.h_reads[2 * i + 1].value(),
+            );
+            builder.when(is_first).assert_all_eq(local.h[i], h_i);
+        }
+        for i in 0..MSG_SIZE {
+            let m_i = u64_from_words(
+                local.input_reads[MSG_OFFSET + 2 * i].value(),
+                local.input_reads[MSG_OFFSET + 2 * i + 1].value(),
+            );
+            builder.when(is_first).assert_all_eq(local.m[i], m_i);
+        }
+
+        // The number of rounds is the first word, which must fit in 24 bits.
+        let rounds = local.input_reads[0].value();
+        builder.when(is_first).assert_eq(
+            local.rounds,
+            rounds[0]
+                + rounds[1] * AB::F::from_canonical_u32(1 << 8)
+                + rounds[2] * AB::F::from_canonical_u32(1 << 16),
+        );
+        builder.when(is_first).assert_zero(rounds[3]);
+
+        // The final block flag is a boolean word.
+        let f = local.input_reads[F_OFFSET].value();
+        builder.when(is_first).assert_bool(f[0]);
+        for i in 1..4 {
+            builder.when(is_first).assert_zero(f[i]);
+        }
+
+        // v[0..8] = h and v[8..16] = IV, then v[12] ^= t[0], v[13] ^= t[1] and v[14] ^= f * !0.
+        let t = [0, 1].map(|i| {
+            u64_from_words(
+                local.input_reads[T_OFFSET + 2 * i].value(),
+                local.input_reads[T_OFFSET + 2 * i + 1].value(),
+            )
+        });
+        for i in 0..STATE_SIZE {
+            builder.when(is_first).assert_all_eq(local.v[i], local.h[i]);
+        }
+        for (i, iv) in BLAKE2B_IV.iter().enumerate() {
+            let iv = u64_to_bytes::<AB::F>(*iv);
+            let v = local.v[STATE_SIZE + i];
+            match i {
+                4 | 5 => {
+                    for j in 0..U64_SIZE {
+                        builder.send_byte(
+                            AB::F::from_canonical_u32(ByteOpcode::XOR as u32),
+                            v[j],
+                            t[i - 4][j],
+                            iv[j],
+                            local.shard,
+                            is_first,
+                        );
+                    }
+                }
+                6 => {
+                    for j in 0..U64_SIZE {
+                        builder.when(is_first).assert_eq(
+                            v[j],
+                            f[0] * (AB::Expr::from_canonical_u32(255) - iv[j] * AB::F::two())
+                                + iv[j],
+                        );
+                    }
+                }
+                _ => builder.when(is_first).assert_all_eq(v, iv),
+            }
+        }
+    }
+
+    /// Constrains a round of the compression function on the working vector.
+    fn constrain_round<AB: SP1AirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &Blake2bCompressCols<AB::Var>,
+        next: &Blake2bCompressCols<AB::Var>,
+    ) {
+        // Permute the message according to the current round.
+        for k in 0..MSG_SIZE {
+            for j in 0..U64_SIZE {
+                let mut permuted = AB::Expr::zero();
+                for s in 0..SIGMA_COUNT {
+                    permuted += local.is_sigma_index_n[s] * local.m[SIGMA[s][k]][j];
+                }
+                builder
+                    .when(local.is_real)
+                    .assert_eq(local.permuted_m[k][j], permuted);
+            }
+        }
+
+        // Call g on the columns and then on the diagonals of the working vector.
+        let mut v = local.v;
+        for operation in 0..OPERATION_COUNT {
+            let index = G_INDEX[operation];
+            let input = [
+                v[index[0]],
+                v[index[1]],
+                v[index[2]],
+                v[index[3]],
+                local.permuted_m[2 * operation],
+                local.permuted_m[2 * operation + 1],
+            ];
+            Blake2bGOperation::<AB::F>::eval(
+                builder,
+                input,
+                local.g[operation],
+                local.shard,
+                local.is_round,
+            );
+            let result = local.g[operation].result();
+            for i in 0..4 {
+                v[index[i]] = result[i];
+            }
+        }
+
+        // The result of the round is the working vector of the next row.
+        for i in 0..v.len() {
+            builder
+                .when_transition()
+                .when(local.is_round)
+                .assert_all_eq(next.v[i], v[i]);
+        }
+    }
+
+    /// Constrains the new state `h[i] ^ v[i] ^ v[i + 8]` written in the final row.
+    fn constrain_final<AB: SP1AirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &Blake2bCompressCols<AB::Var>,
+    ) {
+        for i in 0..STATE_SIZE {
+            XorU64Operation::<AB::F>::eval(
+                builder,
+                local.v[i],
+                local.v[i + STATE_SIZE],
+                local.v_xor[i],
+                local.shard,
+                local.is_final,
+            );
+            XorU64Operation::<AB::F>::eval(
+                builder,
+                local.h[i],
+                local.v_xor[i].value,
+                local.h_xor[i],
+                local.shard,
+                local.is_final,
+            );
+            let new_h_i = u64_from_words(
+                local.h_writes[2 * i].value(),
+                local.h_writes[2 * i + 1].value(),
+            );
+            builder
+                .when(local.is_final)
+                .assert_all_eq(new_h_i, local.h_xor[i].value);
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use std::mem::size_of;
+
+use sp1_derive::AlignedBorrow;
+
+use crate::memory::MemoryReadCols;
+use crate::memory::MemoryWriteCols;
+use crate::operations::uint64::XorU64Operation;
+use crate::operations::uint64::U64_SIZE;
+use crate::operations::IsZeroOperation;
+
+use super::g::Blake2bGOperation;
+use super::INPUT_WORDS;
+use super::MSG_SIZE;
+use super::OPERATION_COUNT;
+use super::SIGMA_COUNT;
+use super::STATE_SIZE;
+use super::WORK_SIZE;
+
+pub const NUM_BLAKE2B_COMPRESS_COLS: usize = size_of::<Blake2bCompressCols<u8>>();
+
+#[derive(AlignedBorrow, Default, Debug, Clone, Copy)]
+#[repr(C)]
+pub struct Blake2bCompressCols<T> {
+    pub shard: T,
+    pub clk: T,
+
+    /// The pointer to the state.
+    pub h_ptr: T,
+
+    /// The pointer to the input block.
+    pub input_ptr: T,
+
+    /// Whether this is the first row of a syscall, in which the inputs are read.
+    pub is_first: T,
+
+    /// The index of the current row within the syscall. Rows `0..rounds` each compute a round.
+    pub round: T,
+
+    /// The number of rounds to compute.
+    pub rounds: T,
+
+    /// Checks whether `rounds - round` is zero, which marks the final row of the syscall.
+    pub rounds_left: IsZeroOperation<T>,
+
+    /// Whether this is the final row of a syscall, in which the new state is written.
+    pub is_final: T,
+
+    /// Whether this row computes a round.
+    pub is_round: T,
+
+    /// Indicates which message permutation, `round % 10`, is used.
+    pub is_sigma_index_n: [T; SIGMA_COUNT],
+
+    /// Reads the state and the input block in the first row.
+    pub h_reads: [MemoryReadCols<T>; 2 * STATE_SIZE],
+    pub input_reads: [MemoryReadCols<T>; INPUT_WORDS],
+
+    /// The state and the message, which are copied over every row of the syscall.
+    pub h: [[T; U64_SIZE]; STATE_SIZE],
+    pub m: [[T; U64_SIZE]; MSG_SIZE],
+
+    /// The working vector at the start of the row.
+    pub v: [[T; U64_SIZE]; WORK_SIZE],
+
+    /// The message permuted by `SIGMA[round % 10]`.
+    pub permuted_m: [[T; U64_SIZE]; MSG_SIZE],
+
+    /// The `g` operations of the round, on the columns and then on the diagonals of `v`.
+    pub g: [Blake2bGOperation<T>; OPERATION_COUNT],
+
+    /// `v[i] ^ v[i + 8]` and `h[i] ^ v[i] ^ v[i + 8]` in the final row.
+    pub v_xor: [XorU64Operation<T>; STATE_SIZE],
+    pub h_xor: [XorU64Operation<T>; STATE_SIZE],
+
+    /// Writes the new state in the final row.
+    pub h_writes: [MemoryWriteCols<T>; 2 * STATE_SIZE],
+
+    /// Indicates if the current row is real or not.
+    pub is_real: T,
+}
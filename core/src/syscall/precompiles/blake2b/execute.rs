@@ -0,0 +1,73 @@
+use crate::runtime::Syscall;
+use crate::syscall::precompiles::blake2b::{
+    blake2b_compress, Blake2bCompressChip, Blake2bCompressEvent, F_OFFSET, INPUT_WORDS, MAX_ROUNDS,
+    MSG_OFFSET, MSG_SIZE, STATE_SIZE, T_OFFSET,
+};
+use crate::syscall::precompiles::SyscallContext;
+
+/// Reassembles little-endian u64s from pairs of words.
+fn words_to_u64s<const N: usize>(words: &[u32]) -> [u64; N] {
+    core::array::from_fn(|i| words[2 * i] as u64 | (words[2 * i + 1] as u64) << 32)
+}
+
+impl Syscall for Blake2bCompressChip {
+    fn num_extra_cycles(&self) -> u32 {
+        1
+    }
+
+    fn execute(&self, rt: &mut SyscallContext, arg1: u32, arg2: u32) -> Option<u32> {
+        let h_ptr = arg1;
+        let input_ptr = arg2;
+        if h_ptr % 4 != 0 || input_ptr % 4 != 0 {
+            panic!();
+        }
+
+        let start_clk = rt.clk;
+
+        // Read the state and the input block.
+        let (h_read_records, h_words) = rt.mr_slice(h_ptr, 2 * STATE_SIZE);
+        let (input_read_records, input) = rt.mr_slice(input_ptr, INPUT_WORDS);
+
+        let rounds = input[0];
+        assert!(rounds <= MAX_ROUNDS, "blake2b_compress: too many rounds");
+        let m: [u64; MSG_SIZE] = words_to_u64s(&input[MSG_OFFSET..T_OFFSET]);
+        let t: [u64; 2] = words_to_u64s(&input[T_OFFSET..F_OFFSET]);
+        let f = match input[F_OFFSET] {
+            0 => false,
+            1 => true,
+            _ => panic!("blake2b_compress: the final block flag must be 0 or 1"),
+        };
+
+        let h: [u64; STATE_SIZE] = words_to_u64s(&h_words);
+        let mut new_h = h;
+        blake2b_compress(rounds, &mut new_h, &m, t, f);
+
+        // Increment the clk by 1 before writing to h, since we've already read h at the start_clk.
+        rt.clk += 1;
+        let new_h_words = new_h
+            .iter()
+            .flat_map(|x| [*x as u32, (*x >> 32) as u32])
+            .collect::<Vec<_>>();
+        let h_write_records = rt.mw_slice(h_ptr, &new_h_words);
+
+        let shard = rt.current_shard();
+        rt.record_mut()
+            .blake2b_compress_events
+            .push(Blake2bCompressEvent {
+                shard,
+                clk: start_clk,
+                h_ptr,
+                input_ptr,
+                rounds,
+                h,
+                m,
+                t,
+                f,
+                h_read_records,
+                input_read_records,
+                h_write_records,
+            });
+
+        None
+    }
+}
@@ -0,0 +1,185 @@
+use p3_field::Field;
+use sp1_derive::AlignedBorrow;
+
+use crate::air::SP1AirBuilder;
+use crate::operations::uint64::{
+    AddU64Operation, FixedRotateRightU64Operation, XorU64Operation, U64_SIZE,
+};
+use crate::runtime::ExecutionRecord;
+
+use super::g_func;
+
+/// Rotates a u64 given as little-endian bytes right by a whole number of bytes.
+pub(crate) fn rotate_right_bytes<T: Copy>(x: [T; U64_SIZE], nb_bytes: usize) -> [T; U64_SIZE] {
+    core::array::from_fn(|i| x[(i + nb_bytes) % U64_SIZE])
+}
+
+/// A set of columns needed to compute the BLAKE2b `g` of the input state.
+///  ``` ignore
+/// fn g(v: &mut [u64; 16], a: usize, b: usize, c: usize, d: usize, x: u64, y: u64) {
+///     v[a] = v[a].wrapping_add(v[b]).wrapping_add(x);
+///     v[d] = (v[d] ^ v[a]).rotate_right(32);
+///     v[c] = v[c].wrapping_add(v[d]);
+///     v[b] = (v[b] ^ v[c]).rotate_right(24);
+///     v[a] = v[a].wrapping_add(v[b]).wrapping_add(y);
+///     v[d] = (v[d] ^ v[a]).rotate_right(16);
+///     v[c] = v[c].wrapping_add(v[d]);
+///     v[b] = (v[b] ^ v[c]).rotate_right(63);
+/// }
+///  ```
+#[derive(AlignedBorrow, Default, Debug, Clone, Copy)]
+#[repr(C)]
+pub struct Blake2bGOperation<T> {
+    pub a_plus_b_plus_x: AddU64Operation<T>,
+    pub d_xor_a: XorU64Operation<T>,
+    // Rotate right by 32 bits by just shifting bytes.
+    pub c_plus_d: AddU64Operation<T>,
+    pub b_xor_c: XorU64Operation<T>,
+    // Rotate right by 24 bits by just shifting bytes.
+    pub a_plus_b_plus_y: AddU64Operation<T>,
+    pub d_xor_a_2: XorU64Operation<T>,
+    // Rotate right by 16 bits by just shifting bytes.
+    pub c_plus_d_2: AddU64Operation<T>,
+    pub b_xor_c_2: XorU64Operation<T>,
+    pub b_xor_c_2_rotate_right_63: FixedRotateRightU64Operation<T>,
+}
+
+impl<T: Copy> Blake2bGOperation<T> {
+    /// `v[a]`, `v[b]`, `v[c]`, `v[d]` after all the steps.
+    pub fn result(&self) -> [[T; U64_SIZE]; 4] {
+        [
+            self.a_plus_b_plus_y.value,
+            self.b_xor_c_2_rotate_right_63.value,
+            self.c_plus_d_2.value,
+            rotate_right_bytes(self.d_xor_a_2.value, 2),
+        ]
+    }
+}
+
+impl<F: Field> Blake2bGOperation<F> {
+    pub fn populate(
+        &mut self,
+        record: &mut ExecutionRecord,
+        shard: u32,
+        input: [u64; 6],
+    ) -> [u64; 4] {
+        let mut a = input[0];
+        let mut b = input[1];
+        let mut c = input[2];
+        let mut d = input[3];
+        let x = input[4];
+        let y = input[5];
+
+        // First 4 steps.
+        {
+            // a = a + b + x.
+            a = self.a_plus_b_plus_x.populate(record, shard, &[a, b, x]);
+
+            // d = (d ^ a).rotate_right(32).
+            d = self.d_xor_a.populate(record, shard, d, a);
+            d = d.rotate_right(32);
+
+            // c = c + d.
+            c = self.c_plus_d.populate(record, shard, &[c, d]);
+
+            // b = (b ^ c).rotate_right(24).
+            b = self.b_xor_c.populate(record, shard, b, c);
+            b = b.rotate_right(24);
+        }
+
+        // Second 4 steps.
+        {
+            // a = a + b + y.
+            a = self.a_plus_b_plus_y.populate(record, shard, &[a, b, y]);
+
+            // d = (d ^ a).rotate_right(16).
+            d = self.d_xor_a_2.populate(record, shard, d, a);
+            d = d.rotate_right(16);
+
+            // c = c + d.
+            c = self.c_plus_d_2.populate(record, shard, &[c, d]);
+
+            // b = (b ^ c).rotate_right(63).
+            b = self.b_xor_c_2.populate(record, shard, b, c);
+            b = self
+                .b_xor_c_2_rotate_right_63
+                .populate(record, shard, b, 63);
+        }
+
+        let result = [a, b, c, d];
+        debug_assert_eq!(result, g_func(input));
+        result
+    }
+
+    pub fn eval<AB: SP1AirBuilder>(
+        builder: &mut AB,
+        input: [[AB::Var; U64_SIZE]; 6],
+        cols: Blake2bGOperation<AB::Var>,
+        shard: AB::Var,
+        is_real: AB::Var,
+    ) {
+        let mut a = input[0];
+        let mut b = input[1];
+        let mut c = input[2];
+        let mut d = input[3];
+        let x = input[4];
+        let y = input[5];
+
+        // First 4 steps.
+        {
+            // a = a + b + x.
+            AddU64Operation::<AB::F>::eval(
+                builder,
+                &[a, b, x],
+                shard,
+                is_real,
+                cols.a_plus_b_plus_x,
+            );
+            a = cols.a_plus_b_plus_x.value;
+
+            // d = (d ^ a).rotate_right(32).
+            XorU64Operation::<AB::F>::eval(builder, d, a, cols.d_xor_a, shard, is_real);
+            d = rotate_right_bytes(cols.d_xor_a.value, 4);
+
+            // c = c + d.
+            AddU64Operation::<AB::F>::eval(builder, &[c, d], shard, is_real, cols.c_plus_d);
+            c = cols.c_plus_d.value;
+
+            // b = (b ^ c).rotate_right(24).
+            XorU64Operation::<AB::F>::eval(builder, b, c, cols.b_xor_c, shard, is_real);
+            b = rotate_right_bytes(cols.b_xor_c.value, 3);
+        }
+
+        // Second 4 steps.
+        {
+            // a = a + b + y.
+            AddU64Operation::<AB::F>::eval(
+                builder,
+                &[a, b, y],
+                shard,
+                is_real,
+                cols.a_plus_b_plus_y,
+            );
+            a = cols.a_plus_b_plus_y.value;
+
+            // d = (d ^ a).rotate_right(16).
+            XorU64Operation::<AB::F>::eval(builder, d, a, cols.d_xor_a_2, shard, is_real);
+            d = rotate_right_bytes(cols.d_xor_a_2.value, 2);
+
+            // c = c + d.
+            AddU64Operation::<AB::F>::eval(builder, &[c, d], shard, is_real, cols.c_plus_d_2);
+            c = cols.c_plus_d_2.value;
+
+            // b = (b ^ c).rotate_right(63).
+            XorU64Operation::<AB::F>::eval(builder, b, c, cols.b_xor_c_2, shard, is_real);
+            FixedRotateRightU64Operation::<AB::F>::eval(
+                builder,
+                cols.b_xor_c_2.value,
+                63,
+                cols.b_xor_c_2_rotate_right_63,
+                shard,
+                is_real,
+            );
+        }
+    }
+}
@@ -0,0 +1,315 @@
+//! This module contains the implementation of the `blake2b_compress` precompile, which is the
+//! BLAKE2b compression function `F` as specified in EIP-152, with a caller-supplied number of
+//! rounds.
+//!
+//! Pseudo-code.
+//!
+//! v[0..8] = h
+//! v[8..16] = IV
+//! v[12] ^= t[0]
+//! v[13] ^= t[1]
+//! if f { v[14] = !v[14] }
+//!
+//! for round in 0..rounds {
+//!    // * Permute the message with `SIGMA[round % 10]`.
+//!    // * Call g on the four columns of v, then on the four diagonals of v.
+//!    //
+//!    // Each round becomes one row in the trace.
+//! }
+//!
+//! h[i] ^= v[i] ^ v[i + 8]
+//!
+//! // This final step becomes one more row in the trace.
+mod air;
+mod columns;
+mod execute;
+mod g;
+mod trace;
+
+pub use columns::*;
+
+use serde::{Deserialize, Serialize};
+
+use crate::runtime::{MemoryReadRecord, MemoryWriteRecord};
+
+/// The number of u64s in the state `h`.
+pub const STATE_SIZE: usize = 8;
+
+/// The number of u64s in the working vector `v`.
+pub const WORK_SIZE: usize = 16;
+
+/// The number of u64s in the message `m`.
+pub const MSG_SIZE: usize = 16;
+
+/// The number of words in the input block: `rounds`, `m`, `t` and `f`.
+pub const INPUT_WORDS: usize = 1 + 2 * MSG_SIZE + 4 + 1;
+
+/// The index of the first word of `m` in the input block.
+pub(crate) const MSG_OFFSET: usize = 1;
+
+/// The index of the first word of `t` in the input block.
+pub(crate) const T_OFFSET: usize = MSG_OFFSET + 2 * MSG_SIZE;
+
+/// The index of `f` in the input block.
+pub(crate) const F_OFFSET: usize = T_OFFSET + 4;
+
+/// The number of distinct message permutations. Round `i` uses `SIGMA[i % SIGMA_COUNT]`.
+pub(crate) const SIGMA_COUNT: usize = 10;
+
+/// The number of times we call `g` in a round.
+pub(crate) const OPERATION_COUNT: usize = 8;
+
+/// The number of rounds must fit in 24 bits, so that the row counter cannot wrap around the field.
+pub const MAX_ROUNDS: u32 = (1 << 24) - 1;
+
+pub const BLAKE2B_IV: [u64; STATE_SIZE] = [
+    0x6a09e667f3bcc908,
+    0xbb67ae8584caa73b,
+    0x3c6ef372fe94f82b,
+    0xa54ff53a5f1d36f1,
+    0x510e527fade682d1,
+    0x9b05688c2b3e6c1f,
+    0x1f83d9abfb41bd6b,
+    0x5be0cd19137e2179,
+];
+
+/// The message permutations.
+pub(crate) const SIGMA: [[usize; MSG_SIZE]; SIGMA_COUNT] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+];
+
+/// The `i`-th row of `G_INDEX` is the indices of `v` used for the `i`-th call to `g` in a round.
+/// The first four calls mix the columns of `v` and the last four mix its diagonals.
+pub(crate) const G_INDEX: [[usize; 4]; OPERATION_COUNT] = [
+    [0, 4, 8, 12],
+    [1, 5, 9, 13],
+    [2, 6, 10, 14],
+    [3, 7, 11, 15],
+    [0, 5, 10, 15],
+    [1, 6, 11, 12],
+    [2, 7, 8, 13],
+    [3, 4, 9, 14],
+];
+
+pub(crate) fn g_func(input: [u64; 6]) -> [u64; 4] {
+    let mut a = input[0];
+    let mut b = input[1];
+    let mut c = input[2];
+    let mut d = input[3];
+    let x = input[4];
+    let y = input[5];
+    a = a.wrapping_add(b).wrapping_add(x);
+    d = (d ^ a).rotate_right(32);
+    c = c.wrapping_add(d);
+    b = (b ^ c).rotate_right(24);
+    a = a.wrapping_add(b).wrapping_add(y);
+    d = (d ^ a).rotate_right(16);
+    c = c.wrapping_add(d);
+    b = (b ^ c).rotate_right(63);
+    [a, b, c, d]
+}
+
+/// Computes the initial working vector of the compression function.
+pub(crate) fn blake2b_init(h: &[u64; STATE_SIZE], t: [u64; 2], f: bool) -> [u64; WORK_SIZE] {
+    let mut v = [0u64; WORK_SIZE];
+    v[..STATE_SIZE].copy_from_slice(h);
+    v[STATE_SIZE..].copy_from_slice(&BLAKE2B_IV);
+    v[12] ^= t[0];
+    v[13] ^= t[1];
+    if f {
+        v[14] = !v[14];
+    }
+    v
+}
+
+/// Applies one round of the compression function to the working vector.
+pub(crate) fn blake2b_round(v: &mut [u64; WORK_SIZE], m: &[u64; MSG_SIZE], round: usize) {
+    let s = &SIGMA[round % SIGMA_COUNT];
+    for (operation, index) in G_INDEX.iter().enumerate() {
+        let result = g_func([
+            v[index[0]],
+            v[index[1]],
+            v[index[2]],
+            v[index[3]],
+            m[s[2 * operation]],
+            m[s[2 * operation + 1]],
+        ]);
+        for i in 0..4 {
+            v[index[i]] = result[i];
+        }
+    }
+}
+
+/// The BLAKE2b compression function `F` from EIP-152.
+pub fn blake2b_compress(
+    rounds: u32,
+    h: &mut [u64; STATE_SIZE],
+    m: &[u64; MSG_SIZE],
+    t: [u64; 2],
+    f: bool,
+) {
+    let mut v = blake2b_init(h, t, f);
+    for round in 0..rounds as usize {
+        blake2b_round(&mut v, m, round);
+    }
+    for i in 0..STATE_SIZE {
+        h[i] ^= v[i] ^ v[i + STATE_SIZE];
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Blake2bCompressEvent {
+    pub shard: u32,
+    pub clk: u32,
+    pub h_ptr: u32,
+    pub input_ptr: u32,
+    pub rounds: u32,
+    pub h: [u64; STATE_SIZE],
+    pub m: [u64; MSG_SIZE],
+    pub t: [u64; 2],
+    pub f: bool,
+    pub h_read_records: Vec<MemoryReadRecord>,
+    pub input_read_records: Vec<MemoryReadRecord>,
+    pub h_write_records: Vec<MemoryWriteRecord>,
+}
+
+/// Implements the BLAKE2b compression function. The inputs to the syscall are a pointer to the 8
+/// u64 state `h`, which is updated in place, and a pointer to the input block: the number of
+/// rounds as a word, the 16 u64 message, the 2 u64 offset counter `t` and the final block flag `f`
+/// as a word.
+///
+/// In the AIR, each syscall takes up `rounds + 1` rows: one per round and a final one which
+/// computes the new state and writes it to memory.
+#[derive(Default)]
+pub struct Blake2bCompressChip;
+
+impl Blake2bCompressChip {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[cfg(test)]
+pub mod compress_tests {
+    use blake2::{Blake2b512, Digest};
+    use rand::Rng;
+
+    use crate::{
+        runtime::{Instruction, Opcode, Program, Runtime, SyscallCode},
+        utils::{run_test, setup_logger},
+    };
+
+    use super::{blake2b_compress, BLAKE2B_IV, MSG_SIZE, STATE_SIZE};
+
+    fn u64s_to_words(values: &[u64]) -> Vec<u32> {
+        values
+            .iter()
+            .flat_map(|x| [*x as u32, (*x >> 32) as u32])
+            .collect()
+    }
+
+    /// Writes the state `h` to `h_ptr` and the rounds, message, offset and final block flag to
+    /// `input_ptr`, and compresses the message into the state.
+    pub fn blake2b_compress_program(
+        h_ptr: u32,
+        h: &[u32],
+        input_ptr: u32,
+        input: &[u32],
+    ) -> Program {
+        let mut instructions = vec![];
+        for (ptr, words) in [(h_ptr, h), (input_ptr, input)] {
+            for (i, word) in words.iter().enumerate() {
+                instructions.extend(vec![
+                    Instruction::new(Opcode::ADD, 29, 0, *word, false, true),
+                    Instruction::new(Opcode::ADD, 30, 0, ptr + i as u32 * 4, false, true),
+                    Instruction::new(Opcode::SW, 29, 30, 0, false, true),
+                ]);
+            }
+        }
+        instructions.extend(vec![
+            Instruction::new(
+                Opcode::ADD,
+                5,
+                0,
+                SyscallCode::BLAKE2B_COMPRESS as u32,
+                false,
+                true,
+            ),
+            Instruction::new(Opcode::ADD, 10, 0, h_ptr, false, true),
+            Instruction::new(Opcode::ADD, 11, 0, input_ptr, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+        ]);
+        Program::new(instructions, 0, 0)
+    }
+
+    /// Runs and proves the syscall and returns the resulting state.
+    fn run_blake2b_compress(
+        rounds: u32,
+        h: [u64; STATE_SIZE],
+        m: [u64; MSG_SIZE],
+        t: [u64; 2],
+        f: bool,
+    ) -> [u64; STATE_SIZE] {
+        setup_logger();
+        let h_ptr = 100;
+        let input_ptr = 1000;
+        let mut input = vec![rounds];
+        input.extend(u64s_to_words(&m));
+        input.extend(u64s_to_words(&t));
+        input.push(f as u32);
+
+        let program = blake2b_compress_program(h_ptr, &u64s_to_words(&h), input_ptr, &input);
+        let mut runtime = Runtime::new(program.clone());
+        runtime.run();
+        let word = |i: usize| runtime.word(h_ptr + i as u32 * 4) as u64;
+        let result = core::array::from_fn(|i| word(i * 2) | word(i * 2 + 1) << 32);
+        run_test(program).unwrap();
+        result
+    }
+
+    #[test]
+    fn test_blake2b_compress_single_block_hash() {
+        let mut rng = rand::thread_rng();
+        let message: Vec<u8> = (0..100).map(|_| rng.gen()).collect();
+
+        // The state of an unkeyed BLAKE2b-512 hash, whose parameter block is `0x01010040`.
+        let mut h = BLAKE2B_IV;
+        h[0] ^= 0x01010040;
+        let mut block = [0u8; 128];
+        block[..message.len()].copy_from_slice(&message);
+        let m = core::array::from_fn(|i| {
+            u64::from_le_bytes(block[i * 8..(i + 1) * 8].try_into().unwrap())
+        });
+
+        let result = run_blake2b_compress(12, h, m, [message.len() as u64, 0], true);
+        let digest = result
+            .iter()
+            .flat_map(|x| x.to_le_bytes())
+            .collect::<Vec<_>>();
+        assert_eq!(digest, Blake2b512::digest(&message).to_vec());
+    }
+
+    #[test]
+    fn test_blake2b_compress_rounds() {
+        let mut rng = rand::thread_rng();
+        for rounds in [0, 1, 23] {
+            let h: [u64; STATE_SIZE] = rng.gen();
+            let m: [u64; MSG_SIZE] = rng.gen();
+            let t: [u64; 2] = rng.gen();
+            let f = rounds % 2 == 1;
+
+            let mut expected = h;
+            blake2b_compress(rounds, &mut expected, &m, t, f);
+            assert_eq!(run_blake2b_compress(rounds, h, m, t, f), expected);
+        }
+    }
+}
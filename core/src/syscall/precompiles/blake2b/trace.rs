@@ -0,0 +1,152 @@
+use std::borrow::BorrowMut;
+
+use p3_field::PrimeField32;
+use p3_matrix::dense::RowMajorMatrix;
+
+use super::columns::{Blake2bCompressCols, NUM_BLAKE2B_COMPRESS_COLS};
+use super::{
+    blake2b_init, blake2b_round, Blake2bCompressChip, BLAKE2B_IV, G_INDEX, MSG_SIZE, SIGMA,
+    SIGMA_COUNT, STATE_SIZE,
+};
+use crate::air::MachineAir;
+use crate::bytes::{ByteLookupEvent, ByteOpcode};
+use crate::operations::uint64::u64_to_bytes;
+use crate::runtime::{ExecutionRecord, Program};
+
+impl<F: PrimeField32> MachineAir<F> for Blake2bCompressChip {
+    type Record = ExecutionRecord;
+    type Program = Program;
+
+    fn name(&self) -> String {
+        "Blake2bCompress".to_string()
+    }
+
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        output: &mut ExecutionRecord,
+    ) -> RowMajorMatrix<F> {
+        let mut rows = Vec::new();
+
+        let mut new_byte_lookup_events = Vec::new();
+
+        for event in input.blake2b_compress_events.iter() {
+            let shard = event.shard;
+            let mut v = blake2b_init(&event.h, event.t, event.f);
+            for round in 0..=event.rounds as usize {
+                let mut row = [F::zero(); NUM_BLAKE2B_COMPRESS_COLS];
+                let cols: &mut Blake2bCompressCols<F> = row.as_mut_slice().borrow_mut();
+                let is_final = round == event.rounds as usize;
+
+                // Assign basic values to the columns.
+                {
+                    cols.shard = F::from_canonical_u32(event.shard);
+                    cols.clk = F::from_canonical_u32(event.clk);
+                    cols.h_ptr = F::from_canonical_u32(event.h_ptr);
+                    cols.input_ptr = F::from_canonical_u32(event.input_ptr);
+
+                    cols.round = F::from_canonical_usize(round);
+                    cols.rounds = F::from_canonical_u32(event.rounds);
+                    cols.rounds_left.populate(event.rounds - round as u32);
+                    cols.is_real = F::one();
+                    cols.is_first = F::from_bool(round == 0);
+                    cols.is_final = F::from_bool(is_final);
+                    cols.is_round = F::from_bool(!is_final);
+                    cols.is_sigma_index_n[round % SIGMA_COUNT] = F::one();
+
+                    cols.h = event.h.map(u64_to_bytes);
+                    cols.m = event.m.map(u64_to_bytes);
+                    cols.v = v.map(u64_to_bytes);
+                    for k in 0..MSG_SIZE {
+                        cols.permuted_m[k] = u64_to_bytes(event.m[SIGMA[round % SIGMA_COUNT][k]]);
+                    }
+                }
+
+                // Read the inputs in the first row.
+                if round == 0 {
+                    for i in 0..cols.h_reads.len() {
+                        cols.h_reads[i]
+                            .populate(event.h_read_records[i], &mut new_byte_lookup_events);
+                    }
+                    for i in 0..cols.input_reads.len() {
+                        cols.input_reads[i]
+                            .populate(event.input_read_records[i], &mut new_byte_lookup_events);
+                    }
+
+                    // v[12] = IV[4] ^ t[0] and v[13] = IV[5] ^ t[1].
+                    for i in 0..2 {
+                        let t_bytes = event.t[i].to_le_bytes();
+                        let iv_bytes = BLAKE2B_IV[4 + i].to_le_bytes();
+                        for j in 0..t_bytes.len() {
+                            new_byte_lookup_events.push(ByteLookupEvent::new(
+                                shard,
+                                ByteOpcode::XOR,
+                                (t_bytes[j] ^ iv_bytes[j]) as u32,
+                                0,
+                                t_bytes[j] as u32,
+                                iv_bytes[j] as u32,
+                            ));
+                        }
+                    }
+                }
+
+                if is_final {
+                    // Compute the new state and write it.
+                    for i in 0..STATE_SIZE {
+                        let v_xor = cols.v_xor[i].populate(output, shard, v[i], v[i + STATE_SIZE]);
+                        cols.h_xor[i].populate(output, shard, event.h[i], v_xor);
+                    }
+                    for i in 0..cols.h_writes.len() {
+                        cols.h_writes[i]
+                            .populate(event.h_write_records[i], &mut new_byte_lookup_events);
+                    }
+                } else {
+                    // Apply the round, checking it against the reference implementation.
+                    let mut expected = v;
+                    blake2b_round(&mut expected, &event.m, round);
+                    let s = &SIGMA[round % SIGMA_COUNT];
+                    for (operation, index) in G_INDEX.iter().enumerate() {
+                        let result = cols.g[operation].populate(
+                            output,
+                            shard,
+                            [
+                                v[index[0]],
+                                v[index[1]],
+                                v[index[2]],
+                                v[index[3]],
+                                event.m[s[2 * operation]],
+                                event.m[s[2 * operation + 1]],
+                            ],
+                        );
+                        for i in 0..4 {
+                            v[index[i]] = result[i];
+                        }
+                    }
+                    debug_assert_eq!(v, expected);
+                }
+
+                rows.push(row);
+            }
+        }
+
+        output.add_byte_lookup_events(new_byte_lookup_events);
+
+        // Pad with at least one row, since the table must end in a non-real row.
+        let nb_rows = rows.len();
+        let mut padded_nb_rows = (nb_rows + 1).next_power_of_two();
+        if padded_nb_rows < 4 {
+            padded_nb_rows = 4;
+        }
+        rows.resize(padded_nb_rows, [F::zero(); NUM_BLAKE2B_COMPRESS_COLS]);
+
+        // Convert the trace to a row major matrix.
+        RowMajorMatrix::new(
+            rows.into_iter().flatten().collect::<Vec<_>>(),
+            NUM_BLAKE2B_COMPRESS_COLS,
+        )
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        !shard.blake2b_compress_events.is_empty()
+    }
+}
@@ -0,0 +1,312 @@
+use core::borrow::{Borrow, BorrowMut};
+use core::mem::size_of;
+use std::marker::PhantomData;
+
+use p3_air::AirBuilder;
+use p3_air::{Air, BaseAir};
+use p3_field::AbstractField;
+use p3_field::PrimeField32;
+use p3_matrix::dense::RowMajorMatrix;
+use p3_matrix::Matrix;
+use serde::Deserialize;
+use serde::Serialize;
+use sp1_derive::AlignedBorrow;
+
+use crate::air::MachineAir;
+use crate::air::SP1AirBuilder;
+use crate::memory::MemoryCols;
+use crate::memory::MemoryReadCols;
+use crate::memory::MemoryWriteCols;
+use crate::runtime::ExecutionRecord;
+use crate::runtime::MemoryReadRecord;
+use crate::runtime::MemoryWriteRecord;
+use crate::runtime::Program;
+use crate::runtime::Syscall;
+use crate::runtime::SyscallCode;
+use crate::syscall::precompiles::SyscallContext;
+use crate::utils::ec::edwards::EdwardsParameters;
+use crate::utils::ec::NUM_WORDS_FIELD_ELEMENT;
+use crate::utils::pad_rows;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EdCompressEvent {
+    pub shard: u32,
+    pub clk: u32,
+    pub ptr: u32,
+    pub x_memory_records: [MemoryReadRecord; NUM_WORDS_FIELD_ELEMENT],
+    pub y_memory_records: [MemoryWriteRecord; NUM_WORDS_FIELD_ELEMENT],
+}
+
+pub const NUM_ED_COMPRESS_COLS: usize = size_of::<EdCompressCols<u8>>();
+
+/// A set of columns to compute `EdCompress` given a pointer to a 16 word slice holding the affine
+/// point `(x, y)`, with both coordinates reduced.
+///
+/// After `EdCompress`, the most significant bit of `y`, which is always zero, is overwritten with
+/// the least significant bit of `x`, so that the second half of the slice is the 32 byte
+/// compressed point.
+#[derive(Debug, Clone, AlignedBorrow)]
+#[repr(C)]
+pub struct EdCompressCols<T> {
+    pub is_real: T,
+    pub shard: T,
+    pub clk: T,
+    pub ptr: T,
+    pub x_access: [MemoryReadCols<T>; NUM_WORDS_FIELD_ELEMENT],
+    pub y_access: [MemoryWriteCols<T>; NUM_WORDS_FIELD_ELEMENT],
+
+    /// The bits of the least significant byte of `x`, the first of which is the sign.
+    pub x_low_byte_bits: [T; 8],
+
+    /// The bits of the most significant byte of `y`.
+    pub y_high_byte_bits: [T; 8],
+}
+
+#[derive(Default)]
+pub struct EdCompressChip<E> {
+    _marker: PhantomData<E>,
+}
+
+impl<E: EdwardsParameters> EdCompressChip<E> {
+    pub fn new() -> Self {
+        Self {
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<E: EdwardsParameters> Syscall for EdCompressChip<E> {
+    fn execute(&self, rt: &mut SyscallContext, arg1: u32, _: u32) -> Option<u32> {
+        let start_clk = rt.clk;
+        let ptr = arg1;
+        assert!(ptr % 4 == 0, "Pointer must be 4-byte aligned.");
+
+        let y_ptr = ptr + 4 * NUM_WORDS_FIELD_ELEMENT as u32;
+        let (x_memory_records_vec, x) = rt.mr_slice(ptr, NUM_WORDS_FIELD_ELEMENT);
+        let mut y = rt.slice_unsafe(y_ptr, NUM_WORDS_FIELD_ELEMENT);
+        assert!(
+            y[NUM_WORDS_FIELD_ELEMENT - 1] >> 31 == 0,
+            "ed_compress: y must be less than 2^255"
+        );
+        y[NUM_WORDS_FIELD_ELEMENT - 1] |= (x[0] & 1) << 31;
+
+        // The two halves are disjoint, so the write happens at the same clk as the read.
+        let y_memory_records_vec = rt.mw_slice(y_ptr, &y);
+
+        let shard = rt.current_shard();
+        rt.record_mut().ed_compress_events.push(EdCompressEvent {
+            shard,
+            clk: start_clk,
+            ptr,
+            x_memory_records: x_memory_records_vec.try_into().unwrap(),
+            y_memory_records: y_memory_records_vec.try_into().unwrap(),
+        });
+        None
+    }
+
+    fn num_extra_cycles(&self) -> u32 {
+        0
+    }
+}
+
+impl<F: PrimeField32, E: EdwardsParameters> MachineAir<F> for EdCompressChip<E> {
+    type Record = ExecutionRecord;
+    type Program = Program;
+
+    fn name(&self) -> String {
+        "EdCompress".to_string()
+    }
+
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        output: &mut ExecutionRecord,
+    ) -> RowMajorMatrix<F> {
+        let mut rows = Vec::new();
+
+        let mut new_byte_lookup_events = Vec::new();
+
+        for event in input.ed_compress_events.iter() {
+            let mut row = [F::zero(); NUM_ED_COMPRESS_COLS];
+            let cols: &mut EdCompressCols<F> = row.as_mut_slice().borrow_mut();
+
+            // Populate basic columns.
+            cols.is_real = F::one();
+            cols.shard = F::from_canonical_u32(event.shard);
+            cols.clk = F::from_canonical_u32(event.clk);
+            cols.ptr = F::from_canonical_u32(event.ptr);
+
+            // Populate the memory access columns.
+            for i in 0..NUM_WORDS_FIELD_ELEMENT {
+                cols.x_access[i].populate(event.x_memory_records[i], &mut new_byte_lookup_events);
+                cols.y_access[i].populate(event.y_memory_records[i], &mut new_byte_lookup_events);
+            }
+
+            // Decompose the bytes which hold the sign and the bit it is written to.
+            let x_low_byte = event.x_memory_records[0].value & 0xff;
+            let y_high_byte = event.y_memory_records[NUM_WORDS_FIELD_ELEMENT - 1].prev_value >> 24;
+            for k in 0..8 {
+                cols.x_low_byte_bits[k] = F::from_canonical_u32((x_low_byte >> k) & 1);
+                cols.y_high_byte_bits[k] = F::from_canonical_u32((y_high_byte >> k) & 1);
+            }
+
+            rows.push(row);
+        }
+        output.add_byte_lookup_events(new_byte_lookup_events);
+
+        pad_rows(&mut rows, || [F::zero(); NUM_ED_COMPRESS_COLS]);
+
+        // Convert the trace to a row major matrix.
+        RowMajorMatrix::new(
+            rows.into_iter().flatten().collect::<Vec<_>>(),
+            NUM_ED_COMPRESS_COLS,
+        )
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        !shard.ed_compress_events.is_empty()
+    }
+}
+
+impl<F, E: EdwardsParameters> BaseAir<F> for EdCompressChip<E> {
+    fn width(&self) -> usize {
+        NUM_ED_COMPRESS_COLS
+    }
+}
+
+impl<AB, E: EdwardsParameters> Air<AB> for EdCompressChip<E>
+where
+    AB: SP1AirBuilder,
+{
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let row = main.row_slice(0);
+        let row: &EdCompressCols<AB::Var> = (*row).borrow();
+
+        builder.assert_bool(row.is_real);
+
+        // Decompose the least significant byte of x and the most significant byte of y.
+        let mut x_low_byte = AB::Expr::zero();
+        let mut y_high_byte = AB::Expr::zero();
+        for k in 0..8 {
+            builder.assert_bool(row.x_low_byte_bits[k]);
+            builder.assert_bool(row.y_high_byte_bits[k]);
+            x_low_byte = x_low_byte + row.x_low_byte_bits[k] * AB::F::from_canonical_u32(1 << k);
+            y_high_byte = y_high_byte + row.y_high_byte_bits[k] * AB::F::from_canonical_u32(1 << k);
+        }
+        let y_high_word = &row.y_access[NUM_WORDS_FIELD_ELEMENT - 1];
+        builder
+            .when(row.is_real)
+            .assert_eq(row.x_access[0].value()[0], x_low_byte);
+        builder
+            .when(row.is_real)
+            .assert_eq(y_high_word.prev_value()[3], y_high_byte);
+
+        // The top bit of y is free, and is set to the sign of x. The rest of y is unchanged.
+        let sign = row.x_low_byte_bits[0];
+        builder
+            .when(row.is_real)
+            .assert_zero(row.y_high_byte_bits[7]);
+        for i in 0..NUM_WORDS_FIELD_ELEMENT {
+            for j in 0..4 {
+                let prev = row.y_access[i].prev_value()[j];
+                let value = row.y_access[i].value()[j];
+                if i == NUM_WORDS_FIELD_ELEMENT - 1 && j == 3 {
+                    builder
+                        .when(row.is_real)
+                        .assert_eq(value, prev + sign * AB::F::from_canonical_u32(1 << 7));
+                } else {
+                    builder.when(row.is_real).assert_eq(value, prev);
+                }
+            }
+        }
+
+        builder.eval_memory_access_slice(
+            row.shard,
+            row.clk.into(),
+            row.ptr,
+            &row.x_access,
+            row.is_real,
+        );
+        builder.eval_memory_access_slice(
+            row.shard,
+            row.clk.into(),
+            row.ptr + AB::F::from_canonical_usize(4 * NUM_WORDS_FIELD_ELEMENT),
+            &row.y_access,
+            row.is_real,
+        );
+
+        builder.receive_syscall(
+            row.shard,
+            row.clk,
+            AB::F::from_canonical_u32(SyscallCode::ED_COMPRESS.syscall_id()),
+            row.ptr,
+            AB::Expr::zero(),
+            row.is_real,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use curve25519_dalek::edwards::EdwardsPoint;
+    use curve25519_dalek::scalar::Scalar;
+    use rand::Rng;
+
+    use crate::{
+        runtime::{Instruction, Opcode, Program, Runtime, SyscallCode},
+        utils::{
+            bytes_to_words_le,
+            ec::{edwards::ed25519::Ed25519, EllipticCurve},
+            run_test, setup_logger,
+        },
+    };
+
+    const POINT_PTR: u32 = 1000;
+
+    /// Writes the point and compresses it into the second half of its slice.
+    pub fn ed_compress_program(point: &[u32]) -> Program {
+        let mut instructions = vec![];
+        for (i, word) in point.iter().enumerate() {
+            instructions.extend(vec![
+                Instruction::new(Opcode::ADD, 29, 0, *word, false, true),
+                Instruction::new(Opcode::ADD, 30, 0, POINT_PTR + i as u32 * 4, false, true),
+                Instruction::new(Opcode::SW, 29, 30, 0, false, true),
+            ]);
+        }
+        instructions.extend(vec![
+            Instruction::new(
+                Opcode::ADD,
+                5,
+                0,
+                SyscallCode::ED_COMPRESS as u32,
+                false,
+                true,
+            ),
+            Instruction::new(Opcode::ADD, 10, 0, POINT_PTR, false, true),
+            Instruction::new(Opcode::ADD, 11, 0, 0, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+        ]);
+        Program::new(instructions, 0, 0)
+    }
+
+    #[test]
+    fn test_ed_compress() {
+        setup_logger();
+        let mut rng = rand::thread_rng();
+        let scalar = Scalar::from_bytes_mod_order(rng.gen::<[u8; 32]>());
+        let expected = EdwardsPoint::mul_base(&scalar).compress().to_bytes();
+        let point = &Ed25519::ec_generator() * &num::BigUint::from_bytes_le(scalar.as_bytes());
+
+        let program = ed_compress_program(&point.to_words_le());
+        let mut runtime = Runtime::new(program.clone());
+        runtime.run();
+
+        // The compressed point is written to the second half of the slice.
+        let compressed = (0..8)
+            .map(|i| runtime.word(POINT_PTR + 32 + i * 4))
+            .collect::<Vec<_>>();
+        assert_eq!(compressed, bytes_to_words_le::<8>(&expected));
+        run_test(program).unwrap();
+    }
+}
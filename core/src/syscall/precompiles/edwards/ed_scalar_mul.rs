@@ -0,0 +1,464 @@
+use core::borrow::{Borrow, BorrowMut};
+use core::mem::size_of;
+use std::marker::PhantomData;
+
+use p3_air::AirBuilder;
+use p3_air::{Air, BaseAir};
+use p3_field::AbstractField;
+use p3_field::PrimeField32;
+use p3_matrix::dense::RowMajorMatrix;
+use p3_matrix::Matrix;
+use serde::Deserialize;
+use serde::Serialize;
+use sp1_derive::AlignedBorrow;
+
+use super::EdAddAssignChip;
+use crate::air::BaseAirBuilder;
+use crate::air::MachineAir;
+use crate::air::SP1AirBuilder;
+use crate::memory::MemoryCols;
+use crate::memory::MemoryReadCols;
+use crate::runtime::ExecutionRecord;
+use crate::runtime::MemoryReadRecord;
+use crate::runtime::Program;
+use crate::runtime::Syscall;
+use crate::runtime::SyscallCode;
+use crate::syscall::precompiles::SyscallContext;
+use crate::utils::ec::edwards::EdwardsParameters;
+use crate::utils::ec::EllipticCurve;
+use crate::utils::pad_rows;
+
+/// The number of words in a point.
+const POINT_WORDS: usize = 16;
+
+/// Each row uses two cycles, and there are two rows per bit of the scalar word: one which doubles
+/// the accumulator and one which adds the point to it.
+const ED_SCALAR_MUL_NUM_EXTRA_CYCLES: u32 = 2 * 2 * 32;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EdScalarMulEvent {
+    pub shard: u32,
+    pub clk: u32,
+    pub result_ptr: u32,
+    pub scalar_ptr: u32,
+    pub scalar: u32,
+    pub scalar_read_records: Vec<MemoryReadRecord>,
+}
+
+pub const NUM_ED_SCALAR_MUL_COLS: usize = size_of::<EdScalarMulCols<u8>>();
+
+/// A set of columns to compute `EdScalarMul` given a pointer to a 32 word slice, whose first half
+/// is an accumulator `A` and whose second half is a point `P`, and a pointer to a scalar word `w`.
+///
+/// The accumulator is overwritten with `[2^32]A + [w]P`, with a double-and-add over the bits of
+/// `w`, from the most significant one. For each bit, there is a row which doubles the accumulator,
+/// followed by a row which adds `P` to it if the bit is set. Both are sent to the
+/// `EdAddAssignChip` as syscalls, at two cycles per row. A scalar of several words is multiplied
+/// by setting `A` to the identity and calling the syscall once per word, from the most significant
+/// one, which keeps the cycles of a call small enough to fit in any shard.
+#[derive(Debug, Clone, AlignedBorrow)]
+#[repr(C)]
+pub struct EdScalarMulCols<T> {
+    pub is_real: T,
+    pub shard: T,
+    pub clk: T,
+    pub result_ptr: T,
+    pub scalar_ptr: T,
+
+    /// Whether this is the first row of the multiplication, in which the syscall is received.
+    pub is_start: T,
+
+    /// Whether this is the last row of the multiplication.
+    pub is_end: T,
+
+    pub is_double_row: T,
+    pub is_add_row: T,
+
+    /// A one-hot encoding of the current bit of the scalar word.
+    pub bit_selector: [T; 32],
+
+    /// The bits of the scalar word, and the current bit.
+    pub scalar_bits: [T; 32],
+    pub bit: T,
+
+    pub do_add: T,
+
+    pub scalar_read: MemoryReadCols<T>,
+}
+
+#[derive(Default)]
+pub struct EdScalarMulChip<E> {
+    _marker: PhantomData<E>,
+}
+
+impl<E: EllipticCurve + EdwardsParameters> EdScalarMulChip<E> {
+    pub fn new() -> Self {
+        Self {
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<E: EllipticCurve + EdwardsParameters> Syscall for EdScalarMulChip<E> {
+    fn execute(&self, rt: &mut SyscallContext, arg1: u32, arg2: u32) -> Option<u32> {
+        let start_clk = rt.clk;
+        let result_ptr = arg1;
+        let scalar_ptr = arg2;
+        if result_ptr % 4 != 0 || scalar_ptr % 4 != 0 {
+            panic!();
+        }
+
+        // The scalar is read at the same timestamps as the point, so they must not overlap.
+        assert!(
+            result_ptr as u64 + 8 * POINT_WORDS as u64 <= scalar_ptr as u64
+                || scalar_ptr as u64 + 4 <= result_ptr as u64,
+            "ed_scalar_mul: the point and the scalar must not overlap"
+        );
+
+        let point_ptr = result_ptr + 4 * POINT_WORDS as u32;
+        let scalar = rt.word_unsafe(scalar_ptr);
+        let add_chip = EdAddAssignChip::<E>::new();
+
+        let mut clk = start_clk;
+        let mut scalar_read_records = Vec::with_capacity(32);
+        for bit_index in (0..32).rev() {
+            rt.clk = clk;
+            add_chip.execute(rt, result_ptr, result_ptr);
+            clk += 2;
+
+            rt.clk = clk;
+            let (record, word) = rt.mr(scalar_ptr);
+            scalar_read_records.push(record);
+            if (word >> bit_index) & 1 == 1 {
+                add_chip.execute(rt, result_ptr, point_ptr);
+            }
+            clk += 2;
+        }
+
+        let event = EdScalarMulEvent {
+            shard: rt.current_shard(),
+            clk: start_clk,
+            result_ptr,
+            scalar_ptr,
+            scalar,
+            scalar_read_records,
+        };
+        rt.record_mut().ed_scalar_mul_events.push(event);
+        None
+    }
+
+    fn num_extra_cycles(&self) -> u32 {
+        ED_SCALAR_MUL_NUM_EXTRA_CYCLES
+    }
+}
+
+impl<F: PrimeField32, E: EllipticCurve + EdwardsParameters> MachineAir<F> for EdScalarMulChip<E> {
+    type Record = ExecutionRecord;
+    type Program = Program;
+
+    fn name(&self) -> String {
+        "EdScalarMul".to_string()
+    }
+
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        output: &mut ExecutionRecord,
+    ) -> RowMajorMatrix<F> {
+        let mut rows = Vec::new();
+
+        let mut new_byte_lookup_events = Vec::new();
+
+        for event in input.ed_scalar_mul_events.iter() {
+            let mut clk = event.clk;
+            let mut scalar_read_records = event.scalar_read_records.iter();
+
+            for bit_index in (0..32).rev() {
+                let bit = (event.scalar >> bit_index) & 1 == 1;
+
+                // The first row of each bit doubles, and the second one adds the point.
+                for is_add_row in [false, true] {
+                    let mut row = [F::zero(); NUM_ED_SCALAR_MUL_COLS];
+                    let cols: &mut EdScalarMulCols<F> = row.as_mut_slice().borrow_mut();
+
+                    // Populate basic columns.
+                    cols.is_real = F::one();
+                    cols.shard = F::from_canonical_u32(event.shard);
+                    cols.clk = F::from_canonical_u32(clk);
+                    cols.result_ptr = F::from_canonical_u32(event.result_ptr);
+                    cols.scalar_ptr = F::from_canonical_u32(event.scalar_ptr);
+                    cols.bit_selector[bit_index] = F::one();
+                    for k in 0..32 {
+                        cols.scalar_bits[k] = F::from_canonical_u32((event.scalar >> k) & 1);
+                    }
+                    cols.bit = F::from_bool(bit);
+
+                    if is_add_row {
+                        cols.is_add_row = F::one();
+                        cols.is_end = F::from_bool(bit_index == 0);
+                        cols.do_add = F::from_bool(bit);
+
+                        let record = scalar_read_records.next().unwrap();
+                        cols.scalar_read
+                            .populate(*record, &mut new_byte_lookup_events);
+                    } else {
+                        cols.is_double_row = F::one();
+                        cols.is_start = F::from_bool(bit_index == 31);
+                    }
+
+                    clk += 2;
+                    rows.push(row);
+                }
+            }
+        }
+        output.add_byte_lookup_events(new_byte_lookup_events);
+
+        pad_rows(&mut rows, || [F::zero(); NUM_ED_SCALAR_MUL_COLS]);
+
+        // Convert the trace to a row major matrix.
+        RowMajorMatrix::new(
+            rows.into_iter().flatten().collect::<Vec<_>>(),
+            NUM_ED_SCALAR_MUL_COLS,
+        )
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        !shard.ed_scalar_mul_events.is_empty()
+    }
+}
+
+impl<F, E: EllipticCurve + EdwardsParameters> BaseAir<F> for EdScalarMulChip<E> {
+    fn width(&self) -> usize {
+        NUM_ED_SCALAR_MUL_COLS
+    }
+}
+
+impl<AB, E: EllipticCurve + EdwardsParameters> Air<AB> for EdScalarMulChip<E>
+where
+    AB: SP1AirBuilder,
+{
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let (local, next) = (main.row_slice(0), main.row_slice(1));
+        let local: &EdScalarMulCols<AB::Var> = (*local).borrow();
+        let next: &EdScalarMulCols<AB::Var> = (*next).borrow();
+
+        // Constrain the flags of the row.
+        builder.assert_bool(local.is_real);
+        builder.assert_bool(local.is_start);
+        builder.assert_bool(local.is_double_row);
+        builder.assert_bool(local.is_add_row);
+        builder.assert_eq(local.is_double_row + local.is_add_row, local.is_real);
+        builder.when(local.is_start).assert_one(local.is_real);
+
+        // The selector is one-hot on real rows, and picks the current bit of the scalar word.
+        let mut bit_selector_sum = AB::Expr::zero();
+        let mut bit = AB::Expr::zero();
+        for k in 0..32 {
+            builder.assert_bool(local.bit_selector[k]);
+            builder.assert_bool(local.scalar_bits[k]);
+            bit_selector_sum = bit_selector_sum + local.bit_selector[k];
+            bit = bit + local.bit_selector[k] * local.scalar_bits[k];
+        }
+        builder.assert_eq(bit_selector_sum, local.is_real);
+        builder.assert_eq(local.bit, bit);
+
+        // The bits are the bits of the scalar word.
+        let scalar_word = local.scalar_read.value();
+        for j in 0..4 {
+            let mut byte = AB::Expr::zero();
+            for k in 0..8 {
+                byte = byte + local.scalar_bits[8 * j + k] * AB::F::from_canonical_u32(1 << k);
+            }
+            builder
+                .when(local.is_add_row)
+                .assert_eq(scalar_word[j], byte);
+        }
+
+        // Constrain the position of the row within the multiplication, and its operation.
+        builder.assert_eq(local.is_end, local.is_add_row * local.bit_selector[0]);
+        builder.assert_eq(local.do_add, local.is_add_row * local.bit);
+
+        // A multiplication starts with the doubling of the most significant bit.
+        builder.when(local.is_start).assert_one(local.is_double_row);
+        builder
+            .when(local.is_start)
+            .assert_one(local.bit_selector[31]);
+        builder
+            .when_first_row()
+            .assert_eq(local.is_start, local.is_real);
+        builder
+            .when_transition()
+            .when(local.is_end)
+            .assert_eq(next.is_start, next.is_real);
+        builder
+            .when_transition()
+            .when_not(local.is_real)
+            .assert_zero(next.is_real);
+        builder
+            .when_last_row()
+            .when(local.is_real)
+            .assert_one(local.is_end);
+
+        // Until its last row, a multiplication continues on the next row.
+        let mut when_continued = builder.when_transition().when(local.is_real - local.is_end);
+        when_continued.assert_one(next.is_real);
+        when_continued.assert_zero(next.is_start);
+        when_continued.assert_eq(local.shard, next.shard);
+        when_continued.assert_eq(local.clk + AB::F::from_canonical_u32(2), next.clk);
+        when_continued.assert_eq(local.result_ptr, next.result_ptr);
+        when_continued.assert_eq(local.scalar_ptr, next.scalar_ptr);
+
+        // After the doubling, add the point for the same bit.
+        let mut when_double = builder.when_transition().when(local.is_double_row);
+        when_double.assert_one(next.is_add_row);
+        for k in 0..32 {
+            when_double.assert_eq(local.bit_selector[k], next.bit_selector[k]);
+        }
+
+        // After the addition, double for the next bit.
+        let mut when_next_bit = builder
+            .when_transition()
+            .when(local.is_add_row - local.is_end);
+        when_next_bit.assert_one(next.is_double_row);
+        for k in 0..32 {
+            when_next_bit.assert_eq(local.bit_selector[(k + 1) % 32], next.bit_selector[k]);
+        }
+
+        // Read the scalar word in the rows of the additions.
+        builder.eval_memory_access(
+            local.shard,
+            local.clk,
+            local.scalar_ptr,
+            &local.scalar_read,
+            local.is_add_row,
+        );
+
+        // Send the doublings and the additions of the accumulator to the addition chip.
+        builder.send_syscall(
+            local.shard,
+            local.clk,
+            AB::F::from_canonical_u32(SyscallCode::ED_ADD.syscall_id()),
+            local.result_ptr,
+            local.result_ptr,
+            local.is_double_row,
+        );
+        builder.send_syscall(
+            local.shard,
+            local.clk,
+            AB::F::from_canonical_u32(SyscallCode::ED_ADD.syscall_id()),
+            local.result_ptr,
+            local.result_ptr + AB::F::from_canonical_usize(4 * POINT_WORDS),
+            local.do_add,
+        );
+
+        builder.receive_syscall(
+            local.shard,
+            local.clk,
+            AB::F::from_canonical_u32(SyscallCode::ED_SCALAR_MUL.syscall_id()),
+            local.result_ptr,
+            local.scalar_ptr,
+            local.is_start,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigUint;
+    use rand::Rng;
+
+    use crate::{
+        runtime::{Instruction, Opcode, Program, Runtime, SyscallCode},
+        utils::{
+            ec::{edwards::ed25519::Ed25519, AffinePoint, EllipticCurve},
+            run_test, setup_logger,
+        },
+    };
+
+    use super::POINT_WORDS;
+
+    const RESULT_PTR: u32 = 1000;
+    const SCALAR_PTR: u32 = 2000;
+
+    /// Writes the accumulator, the point and the scalar words, and multiplies with one syscall per
+    /// scalar word, from the most significant one.
+    pub fn ed_scalar_mul_program(accumulator: &[u32], point: &[u32], scalar: &[u32]) -> Program {
+        let mut instructions = vec![];
+        for (ptr, words) in [
+            (RESULT_PTR, accumulator),
+            (RESULT_PTR + 4 * POINT_WORDS as u32, point),
+            (SCALAR_PTR, scalar),
+        ] {
+            for (i, word) in words.iter().enumerate() {
+                instructions.extend(vec![
+                    Instruction::new(Opcode::ADD, 29, 0, *word, false, true),
+                    Instruction::new(Opcode::ADD, 30, 0, ptr + i as u32 * 4, false, true),
+                    Instruction::new(Opcode::SW, 29, 30, 0, false, true),
+                ]);
+            }
+        }
+        let code = SyscallCode::ED_SCALAR_MUL as u32;
+        for i in (0..scalar.len() as u32).rev() {
+            instructions.extend(vec![
+                Instruction::new(Opcode::ADD, 5, 0, code, false, true),
+                Instruction::new(Opcode::ADD, 10, 0, RESULT_PTR, false, true),
+                Instruction::new(Opcode::ADD, 11, 0, SCALAR_PTR + i * 4, false, true),
+                Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+            ]);
+        }
+        Program::new(instructions, 0, 0)
+    }
+
+    /// Runs and proves the program, and returns the accumulator.
+    fn run_ed_scalar_mul(accumulator: &[u32], point: &[u32], scalar: &[u32]) -> Vec<u32> {
+        setup_logger();
+        let program = ed_scalar_mul_program(accumulator, point, scalar);
+        let mut runtime = Runtime::new(program.clone());
+        runtime.run();
+        let result = (0..POINT_WORDS as u32)
+            .map(|i| runtime.word(RESULT_PTR + i * 4))
+            .collect();
+        run_test(program).unwrap();
+        result
+    }
+
+    fn identity() -> Vec<u32> {
+        let mut identity = vec![0u32; POINT_WORDS];
+        identity[POINT_WORDS / 2] = 1;
+        identity
+    }
+
+    fn test_ed_scalar_mul(point: &AffinePoint<Ed25519>, scalar: &BigUint) {
+        let mut scalar_words = scalar.to_u32_digits();
+        scalar_words.resize(8, 0);
+        let result = run_ed_scalar_mul(&identity(), &point.to_words_le(), &scalar_words);
+        assert_eq!(result, (point * scalar).to_words_le());
+    }
+
+    #[test]
+    fn test_ed_scalar_mul_random() {
+        let mut rng = rand::thread_rng();
+        let point = &Ed25519::ec_generator() * &BigUint::from_bytes_le(&rng.gen::<[u8; 32]>());
+        let scalar = BigUint::from_bytes_le(&rng.gen::<[u8; 31]>());
+        test_ed_scalar_mul(&point, &scalar);
+    }
+
+    #[test]
+    fn test_ed_scalar_mul_zero() {
+        test_ed_scalar_mul(&Ed25519::ec_generator(), &BigUint::from(0u32));
+    }
+
+    #[test]
+    fn test_ed_scalar_mul_accumulator() {
+        // A single call computes `[2^32]A + [w]P`.
+        let generator = Ed25519::ec_generator();
+        let accumulator = &generator * &BigUint::from(7u32);
+        let point = &generator * &BigUint::from(11u32);
+        let word = 0xdead_beef_u32;
+        let result = run_ed_scalar_mul(&accumulator.to_words_le(), &point.to_words_le(), &[word]);
+
+        let expected = &accumulator * (BigUint::from(1u32) << 32) + &point * BigUint::from(word);
+        assert_eq!(result, expected.to_words_le());
+    }
+}
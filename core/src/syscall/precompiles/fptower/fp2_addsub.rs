@@ -0,0 +1,334 @@
+use core::borrow::{Borrow, BorrowMut};
+use core::mem::size_of;
+use std::marker::PhantomData;
+
+use generic_array::GenericArray;
+use num::BigUint;
+use num::Zero;
+use p3_air::{Air, AirBuilder, BaseAir};
+use p3_field::AbstractField;
+use p3_field::PrimeField32;
+use p3_matrix::dense::RowMajorMatrix;
+use p3_matrix::Matrix;
+use sp1_derive::AlignedBorrow;
+use typenum::Unsigned;
+
+use super::{create_fp2_event, fp2_from_words_le, Fp2Operation, FpOpField};
+use crate::air::MachineAir;
+use crate::air::SP1AirBuilder;
+use crate::memory::MemoryCols;
+use crate::memory::MemoryReadCols;
+use crate::memory::MemoryWriteCols;
+use crate::operations::field::field_op::FieldOpCols;
+use crate::operations::field::params::Limbs;
+use crate::runtime::ExecutionRecord;
+use crate::runtime::Program;
+use crate::runtime::Syscall;
+use crate::runtime::SyscallCode;
+use crate::syscall::precompiles::SyscallContext;
+use crate::utils::ec::field::FieldParameters;
+use crate::utils::ec::field::NumLimbs;
+use crate::utils::ec::field::NumWords;
+use crate::utils::limbs_from_prev_access;
+use crate::utils::pad_rows;
+
+pub const fn num_fp2_addsub_cols<P: FieldParameters + NumWords>() -> usize {
+    size_of::<Fp2AddSubAssignCols<u8, P>>()
+}
+
+/// A set of columns to compute `x + y` or `x - y` for two elements of `Fp2 = Fp[u] / (u^2 + 1)`.
+///
+/// Both operations are coefficient-wise, so a single `FieldOpCols` per coefficient suffices.
+#[derive(Debug, Clone, AlignedBorrow)]
+#[repr(C)]
+pub struct Fp2AddSubAssignCols<T, P: FieldParameters + NumWords> {
+    pub is_real: T,
+    pub shard: T,
+    pub clk: T,
+    pub x_ptr: T,
+    pub y_ptr: T,
+    pub x_access: GenericArray<MemoryWriteCols<T>, P::WordsCurvePoint>,
+    pub y_access: GenericArray<MemoryReadCols<T>, P::WordsCurvePoint>,
+    pub(crate) c0: FieldOpCols<T, P>,
+    pub(crate) c1: FieldOpCols<T, P>,
+}
+
+/// A chip that adds or subtracts two Fp2 elements, depending on `op`.
+pub struct Fp2AddSubAssignChip<P> {
+    op: Fp2Operation,
+    _marker: PhantomData<P>,
+}
+
+impl<P: FpOpField> Fp2AddSubAssignChip<P> {
+    pub fn new(op: Fp2Operation) -> Self {
+        assert!(
+            op == Fp2Operation::Add || op == Fp2Operation::Sub,
+            "Fp2AddSubAssignChip only supports addition and subtraction"
+        );
+        Self {
+            op,
+            _marker: PhantomData,
+        }
+    }
+
+    /// The syscall of `op` over `P`, which also keys its events in the record.
+    fn syscall_code(&self) -> SyscallCode {
+        match self.op {
+            Fp2Operation::Add => P::FP2_ADD,
+            Fp2Operation::Sub => P::FP2_SUB,
+            Fp2Operation::Mul => unreachable!(),
+        }
+    }
+
+    fn populate_field_ops<F: PrimeField32>(
+        &self,
+        cols: &mut Fp2AddSubAssignCols<F, P>,
+        x: (BigUint, BigUint),
+        y: (BigUint, BigUint),
+    ) {
+        cols.c0.populate(&x.0, &y.0, self.op.into());
+        cols.c1.populate(&x.1, &y.1, self.op.into());
+    }
+}
+
+impl<P: FpOpField> Syscall for Fp2AddSubAssignChip<P> {
+    fn execute(&self, rt: &mut SyscallContext, arg1: u32, arg2: u32) -> Option<u32> {
+        let event = create_fp2_event::<P>(rt, arg1, arg2, self.op);
+        rt.record_mut()
+            .fp2_events
+            .entry(self.syscall_code())
+            .or_default()
+            .push(event);
+        None
+    }
+
+    fn num_extra_cycles(&self) -> u32 {
+        1
+    }
+}
+
+impl<F: PrimeField32, P: FpOpField> MachineAir<F> for Fp2AddSubAssignChip<P>
+where
+    [(); num_fp2_addsub_cols::<P>()]:,
+{
+    type Record = ExecutionRecord;
+    type Program = Program;
+
+    fn name(&self) -> String {
+        format!("{}Fp2{:?}Assign", P::NAME, self.op)
+    }
+
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        output: &mut ExecutionRecord,
+    ) -> RowMajorMatrix<F> {
+        let events = input
+            .fp2_events
+            .get(&self.syscall_code())
+            .map_or(&[][..], |events| events.as_slice());
+
+        let mut rows = Vec::new();
+
+        let mut new_byte_lookup_events = Vec::new();
+
+        for event in events.iter() {
+            let mut row = [F::zero(); num_fp2_addsub_cols::<P>()];
+            let cols: &mut Fp2AddSubAssignCols<F, P> = row.as_mut_slice().borrow_mut();
+
+            // Populate basic columns.
+            cols.is_real = F::one();
+            cols.shard = F::from_canonical_u32(event.shard);
+            cols.clk = F::from_canonical_u32(event.clk);
+            cols.x_ptr = F::from_canonical_u32(event.x_ptr);
+            cols.y_ptr = F::from_canonical_u32(event.y_ptr);
+
+            self.populate_field_ops(
+                cols,
+                fp2_from_words_le(&event.x),
+                fp2_from_words_le(&event.y),
+            );
+
+            // Populate the memory access columns.
+            for i in 0..cols.y_access.len() {
+                cols.y_access[i].populate(event.y_memory_records[i], &mut new_byte_lookup_events);
+            }
+            for i in 0..cols.x_access.len() {
+                cols.x_access[i].populate(event.x_memory_records[i], &mut new_byte_lookup_events);
+            }
+
+            rows.push(row);
+        }
+        output.add_byte_lookup_events(new_byte_lookup_events);
+
+        pad_rows(&mut rows, || {
+            let mut row = [F::zero(); num_fp2_addsub_cols::<P>()];
+            let cols: &mut Fp2AddSubAssignCols<F, P> = row.as_mut_slice().borrow_mut();
+            let zero = BigUint::zero();
+            self.populate_field_ops(cols, (zero.clone(), zero.clone()), (zero.clone(), zero));
+            row
+        });
+
+        // Convert the trace to a row major matrix.
+        RowMajorMatrix::new(
+            rows.into_iter().flatten().collect::<Vec<_>>(),
+            num_fp2_addsub_cols::<P>(),
+        )
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        shard
+            .fp2_events
+            .get(&self.syscall_code())
+            .is_some_and(|events| !events.is_empty())
+    }
+}
+
+impl<F, P: FpOpField> BaseAir<F> for Fp2AddSubAssignChip<P> {
+    fn width(&self) -> usize {
+        num_fp2_addsub_cols::<P>()
+    }
+}
+
+impl<AB, P: FpOpField> Air<AB> for Fp2AddSubAssignChip<P>
+where
+    AB: SP1AirBuilder,
+    Limbs<AB::Var, <P as NumLimbs>::Limbs>: Copy,
+{
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let row = main.row_slice(0);
+        let row: &Fp2AddSubAssignCols<AB::Var, P> = (*row).borrow();
+
+        let num_words_field_element = <P as NumLimbs>::Limbs::USIZE / 4;
+
+        let x_c0 = limbs_from_prev_access(&row.x_access[0..num_words_field_element]);
+        let x_c1 = limbs_from_prev_access(&row.x_access[num_words_field_element..]);
+
+        let y_c0 = limbs_from_prev_access(&row.y_access[0..num_words_field_element]);
+        let y_c1 = limbs_from_prev_access(&row.y_access[num_words_field_element..]);
+
+        row.c0
+            .eval::<AB, _, _>(builder, &x_c0, &y_c0, self.op.into());
+        row.c1
+            .eval::<AB, _, _>(builder, &x_c1, &y_c1, self.op.into());
+
+        // Constraint self.x_access.value = [self.c0.result, self.c1.result]. This is to ensure that
+        // x_access is updated with the new value.
+        for i in 0..P::NB_LIMBS {
+            builder
+                .when(row.is_real)
+                .assert_eq(row.c0.result[i], row.x_access[i / 4].value()[i % 4]);
+            builder.when(row.is_real).assert_eq(
+                row.c1.result[i],
+                row.x_access[num_words_field_element + i / 4].value()[i % 4],
+            );
+        }
+
+        builder.eval_memory_access_slice(
+            row.shard,
+            row.clk.into(),
+            row.y_ptr,
+            &row.y_access,
+            row.is_real,
+        );
+        builder.eval_memory_access_slice(
+            row.shard,
+            row.clk + AB::F::from_canonical_u32(1), // We write x at +1 since x, y could be the same.
+            row.x_ptr,
+            &row.x_access,
+            row.is_real,
+        );
+
+        // Fetch the syscall id for the field and operation.
+        let syscall_id_fe = AB::F::from_canonical_u32(self.syscall_code().syscall_id());
+
+        builder.receive_syscall(
+            row.shard,
+            row.clk,
+            syscall_id_fe,
+            row.x_ptr,
+            row.y_ptr,
+            row.is_real,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigUint;
+
+    use crate::{
+        runtime::{Runtime, SyscallCode},
+        syscall::precompiles::fptower::{
+            fp2_op, fp2_to_words_le, tests::fp2_program, Fp2Operation,
+        },
+        utils::{
+            ec::field::FieldParameters,
+            ec::weierstrass::{bls12381::Bls12381BaseField, bn254::Bn254BaseField},
+            run_test, setup_logger,
+        },
+    };
+
+    fn test_fp2_op<P: FieldParameters>(syscall: SyscallCode, op: Fp2Operation) {
+        setup_logger();
+        let (x_ptr, y_ptr) = (1000, 2000);
+        let modulus = P::modulus();
+        let x = (&modulus - 1u32, BigUint::from(12345u32));
+        let y = (BigUint::from(7u32), &modulus - 3u32);
+        let (c0, c1) = fp2_op::<P>(&x, &y, op);
+        let expected = fp2_to_words_le::<P>(&c0, &c1);
+
+        let program = fp2_program(
+            syscall,
+            x_ptr,
+            &fp2_to_words_le::<P>(&x.0, &x.1),
+            y_ptr,
+            &fp2_to_words_le::<P>(&y.0, &y.1),
+        );
+        let mut runtime = Runtime::new(program.clone());
+        runtime.run();
+        for (i, word) in expected.iter().enumerate() {
+            assert_eq!(runtime.word(x_ptr + i as u32 * 4), *word);
+        }
+        run_test(program).unwrap();
+    }
+
+    #[test]
+    fn test_bn254_fp2_add() {
+        test_fp2_op::<Bn254BaseField>(SyscallCode::BN254_FP2_ADD, Fp2Operation::Add);
+    }
+
+    #[test]
+    fn test_bn254_fp2_sub() {
+        test_fp2_op::<Bn254BaseField>(SyscallCode::BN254_FP2_SUB, Fp2Operation::Sub);
+    }
+
+    #[test]
+    fn test_bls12381_fp2_add() {
+        test_fp2_op::<Bls12381BaseField>(SyscallCode::BLS12381_FP2_ADD, Fp2Operation::Add);
+    }
+
+    #[test]
+    fn test_bls12381_fp2_sub() {
+        test_fp2_op::<Bls12381BaseField>(SyscallCode::BLS12381_FP2_SUB, Fp2Operation::Sub);
+    }
+
+    #[test]
+    fn test_fp2_op_unreduced() {
+        // Operands read from memory may be at least the modulus.
+        let modulus = Bn254BaseField::modulus();
+        let zero = (BigUint::from(0u32), BigUint::from(0u32));
+        let max = (BigUint::from(1u32) << 256) - 1u32;
+        let (x, y) = (
+            (max.clone(), max.clone()),
+            (&max % &modulus, &max % &modulus),
+        );
+        for op in [Fp2Operation::Add, Fp2Operation::Sub, Fp2Operation::Mul] {
+            assert_eq!(
+                fp2_op::<Bn254BaseField>(&zero, &x, op),
+                fp2_op::<Bn254BaseField>(&zero, &y, op)
+            );
+        }
+    }
+}
@@ -0,0 +1,186 @@
+mod fp2_addsub;
+mod fp2_mul;
+
+pub use fp2_addsub::*;
+pub use fp2_mul::*;
+
+use num::BigUint;
+use serde::{Deserialize, Serialize};
+
+use crate::operations::field::field_op::FieldOperation;
+use crate::runtime::{MemoryReadRecord, MemoryWriteRecord, SyscallCode, SyscallContext};
+use crate::utils::ec::field::{FieldParameters, NumWords};
+use crate::utils::ec::weierstrass::bls12381::Bls12381BaseField;
+use crate::utils::ec::weierstrass::bn254::Bn254BaseField;
+
+/// An operation accelerated over `Fp2`. Unlike [FieldOperation], there is no division.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fp2Operation {
+    Add,
+    Sub,
+    Mul,
+}
+
+impl From<Fp2Operation> for FieldOperation {
+    /// The operation on each coefficient, which is the whole operation for `Add` and `Sub`.
+    fn from(op: Fp2Operation) -> Self {
+        match op {
+            Fp2Operation::Add => FieldOperation::Add,
+            Fp2Operation::Sub => FieldOperation::Sub,
+            Fp2Operation::Mul => FieldOperation::Mul,
+        }
+    }
+}
+
+/// A base field over which the quadratic extension `Fp2 = Fp[u] / (u^2 + 1)` is accelerated.
+///
+/// The syscalls key the field's events in the record, so a new field only needs an impl here and
+/// its chips registered.
+pub trait FpOpField: FieldParameters + NumWords {
+    /// The prefix of the chip names, e.g. `Bn254` for `Bn254Fp2AddAssign`.
+    const NAME: &'static str;
+
+    const FP2_ADD: SyscallCode;
+    const FP2_SUB: SyscallCode;
+    const FP2_MUL: SyscallCode;
+}
+
+impl FpOpField for Bn254BaseField {
+    const NAME: &'static str = "Bn254";
+    const FP2_ADD: SyscallCode = SyscallCode::BN254_FP2_ADD;
+    const FP2_SUB: SyscallCode = SyscallCode::BN254_FP2_SUB;
+    const FP2_MUL: SyscallCode = SyscallCode::BN254_FP2_MUL;
+}
+
+impl FpOpField for Bls12381BaseField {
+    const NAME: &'static str = "Bls12381";
+    const FP2_ADD: SyscallCode = SyscallCode::BLS12381_FP2_ADD;
+    const FP2_SUB: SyscallCode = SyscallCode::BLS12381_FP2_SUB;
+    const FP2_MUL: SyscallCode = SyscallCode::BLS12381_FP2_MUL;
+}
+
+/// Fp2 operation event.
+///
+/// Both operands are laid out in memory as `c0 || c1`, each coefficient being a little-endian
+/// field element. The result overwrites `x`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Fp2Event {
+    pub shard: u32,
+    pub clk: u32,
+    pub x_ptr: u32,
+    pub x: Vec<u32>,
+    pub y_ptr: u32,
+    pub y: Vec<u32>,
+    pub x_memory_records: Vec<MemoryWriteRecord>,
+    pub y_memory_records: Vec<MemoryReadRecord>,
+}
+
+/// Splits the words of an Fp2 element into its two coefficients.
+pub fn fp2_from_words_le(words: &[u32]) -> (BigUint, BigUint) {
+    let (c0, c1) = words.split_at(words.len() / 2);
+    (BigUint::from_slice(c0), BigUint::from_slice(c1))
+}
+
+/// Serializes the coefficients of an Fp2 element as `c0 || c1` little-endian words.
+pub fn fp2_to_words_le<P: FieldParameters>(c0: &BigUint, c1: &BigUint) -> Vec<u32> {
+    P::to_limbs(c0)
+        .chunks_exact(4)
+        .chain(P::to_limbs(c1).chunks_exact(4))
+        .map(|chunk| u32::from_le_bytes(chunk.try_into().unwrap()))
+        .collect()
+}
+
+/// Computes `x op y` in `Fp[u] / (u^2 + 1)`. The coefficients are reduced first, since they are
+/// read from memory and so may be at least the modulus.
+pub fn fp2_op<P: FieldParameters>(
+    x: &(BigUint, BigUint),
+    y: &(BigUint, BigUint),
+    op: Fp2Operation,
+) -> (BigUint, BigUint) {
+    let modulus = P::modulus();
+    let (x0, x1) = (&x.0 % &modulus, &x.1 % &modulus);
+    let (y0, y1) = (&y.0 % &modulus, &y.1 % &modulus);
+    match op {
+        Fp2Operation::Add => ((x0 + y0) % &modulus, (x1 + y1) % &modulus),
+        Fp2Operation::Sub => (
+            (&modulus + x0 - y0) % &modulus,
+            (&modulus + x1 - y1) % &modulus,
+        ),
+        Fp2Operation::Mul => (
+            (&modulus * &modulus + &x0 * &y0 - &x1 * &y1) % &modulus,
+            (x0 * y1 + x1 * y0) % &modulus,
+        ),
+    }
+}
+
+/// Reads the two Fp2 operands, writes `x op y` back to `x` and returns the resulting event.
+pub fn create_fp2_event<P: FpOpField>(
+    rt: &mut SyscallContext,
+    arg1: u32,
+    arg2: u32,
+    op: Fp2Operation,
+) -> Fp2Event {
+    let start_clk = rt.clk;
+    let x_ptr = arg1;
+    if x_ptr % 4 != 0 {
+        panic!();
+    }
+    let y_ptr = arg2;
+    if y_ptr % 4 != 0 {
+        panic!();
+    }
+
+    let num_words = P::NB_LIMBS / 4 * 2;
+
+    let x = rt.slice_unsafe(x_ptr, num_words);
+    let (y_memory_records, y) = rt.mr_slice(y_ptr, num_words);
+    // When we write to x, we want the clk to be incremented because x and y could be the same.
+    rt.clk += 1;
+
+    let (c0, c1) = fp2_op::<P>(&fp2_from_words_le(&x), &fp2_from_words_le(&y), op);
+    let result = fp2_to_words_le::<P>(&c0, &c1);
+    let x_memory_records = rt.mw_slice(x_ptr, &result);
+
+    Fp2Event {
+        shard: rt.current_shard(),
+        clk: start_clk,
+        x_ptr,
+        x,
+        y_ptr,
+        y,
+        x_memory_records,
+        y_memory_records,
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use crate::runtime::{Instruction, Opcode, Program, SyscallCode};
+
+    /// Writes the Fp2 elements `x` and `y` to `x_ptr` and `y_ptr` and applies `syscall` to them.
+    pub fn fp2_program(
+        syscall: SyscallCode,
+        x_ptr: u32,
+        x: &[u32],
+        y_ptr: u32,
+        y: &[u32],
+    ) -> Program {
+        let mut instructions = vec![];
+        for (ptr, words) in [(x_ptr, x), (y_ptr, y)] {
+            for (i, word) in words.iter().enumerate() {
+                instructions.extend(vec![
+                    Instruction::new(Opcode::ADD, 29, 0, *word, false, true),
+                    Instruction::new(Opcode::ADD, 30, 0, ptr + i as u32 * 4, false, true),
+                    Instruction::new(Opcode::SW, 29, 30, 0, false, true),
+                ]);
+            }
+        }
+        instructions.extend(vec![
+            Instruction::new(Opcode::ADD, 5, 0, syscall as u32, false, true),
+            Instruction::new(Opcode::ADD, 10, 0, x_ptr, false, true),
+            Instruction::new(Opcode::ADD, 11, 0, y_ptr, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+        ]);
+        Program::new(instructions, 0, 0)
+    }
+}
@@ -14,6 +14,50 @@ const PI: [usize; 24] = [
     10, 7, 11, 17, 18, 3, 5, 16, 8, 21, 24, 4, 15, 23, 19, 13, 12, 2, 20, 14, 22, 9, 6, 1,
 ];
 
+/// Applies the Keccak-f[1600] permutation to the given state of `STATE_SIZE` lanes.
+pub(crate) fn keccak_permute(state: &mut [u64]) {
+    for i in 0..NUM_ROUNDS {
+        let mut array: [u64; 5 * 5] = [0; 5 * 5];
+
+        // Theta
+        for x in 0..5 {
+            for y_count in 0..5 {
+                let y = y_count * 5;
+                array[x] ^= state[x + y];
+            }
+        }
+
+        for x in 0..5 {
+            for y_count in 0..5 {
+                let y = y_count * 5;
+                state[y + x] ^= array[(x + 4) % 5] ^ array[(x + 1) % 5].rotate_left(1);
+            }
+        }
+
+        // Rho and pi
+        let mut last = state[1];
+        for x in 0..24 {
+            array[0] = state[PI[x]];
+            state[PI[x]] = last.rotate_left(RHO[x]);
+            last = array[0];
+        }
+
+        // Chi
+        for y_step in 0..5 {
+            let y = y_step * 5;
+
+            array[..5].copy_from_slice(&state[y..(5 + y)]);
+
+            for x in 0..5 {
+                state[y + x] = array[x] ^ ((!array[(x + 1) % 5]) & (array[(x + 2) % 5]));
+            }
+        }
+
+        // Iota
+        state[0] ^= RC[i];
+    }
+}
+
 impl Syscall for KeccakPermuteChip {
     fn num_extra_cycles(&self) -> u32 {
         1
@@ -42,46 +86,7 @@ impl Syscall for KeccakPermuteChip {
 
         let saved_state = state.clone();
 
-        for i in 0..NUM_ROUNDS {
-            let mut array: [u64; 5 * 5] = [0; 5 * 5];
-
-            // Theta
-            for x in 0..5 {
-                for y_count in 0..5 {
-                    let y = y_count * 5;
-                    array[x] ^= state[x + y];
-                }
-            }
-
-            for x in 0..5 {
-                for y_count in 0..5 {
-                    let y = y_count * 5;
-                    state[y + x] ^= array[(x + 4) % 5] ^ array[(x + 1) % 5].rotate_left(1);
-                }
-            }
-
-            // Rho and pi
-            let mut last = state[1];
-            for x in 0..24 {
-                array[0] = state[PI[x]];
-                state[PI[x]] = last.rotate_left(RHO[x]);
-                last = array[0];
-            }
-
-            // Chi
-            for y_step in 0..5 {
-                let y = y_step * 5;
-
-                array[..5].copy_from_slice(&state[y..(5 + y)]);
-
-                for x in 0..5 {
-                    state[y + x] = array[x] ^ ((!array[(x + 1) % 5]) & (array[(x + 2) % 5]));
-                }
-            }
-
-            // Iota
-            state[0] ^= RC[i];
-        }
+        keccak_permute(&mut state);
 
         // Increment the clk by 1 before writing because we read from memory at start_clk.
         rt.clk += 1;
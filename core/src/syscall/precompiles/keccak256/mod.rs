@@ -1,6 +1,7 @@
 mod air;
 pub mod columns;
 mod execute;
+pub mod sponge;
 mod trace;
 
 use p3_keccak_air::KeccakAir;
@@ -8,6 +9,8 @@ use serde::{Deserialize, Serialize};
 
 use crate::runtime::{MemoryReadRecord, MemoryWriteRecord};
 
+pub use sponge::{Keccak256HashChip, Keccak256HashEvent};
+
 pub(crate) const STATE_SIZE: usize = 25;
 
 // The permutation state is 25 u64's.  Our word size is 32 bits, so it is 50 words.
@@ -0,0 +1,317 @@
+use core::borrow::Borrow;
+
+use p3_air::{Air, AirBuilder, BaseAir};
+use p3_field::AbstractField;
+use p3_keccak_air::{KeccakAir, NUM_KECCAK_COLS, NUM_ROUNDS, U64_LIMBS};
+use p3_matrix::Matrix;
+
+use super::columns::{Keccak256HashCols, NUM_KECCAK256_HASH_COLS};
+use super::{Keccak256HashChip, DIGEST_WORDS, RATE_BYTES, RATE_LANES};
+use crate::air::{BaseAirBuilder, SP1AirBuilder, SubAirBuilder};
+use crate::bytes::ByteOpcode;
+use crate::memory::MemoryCols;
+use crate::runtime::SyscallCode;
+use crate::syscall::precompiles::keccak256::STATE_SIZE;
+
+impl<F> BaseAir<F> for Keccak256HashChip {
+    fn width(&self) -> usize {
+        NUM_KECCAK256_HASH_COLS
+    }
+}
+
+impl<AB> Air<AB> for Keccak256HashChip
+where
+    AB: SP1AirBuilder,
+{
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let (local, next) = (main.row_slice(0), main.row_slice(1));
+        let local: &Keccak256HashCols<AB::Var> = (*local).borrow();
+        let next: &Keccak256HashCols<AB::Var> = (*next).borrow();
+
+        self.constrain_control_flow_flags(builder, local, next);
+
+        self.constrain_absorb(builder, local, next);
+
+        self.constrain_memory(builder, local);
+
+        // Receive the syscall in the first row of the first block.
+        builder.receive_syscall(
+            local.shard,
+            local.clk,
+            AB::F::from_canonical_u32(SyscallCode::KECCAK256_HASH.syscall_id()),
+            local.out_ptr,
+            local.args_ptr,
+            local.read_args,
+        );
+
+        let mut sub_builder =
+            SubAirBuilder::<AB, KeccakAir, AB::Var>::new(builder, 0..NUM_KECCAK_COLS);
+
+        // Eval the plonky3 keccak air
+        self.p3_keccak.eval(&mut sub_builder);
+    }
+}
+
+impl Keccak256HashChip {
+    /// Constrains the flags, and that the blocks of an input follow each other.
+    fn constrain_control_flow_flags<AB: SP1AirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &Keccak256HashCols<AB::Var>,
+        next: &Keccak256HashCols<AB::Var>,
+    ) {
+        let first_step = local.keccak.step_flags[0];
+        let final_step = local.keccak.step_flags[NUM_ROUNDS - 1];
+
+        builder.assert_bool(local.is_real);
+        builder.assert_bool(local.is_first_block);
+        builder.assert_bool(local.is_last_block);
+        builder.when(local.is_first_block).assert_one(local.is_real);
+        builder.when(local.is_last_block).assert_one(local.is_real);
+        builder.assert_eq(local.is_absorb, first_step * local.is_real);
+        builder.assert_eq(local.read_args, local.is_absorb * local.is_first_block);
+        builder.assert_eq(local.is_block_end, final_step * local.is_real);
+        builder.assert_eq(local.is_squeeze, local.is_block_end * local.is_last_block);
+
+        // An input starts on the first real row and after the last block of each input.
+        builder
+            .when_first_row()
+            .assert_eq(local.is_first_block, local.is_real);
+        builder
+            .when_transition()
+            .when(local.is_squeeze)
+            .assert_eq(next.is_first_block, next.is_real);
+        builder
+            .when_transition()
+            .when_not(local.is_real)
+            .assert_zero(next.is_real);
+
+        // Constrain that the inputs stay the same throughout the 24 rows of each block.
+        let mut not_last_in_cycle = local.keccak.step_flags[0].into();
+        for i in 1..NUM_ROUNDS - 1 {
+            not_last_in_cycle = not_last_in_cycle + local.keccak.step_flags[i];
+        }
+        let mut within_block = builder
+            .when_transition()
+            .when(not_last_in_cycle.clone() * local.is_real);
+        within_block.assert_eq(local.shard, next.shard);
+        within_block.assert_eq(local.clk, next.clk);
+        within_block.assert_eq(local.out_ptr, next.out_ptr);
+        within_block.assert_eq(local.args_ptr, next.args_ptr);
+        within_block.assert_eq(local.block_ptr, next.block_ptr);
+        within_block.assert_eq(local.bytes_left, next.bytes_left);
+        within_block.assert_eq(local.is_first_block, next.is_first_block);
+        within_block.assert_eq(local.is_last_block, next.is_last_block);
+        builder
+            .when_transition()
+            .when(not_last_in_cycle)
+            .assert_eq(local.is_real, next.is_real);
+
+        // Between two blocks of the same input, move on to the next block.
+        let mut between_blocks = builder
+            .when_transition()
+            .when(local.is_block_end - local.is_squeeze);
+        between_blocks.assert_one(next.is_real);
+        between_blocks.assert_zero(next.is_first_block);
+        between_blocks.assert_eq(local.shard, next.shard);
+        between_blocks.assert_eq(local.clk, next.clk);
+        between_blocks.assert_eq(local.out_ptr, next.out_ptr);
+        between_blocks.assert_eq(local.args_ptr, next.args_ptr);
+        between_blocks.assert_eq(
+            local.block_ptr + AB::F::from_canonical_usize(RATE_BYTES),
+            next.block_ptr,
+        );
+        between_blocks.assert_eq(
+            local.bytes_left,
+            next.bytes_left + AB::F::from_canonical_usize(RATE_BYTES),
+        );
+
+        // The last row must be nonreal because the number of rows per block is not a power of 2.
+        // This constraint ensures that the table does not end abruptly.
+        builder.when_last_row().assert_zero(local.is_real);
+    }
+
+    /// Constrains the state at the start of each block to be the previous state, which is zero for
+    /// the first block, XORed with the block of the padded input.
+    fn constrain_absorb<AB: SP1AirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &Keccak256HashCols<AB::Var>,
+        next: &Keccak256HashCols<AB::Var>,
+    ) {
+        let expr_2_pow_8 = AB::Expr::from_canonical_u32(1 << 8);
+        let u16_from_bytes =
+            |bytes: &[AB::Var]| -> AB::Expr { bytes[0] + bytes[1] * expr_2_pow_8.clone() };
+
+        // The number of bytes left is range checked, so that a block which is not the last one
+        // has at least `RATE_BYTES` bytes left.
+        builder.assert_eq(
+            local.bytes_left,
+            local.bytes_left_16bit_limb
+                + local.bytes_left_8bit_limb * AB::F::from_canonical_u32(1 << 16),
+        );
+        builder.send_byte(
+            AB::F::from_canonical_u32(ByteOpcode::U16Range as u32),
+            local.bytes_left_16bit_limb,
+            AB::F::zero(),
+            AB::F::zero(),
+            local.shard,
+            local.is_absorb,
+        );
+        builder.send_byte(
+            AB::F::from_canonical_u32(ByteOpcode::U8Range as u32),
+            AB::F::zero(),
+            AB::F::zero(),
+            local.bytes_left_8bit_limb,
+            local.shard,
+            local.is_absorb,
+        );
+
+        // In the last block, the padding starts at exactly one index, which is `bytes_left`.
+        let mut padding_start_sum = AB::Expr::zero();
+        let mut padding_start_index = AB::Expr::zero();
+        for j in 0..RATE_BYTES {
+            builder.assert_bool(local.padding_start[j]);
+            padding_start_sum = padding_start_sum + local.padding_start[j];
+            padding_start_index =
+                padding_start_index + local.padding_start[j] * AB::F::from_canonical_usize(j);
+        }
+        builder
+            .when(local.is_absorb)
+            .assert_eq(padding_start_sum, local.is_last_block);
+        builder
+            .when(local.is_absorb)
+            .when(local.is_last_block)
+            .assert_eq(padding_start_index, local.bytes_left);
+
+        // The block is the input up to the padding, then `0x01 0x00 ... 0x00 0x80`.
+        let mut is_padding = AB::Expr::zero();
+        for j in 0..RATE_BYTES {
+            is_padding = is_padding + local.padding_start[j];
+            let input_byte = local.input_reads[j / 4].value()[j % 4];
+            let mut block_byte =
+                input_byte * (AB::Expr::one() - is_padding.clone()) + local.padding_start[j];
+            if j == RATE_BYTES - 1 {
+                block_byte = block_byte + local.is_last_block * AB::F::from_canonical_u32(0x80);
+            }
+            builder
+                .when(local.is_absorb)
+                .assert_eq(local.block_bytes[j], block_byte);
+        }
+
+        // XOR the block into the previous state.
+        for j in 0..RATE_BYTES {
+            builder.send_byte(
+                AB::F::from_canonical_u32(ByteOpcode::XOR as u32),
+                local.xored_bytes[j],
+                local.prev_state_bytes[j],
+                local.block_bytes[j],
+                local.shard,
+                local.is_absorb,
+            );
+        }
+        for i in 0..STATE_SIZE {
+            let (y_idx, x_idx) = (i / 5, i % 5);
+            for limb in 0..U64_LIMBS {
+                let a = local.keccak.a[y_idx][x_idx][limb];
+                if i < RATE_LANES {
+                    let byte = 8 * i + 2 * limb;
+                    builder
+                        .when(local.is_absorb)
+                        .assert_eq(a, u16_from_bytes(&local.xored_bytes[byte..byte + 2]));
+                } else {
+                    // The capacity is zero in the first block, and carried over otherwise.
+                    builder
+                        .when(local.is_absorb)
+                        .when(local.is_first_block)
+                        .assert_zero(a);
+                    builder
+                        .when_transition()
+                        .when(local.is_block_end - local.is_squeeze)
+                        .assert_eq(
+                            next.keccak.a[y_idx][x_idx][limb],
+                            local.keccak.a_prime_prime_prime(x_idx, y_idx, limb),
+                        );
+                }
+            }
+        }
+
+        // The previous state is zero in the first block, and the output of the previous
+        // permutation otherwise.
+        for j in 0..RATE_BYTES {
+            builder
+                .when(local.is_absorb)
+                .when(local.is_first_block)
+                .assert_zero(local.prev_state_bytes[j]);
+        }
+        for i in 0..RATE_LANES {
+            let (y_idx, x_idx) = (i / 5, i % 5);
+            for limb in 0..U64_LIMBS {
+                let byte = 8 * i + 2 * limb;
+                builder
+                    .when_transition()
+                    .when(local.is_block_end - local.is_squeeze)
+                    .assert_eq(
+                        u16_from_bytes(&next.prev_state_bytes[byte..byte + 2]),
+                        local.keccak.a_prime_prime_prime(x_idx, y_idx, limb),
+                    );
+            }
+        }
+    }
+
+    /// Constrains the memory accesses: the arguments are read in the first row, each block is read
+    /// in its first row, and the digest is written in the last row.
+    fn constrain_memory<AB: SP1AirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &Keccak256HashCols<AB::Var>,
+    ) {
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.clk.into(),
+            local.args_ptr,
+            &local.args_reads,
+            local.read_args,
+        );
+        builder
+            .when(local.read_args)
+            .assert_eq(local.block_ptr, local.args_reads[0].value().reduce::<AB>());
+        builder
+            .when(local.read_args)
+            .assert_eq(local.bytes_left, local.args_reads[1].value().reduce::<AB>());
+
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.clk.into(),
+            local.block_ptr,
+            &local.input_reads,
+            local.is_absorb,
+        );
+
+        // We write the digest at +1 since the inputs were read at clk.
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.clk + AB::F::one(),
+            local.out_ptr,
+            &local.out_writes,
+            local.is_squeeze,
+        );
+        let expr_2_pow_8 = AB::Expr::from_canonical_u32(1 << 8);
+        for i in 0..DIGEST_WORDS {
+            let word = local.out_writes[i].value();
+            builder.slice_range_check_u8(&word.0, local.shard, local.is_squeeze);
+
+            // Word `i` holds the limbs `2 * (i % 2)` and `2 * (i % 2) + 1` of lane `i / 2`.
+            for half in 0..2 {
+                let limb = local
+                    .keccak
+                    .a_prime_prime_prime(i / 2, 0, 2 * (i % 2) + half);
+                builder.when(local.is_squeeze).assert_eq(
+                    word[2 * half] + word[2 * half + 1] * expr_2_pow_8.clone(),
+                    limb,
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use core::mem::size_of;
+
+use p3_keccak_air::KeccakCols;
+use sp1_derive::AlignedBorrow;
+
+use crate::memory::{MemoryReadCols, MemoryWriteCols};
+
+use super::{ARGS_WORDS, DIGEST_WORDS, RATE_BYTES, RATE_WORDS};
+
+/// Keccak256HashCols is the column layout for the keccak256 sponge.
+///
+/// The columns defined in the `p3_keccak_air` crate are embedded here as `keccak`, and each
+/// absorbed block uses the 24 rows of a permutation. Other columns are used to track the VM
+/// context and to absorb the input.
+#[derive(AlignedBorrow)]
+#[repr(C)]
+pub(crate) struct Keccak256HashCols<T> {
+    /// Keccak columns from p3_keccak_air. Note it is assumed in trace gen to be the first field.
+    pub keccak: KeccakCols<T>,
+
+    pub shard: T,
+    pub clk: T,
+    pub out_ptr: T,
+    pub args_ptr: T,
+
+    /// The address of the current block of the input.
+    pub block_ptr: T,
+
+    /// The number of input bytes from the start of the current block, and its 16-bit and 8-bit
+    /// limbs.
+    pub bytes_left: T,
+    pub bytes_left_16bit_limb: T,
+    pub bytes_left_8bit_limb: T,
+
+    /// Reads the input pointer and length in the first row of the first block.
+    pub args_reads: [MemoryReadCols<T>; ARGS_WORDS],
+
+    /// Reads the block in the first row of each block.
+    pub input_reads: [MemoryReadCols<T>; RATE_WORDS],
+
+    /// Writes the digest in the last row of the last block.
+    pub out_writes: [MemoryWriteCols<T>; DIGEST_WORDS],
+
+    /// The first `RATE_BYTES` bytes of the state before absorbing the block.
+    pub prev_state_bytes: [T; RATE_BYTES],
+
+    /// The block of the padded input.
+    pub block_bytes: [T; RATE_BYTES],
+
+    /// The first `RATE_BYTES` bytes of the state after absorbing the block, `prev ^ block`.
+    pub xored_bytes: [T; RATE_BYTES],
+
+    /// In the last block, indicates the index `bytes_left` of the first padding byte.
+    pub padding_start: [T; RATE_BYTES],
+
+    /// Whether this row belongs to the first or the last block of the input.
+    pub is_first_block: T,
+    pub is_last_block: T,
+
+    /// If row is real and the first cycle of a block, in which the block is absorbed.
+    pub is_absorb: T,
+
+    /// If row is real and the first cycle of the first block, in which the syscall is received.
+    pub read_args: T,
+
+    /// If row is real and the last cycle of a block.
+    pub is_block_end: T,
+
+    /// If row is real and the last cycle of the last block, in which the digest is written.
+    pub is_squeeze: T,
+
+    pub is_real: T,
+}
+
+pub const NUM_KECCAK256_HASH_COLS: usize = size_of::<Keccak256HashCols<u8>>();
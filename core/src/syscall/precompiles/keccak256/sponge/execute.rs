@@ -0,0 +1,74 @@
+use super::{
+    num_blocks, padded_block, Keccak256HashChip, Keccak256HashEvent, ARGS_WORDS, DIGEST_WORDS,
+    MAX_INPUT_LEN, RATE_BYTES, RATE_LANES, RATE_WORDS,
+};
+use crate::runtime::Syscall;
+use crate::syscall::precompiles::keccak256::execute::keccak_permute;
+use crate::syscall::precompiles::keccak256::STATE_SIZE;
+use crate::syscall::precompiles::SyscallContext;
+
+impl Syscall for Keccak256HashChip {
+    fn num_extra_cycles(&self) -> u32 {
+        1
+    }
+
+    fn execute(&self, rt: &mut SyscallContext, arg1: u32, arg2: u32) -> Option<u32> {
+        let start_clk = rt.clk;
+        let out_ptr = arg1;
+        let args_ptr = arg2;
+        if out_ptr % 4 != 0 || args_ptr % 4 != 0 {
+            panic!();
+        }
+
+        // Read the input pointer and length.
+        let (args_read_records, args) = rt.mr_slice(args_ptr, ARGS_WORDS);
+        let (input_ptr, len) = (args[0], args[1]);
+        if input_ptr % 4 != 0 {
+            panic!();
+        }
+        assert!(len < MAX_INPUT_LEN, "keccak256_hash: the input is too long");
+
+        // Absorb the padded input one block at a time.
+        let mut state = [0u64; STATE_SIZE];
+        let mut pre_states = Vec::new();
+        let mut input_read_records = Vec::new();
+        for i in 0..num_blocks(len) {
+            let block_ptr = input_ptr + (i * RATE_BYTES) as u32;
+            let (records, words) = rt.mr_slice(block_ptr, RATE_WORDS);
+            let block = padded_block(&words, len as usize - i * RATE_BYTES);
+            for j in 0..RATE_LANES {
+                state[j] ^= u64::from_le_bytes(block[j * 8..(j + 1) * 8].try_into().unwrap());
+            }
+            pre_states.push(state);
+            keccak_permute(&mut state);
+            input_read_records.push(records);
+        }
+
+        let digest: [u32; DIGEST_WORDS] =
+            core::array::from_fn(|i| (state[i / 2] >> (32 * (i % 2))) as u32);
+
+        // Increment the clk by 1 before writing because we read from memory at start_clk.
+        rt.clk += 1;
+        let out_write_records = rt.mw_slice(out_ptr, &digest);
+
+        // Push the keccak256 hash event.
+        let shard = rt.current_shard();
+        rt.record_mut()
+            .keccak256_hash_events
+            .push(Keccak256HashEvent {
+                shard,
+                clk: start_clk,
+                out_ptr,
+                args_ptr,
+                input_ptr,
+                len,
+                pre_states,
+                digest,
+                args_read_records,
+                input_read_records,
+                out_write_records,
+            });
+
+        None
+    }
+}
@@ -0,0 +1,206 @@
+//! This module contains the implementation of the `keccak256_hash` precompile, which is the
+//! Keccak-256 sponge, as used by Ethereum, over an input read directly from memory.
+//!
+//! Pseudo-code.
+//!
+//! state = [0; 25]
+//!
+//! for block in pad(input).chunks(136) {
+//!    // * XOR the block into the first 17 lanes of the state.
+//!    // * Apply the Keccak-f[1600] permutation.
+//!    //
+//!    // Each block becomes the 24 rows of a `KeccakPermuteChip` permutation in the trace.
+//! }
+//!
+//! out = state[0..4]
+mod air;
+pub mod columns;
+mod execute;
+mod trace;
+
+use p3_keccak_air::KeccakAir;
+use serde::{Deserialize, Serialize};
+
+use super::STATE_SIZE;
+use crate::runtime::{MemoryReadRecord, MemoryWriteRecord};
+
+/// The number of bytes absorbed per permutation.
+pub const RATE_BYTES: usize = 136;
+
+/// The number of words absorbed per permutation.
+pub const RATE_WORDS: usize = RATE_BYTES / 4;
+
+/// The number of lanes absorbed per permutation.
+pub const RATE_LANES: usize = RATE_BYTES / 8;
+
+/// The number of words in the digest.
+pub const DIGEST_WORDS: usize = 8;
+
+/// The number of words in the arguments: the input pointer and the input length.
+pub const ARGS_WORDS: usize = 2;
+
+/// The input length must be less than this bound, so that it fits in a 16-bit and an 8-bit limb.
+pub const MAX_INPUT_LEN: u32 = 1 << 24;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Keccak256HashEvent {
+    pub shard: u32,
+    pub clk: u32,
+    pub out_ptr: u32,
+    pub args_ptr: u32,
+    pub input_ptr: u32,
+    pub len: u32,
+    /// The state before each permutation, after absorbing the block.
+    pub pre_states: Vec<[u64; STATE_SIZE]>,
+    pub digest: [u32; DIGEST_WORDS],
+    pub args_read_records: Vec<MemoryReadRecord>,
+    /// The memory reads of each block of the input.
+    pub input_read_records: Vec<Vec<MemoryReadRecord>>,
+    pub out_write_records: Vec<MemoryWriteRecord>,
+}
+
+pub struct Keccak256HashChip {
+    p3_keccak: KeccakAir,
+}
+
+impl Keccak256HashChip {
+    pub fn new() -> Self {
+        Self {
+            p3_keccak: KeccakAir {},
+        }
+    }
+}
+
+/// Returns the number of blocks absorbed for an input of `len` bytes. The padding always adds at
+/// least one byte, so there is always a final, partial block.
+pub fn num_blocks(len: u32) -> usize {
+    len as usize / RATE_BYTES + 1
+}
+
+/// Returns the block of the padded input, given the words read from memory for it and the number
+/// of input bytes left from the start of the block. Bytes past the end of the input are ignored.
+pub(crate) fn padded_block(words: &[u32], bytes_left: usize) -> [u8; RATE_BYTES] {
+    let mut block = [0u8; RATE_BYTES];
+    for (i, word) in words.iter().enumerate() {
+        block[i * 4..(i + 1) * 4].copy_from_slice(&word.to_le_bytes());
+    }
+    if bytes_left < RATE_BYTES {
+        // The Keccak padding `0x01 0x00 ... 0x00 0x80`, where the two bytes may coincide.
+        block[bytes_left..].fill(0);
+        block[bytes_left] |= 0x01;
+        block[RATE_BYTES - 1] |= 0x80;
+    }
+    block
+}
+
+#[cfg(test)]
+pub mod hash_tests {
+    use rand::Rng;
+    use tiny_keccak::Hasher;
+
+    use crate::{
+        runtime::{Instruction, Opcode, Program, Runtime, SyscallCode},
+        utils::{run_test, setup_logger},
+    };
+
+    use super::{num_blocks, DIGEST_WORDS, RATE_BYTES};
+
+    fn bytes_to_words(bytes: &[u8]) -> Vec<u32> {
+        bytes
+            .chunks(4)
+            .map(|chunk| {
+                let mut word = [0u8; 4];
+                word[..chunk.len()].copy_from_slice(chunk);
+                u32::from_le_bytes(word)
+            })
+            .collect()
+    }
+
+    /// Writes `input` to `input_ptr` and its pointer and length to `args_ptr`, and hashes it into
+    /// `out_ptr`.
+    pub fn keccak256_hash_program(
+        out_ptr: u32,
+        args_ptr: u32,
+        input_ptr: u32,
+        input: &[u32],
+        len: u32,
+    ) -> Program {
+        let args = [input_ptr, len];
+        let mut instructions = vec![];
+        for (ptr, words) in [(input_ptr, input), (args_ptr, &args[..])] {
+            for (i, word) in words.iter().enumerate() {
+                instructions.extend(vec![
+                    Instruction::new(Opcode::ADD, 29, 0, *word, false, true),
+                    Instruction::new(Opcode::ADD, 30, 0, ptr + i as u32 * 4, false, true),
+                    Instruction::new(Opcode::SW, 29, 30, 0, false, true),
+                ]);
+            }
+        }
+        instructions.extend(vec![
+            Instruction::new(
+                Opcode::ADD,
+                5,
+                0,
+                SyscallCode::KECCAK256_HASH as u32,
+                false,
+                true,
+            ),
+            Instruction::new(Opcode::ADD, 10, 0, out_ptr, false, true),
+            Instruction::new(Opcode::ADD, 11, 0, args_ptr, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+        ]);
+        Program::new(instructions, 0, 0)
+    }
+
+    /// Runs and proves the syscall and returns the resulting digest.
+    fn run_keccak256_hash(input: &[u8]) -> [u8; 32] {
+        setup_logger();
+        let out_ptr = 100;
+        let args_ptr = 200;
+        let input_ptr = 1000;
+
+        // Fill the memory past the end of the input, which must be ignored.
+        let mut padded = input.to_vec();
+        padded.resize(num_blocks(input.len() as u32) * RATE_BYTES, 0xaa);
+
+        let program = keccak256_hash_program(
+            out_ptr,
+            args_ptr,
+            input_ptr,
+            &bytes_to_words(&padded),
+            input.len() as u32,
+        );
+        let mut runtime = Runtime::new(program.clone());
+        runtime.run();
+
+        let mut result = [0u8; 32];
+        for i in 0..DIGEST_WORDS {
+            let word = runtime.word(out_ptr + i as u32 * 4);
+            result[i * 4..(i + 1) * 4].copy_from_slice(&word.to_le_bytes());
+        }
+        run_test(program).unwrap();
+        result
+    }
+
+    fn keccak256(input: &[u8]) -> [u8; 32] {
+        let mut keccak = tiny_keccak::Keccak::v256();
+        keccak.update(input);
+        let mut hash = [0u8; 32];
+        keccak.finalize(&mut hash);
+        hash
+    }
+
+    #[test]
+    fn test_keccak256_hash_empty() {
+        assert_eq!(run_keccak256_hash(&[]), keccak256(&[]));
+    }
+
+    #[test]
+    fn test_keccak256_hash_block_boundaries() {
+        let mut rng = rand::thread_rng();
+        for len in [1, 135, 136, 137, 300] {
+            let input = (0..len).map(|_| rng.gen()).collect::<Vec<u8>>();
+            assert_eq!(run_keccak256_hash(&input), keccak256(&input));
+        }
+    }
+}
@@ -0,0 +1,192 @@
+use std::borrow::BorrowMut;
+
+use p3_field::PrimeField32;
+use p3_keccak_air::{generate_trace_rows, NUM_KECCAK_COLS, NUM_ROUNDS};
+use p3_matrix::dense::RowMajorMatrix;
+use p3_matrix::Matrix;
+use tracing::instrument;
+
+use super::columns::{Keccak256HashCols, NUM_KECCAK256_HASH_COLS};
+use super::{padded_block, Keccak256HashChip, RATE_BYTES};
+use crate::air::MachineAir;
+use crate::bytes::{ByteLookupEvent, ByteOpcode};
+use crate::runtime::{ExecutionRecord, Program};
+use crate::syscall::precompiles::keccak256::execute::keccak_permute;
+use crate::syscall::precompiles::keccak256::STATE_SIZE;
+
+/// Returns the little-endian bytes of the state.
+fn state_to_bytes(state: &[u64; STATE_SIZE]) -> Vec<u8> {
+    state.iter().flat_map(|lane| lane.to_le_bytes()).collect()
+}
+
+impl<F: PrimeField32> MachineAir<F> for Keccak256HashChip {
+    type Record = ExecutionRecord;
+    type Program = Program;
+
+    fn name(&self) -> String {
+        "Keccak256Hash".to_string()
+    }
+
+    #[instrument(name = "generate keccak256 hash trace", level = "debug", skip_all)]
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        output: &mut ExecutionRecord,
+    ) -> RowMajorMatrix<F> {
+        let mut rows = Vec::new();
+
+        let mut new_byte_lookup_events = Vec::new();
+
+        for event in input.keccak256_hash_events.iter() {
+            let shard = event.shard;
+            let num_blocks = event.pre_states.len();
+
+            // First generate the p3_keccak_air traces of all the blocks at once.
+            let p3_keccak_trace = generate_trace_rows::<F>(event.pre_states.clone());
+
+            let mut prev_state = [0u64; STATE_SIZE];
+            for (block_index, pre_state) in event.pre_states.iter().enumerate() {
+                let is_first_block = block_index == 0;
+                let is_last_block = block_index == num_blocks - 1;
+                let bytes_left = event.len as usize - block_index * RATE_BYTES;
+
+                // Create all the rows for the permutation of the block.
+                for i in 0..NUM_ROUNDS {
+                    let p3_keccak_row = p3_keccak_trace.row(i + block_index * NUM_ROUNDS);
+                    let mut row = [F::zero(); NUM_KECCAK256_HASH_COLS];
+                    // Copy p3_keccak_row into start of cols
+                    row[..NUM_KECCAK_COLS]
+                        .copy_from_slice(p3_keccak_row.collect::<Vec<_>>().as_slice());
+                    let cols: &mut Keccak256HashCols<F> = row.as_mut_slice().borrow_mut();
+
+                    cols.shard = F::from_canonical_u32(shard);
+                    cols.clk = F::from_canonical_u32(event.clk);
+                    cols.out_ptr = F::from_canonical_u32(event.out_ptr);
+                    cols.args_ptr = F::from_canonical_u32(event.args_ptr);
+                    cols.block_ptr =
+                        F::from_canonical_u32(event.input_ptr + (block_index * RATE_BYTES) as u32);
+                    cols.bytes_left = F::from_canonical_usize(bytes_left);
+                    cols.bytes_left_16bit_limb = F::from_canonical_usize(bytes_left & 0xffff);
+                    cols.bytes_left_8bit_limb = F::from_canonical_usize(bytes_left >> 16);
+                    cols.is_first_block = F::from_bool(is_first_block);
+                    cols.is_last_block = F::from_bool(is_last_block);
+                    cols.is_real = F::one();
+
+                    // If this is the first row, then absorb the block.
+                    if i == 0 {
+                        cols.is_absorb = F::one();
+                        if is_first_block {
+                            cols.read_args = F::one();
+                            for (j, read_record) in event.args_read_records.iter().enumerate() {
+                                cols.args_reads[j]
+                                    .populate(*read_record, &mut new_byte_lookup_events);
+                            }
+                        }
+
+                        let records = &event.input_read_records[block_index];
+                        for (j, read_record) in records.iter().enumerate() {
+                            cols.input_reads[j].populate(*read_record, &mut new_byte_lookup_events);
+                        }
+                        new_byte_lookup_events.push(ByteLookupEvent::new(
+                            shard,
+                            ByteOpcode::U16Range,
+                            (bytes_left & 0xffff) as u32,
+                            0,
+                            0,
+                            0,
+                        ));
+                        new_byte_lookup_events.push(ByteLookupEvent::new(
+                            shard,
+                            ByteOpcode::U8Range,
+                            0,
+                            0,
+                            0,
+                            (bytes_left >> 16) as u32,
+                        ));
+
+                        let words = records.iter().map(|r| r.value).collect::<Vec<_>>();
+                        let block = padded_block(&words, bytes_left);
+                        let prev_bytes = state_to_bytes(&prev_state);
+                        let pre_bytes = state_to_bytes(pre_state);
+                        for j in 0..RATE_BYTES {
+                            cols.prev_state_bytes[j] = F::from_canonical_u8(prev_bytes[j]);
+                            cols.block_bytes[j] = F::from_canonical_u8(block[j]);
+                            cols.xored_bytes[j] = F::from_canonical_u8(pre_bytes[j]);
+                            debug_assert_eq!(pre_bytes[j], prev_bytes[j] ^ block[j]);
+                            new_byte_lookup_events.push(ByteLookupEvent::new(
+                                shard,
+                                ByteOpcode::XOR,
+                                pre_bytes[j] as u32,
+                                0,
+                                prev_bytes[j] as u32,
+                                block[j] as u32,
+                            ));
+                        }
+                        if is_last_block {
+                            cols.padding_start[bytes_left] = F::one();
+                        }
+                    }
+
+                    // If this is the last row, then the block is done.
+                    if i == NUM_ROUNDS - 1 {
+                        cols.is_block_end = F::one();
+                        if is_last_block {
+                            cols.is_squeeze = F::one();
+                            for (j, write_record) in event.out_write_records.iter().enumerate() {
+                                cols.out_writes[j]
+                                    .populate(*write_record, &mut new_byte_lookup_events);
+                            }
+                            let digest_bytes = event
+                                .digest
+                                .iter()
+                                .flat_map(|word| word.to_le_bytes())
+                                .collect::<Vec<_>>();
+                            output.add_u8_range_checks(shard, &digest_bytes);
+                        }
+                    }
+
+                    rows.push(row);
+                }
+
+                prev_state = *pre_state;
+                keccak_permute(&mut prev_state);
+            }
+        }
+
+        output.add_byte_lookup_events(new_byte_lookup_events);
+
+        let nb_rows = rows.len();
+        let mut padded_nb_rows = nb_rows.next_power_of_two();
+        if padded_nb_rows == 2 || padded_nb_rows == 1 {
+            padded_nb_rows = 4;
+        }
+        if padded_nb_rows > nb_rows {
+            let dummy_keccak_rows = generate_trace_rows::<F>(vec![[0; STATE_SIZE]]);
+            let mut dummy_rows = Vec::new();
+            for i in 0..NUM_ROUNDS {
+                let dummy_row = dummy_keccak_rows.row(i);
+                let mut row = [F::zero(); NUM_KECCAK256_HASH_COLS];
+                row[..NUM_KECCAK_COLS].copy_from_slice(dummy_row.collect::<Vec<_>>().as_slice());
+                dummy_rows.push(row);
+            }
+            rows.append(
+                &mut dummy_rows
+                    .iter()
+                    .cloned()
+                    .cycle()
+                    .take(padded_nb_rows - nb_rows)
+                    .collect::<Vec<_>>(),
+            );
+        }
+
+        // Convert the trace to a row major matrix.
+        RowMajorMatrix::new(
+            rows.into_iter().flatten().collect::<Vec<_>>(),
+            NUM_KECCAK256_HASH_COLS,
+        )
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        !shard.keccak256_hash_events.is_empty()
+    }
+}
@@ -0,0 +1,236 @@
+use core::borrow::{Borrow, BorrowMut};
+use core::mem::size_of;
+
+use p3_air::AirBuilder;
+use p3_air::{Air, BaseAir};
+use p3_field::AbstractField;
+use p3_field::PrimeField32;
+use p3_matrix::dense::RowMajorMatrix;
+use p3_matrix::Matrix;
+use sp1_derive::AlignedBorrow;
+
+use super::{MemCopyEvent, MEMCPY_WORDS};
+use crate::air::MachineAir;
+use crate::air::SP1AirBuilder;
+use crate::air::WordAirBuilder;
+use crate::memory::MemoryCols;
+use crate::memory::MemoryReadCols;
+use crate::memory::MemoryWriteCols;
+use crate::runtime::ExecutionRecord;
+use crate::runtime::Program;
+use crate::runtime::Syscall;
+use crate::runtime::SyscallCode;
+use crate::syscall::precompiles::SyscallContext;
+use crate::utils::pad_rows;
+
+pub const NUM_MEMCPY_COLS: usize = size_of::<MemCopyCols<u8>>();
+
+/// A set of columns to copy `MEMCPY_WORDS` words from `src_ptr` to `dst_ptr`.
+#[derive(Debug, Clone, AlignedBorrow)]
+#[repr(C)]
+pub struct MemCopyCols<T> {
+    pub is_real: T,
+    pub shard: T,
+    pub clk: T,
+    pub src_ptr: T,
+    pub dst_ptr: T,
+    pub src_access: [MemoryReadCols<T>; MEMCPY_WORDS],
+    pub dst_access: [MemoryWriteCols<T>; MEMCPY_WORDS],
+}
+
+#[derive(Default)]
+pub struct MemCopyChip;
+
+impl MemCopyChip {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Syscall for MemCopyChip {
+    fn execute(&self, rt: &mut SyscallContext, arg1: u32, arg2: u32) -> Option<u32> {
+        let start_clk = rt.clk;
+        let dst_ptr = arg1;
+        if dst_ptr % 4 != 0 {
+            panic!();
+        }
+        let src_ptr = arg2;
+        if src_ptr % 4 != 0 {
+            panic!();
+        }
+
+        let (read_records, values) = rt.mr_slice(src_ptr, MEMCPY_WORDS);
+        // The writes happen one cycle after the reads, since the regions may overlap.
+        rt.clk += 1;
+        let write_records = rt.mw_slice(dst_ptr, &values);
+
+        let shard = rt.current_shard();
+        rt.record_mut().memcpy_events.push(MemCopyEvent {
+            shard,
+            clk: start_clk,
+            src_ptr,
+            dst_ptr,
+            read_records,
+            write_records,
+        });
+
+        None
+    }
+
+    fn num_extra_cycles(&self) -> u32 {
+        1
+    }
+}
+
+impl<F: PrimeField32> MachineAir<F> for MemCopyChip {
+    type Record = ExecutionRecord;
+    type Program = Program;
+
+    fn name(&self) -> String {
+        "MemCopy".to_string()
+    }
+
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        output: &mut ExecutionRecord,
+    ) -> RowMajorMatrix<F> {
+        let mut rows = Vec::new();
+
+        let mut new_byte_lookup_events = Vec::new();
+
+        for event in input.memcpy_events.iter() {
+            let mut row = [F::zero(); NUM_MEMCPY_COLS];
+            let cols: &mut MemCopyCols<F> = row.as_mut_slice().borrow_mut();
+
+            // Populate basic columns.
+            cols.is_real = F::one();
+            cols.shard = F::from_canonical_u32(event.shard);
+            cols.clk = F::from_canonical_u32(event.clk);
+            cols.src_ptr = F::from_canonical_u32(event.src_ptr);
+            cols.dst_ptr = F::from_canonical_u32(event.dst_ptr);
+
+            // Populate the memory access columns.
+            for i in 0..MEMCPY_WORDS {
+                cols.src_access[i].populate(event.read_records[i], &mut new_byte_lookup_events);
+                cols.dst_access[i].populate(event.write_records[i], &mut new_byte_lookup_events);
+            }
+
+            rows.push(row);
+        }
+        output.add_byte_lookup_events(new_byte_lookup_events);
+
+        pad_rows(&mut rows, || [F::zero(); NUM_MEMCPY_COLS]);
+
+        // Convert the trace to a row major matrix.
+        RowMajorMatrix::new(
+            rows.into_iter().flatten().collect::<Vec<_>>(),
+            NUM_MEMCPY_COLS,
+        )
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        !shard.memcpy_events.is_empty()
+    }
+}
+
+impl<F> BaseAir<F> for MemCopyChip {
+    fn width(&self) -> usize {
+        NUM_MEMCPY_COLS
+    }
+}
+
+impl<AB> Air<AB> for MemCopyChip
+where
+    AB: SP1AirBuilder,
+{
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let row = main.row_slice(0);
+        let row: &MemCopyCols<AB::Var> = (*row).borrow();
+
+        // Each written word is the word read from the source.
+        for i in 0..MEMCPY_WORDS {
+            builder
+                .when(row.is_real)
+                .assert_word_eq(*row.dst_access[i].value(), *row.src_access[i].value());
+        }
+
+        builder.eval_memory_access_slice(
+            row.shard,
+            row.clk.into(),
+            row.src_ptr,
+            &row.src_access,
+            row.is_real,
+        );
+        builder.eval_memory_access_slice(
+            row.shard,
+            row.clk + AB::F::from_canonical_u32(1), // We write at +1 since the regions may overlap.
+            row.dst_ptr,
+            &row.dst_access,
+            row.is_real,
+        );
+
+        builder.receive_syscall(
+            row.shard,
+            row.clk,
+            AB::F::from_canonical_u32(SyscallCode::MEMCPY.syscall_id()),
+            row.dst_ptr,
+            row.src_ptr,
+            row.is_real,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MEMCPY_WORDS;
+    use crate::{
+        runtime::{Instruction, Opcode, Program, Runtime, SyscallCode},
+        utils::{run_test, setup_logger},
+    };
+
+    /// Writes `words` to `src_ptr` and copies them to `dst_ptr`.
+    pub fn memcpy_program(dst_ptr: u32, src_ptr: u32, words: &[u32]) -> Program {
+        let mut instructions = vec![];
+        for (i, word) in words.iter().enumerate() {
+            instructions.extend(vec![
+                Instruction::new(Opcode::ADD, 29, 0, *word, false, true),
+                Instruction::new(Opcode::ADD, 30, 0, src_ptr + i as u32 * 4, false, true),
+                Instruction::new(Opcode::SW, 29, 30, 0, false, true),
+            ]);
+        }
+        instructions.extend(vec![
+            Instruction::new(Opcode::ADD, 5, 0, SyscallCode::MEMCPY as u32, false, true),
+            Instruction::new(Opcode::ADD, 10, 0, dst_ptr, false, true),
+            Instruction::new(Opcode::ADD, 11, 0, src_ptr, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+        ]);
+        Program::new(instructions, 0, 0)
+    }
+
+    fn test_memcpy(dst_ptr: u32, src_ptr: u32) {
+        setup_logger();
+        let words = (0..MEMCPY_WORDS as u32)
+            .map(|i| 0x0101_0101 * (i + 1))
+            .collect::<Vec<_>>();
+        let program = memcpy_program(dst_ptr, src_ptr, &words);
+        let mut runtime = Runtime::new(program.clone());
+        runtime.run();
+        for (i, word) in words.iter().enumerate() {
+            assert_eq!(runtime.word(dst_ptr + i as u32 * 4), *word);
+        }
+        run_test(program).unwrap();
+    }
+
+    #[test]
+    fn test_memcpy_simple() {
+        test_memcpy(2000, 1000);
+    }
+
+    #[test]
+    fn test_memcpy_overlapping() {
+        test_memcpy(1000 + 16, 1000);
+        test_memcpy(1000, 1000 + 16);
+    }
+}
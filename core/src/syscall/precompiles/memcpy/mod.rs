@@ -0,0 +1,24 @@
+mod air;
+
+pub use air::*;
+
+use serde::{Deserialize, Serialize};
+
+use crate::runtime::{MemoryReadRecord, MemoryWriteRecord};
+
+/// The number of words copied by one `memcpy` syscall.
+pub const MEMCPY_WORDS: usize = 32;
+
+/// Memory copy event.
+///
+/// The syscall copies `MEMCPY_WORDS` words from `src_ptr` to `dst_ptr`. Both pointers are word
+/// aligned, and the regions may overlap since every read happens before the first write.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemCopyEvent {
+    pub shard: u32,
+    pub clk: u32,
+    pub src_ptr: u32,
+    pub dst_ptr: u32,
+    pub read_records: Vec<MemoryReadRecord>,
+    pub write_records: Vec<MemoryWriteRecord>,
+}
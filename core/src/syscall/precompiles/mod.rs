@@ -1,8 +1,16 @@
+pub mod bigint;
+pub mod blake2b;
 pub mod blake3;
 pub mod edwards;
+pub mod fptower;
 pub mod k256;
 pub mod keccak256;
+pub mod memcpy;
+pub mod poseidon2;
+pub mod ripemd160;
 pub mod sha256;
+pub mod sha512;
+pub mod uint256;
 pub mod weierstrass;
 
 use serde::{Deserialize, Serialize};
@@ -17,11 +25,11 @@ pub struct ECAddEvent {
     pub shard: u32,
     pub clk: u32,
     pub p_ptr: u32,
-    pub p: [u32; 16],
+    pub p: Vec<u32>,
     pub q_ptr: u32,
-    pub q: [u32; 16],
-    pub p_memory_records: [MemoryWriteRecord; 16],
-    pub q_memory_records: [MemoryReadRecord; 16],
+    pub q: Vec<u32>,
+    pub p_memory_records: Vec<MemoryWriteRecord>,
+    pub q_memory_records: Vec<MemoryReadRecord>,
 }
 
 pub fn create_ec_add_event<E: EllipticCurve>(
@@ -39,10 +47,10 @@ pub fn create_ec_add_event<E: EllipticCurve>(
         panic!();
     }
 
-    let p: [u32; 16] = rt.slice_unsafe(p_ptr, 16).try_into().unwrap();
-    let (q_memory_records_vec, q_vec) = rt.mr_slice(q_ptr, 16);
-    let q_memory_records = q_memory_records_vec.try_into().unwrap();
-    let q: [u32; 16] = q_vec.try_into().unwrap();
+    let num_words = E::NB_LIMBS / 4 * 2;
+
+    let p = rt.slice_unsafe(p_ptr, num_words);
+    let (q_memory_records, q) = rt.mr_slice(q_ptr, num_words);
     // When we write to p, we want the clk to be incremented because p and q could be the same.
     rt.clk += 1;
 
@@ -51,7 +59,7 @@ pub fn create_ec_add_event<E: EllipticCurve>(
     let result_affine = p_affine + q_affine;
     let result_words = result_affine.to_words_le();
 
-    let p_memory_records = rt.mw_slice(p_ptr, &result_words).try_into().unwrap();
+    let p_memory_records = rt.mw_slice(p_ptr, &result_words);
 
     ECAddEvent {
         shard: rt.current_shard(),
@@ -71,8 +79,8 @@ pub struct ECDoubleEvent {
     pub shard: u32,
     pub clk: u32,
     pub p_ptr: u32,
-    pub p: [u32; 16],
-    pub p_memory_records: [MemoryWriteRecord; 16],
+    pub p: Vec<u32>,
+    pub p_memory_records: Vec<MemoryWriteRecord>,
 }
 
 pub fn create_ec_double_event<E: EllipticCurve>(
@@ -86,11 +94,13 @@ pub fn create_ec_double_event<E: EllipticCurve>(
         panic!();
     }
 
-    let p: [u32; 16] = rt.slice_unsafe(p_ptr, 16).try_into().unwrap();
+    let num_words = E::NB_LIMBS / 4 * 2;
+
+    let p = rt.slice_unsafe(p_ptr, num_words);
     let p_affine = AffinePoint::<E>::from_words_le(&p);
     let result_affine = E::ec_double(&p_affine);
     let result_words = result_affine.to_words_le();
-    let p_memory_records = rt.mw_slice(p_ptr, &result_words).try_into().unwrap();
+    let p_memory_records = rt.mw_slice(p_ptr, &result_words);
 
     ECDoubleEvent {
         shard: rt.current_shard(),
@@ -100,3 +110,42 @@ pub fn create_ec_double_event<E: EllipticCurve>(
         p_memory_records,
     }
 }
+
+/// Elliptic curve multi-scalar multiplication event.
+///
+/// The additions and doublings of the accumulator are recorded as their own `ECAddEvent`s and
+/// `ECDoubleEvent`s, so this event only holds the memory accesses of the multiplication itself.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ECMsmEvent {
+    pub shard: u32,
+    pub clk: u32,
+    pub result_ptr: u32,
+    pub args_ptr: u32,
+    pub num_points: u32,
+    pub points_ptr: u32,
+    pub scalars_ptr: u32,
+    pub scalars: Vec<u32>,
+    pub args_read_records: Vec<MemoryReadRecord>,
+    /// The reads of the scalar words, one for each point and bit.
+    pub scalar_read_records: Vec<MemoryReadRecord>,
+    /// The reads of the point in each row which adds it, in order.
+    pub point_read_records: Vec<Vec<MemoryReadRecord>>,
+    /// The reads of the accumulator in each row which compares it with the point, in order.
+    pub acc_read_records: Vec<Vec<MemoryReadRecord>>,
+    /// The copies of a point into the accumulator when it is the identity, in order.
+    pub copy_write_records: Vec<Vec<MemoryWriteRecord>>,
+    pub is_identity_write_record: MemoryWriteRecord,
+}
+
+/// Elliptic curve point decompress event.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ECDecompressEvent {
+    pub shard: u32,
+    pub clk: u32,
+    pub ptr: u32,
+    pub is_odd: bool,
+    pub x_bytes: Vec<u8>,
+    pub decompressed_y_bytes: Vec<u8>,
+    pub x_memory_records: Vec<MemoryReadRecord>,
+    pub y_memory_records: Vec<MemoryWriteRecord>,
+}
@@ -0,0 +1,257 @@
+use core::borrow::Borrow;
+
+use p3_air::{Air, AirBuilder, BaseAir};
+use p3_field::AbstractField;
+use p3_matrix::Matrix;
+use sp1_primitives::RC_16_30_U32;
+
+use super::columns::{Poseidon2PermuteCols, NUM_POSEIDON2_PERMUTE_COLS};
+use super::{
+    matmul_external, matmul_internal, Poseidon2PermuteChip, MATRIX_DIAG_16_BABYBEAR_U32,
+    NUM_FULL_ROUNDS, NUM_PARTIAL_ROUNDS, NUM_ROUNDS, NUM_ROWS_PER_PERMUTATION, WIDTH,
+};
+use crate::air::{SP1AirBuilder, WordAirBuilder};
+use crate::memory::MemoryCols;
+use crate::operations::BabyBearRangeCheckOperation;
+use crate::runtime::SyscallCode;
+
+impl<F> BaseAir<F> for Poseidon2PermuteChip {
+    fn width(&self) -> usize {
+        NUM_POSEIDON2_PERMUTE_COLS
+    }
+}
+
+impl<AB> Air<AB> for Poseidon2PermuteChip
+where
+    AB: SP1AirBuilder,
+{
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let (local, next) = (main.row_slice(0), main.row_slice(1));
+        let local: &Poseidon2PermuteCols<AB::Var> = (*local).borrow();
+        let next: &Poseidon2PermuteCols<AB::Var> = (*next).borrow();
+
+        self.constrain_control_flow_flags(builder, local, next);
+
+        self.constrain_memory(builder, local);
+
+        self.constrain_round(builder, local);
+
+        // Receive the syscall in the first row of each permutation.
+        builder.receive_syscall(
+            local.shard,
+            local.clk,
+            AB::F::from_canonical_u32(SyscallCode::POSEIDON2_PERMUTE.syscall_id()),
+            local.state_addr,
+            AB::Expr::zero(),
+            local.rounds[0],
+        );
+    }
+}
+
+impl Poseidon2PermuteChip {
+    /// Constrains the round flags, and that the rows of a permutation follow each other.
+    fn constrain_control_flow_flags<AB: SP1AirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &Poseidon2PermuteCols<AB::Var>,
+        next: &Poseidon2PermuteCols<AB::Var>,
+    ) {
+        let last = NUM_ROWS_PER_PERMUTATION - 1;
+
+        // Exactly one round flag is set in real rows, and none in padding rows.
+        builder.assert_bool(local.is_real);
+        let mut rounds_sum = AB::Expr::zero();
+        for i in 0..NUM_ROWS_PER_PERMUTATION {
+            builder.assert_bool(local.rounds[i]);
+            rounds_sum += local.rounds[i].into();
+        }
+        builder.assert_eq(rounds_sum, local.is_real);
+
+        // Constrain the initial, external and internal flags.
+        let rounds_f_beginning = NUM_FULL_ROUNDS / 2;
+        let p_end = rounds_f_beginning + NUM_PARTIAL_ROUNDS;
+        builder.assert_eq(local.is_initial, local.rounds[0]);
+        let is_external = (0..rounds_f_beginning)
+            .chain(p_end..NUM_ROUNDS)
+            .map(|i| local.rounds[i + 1].into())
+            .sum::<AB::Expr>();
+        builder.assert_eq(local.is_external, is_external);
+        let is_internal = (rounds_f_beginning..p_end)
+            .map(|i| local.rounds[i + 1].into())
+            .sum::<AB::Expr>();
+        builder.assert_eq(local.is_internal, is_internal);
+
+        // A permutation starts on the first real row and after the last row of each permutation.
+        builder
+            .when_first_row()
+            .assert_eq(local.rounds[0], local.is_real);
+        builder
+            .when_transition()
+            .when(local.rounds[last])
+            .assert_eq(next.rounds[0], next.is_real);
+        builder
+            .when_transition()
+            .when_not(local.is_real)
+            .assert_zero(next.is_real);
+
+        // Within a permutation, each row moves on to the next round and the inputs stay the same.
+        let is_continuing: AB::Expr = local.is_real - local.rounds[last];
+        for i in 0..last {
+            builder
+                .when_transition()
+                .when(is_continuing.clone())
+                .assert_eq(local.rounds[i], next.rounds[i + 1]);
+        }
+        builder
+            .when_transition()
+            .when(is_continuing.clone())
+            .assert_eq(local.shard, next.shard);
+        builder
+            .when_transition()
+            .when(is_continuing.clone())
+            .assert_eq(local.clk, next.clk);
+        builder
+            .when_transition()
+            .when(is_continuing.clone())
+            .assert_eq(local.state_addr, next.state_addr);
+        for i in 0..WIDTH {
+            builder
+                .when_transition()
+                .when(is_continuing.clone())
+                .assert_eq(local.output[i], next.input[i]);
+        }
+
+        // The last row must be nonreal because the number of rows per permutation is not a power
+        // of 2. This constraint ensures that the table does not end abruptly.
+        builder.when_last_row().assert_zero(local.is_real);
+    }
+
+    /// Constrains the state to be read in the first row and written in the last row.
+    fn constrain_memory<AB: SP1AirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &Poseidon2PermuteCols<AB::Var>,
+    ) {
+        let is_first = local.rounds[0];
+        let is_last = local.rounds[NUM_ROWS_PER_PERMUTATION - 1];
+        let do_memory_check: AB::Expr = is_first + is_last;
+
+        for i in 0..WIDTH {
+            builder.eval_memory_access(
+                local.shard,
+                local.clk + is_last, // The clk increments by 1 for the write.
+                local.state_addr + AB::F::from_canonical_u32(i as u32 * 4),
+                &local.state_mem[i],
+                do_memory_check.clone(),
+            );
+
+            // The memory words are canonical BabyBear elements, so that their reductions are
+            // unique.
+            let value = *local.state_mem[i].value();
+            BabyBearRangeCheckOperation::<AB::F>::eval(
+                builder,
+                value,
+                local.state_range_checks[i],
+                local.shard,
+                do_memory_check.clone(),
+            );
+
+            // The first row reads the input and the last row writes the output.
+            builder
+                .when(is_first)
+                .assert_word_eq(value, *local.state_mem[i].prev_value());
+            builder
+                .when(is_first)
+                .assert_eq(value.reduce::<AB>(), local.input[i]);
+            builder
+                .when(is_last)
+                .assert_eq(value.reduce::<AB>(), local.output[i]);
+        }
+    }
+
+    /// Constrains a row of the permutation, following the recursion `Poseidon2Chip`.
+    fn constrain_round<AB: SP1AirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &Poseidon2PermuteCols<AB::Var>,
+    ) {
+        // Convert the u32 round constants to field elements.
+        let constants: [[AB::F; WIDTH]; NUM_ROUNDS] = RC_16_30_U32
+            .iter()
+            .map(|round| round.map(AB::F::from_wrapped_u32))
+            .collect::<Vec<_>>()
+            .try_into()
+            .unwrap();
+
+        // Apply the round constants.
+        //
+        // Initial Layer: Don't apply the round constants.
+        // External Layers: Apply the round constants.
+        // Internal Layers: Only apply the round constants to the first element.
+        for i in 0..WIDTH {
+            let mut result: AB::Expr = local.input[i].into();
+            for r in 0..NUM_ROUNDS {
+                if i == 0 {
+                    result += local.rounds[r + 1]
+                        * constants[r][i]
+                        * (local.is_external + local.is_internal);
+                } else {
+                    result += local.rounds[r + 1] * constants[r][i] * local.is_external;
+                }
+            }
+            builder.assert_eq(result, local.add_rc[i]);
+        }
+
+        // Apply the sbox.
+        //
+        // To differentiate between external and internal layers, we use a masking operation
+        // to only apply the state change to the first element for internal layers.
+        for i in 0..WIDTH {
+            let sbox_deg_3 = local.add_rc[i] * local.add_rc[i] * local.add_rc[i];
+            builder.assert_eq(sbox_deg_3, local.sbox_deg_3[i]);
+            let sbox_deg_7 = local.sbox_deg_3[i] * local.sbox_deg_3[i] * local.add_rc[i];
+            builder.assert_eq(sbox_deg_7, local.sbox_deg_7[i]);
+        }
+        let sbox_result: [AB::Expr; WIDTH] = core::array::from_fn(|i| {
+            if i == 0 {
+                // Initial Layer: Pass through the result of the round constant layer.
+                // External and Internal Layers: Pass through the result of the sbox layer.
+                local.is_initial * local.add_rc[i]
+                    + (AB::Expr::one() - local.is_initial) * local.sbox_deg_7[i]
+            } else {
+                // Initial and Internal Layers: Pass through the result of the round constant
+                // layer.
+                // External Layer: Pass through the result of the sbox layer.
+                (local.is_initial + local.is_internal) * local.add_rc[i]
+                    + (AB::Expr::one() - (local.is_initial + local.is_internal))
+                        * local.sbox_deg_7[i]
+            }
+        });
+
+        // EXTERNAL LAYER + INITIAL LAYER
+        {
+            let mut state: [AB::Expr; WIDTH] = sbox_result.clone();
+            matmul_external(&mut state);
+            for i in 0..WIDTH {
+                builder
+                    .when(local.is_external + local.is_initial)
+                    .assert_eq(state[i].clone(), local.output[i]);
+            }
+        }
+
+        // INTERNAL LAYER
+        {
+            let mut state: [AB::Expr; WIDTH] = sbox_result;
+            matmul_internal(
+                &mut state,
+                MATRIX_DIAG_16_BABYBEAR_U32.map(AB::F::from_wrapped_u32),
+            );
+            for i in 0..WIDTH {
+                builder
+                    .when(local.is_internal)
+                    .assert_eq(state[i].clone(), local.output[i]);
+            }
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use core::mem::size_of;
+
+use sp1_derive::AlignedBorrow;
+
+use crate::memory::MemoryReadWriteCols;
+use crate::operations::BabyBearRangeCheckOperation;
+
+use super::{NUM_ROWS_PER_PERMUTATION, WIDTH};
+
+pub const NUM_POSEIDON2_PERMUTE_COLS: usize = size_of::<Poseidon2PermuteCols<u8>>();
+
+/// Poseidon2PermuteCols is the column layout for the Poseidon2 permutation.
+///
+/// The permutation columns follow the layout of the recursion `Poseidon2Chip`, with one row for
+/// the initial linear layer and one row per round. Other columns are used to track the VM context.
+#[derive(AlignedBorrow, Default, Debug, Clone, Copy)]
+#[repr(C)]
+pub struct Poseidon2PermuteCols<T> {
+    pub shard: T,
+    pub clk: T,
+    pub state_addr: T,
+
+    /// Memory columns for the state, read in the first row and written in the last row.
+    pub state_mem: [MemoryReadWriteCols<T>; WIDTH],
+
+    /// Checks that the memory words are canonical BabyBear elements.
+    pub state_range_checks: [BabyBearRangeCheckOperation<T>; WIDTH],
+
+    /// The state at the start of the row.
+    pub input: [T; WIDTH],
+
+    /// Indicates which row of the permutation this is.
+    pub rounds: [T; NUM_ROWS_PER_PERMUTATION],
+
+    pub add_rc: [T; WIDTH],
+    pub sbox_deg_3: [T; WIDTH],
+    pub sbox_deg_7: [T; WIDTH],
+
+    /// The state at the end of the row.
+    pub output: [T; WIDTH],
+
+    pub is_initial: T,
+    pub is_internal: T,
+    pub is_external: T,
+
+    pub is_real: T,
+}
@@ -0,0 +1,55 @@
+use p3_baby_bear::BabyBear;
+use p3_field::PrimeField32;
+
+use super::{poseidon2_permute, Poseidon2PermuteChip, Poseidon2PermuteEvent, WIDTH};
+use crate::runtime::Syscall;
+use crate::syscall::precompiles::SyscallContext;
+
+impl Syscall for Poseidon2PermuteChip {
+    fn num_extra_cycles(&self) -> u32 {
+        1
+    }
+
+    fn execute(&self, rt: &mut SyscallContext, arg1: u32, arg2: u32) -> Option<u32> {
+        let start_clk = rt.clk;
+        let state_ptr = arg1;
+        if state_ptr % 4 != 0 {
+            panic!();
+        }
+        if arg2 != 0 {
+            panic!("Expected arg2 to be 0, got {}", arg2);
+        }
+
+        let (state_read_records, pre_state) = rt.mr_slice(state_ptr, WIDTH);
+        let pre_state: [u32; WIDTH] = pre_state.try_into().unwrap();
+        for x in pre_state.iter() {
+            assert!(
+                *x < BabyBear::ORDER_U32,
+                "poseidon2_permute: {} is not a canonical BabyBear element",
+                x
+            );
+        }
+
+        let post_state = poseidon2_permute(pre_state);
+
+        // Increment the clk by 1 before writing because we read from memory at start_clk.
+        rt.clk += 1;
+        let state_write_records = rt.mw_slice(state_ptr, &post_state);
+
+        // Push the Poseidon2 permute event.
+        let shard = rt.current_shard();
+        rt.record_mut()
+            .poseidon2_permute_events
+            .push(Poseidon2PermuteEvent {
+                shard,
+                clk: start_clk,
+                pre_state,
+                post_state,
+                state_read_records,
+                state_write_records,
+                state_addr: state_ptr,
+            });
+
+        None
+    }
+}
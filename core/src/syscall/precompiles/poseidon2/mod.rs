@@ -0,0 +1,280 @@
+//! This module contains the implementation of the `poseidon2_permute` precompile, which is the
+//! Poseidon2 permutation over BabyBear used by the recursion layer and by
+//! `sp1_primitives::poseidon2_hash`.
+//!
+//! Pseudo-code.
+//!
+//! state = external_linear_layer(state)
+//!
+//! for r in 0..30 {
+//!    // * External rounds (the first 4 and the last 4) add the round constants to every element,
+//!    //   apply the sbox `x^7` to every element and then the external linear layer.
+//!    // * Internal rounds add the round constant to the first element, apply the sbox to the first
+//!    //   element and then the internal linear layer.
+//! }
+//!
+//! // The initial linear layer and each round become one row in the trace.
+#![allow(clippy::needless_range_loop)]
+
+mod air;
+pub mod columns;
+mod execute;
+mod trace;
+
+use lazy_static::lazy_static;
+use p3_baby_bear::BabyBear;
+use p3_field::{AbstractField, Field, PrimeField32};
+use p3_symmetric::Permutation;
+use serde::{Deserialize, Serialize};
+use sp1_primitives::{poseidon2_init, RC_16_30_U32};
+
+use crate::runtime::{MemoryReadRecord, MemoryWriteRecord};
+use crate::utils::InnerPerm;
+
+/// The width of the permutation.
+pub const WIDTH: usize = 16;
+
+pub const NUM_FULL_ROUNDS: usize = 8;
+pub const NUM_PARTIAL_ROUNDS: usize = 22;
+pub const NUM_ROUNDS: usize = NUM_FULL_ROUNDS + NUM_PARTIAL_ROUNDS;
+
+/// The number of rows per permutation: the initial linear layer followed by every round.
+pub(crate) const NUM_ROWS_PER_PERMUTATION: usize = NUM_ROUNDS + 1;
+
+lazy_static! {
+    static ref POSEIDON2_PERM: InnerPerm = poseidon2_init();
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Poseidon2PermuteEvent {
+    pub shard: u32,
+    pub clk: u32,
+    pub pre_state: [u32; WIDTH],
+    pub post_state: [u32; WIDTH],
+    pub state_read_records: Vec<MemoryReadRecord>,
+    pub state_write_records: Vec<MemoryWriteRecord>,
+    pub state_addr: u32,
+}
+
+/// A chip that implements the Poseidon2 permutation over a state of 16 BabyBear elements.
+#[derive(Default)]
+pub struct Poseidon2PermuteChip;
+
+impl Poseidon2PermuteChip {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// Applies the Poseidon2 permutation to a state of canonical BabyBear elements.
+pub fn poseidon2_permute(state: [u32; WIDTH]) -> [u32; WIDTH] {
+    POSEIDON2_PERM
+        .permute(state.map(BabyBear::from_canonical_u32))
+        .map(|x| x.as_canonical_u32())
+}
+
+/// Returns whether the round `r` of the permutation, counted from zero, is an external round.
+pub(crate) fn is_external_round(r: usize) -> bool {
+    r < NUM_FULL_ROUNDS / 2 || r >= NUM_FULL_ROUNDS / 2 + NUM_PARTIAL_ROUNDS
+}
+
+/// The sbox `x^7`, computed as `x^3 * x^3 * x` like in the constraints.
+pub(crate) fn sbox<F: Field>(x: F) -> F {
+    let x3 = x * x * x;
+    x3 * x3 * x
+}
+
+/// Computes a row of the permutation: the initial linear layer if `r == 0`, and the round `r - 1`
+/// otherwise. Returns the state after the round constants and the state after the linear layer.
+pub(crate) fn poseidon2_row<F: PrimeField32>(
+    r: usize,
+    input: [F; WIDTH],
+) -> ([F; WIDTH], [F; WIDTH]) {
+    let mut add_rc = input;
+    let mut state = input;
+    if r != 0 {
+        let round = r - 1;
+        if is_external_round(round) {
+            for j in 0..WIDTH {
+                add_rc[j] += F::from_wrapped_u32(RC_16_30_U32[round][j]);
+                state[j] = sbox(add_rc[j]);
+            }
+        } else {
+            add_rc[0] += F::from_wrapped_u32(RC_16_30_U32[round][0]);
+            state = add_rc;
+            state[0] = sbox(add_rc[0]);
+        }
+    }
+
+    if r != 0 && !is_external_round(r - 1) {
+        matmul_internal(
+            &mut state,
+            MATRIX_DIAG_16_BABYBEAR_U32.map(F::from_wrapped_u32),
+        );
+    } else {
+        matmul_external(&mut state);
+    }
+    (add_rc, state)
+}
+
+// TODO: Make this public inside Plonky3 and import directly.
+pub fn apply_m_4<AF>(x: &mut [AF])
+where
+    AF: AbstractField,
+{
+    let t0 = x[0].clone() + x[1].clone();
+    let t1 = x[2].clone() + x[3].clone();
+    let t2 = x[1].clone() + x[1].clone() + t1.clone();
+    let t3 = x[3].clone() + x[3].clone() + t0.clone();
+    let t4 = t1.clone() + t1.clone() + t1.clone() + t1 + t3.clone();
+    let t5 = t0.clone() + t0.clone() + t0.clone() + t0 + t2.clone();
+    let t6 = t3 + t5.clone();
+    let t7 = t2 + t4.clone();
+    x[0] = t6;
+    x[1] = t5;
+    x[2] = t7;
+    x[3] = t4;
+}
+
+/// Applies the external linear layer: `M_4` on each consecutive four elements of the state,
+/// followed by the outer circulant matrix.
+pub fn matmul_external<AF: AbstractField, const WIDTH: usize>(state: &mut [AF; WIDTH]) {
+    for i in (0..WIDTH).step_by(4) {
+        apply_m_4(&mut state[i..i + 4]);
+    }
+
+    // The formula for each y_i involves 2x_i' term and x_j' terms for each j that equals i mod 4.
+    // In other words, we can add a single copy of x_i' to the appropriate one of our precomputed
+    // sums of every four elements.
+    let sums: [AF; 4] = core::array::from_fn(|k| {
+        (0..WIDTH)
+            .step_by(4)
+            .map(|j| state[j + k].clone())
+            .sum::<AF>()
+    });
+    for i in 0..WIDTH {
+        state[i] += sums[i % 4].clone();
+    }
+}
+
+// TODO: Make this public inside Plonky3 and import directly.
+pub fn matmul_internal<F: Field, AF: AbstractField<F = F>, const WIDTH: usize>(
+    state: &mut [AF; WIDTH],
+    mat_internal_diag_m_1: [F; WIDTH],
+) {
+    let sum: AF = state.iter().cloned().sum();
+    for i in 0..WIDTH {
+        state[i] *= AF::from_f(mat_internal_diag_m_1[i]);
+        state[i] += sum.clone();
+    }
+}
+
+pub const MATRIX_DIAG_16_BABYBEAR_U32: [u32; 16] = [
+    0x0a632d94, 0x6db657b7, 0x56fbdc9e, 0x052b3d8a, 0x33745201, 0x5c03108c, 0x0beba37b, 0x258c2e8b,
+    0x12029f39, 0x694909ce, 0x6d231724, 0x21c3b222, 0x3c0904a5, 0x01d6acda, 0x27705c83, 0x5231c802,
+];
+
+#[cfg(test)]
+pub mod permute_tests {
+    use p3_baby_bear::BabyBear;
+    use p3_field::{AbstractField, PrimeField32};
+    use rand::Rng;
+    use sp1_primitives::poseidon2_hash;
+
+    use crate::runtime::{Instruction, Opcode, Program, Runtime, SyscallCode};
+    use crate::utils::{run_test, setup_logger};
+
+    use super::{poseidon2_permute, poseidon2_row, NUM_ROWS_PER_PERMUTATION, WIDTH};
+
+    /// Writes `state` to `state_ptr` and permutes it.
+    pub fn poseidon2_permute_program(state_ptr: u32, state: &[u32; WIDTH]) -> Program {
+        let mut instructions = vec![];
+        for (i, word) in state.iter().enumerate() {
+            instructions.extend(vec![
+                Instruction::new(Opcode::ADD, 29, 0, *word, false, true),
+                Instruction::new(Opcode::ADD, 30, 0, state_ptr + i as u32 * 4, false, true),
+                Instruction::new(Opcode::SW, 29, 30, 0, false, true),
+            ]);
+        }
+        instructions.extend(vec![
+            Instruction::new(
+                Opcode::ADD,
+                5,
+                0,
+                SyscallCode::POSEIDON2_PERMUTE as u32,
+                false,
+                true,
+            ),
+            Instruction::new(Opcode::ADD, 10, 0, state_ptr, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+        ]);
+        Program::new(instructions, 0, 0)
+    }
+
+    /// Runs and proves the syscall and returns the resulting state.
+    fn run_poseidon2_permute(state: [u32; WIDTH]) -> [u32; WIDTH] {
+        setup_logger();
+        let state_ptr = 100;
+        let program = poseidon2_permute_program(state_ptr, &state);
+        let mut runtime = Runtime::new(program.clone());
+        runtime.run();
+        let result = core::array::from_fn(|i| runtime.word(state_ptr + i as u32 * 4));
+        run_test(program).unwrap();
+        result
+    }
+
+    fn random_state() -> [u32; WIDTH] {
+        let mut rng = rand::thread_rng();
+        core::array::from_fn(|_| rng.gen_range(0..BabyBear::ORDER_U32))
+    }
+
+    #[test]
+    fn test_poseidon2_rows_match_permutation() {
+        let state = random_state();
+        let mut round_input = state.map(BabyBear::from_canonical_u32);
+        for r in 0..NUM_ROWS_PER_PERMUTATION {
+            round_input = poseidon2_row(r, round_input).1;
+        }
+        assert_eq!(
+            round_input.map(|x| x.as_canonical_u32()),
+            poseidon2_permute(state)
+        );
+    }
+
+    #[test]
+    fn test_poseidon2_permute() {
+        let state = random_state();
+        assert_eq!(run_poseidon2_permute(state), poseidon2_permute(state));
+    }
+
+    #[test]
+    fn test_poseidon2_permute_edge_values() {
+        let mut state = [0; WIDTH];
+        state[0] = BabyBear::ORDER_U32 - 1;
+        state[1] = 1;
+        state[2] = 0x77ff_ffff;
+        assert_eq!(run_poseidon2_permute(state), poseidon2_permute(state));
+    }
+
+    #[test]
+    fn test_poseidon2_permute_matches_hash() {
+        // A single block of the sponge in `poseidon2_hash` is one permutation of the zero-padded
+        // block.
+        let input = [1u32, 2, 3, 4, 5, 6, 7, 8];
+        let mut state = [0; WIDTH];
+        state[..8].copy_from_slice(&input);
+        let digest = poseidon2_hash(input.map(BabyBear::from_canonical_u32).to_vec());
+        assert_eq!(
+            run_poseidon2_permute(state)[..8],
+            digest.map(|x| x.as_canonical_u32())
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_poseidon2_permute_non_canonical() {
+        let mut state = [0; WIDTH];
+        state[3] = BabyBear::ORDER_U32;
+        run_poseidon2_permute(state);
+    }
+}
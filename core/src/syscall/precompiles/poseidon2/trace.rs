@@ -0,0 +1,105 @@
+use std::borrow::BorrowMut;
+
+use p3_field::PrimeField32;
+use p3_matrix::dense::RowMajorMatrix;
+use tracing::instrument;
+
+use super::columns::{Poseidon2PermuteCols, NUM_POSEIDON2_PERMUTE_COLS};
+use super::{is_external_round, poseidon2_row, Poseidon2PermuteChip, NUM_ROWS_PER_PERMUTATION};
+use crate::air::MachineAir;
+use crate::runtime::{ExecutionRecord, Program};
+
+impl<F: PrimeField32> MachineAir<F> for Poseidon2PermuteChip {
+    type Record = ExecutionRecord;
+    type Program = Program;
+
+    fn name(&self) -> String {
+        "Poseidon2Permute".to_string()
+    }
+
+    #[instrument(name = "generate poseidon2 permute trace", level = "debug", skip_all)]
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        output: &mut ExecutionRecord,
+    ) -> RowMajorMatrix<F> {
+        let mut rows = Vec::new();
+
+        let mut new_byte_lookup_events = Vec::new();
+
+        for event in input.poseidon2_permute_events.iter() {
+            let shard = event.shard;
+            let mut round_input = event.pre_state.map(F::from_canonical_u32);
+
+            for r in 0..NUM_ROWS_PER_PERMUTATION {
+                let mut row = [F::zero(); NUM_POSEIDON2_PERMUTE_COLS];
+                let cols: &mut Poseidon2PermuteCols<F> = row.as_mut_slice().borrow_mut();
+
+                cols.shard = F::from_canonical_u32(shard);
+                cols.clk = F::from_canonical_u32(event.clk);
+                cols.state_addr = F::from_canonical_u32(event.state_addr);
+                cols.is_real = F::one();
+                cols.rounds[r] = F::one();
+
+                if r == 0 {
+                    cols.is_initial = F::one();
+                } else if is_external_round(r - 1) {
+                    cols.is_external = F::one();
+                } else {
+                    cols.is_internal = F::one();
+                }
+
+                // Compute the round, and the sbox on the state after the round constants.
+                let (add_rc, round_output) = poseidon2_row(r, round_input);
+                cols.input = round_input;
+                cols.add_rc = add_rc;
+                for j in 0..add_rc.len() {
+                    cols.sbox_deg_3[j] = add_rc[j] * add_rc[j] * add_rc[j];
+                    cols.sbox_deg_7[j] = cols.sbox_deg_3[j] * cols.sbox_deg_3[j] * add_rc[j];
+                }
+                cols.output = round_output;
+                round_input = round_output;
+
+                // If this is the first row, then populate read memory accesses.
+                if r == 0 {
+                    for (j, read_record) in event.state_read_records.iter().enumerate() {
+                        cols.state_mem[j].populate_read(*read_record, &mut new_byte_lookup_events);
+                        cols.state_range_checks[j].populate(output, shard, read_record.value);
+                    }
+                }
+
+                // If this is the last row, then populate write memory accesses.
+                if r == NUM_ROWS_PER_PERMUTATION - 1 {
+                    debug_assert_eq!(round_output.map(|x| x.as_canonical_u32()), event.post_state);
+                    for (j, write_record) in event.state_write_records.iter().enumerate() {
+                        cols.state_mem[j]
+                            .populate_write(*write_record, &mut new_byte_lookup_events);
+                        cols.state_range_checks[j].populate(output, shard, write_record.value);
+                    }
+                }
+
+                rows.push(row);
+            }
+        }
+
+        output.add_byte_lookup_events(new_byte_lookup_events);
+
+        // The number of rows is a multiple of 31, so padding always adds at least one nonreal row.
+        let nb_rows = rows.len();
+        let mut padded_nb_rows = nb_rows.next_power_of_two();
+        if padded_nb_rows < 4 {
+            padded_nb_rows = 4;
+        }
+        rows.resize(padded_nb_rows, [F::zero(); NUM_POSEIDON2_PERMUTE_COLS]);
+
+        // Convert the trace to a row major matrix.
+        RowMajorMatrix::new(
+            rows.into_iter().flatten().collect::<Vec<_>>(),
+            NUM_POSEIDON2_PERMUTE_COLS,
+        )
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        !shard.poseidon2_permute_events.is_empty()
+    }
+}
@@ -0,0 +1,318 @@
+use core::borrow::Borrow;
+
+use p3_air::{Air, AirBuilder, BaseAir};
+use p3_field::AbstractField;
+use p3_matrix::Matrix;
+
+use super::columns::{Ripemd160CompressCols, NUM_RIPEMD160_COMPRESS_COLS};
+use super::step::{Ripemd160StepOperation, NUM_ROTATION_BITS};
+use super::{
+    Ripemd160CompressChip, BLOCK_SIZE, K_LEFT, K_RIGHT, MIN_ROTATION, NUM_ROUNDS, ROUND_SIZE,
+    R_LEFT, R_RIGHT, STATE_SIZE, S_LEFT, S_RIGHT,
+};
+use crate::air::{BaseAirBuilder, SP1AirBuilder, Word, WordAirBuilder};
+use crate::memory::MemoryCols;
+use crate::operations::AddOperation;
+use crate::runtime::SyscallCode;
+
+impl<F> BaseAir<F> for Ripemd160CompressChip {
+    fn width(&self) -> usize {
+        NUM_RIPEMD160_COMPRESS_COLS
+    }
+}
+
+impl<AB> Air<AB> for Ripemd160CompressChip
+where
+    AB: SP1AirBuilder,
+{
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let (local, next) = (main.row_slice(0), main.row_slice(1));
+        let local: &Ripemd160CompressCols<AB::Var> = (*local).borrow();
+        let next: &Ripemd160CompressCols<AB::Var> = (*next).borrow();
+
+        self.constrain_control_flow_flags(builder, local, next);
+
+        self.constrain_memory(builder, local);
+
+        self.constrain_init(builder, local);
+
+        self.constrain_step(builder, local, next);
+
+        self.constrain_final(builder, local);
+
+        builder.receive_syscall(
+            local.shard,
+            local.clk,
+            AB::F::from_canonical_u32(SyscallCode::RIPEMD160_COMPRESS.syscall_id()),
+            local.h_ptr,
+            local.block_ptr,
+            local.is_first,
+        );
+    }
+}
+
+impl Ripemd160CompressChip {
+    /// Constrains the control flow flags: the round and the index of each step, and the final row.
+    fn constrain_control_flow_flags<AB: SP1AirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &Ripemd160CompressCols<AB::Var>,
+        next: &Ripemd160CompressCols<AB::Var>,
+    ) {
+        builder.assert_bool(local.is_real);
+        builder.assert_bool(local.is_first);
+        builder.assert_bool(local.is_step);
+
+        // Exactly one round flag is set in a step, and exactly one index flag in a real row. The
+        // real row which is not a step is the final row.
+        let mut round_sum = AB::Expr::zero();
+        for k in 0..NUM_ROUNDS {
+            builder.assert_bool(local.is_round_n[k]);
+            round_sum += local.is_round_n[k].into();
+        }
+        builder.assert_eq(local.is_step, round_sum);
+        let mut index_sum = AB::Expr::zero();
+        for i in 0..ROUND_SIZE {
+            builder.assert_bool(local.is_index_n[i]);
+            index_sum += local.is_index_n[i].into();
+        }
+        builder.assert_eq(local.is_real, index_sum);
+        builder.assert_eq(local.is_final, local.is_real - local.is_step);
+
+        // A syscall starts on the first real row and after each final row.
+        builder
+            .when_first_row()
+            .assert_eq(local.is_first, local.is_real);
+        builder
+            .when_transition()
+            .assert_eq(next.is_first, local.is_final * next.is_real);
+        builder.when(local.is_first).assert_one(local.is_real);
+        builder.when(local.is_first).assert_one(local.is_round_n[0]);
+        builder.when(local.is_first).assert_one(local.is_index_n[0]);
+
+        // A step is always followed by another row of the same syscall, and padding is only
+        // followed by padding.
+        builder
+            .when_transition()
+            .when(local.is_step)
+            .assert_one(next.is_real);
+        builder
+            .when_transition()
+            .when_not(local.is_real)
+            .assert_zero(next.is_real);
+
+        // Assert that the table ends in nonreal columns, so that every syscall reaches its final
+        // row. The table is padded with at least one row to guarantee this.
+        builder.when_last_row().assert_zero(local.is_real);
+
+        // Each step moves on to the next index, and to the next round after the last index. After
+        // the last step of the last round, no round flag is set, so the next row is the final one.
+        let is_last_index = local.is_index_n[ROUND_SIZE - 1];
+        for i in 0..ROUND_SIZE {
+            builder
+                .when_transition()
+                .when(local.is_step)
+                .assert_eq(local.is_index_n[i], next.is_index_n[(i + 1) % ROUND_SIZE]);
+        }
+        for k in 0..NUM_ROUNDS {
+            let mut expected = local.is_round_n[k] - local.is_round_n[k] * is_last_index;
+            if k > 0 {
+                expected += local.is_round_n[k - 1] * is_last_index;
+            }
+            builder
+                .when_transition()
+                .when(local.is_step)
+                .assert_eq(next.is_round_n[k], expected);
+        }
+
+        // Copy over the inputs until the final row.
+        builder
+            .when_transition()
+            .when(local.is_step)
+            .assert_eq(local.shard, next.shard);
+        builder
+            .when_transition()
+            .when(local.is_step)
+            .assert_eq(local.clk, next.clk);
+        builder
+            .when_transition()
+            .when(local.is_step)
+            .assert_eq(local.h_ptr, next.h_ptr);
+        builder
+            .when_transition()
+            .when(local.is_step)
+            .assert_eq(local.block_ptr, next.block_ptr);
+        for i in 0..STATE_SIZE {
+            builder
+                .when_transition()
+                .when(local.is_step)
+                .assert_word_eq(local.h[i], next.h[i]);
+        }
+        for i in 0..BLOCK_SIZE {
+            builder
+                .when_transition()
+                .when(local.is_step)
+                .assert_word_eq(local.block[i], next.block[i]);
+        }
+    }
+
+    /// Constrains the memory accesses: the inputs are read in the first row and the new state is
+    /// written in the final row.
+    fn constrain_memory<AB: SP1AirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &Ripemd160CompressCols<AB::Var>,
+    ) {
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.clk.into(),
+            local.h_ptr,
+            &local.h_reads,
+            local.is_first,
+        );
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.clk.into(),
+            local.block_ptr,
+            &local.block_reads,
+            local.is_first,
+        );
+        // We write h at +1 since it was already read at clk.
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.clk + AB::F::one(),
+            local.h_ptr,
+            &local.h_writes,
+            local.is_final,
+        );
+    }
+
+    /// Constrains the inputs read in the first row and the initial working states of both lines.
+    fn constrain_init<AB: SP1AirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &Ripemd160CompressCols<AB::Var>,
+    ) {
+        let is_first = local.is_first;
+        for i in 0..STATE_SIZE {
+            builder
+                .when(is_first)
+                .assert_word_eq(local.h[i], *local.h_reads[i].value());
+            builder
+                .when(is_first)
+                .assert_word_eq(local.left[i], local.h[i]);
+            builder
+                .when(is_first)
+                .assert_word_eq(local.right[i], local.h[i]);
+        }
+        for i in 0..BLOCK_SIZE {
+            builder
+                .when(is_first)
+                .assert_word_eq(local.block[i], *local.block_reads[i].value());
+        }
+    }
+
+    /// Constrains a step of both lines on their working states.
+    fn constrain_step<AB: SP1AirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &Ripemd160CompressCols<AB::Var>,
+        next: &Ripemd160CompressCols<AB::Var>,
+    ) {
+        // Select the message word and the rotation of the step, and the constants of the round.
+        for k in 0..NUM_ROUNDS {
+            let is_round = local.is_round_n[k];
+            for i in 0..ROUND_SIZE {
+                let j = k * ROUND_SIZE + i;
+                let is_step_j = is_round * local.is_index_n[i];
+                builder
+                    .when(is_step_j.clone())
+                    .assert_word_eq(local.left_step.x, local.block[R_LEFT[j]]);
+                builder
+                    .when(is_step_j.clone())
+                    .assert_word_eq(local.right_step.x, local.block[R_RIGHT[j]]);
+                for b in 0..NUM_ROTATION_BITS {
+                    builder.when(is_step_j.clone()).assert_eq(
+                        local.left_step.rotation_bits[b],
+                        AB::F::from_canonical_usize(((S_LEFT[j] - MIN_ROTATION) >> b) & 1),
+                    );
+                    builder.when(is_step_j.clone()).assert_eq(
+                        local.right_step.rotation_bits[b],
+                        AB::F::from_canonical_usize(((S_RIGHT[j] - MIN_ROTATION) >> b) & 1),
+                    );
+                }
+            }
+            builder
+                .when(is_round)
+                .assert_word_eq(local.left_step.k, Word::<AB::F>::from(K_LEFT[k]));
+            builder
+                .when(is_round)
+                .assert_word_eq(local.right_step.k, Word::<AB::F>::from(K_RIGHT[k]));
+        }
+
+        // The right line uses the boolean functions in the reverse order of the rounds.
+        let mut reversed_rounds = local.is_round_n;
+        reversed_rounds.reverse();
+        Ripemd160StepOperation::<AB::F>::eval(
+            builder,
+            local.left,
+            local.is_round_n,
+            local.left_step,
+            local.shard,
+            local.is_step,
+        );
+        Ripemd160StepOperation::<AB::F>::eval(
+            builder,
+            local.right,
+            reversed_rounds,
+            local.right_step,
+            local.shard,
+            local.is_step,
+        );
+
+        // The result of the step is the working state of the next row.
+        let left = local.left_step.result(local.left);
+        let right = local.right_step.result(local.right);
+        for i in 0..STATE_SIZE {
+            builder
+                .when_transition()
+                .when(local.is_step)
+                .assert_word_eq(next.left[i], left[i]);
+            builder
+                .when_transition()
+                .when(local.is_step)
+                .assert_word_eq(next.right[i], right[i]);
+        }
+    }
+
+    /// Constrains the new state `h[i + 1] + left[i + 2] + right[i + 3]` written in the final row.
+    fn constrain_final<AB: SP1AirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &Ripemd160CompressCols<AB::Var>,
+    ) {
+        for i in 0..STATE_SIZE {
+            AddOperation::<AB::F>::eval(
+                builder,
+                local.h[(i + 1) % STATE_SIZE],
+                local.left[(i + 2) % STATE_SIZE],
+                local.h_add_left[i],
+                local.shard,
+                local.is_final.into(),
+            );
+            AddOperation::<AB::F>::eval(
+                builder,
+                local.h_add_left[i].value,
+                local.right[(i + 3) % STATE_SIZE],
+                local.h_add_left_add_right[i],
+                local.shard,
+                local.is_final.into(),
+            );
+            builder.when(local.is_final).assert_word_eq(
+                *local.h_writes[i].value(),
+                local.h_add_left_add_right[i].value,
+            );
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use std::mem::size_of;
+
+use sp1_derive::AlignedBorrow;
+
+use crate::air::Word;
+use crate::memory::MemoryReadCols;
+use crate::memory::MemoryWriteCols;
+use crate::operations::AddOperation;
+
+use super::step::Ripemd160StepOperation;
+use super::BLOCK_SIZE;
+use super::NUM_ROUNDS;
+use super::ROUND_SIZE;
+use super::STATE_SIZE;
+
+pub const NUM_RIPEMD160_COMPRESS_COLS: usize = size_of::<Ripemd160CompressCols<u8>>();
+
+#[derive(AlignedBorrow, Default, Debug, Clone, Copy)]
+#[repr(C)]
+pub struct Ripemd160CompressCols<T> {
+    pub shard: T,
+    pub clk: T,
+
+    /// The pointer to the state.
+    pub h_ptr: T,
+
+    /// The pointer to the block.
+    pub block_ptr: T,
+
+    /// Whether this is the first row of a syscall, in which the inputs are read.
+    pub is_first: T,
+
+    /// Whether this row computes a step of both lines.
+    pub is_step: T,
+
+    /// Whether this is the final row of a syscall, in which the new state is written.
+    pub is_final: T,
+
+    /// Indicates the round of the step. None of the flags is set in the final row.
+    pub is_round_n: [T; NUM_ROUNDS],
+
+    /// Indicates the index of the step within its round.
+    pub is_index_n: [T; ROUND_SIZE],
+
+    /// Reads the state and the block in the first row.
+    pub h_reads: [MemoryReadCols<T>; STATE_SIZE],
+    pub block_reads: [MemoryReadCols<T>; BLOCK_SIZE],
+
+    /// The state and the block, which are copied over every row of the syscall.
+    pub h: [Word<T>; STATE_SIZE],
+    pub block: [Word<T>; BLOCK_SIZE],
+
+    /// The working states `[a, b, c, d, e]` of both lines at the start of the row.
+    pub left: [Word<T>; STATE_SIZE],
+    pub right: [Word<T>; STATE_SIZE],
+
+    /// The steps of both lines.
+    pub left_step: Ripemd160StepOperation<T>,
+    pub right_step: Ripemd160StepOperation<T>,
+
+    /// `h[i + 1] + left[i + 2]` and `h[i + 1] + left[i + 2] + right[i + 3]` in the final row.
+    pub h_add_left: [AddOperation<T>; STATE_SIZE],
+    pub h_add_left_add_right: [AddOperation<T>; STATE_SIZE],
+
+    /// Writes the new state in the final row.
+    pub h_writes: [MemoryWriteCols<T>; STATE_SIZE],
+
+    /// Indicates if the current row is real or not.
+    pub is_real: T,
+}
@@ -0,0 +1,51 @@
+use crate::runtime::Syscall;
+use crate::syscall::precompiles::ripemd160::{
+    ripemd160_compress, Ripemd160CompressChip, Ripemd160CompressEvent, BLOCK_SIZE, STATE_SIZE,
+};
+use crate::syscall::precompiles::SyscallContext;
+
+impl Syscall for Ripemd160CompressChip {
+    fn num_extra_cycles(&self) -> u32 {
+        1
+    }
+
+    fn execute(&self, rt: &mut SyscallContext, arg1: u32, arg2: u32) -> Option<u32> {
+        let h_ptr = arg1;
+        let block_ptr = arg2;
+        if h_ptr % 4 != 0 || block_ptr % 4 != 0 {
+            panic!();
+        }
+
+        let start_clk = rt.clk;
+
+        // Read the state and the block.
+        let (h_read_records, h) = rt.mr_slice(h_ptr, STATE_SIZE);
+        let (block_read_records, block) = rt.mr_slice(block_ptr, BLOCK_SIZE);
+        let h: [u32; STATE_SIZE] = h.try_into().unwrap();
+        let block: [u32; BLOCK_SIZE] = block.try_into().unwrap();
+
+        let mut new_h = h;
+        ripemd160_compress(&mut new_h, &block);
+
+        // Increment the clk by 1 before writing to h, since we've already read h at the start_clk.
+        rt.clk += 1;
+        let h_write_records = rt.mw_slice(h_ptr, &new_h);
+
+        let shard = rt.current_shard();
+        rt.record_mut()
+            .ripemd160_compress_events
+            .push(Ripemd160CompressEvent {
+                shard,
+                clk: start_clk,
+                h_ptr,
+                block_ptr,
+                h,
+                block,
+                h_read_records,
+                block_read_records,
+                h_write_records,
+            });
+
+        None
+    }
+}
@@ -0,0 +1,255 @@
+//! This module contains the implementation of the `ripemd160_compress` precompile, which is the
+//! RIPEMD-160 compression function applied to a single 64-byte block.
+//!
+//! Pseudo-code.
+//!
+//! left = h
+//! right = h
+//!
+//! for j in 0..80 {
+//!    // * Apply step `j` to the left line, with the function `f(j / 16)`.
+//!    // * Apply step `j` to the right line, with the function `f(4 - j / 16)`.
+//!    //
+//!    // Each step of both lines becomes one row in the trace.
+//! }
+//!
+//! h[i] = h[i + 1] + left[i + 2] + right[i + 3]
+//!
+//! // This final step becomes one more row in the trace.
+mod air;
+mod columns;
+mod execute;
+mod step;
+mod trace;
+
+pub use columns::*;
+
+use serde::{Deserialize, Serialize};
+
+use crate::runtime::{MemoryReadRecord, MemoryWriteRecord};
+
+/// The number of words in the state `h`.
+pub const STATE_SIZE: usize = 5;
+
+/// The number of words in a block.
+pub const BLOCK_SIZE: usize = 16;
+
+/// The number of rounds, each of which uses its own boolean function and constants.
+pub(crate) const NUM_ROUNDS: usize = 5;
+
+/// The number of steps in a round.
+pub(crate) const ROUND_SIZE: usize = 16;
+
+/// The number of steps of each line.
+pub(crate) const NUM_STEPS: usize = NUM_ROUNDS * ROUND_SIZE;
+
+/// The smallest rotation of a step. All rotations are between 5 and 15.
+pub(crate) const MIN_ROTATION: usize = 5;
+
+pub const RIPEMD160_IV: [u32; STATE_SIZE] =
+    [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476, 0xc3d2e1f0];
+
+/// The constants added in each round of the left and of the right line.
+pub(crate) const K_LEFT: [u32; NUM_ROUNDS] =
+    [0x00000000, 0x5a827999, 0x6ed9eba1, 0x8f1bbcdc, 0xa953fd4e];
+pub(crate) const K_RIGHT: [u32; NUM_ROUNDS] =
+    [0x50a28be6, 0x5c4dd124, 0x6d703ef3, 0x7a6d76e9, 0x00000000];
+
+/// The index of the message word used by each step of the left and of the right line.
+pub(crate) const R_LEFT: [usize; NUM_STEPS] = [
+    0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, //
+    7, 4, 13, 1, 10, 6, 15, 3, 12, 0, 9, 5, 2, 14, 11, 8, //
+    3, 10, 14, 4, 9, 15, 8, 1, 2, 7, 0, 6, 13, 11, 5, 12, //
+    1, 9, 11, 10, 0, 8, 12, 4, 13, 3, 7, 15, 14, 5, 6, 2, //
+    4, 0, 5, 9, 7, 12, 2, 10, 14, 1, 3, 8, 11, 6, 15, 13,
+];
+pub(crate) const R_RIGHT: [usize; NUM_STEPS] = [
+    5, 14, 7, 0, 9, 2, 11, 4, 13, 6, 15, 8, 1, 10, 3, 12, //
+    6, 11, 3, 7, 0, 13, 5, 10, 14, 15, 8, 12, 4, 9, 1, 2, //
+    15, 5, 1, 3, 7, 14, 6, 9, 11, 8, 12, 2, 10, 0, 4, 13, //
+    8, 6, 4, 1, 3, 11, 15, 0, 5, 12, 2, 13, 9, 7, 10, 14, //
+    12, 15, 10, 4, 1, 5, 8, 7, 6, 2, 13, 14, 0, 3, 9, 11,
+];
+
+/// The left rotation of each step of the left and of the right line.
+pub(crate) const S_LEFT: [usize; NUM_STEPS] = [
+    11, 14, 15, 12, 5, 8, 7, 9, 11, 13, 14, 15, 6, 7, 9, 8, //
+    7, 6, 8, 13, 11, 9, 7, 15, 7, 12, 15, 9, 11, 7, 13, 12, //
+    11, 13, 6, 7, 14, 9, 13, 15, 14, 8, 13, 6, 5, 12, 7, 5, //
+    11, 12, 14, 15, 14, 15, 9, 8, 9, 14, 5, 6, 8, 6, 5, 12, //
+    9, 15, 5, 11, 6, 8, 13, 12, 5, 12, 13, 14, 11, 8, 5, 6,
+];
+pub(crate) const S_RIGHT: [usize; NUM_STEPS] = [
+    8, 9, 9, 11, 13, 15, 15, 5, 7, 7, 8, 11, 14, 14, 12, 6, //
+    9, 13, 15, 7, 12, 8, 9, 11, 7, 7, 12, 7, 6, 15, 13, 11, //
+    9, 7, 15, 11, 8, 6, 6, 14, 12, 13, 5, 14, 13, 13, 7, 5, //
+    15, 5, 8, 11, 14, 14, 6, 14, 6, 9, 12, 9, 12, 5, 15, 8, //
+    8, 5, 12, 9, 12, 5, 14, 6, 8, 13, 6, 5, 15, 13, 11, 11,
+];
+
+/// The boolean function of round `round` of the left line, which is round `4 - round` of the
+/// right line.
+pub(crate) fn ripemd160_f(round: usize, b: u32, c: u32, d: u32) -> u32 {
+    match round {
+        0 => b ^ c ^ d,
+        1 => (b & c) | (!b & d),
+        2 => (b | !c) ^ d,
+        3 => (b & d) | (c & !d),
+        4 => b ^ (c | !d),
+        _ => unreachable!(),
+    }
+}
+
+/// Applies a step to the working state `[a, b, c, d, e]` of a line.
+pub(crate) fn ripemd160_step(v: &mut [u32; STATE_SIZE], f: u32, x: u32, k: u32, s: usize) {
+    let [a, b, c, d, e] = *v;
+    let t = a
+        .wrapping_add(f)
+        .wrapping_add(x)
+        .wrapping_add(k)
+        .rotate_left(s as u32)
+        .wrapping_add(e);
+    *v = [e, t, b, c.rotate_left(10), d];
+}
+
+/// Combines the state with the working states of both lines after the last step.
+pub(crate) fn ripemd160_finalize(
+    h: &[u32; STATE_SIZE],
+    left: &[u32; STATE_SIZE],
+    right: &[u32; STATE_SIZE],
+) -> [u32; STATE_SIZE] {
+    core::array::from_fn(|i| {
+        h[(i + 1) % STATE_SIZE]
+            .wrapping_add(left[(i + 2) % STATE_SIZE])
+            .wrapping_add(right[(i + 3) % STATE_SIZE])
+    })
+}
+
+/// The RIPEMD-160 compression function, where the block is given as little-endian words.
+pub fn ripemd160_compress(h: &mut [u32; STATE_SIZE], block: &[u32; BLOCK_SIZE]) {
+    let mut left = *h;
+    let mut right = *h;
+    for j in 0..NUM_STEPS {
+        let round = j / ROUND_SIZE;
+        let f = ripemd160_f(round, left[1], left[2], left[3]);
+        ripemd160_step(&mut left, f, block[R_LEFT[j]], K_LEFT[round], S_LEFT[j]);
+        let f = ripemd160_f(NUM_ROUNDS - 1 - round, right[1], right[2], right[3]);
+        ripemd160_step(&mut right, f, block[R_RIGHT[j]], K_RIGHT[round], S_RIGHT[j]);
+    }
+    *h = ripemd160_finalize(h, &left, &right);
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Ripemd160CompressEvent {
+    pub shard: u32,
+    pub clk: u32,
+    pub h_ptr: u32,
+    pub block_ptr: u32,
+    pub h: [u32; STATE_SIZE],
+    pub block: [u32; BLOCK_SIZE],
+    pub h_read_records: Vec<MemoryReadRecord>,
+    pub block_read_records: Vec<MemoryReadRecord>,
+    pub h_write_records: Vec<MemoryWriteRecord>,
+}
+
+/// Implements the RIPEMD-160 compression function. The inputs to the syscall are a pointer to the
+/// 5 word state `h`, which is updated in place, and a pointer to the 16 word block.
+///
+/// In the AIR, each syscall takes up 81 rows: one per step, in which both lines are computed, and
+/// a final one which computes the new state and writes it to memory.
+#[derive(Default)]
+pub struct Ripemd160CompressChip;
+
+impl Ripemd160CompressChip {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[cfg(test)]
+pub mod compress_tests {
+    use rand::Rng;
+    use ripemd::{Digest, Ripemd160};
+
+    use crate::{
+        runtime::{Instruction, Opcode, Program, Runtime, SyscallCode},
+        utils::{run_test, setup_logger},
+    };
+
+    use super::{ripemd160_compress, BLOCK_SIZE, RIPEMD160_IV, STATE_SIZE};
+
+    const H_PTR: u32 = 100;
+    const BLOCK_PTR: u32 = 1000;
+
+    /// Writes the state and the block, and compresses the block into the state.
+    pub fn ripemd160_compress_program(h: &[u32; STATE_SIZE], block: &[u32; BLOCK_SIZE]) -> Program {
+        let mut instructions = vec![];
+        for (ptr, words) in [(H_PTR, &h[..]), (BLOCK_PTR, &block[..])] {
+            for (i, word) in words.iter().enumerate() {
+                instructions.extend(vec![
+                    Instruction::new(Opcode::ADD, 29, 0, *word, false, true),
+                    Instruction::new(Opcode::ADD, 30, 0, ptr + i as u32 * 4, false, true),
+                    Instruction::new(Opcode::SW, 29, 30, 0, false, true),
+                ]);
+            }
+        }
+        instructions.extend(vec![
+            Instruction::new(
+                Opcode::ADD,
+                5,
+                0,
+                SyscallCode::RIPEMD160_COMPRESS as u32,
+                false,
+                true,
+            ),
+            Instruction::new(Opcode::ADD, 10, 0, H_PTR, false, true),
+            Instruction::new(Opcode::ADD, 11, 0, BLOCK_PTR, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+        ]);
+        Program::new(instructions, 0, 0)
+    }
+
+    /// Runs and proves the syscall, and returns the resulting state.
+    fn run_ripemd160_compress(h: [u32; STATE_SIZE], block: [u32; BLOCK_SIZE]) -> [u32; STATE_SIZE] {
+        setup_logger();
+        let program = ripemd160_compress_program(&h, &block);
+        let mut runtime = Runtime::new(program.clone());
+        runtime.run();
+        let result = core::array::from_fn(|i| runtime.word(H_PTR + i as u32 * 4));
+        run_test(program).unwrap();
+        result
+    }
+
+    #[test]
+    fn test_ripemd160_compress_single_block_hash() {
+        let mut rng = rand::thread_rng();
+        let message: Vec<u8> = (0..40).map(|_| rng.gen()).collect();
+
+        // Pad the message into a single block, with its length in bits at the end.
+        let mut block = [0u8; 64];
+        block[..message.len()].copy_from_slice(&message);
+        block[message.len()] = 0x80;
+        block[56..].copy_from_slice(&(8 * message.len() as u64).to_le_bytes());
+        let block = core::array::from_fn(|i| {
+            u32::from_le_bytes(block[i * 4..(i + 1) * 4].try_into().unwrap())
+        });
+
+        let result = run_ripemd160_compress(RIPEMD160_IV, block);
+        let digest = result
+            .iter()
+            .flat_map(|x| x.to_le_bytes())
+            .collect::<Vec<_>>();
+        assert_eq!(digest, Ripemd160::digest(&message).to_vec());
+    }
+
+    #[test]
+    fn test_ripemd160_compress_random() {
+        let mut rng = rand::thread_rng();
+        let h: [u32; STATE_SIZE] = rng.gen();
+        let block: [u32; BLOCK_SIZE] = rng.gen();
+
+        let mut expected = h;
+        ripemd160_compress(&mut expected, &block);
+        assert_eq!(run_ripemd160_compress(h, block), expected);
+    }
+}
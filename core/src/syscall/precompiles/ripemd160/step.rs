@@ -0,0 +1,285 @@
+use p3_field::AbstractField;
+use p3_field::Field;
+use sp1_derive::AlignedBorrow;
+
+use super::{ripemd160_f, MIN_ROTATION, NUM_ROUNDS, STATE_SIZE};
+use crate::air::SP1AirBuilder;
+use crate::air::Word;
+use crate::operations::Add4Operation;
+use crate::operations::AddOperation;
+use crate::operations::AndOperation;
+use crate::operations::FixedRotateRightOperation;
+use crate::operations::NotOperation;
+use crate::operations::OrOperation;
+use crate::operations::XorOperation;
+use crate::runtime::ExecutionRecord;
+
+/// The number of conditional rotations, by 1, 2, 4 and 8, which follow the rotation by
+/// `MIN_ROTATION`.
+pub(crate) const NUM_ROTATION_BITS: usize = 4;
+
+/// A set of columns needed to compute a step of one of the lines of RIPEMD-160.
+///  ``` ignore
+/// fn step(v: &mut [u32; 5], f: u32, x: u32, k: u32, s: u32) {
+///     let [a, b, c, d, e] = *v;
+///     let t = (a + f + x + k).rotate_left(s) + e;
+///     *v = [e, t, b, c.rotate_left(10), d];
+/// }
+///  ```
+///
+/// The five boolean functions of `b`, `c` and `d` are all computed, and the round selects one of
+/// them as `f`. The rotation by `s` is a rotation by 5, followed by rotations by 1, 2, 4 and 8
+/// which are kept or skipped according to the bits of `s - 5`.
+#[derive(AlignedBorrow, Default, Debug, Clone, Copy)]
+#[repr(C)]
+pub struct Ripemd160StepOperation<T> {
+    /// The message word and the constant of the step.
+    pub x: Word<T>,
+    pub k: Word<T>,
+
+    /// `f1 := b ^ c ^ d`.
+    pub b_xor_c: XorOperation<T>,
+    pub f1: XorOperation<T>,
+
+    /// `f2 := (b & c) | (!b & d)`, computed as `((c ^ d) & b) ^ d`.
+    pub c_xor_d: XorOperation<T>,
+    pub c_xor_d_and_b: AndOperation<T>,
+    pub f2: XorOperation<T>,
+
+    /// `f3 := (b | !c) ^ d`.
+    pub c_not: NotOperation<T>,
+    pub b_or_c_not: OrOperation<T>,
+    pub f3: XorOperation<T>,
+
+    /// `f4 := (b & d) | (c & !d)`, computed as `((b ^ c) & d) ^ c`.
+    pub b_xor_c_and_d: AndOperation<T>,
+    pub f4: XorOperation<T>,
+
+    /// `f5 := b ^ (c | !d)`.
+    pub d_not: NotOperation<T>,
+    pub c_or_d_not: OrOperation<T>,
+    pub f5: XorOperation<T>,
+
+    /// The function selected by the round.
+    pub f: Word<T>,
+
+    /// `a + f + x + k`.
+    pub sum: Add4Operation<T>,
+
+    /// The bits of `s - 5`.
+    pub rotation_bits: [T; NUM_ROTATION_BITS],
+
+    /// The sum rotated left by 5.
+    pub sum_rotl_5: FixedRotateRightOperation<T>,
+
+    /// The rotations by 1, 2, 4 and 8 of the previous value, and the value after each of them is
+    /// kept or skipped.
+    pub rotl: [FixedRotateRightOperation<T>; NUM_ROTATION_BITS],
+    pub rotated: [Word<T>; NUM_ROTATION_BITS],
+
+    /// `t := (a + f + x + k).rotate_left(s) + e`.
+    pub t: AddOperation<T>,
+
+    pub c_rotl_10: FixedRotateRightOperation<T>,
+}
+
+impl<T: Copy> Ripemd160StepOperation<T> {
+    /// The working state `[e, t, b, c.rotate_left(10), d]` after the step.
+    pub fn result(&self, v: [Word<T>; STATE_SIZE]) -> [Word<T>; STATE_SIZE] {
+        [v[4], self.t.value, v[1], self.c_rotl_10.value, v[3]]
+    }
+}
+
+impl<F: Field> Ripemd160StepOperation<F> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn populate(
+        &mut self,
+        record: &mut ExecutionRecord,
+        shard: u32,
+        v: [u32; STATE_SIZE],
+        round: usize,
+        x: u32,
+        k: u32,
+        s: usize,
+    ) -> [u32; STATE_SIZE] {
+        let [a, b, c, d, e] = v;
+        self.x = Word::from(x);
+        self.k = Word::from(k);
+
+        let b_xor_c = self.b_xor_c.populate(record, shard, b, c);
+        let f1 = self.f1.populate(record, shard, b_xor_c, d);
+
+        let c_xor_d = self.c_xor_d.populate(record, shard, c, d);
+        let c_xor_d_and_b = self.c_xor_d_and_b.populate(record, shard, c_xor_d, b);
+        let f2 = self.f2.populate(record, shard, c_xor_d_and_b, d);
+
+        let c_not = self.c_not.populate(record, shard, c);
+        let b_or_c_not = self.b_or_c_not.populate(record, shard, b, c_not);
+        let f3 = self.f3.populate(record, shard, b_or_c_not, d);
+
+        let b_xor_c_and_d = self.b_xor_c_and_d.populate(record, shard, b_xor_c, d);
+        let f4 = self.f4.populate(record, shard, b_xor_c_and_d, c);
+
+        let d_not = self.d_not.populate(record, shard, d);
+        let c_or_d_not = self.c_or_d_not.populate(record, shard, c, d_not);
+        let f5 = self.f5.populate(record, shard, b, c_or_d_not);
+
+        let f = [f1, f2, f3, f4, f5][round];
+        debug_assert_eq!(f, ripemd160_f(round, b, c, d));
+        self.f = Word::from(f);
+
+        let sum = self.sum.populate(record, shard, a, f, x, k);
+
+        // Rotate left by 5, then by the bits of `s - 5`.
+        let mut rotated = self
+            .sum_rotl_5
+            .populate(record, shard, sum, 32 - MIN_ROTATION);
+        let bits = s - MIN_ROTATION;
+        for i in 0..NUM_ROTATION_BITS {
+            let bit = (bits >> i) & 1 == 1;
+            let candidate = self.rotl[i].populate(record, shard, rotated, 32 - (1 << i));
+            if bit {
+                rotated = candidate;
+            }
+            self.rotation_bits[i] = F::from_bool(bit);
+            self.rotated[i] = Word::from(rotated);
+        }
+
+        let t = self.t.populate(record, shard, rotated, e);
+        let c_rotl_10 = self.c_rotl_10.populate(record, shard, c, 32 - 10);
+
+        [e, t, b, c_rotl_10, d]
+    }
+
+    /// Evaluates the step on the working state `v`. The function of the step is `f_index`, whose
+    /// entries are the flags of the rounds which use each of the five functions.
+    pub fn eval<AB: SP1AirBuilder>(
+        builder: &mut AB,
+        v: [Word<AB::Var>; STATE_SIZE],
+        f_index: [AB::Var; NUM_ROUNDS],
+        cols: Ripemd160StepOperation<AB::Var>,
+        shard: AB::Var,
+        is_real: AB::Var,
+    ) {
+        let [a, b, c, d, e] = v;
+
+        XorOperation::<AB::F>::eval(builder, b, c, cols.b_xor_c, shard, is_real);
+        XorOperation::<AB::F>::eval(builder, cols.b_xor_c.value, d, cols.f1, shard, is_real);
+
+        XorOperation::<AB::F>::eval(builder, c, d, cols.c_xor_d, shard, is_real);
+        AndOperation::<AB::F>::eval(
+            builder,
+            cols.c_xor_d.value,
+            b,
+            cols.c_xor_d_and_b,
+            shard,
+            is_real,
+        );
+        XorOperation::<AB::F>::eval(
+            builder,
+            cols.c_xor_d_and_b.value,
+            d,
+            cols.f2,
+            shard,
+            is_real,
+        );
+
+        NotOperation::<AB::F>::eval(builder, c, cols.c_not, shard, is_real);
+        OrOperation::<AB::F>::eval(
+            builder,
+            b,
+            cols.c_not.value,
+            cols.b_or_c_not,
+            shard,
+            is_real,
+        );
+        XorOperation::<AB::F>::eval(builder, cols.b_or_c_not.value, d, cols.f3, shard, is_real);
+
+        AndOperation::<AB::F>::eval(
+            builder,
+            cols.b_xor_c.value,
+            d,
+            cols.b_xor_c_and_d,
+            shard,
+            is_real,
+        );
+        XorOperation::<AB::F>::eval(
+            builder,
+            cols.b_xor_c_and_d.value,
+            c,
+            cols.f4,
+            shard,
+            is_real,
+        );
+
+        NotOperation::<AB::F>::eval(builder, d, cols.d_not, shard, is_real);
+        OrOperation::<AB::F>::eval(
+            builder,
+            c,
+            cols.d_not.value,
+            cols.c_or_d_not,
+            shard,
+            is_real,
+        );
+        XorOperation::<AB::F>::eval(builder, b, cols.c_or_d_not.value, cols.f5, shard, is_real);
+
+        // Select the function of the round.
+        let functions = [
+            cols.f1.value,
+            cols.f2.value,
+            cols.f3.value,
+            cols.f4.value,
+            cols.f5.value,
+        ];
+        for j in 0..4 {
+            let mut f = AB::Expr::zero();
+            for (flag, function) in f_index.iter().zip(functions.iter()) {
+                f += *flag * function[j];
+            }
+            builder.when(is_real).assert_eq(cols.f[j], f);
+        }
+
+        Add4Operation::<AB::F>::eval(builder, a, cols.f, cols.x, cols.k, shard, is_real, cols.sum);
+
+        // Rotate left by 5, then by 1, 2, 4 and 8 when the corresponding bit of `s - 5` is set.
+        FixedRotateRightOperation::<AB::F>::eval(
+            builder,
+            cols.sum.value,
+            32 - MIN_ROTATION,
+            cols.sum_rotl_5,
+            shard,
+            is_real,
+        );
+        let mut rotated = cols.sum_rotl_5.value;
+        for i in 0..NUM_ROTATION_BITS {
+            let bit = cols.rotation_bits[i];
+            builder.assert_bool(bit);
+            FixedRotateRightOperation::<AB::F>::eval(
+                builder,
+                rotated,
+                32 - (1 << i),
+                cols.rotl[i],
+                shard,
+                is_real,
+            );
+            for j in 0..4 {
+                builder.when(is_real).assert_eq(
+                    cols.rotated[i][j],
+                    bit * cols.rotl[i].value[j] + (AB::Expr::one() - bit) * rotated[j],
+                );
+            }
+            rotated = cols.rotated[i];
+        }
+
+        AddOperation::<AB::F>::eval(builder, rotated, e, cols.t, shard, is_real.into());
+
+        FixedRotateRightOperation::<AB::F>::eval(
+            builder,
+            c,
+            32 - 10,
+            cols.c_rotl_10,
+            shard,
+            is_real,
+        );
+    }
+}
@@ -0,0 +1,144 @@
+use std::borrow::BorrowMut;
+
+use p3_field::PrimeField32;
+use p3_matrix::dense::RowMajorMatrix;
+
+use super::columns::{Ripemd160CompressCols, NUM_RIPEMD160_COMPRESS_COLS};
+use super::{
+    ripemd160_compress, ripemd160_finalize, Ripemd160CompressChip, K_LEFT, K_RIGHT, NUM_ROUNDS,
+    NUM_STEPS, ROUND_SIZE, R_LEFT, R_RIGHT, STATE_SIZE, S_LEFT, S_RIGHT,
+};
+use crate::air::{MachineAir, Word};
+use crate::runtime::{ExecutionRecord, Program};
+
+impl<F: PrimeField32> MachineAir<F> for Ripemd160CompressChip {
+    type Record = ExecutionRecord;
+    type Program = Program;
+
+    fn name(&self) -> String {
+        "Ripemd160Compress".to_string()
+    }
+
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        output: &mut ExecutionRecord,
+    ) -> RowMajorMatrix<F> {
+        let mut rows = Vec::new();
+
+        let mut new_byte_lookup_events = Vec::new();
+
+        for event in input.ripemd160_compress_events.iter() {
+            let shard = event.shard;
+            let mut left = event.h;
+            let mut right = event.h;
+            for j in 0..=NUM_STEPS {
+                let mut row = [F::zero(); NUM_RIPEMD160_COMPRESS_COLS];
+                let cols: &mut Ripemd160CompressCols<F> = row.as_mut_slice().borrow_mut();
+                let is_final = j == NUM_STEPS;
+                let round = j / ROUND_SIZE;
+
+                // Assign basic values to the columns.
+                {
+                    cols.shard = F::from_canonical_u32(event.shard);
+                    cols.clk = F::from_canonical_u32(event.clk);
+                    cols.h_ptr = F::from_canonical_u32(event.h_ptr);
+                    cols.block_ptr = F::from_canonical_u32(event.block_ptr);
+
+                    cols.is_real = F::one();
+                    cols.is_first = F::from_bool(j == 0);
+                    cols.is_final = F::from_bool(is_final);
+                    cols.is_step = F::from_bool(!is_final);
+                    if !is_final {
+                        cols.is_round_n[round] = F::one();
+                    }
+                    cols.is_index_n[j % ROUND_SIZE] = F::one();
+
+                    cols.h = event.h.map(Word::from);
+                    cols.block = event.block.map(Word::from);
+                    cols.left = left.map(Word::from);
+                    cols.right = right.map(Word::from);
+                }
+
+                // Read the inputs in the first row.
+                if j == 0 {
+                    for i in 0..cols.h_reads.len() {
+                        cols.h_reads[i]
+                            .populate(event.h_read_records[i], &mut new_byte_lookup_events);
+                    }
+                    for i in 0..cols.block_reads.len() {
+                        cols.block_reads[i]
+                            .populate(event.block_read_records[i], &mut new_byte_lookup_events);
+                    }
+                }
+
+                if is_final {
+                    // Compute the new state and write it, checking it against the reference
+                    // implementation.
+                    for i in 0..STATE_SIZE {
+                        let h_add_left = cols.h_add_left[i].populate(
+                            output,
+                            shard,
+                            event.h[(i + 1) % STATE_SIZE],
+                            left[(i + 2) % STATE_SIZE],
+                        );
+                        cols.h_add_left_add_right[i].populate(
+                            output,
+                            shard,
+                            h_add_left,
+                            right[(i + 3) % STATE_SIZE],
+                        );
+                    }
+                    for i in 0..cols.h_writes.len() {
+                        cols.h_writes[i]
+                            .populate(event.h_write_records[i], &mut new_byte_lookup_events);
+                    }
+                    let mut expected = event.h;
+                    ripemd160_compress(&mut expected, &event.block);
+                    debug_assert_eq!(ripemd160_finalize(&event.h, &left, &right), expected);
+                } else {
+                    left = cols.left_step.populate(
+                        output,
+                        shard,
+                        left,
+                        round,
+                        event.block[R_LEFT[j]],
+                        K_LEFT[round],
+                        S_LEFT[j],
+                    );
+                    right = cols.right_step.populate(
+                        output,
+                        shard,
+                        right,
+                        NUM_ROUNDS - 1 - round,
+                        event.block[R_RIGHT[j]],
+                        K_RIGHT[round],
+                        S_RIGHT[j],
+                    );
+                }
+
+                rows.push(row);
+            }
+        }
+
+        output.add_byte_lookup_events(new_byte_lookup_events);
+
+        // Pad with at least one row, since the table must end in a non-real row.
+        let nb_rows = rows.len();
+        let mut padded_nb_rows = (nb_rows + 1).next_power_of_two();
+        if padded_nb_rows < 4 {
+            padded_nb_rows = 4;
+        }
+        rows.resize(padded_nb_rows, [F::zero(); NUM_RIPEMD160_COMPRESS_COLS]);
+
+        // Convert the trace to a row major matrix.
+        RowMajorMatrix::new(
+            rows.into_iter().flatten().collect::<Vec<_>>(),
+            NUM_RIPEMD160_COMPRESS_COLS,
+        )
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        !shard.ripemd160_compress_events.is_empty()
+    }
+}
@@ -0,0 +1,585 @@
+use core::borrow::Borrow;
+
+use p3_air::{Air, AirBuilder, BaseAir};
+use p3_field::AbstractField;
+use p3_matrix::Matrix;
+
+use super::columns::{Sha512CompressCols, NUM_SHA512_COMPRESS_COLS};
+use super::{Sha512CompressChip, SHA512_COMPRESS_K};
+use crate::air::{BaseAirBuilder, SP1AirBuilder};
+use crate::memory::MemoryCols;
+use crate::operations::uint64::{
+    u64_from_words, u64_to_bytes, AddU64Operation, AndU64Operation, FixedRotateRightU64Operation,
+    NotU64Operation, XorU64Operation, U64_SIZE,
+};
+use crate::runtime::SyscallCode;
+
+impl<F> BaseAir<F> for Sha512CompressChip {
+    fn width(&self) -> usize {
+        NUM_SHA512_COMPRESS_COLS
+    }
+}
+
+impl<AB> Air<AB> for Sha512CompressChip
+where
+    AB: SP1AirBuilder,
+{
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let (local, next) = (main.row_slice(0), main.row_slice(1));
+        let local: &Sha512CompressCols<AB::Var> = (*local).borrow();
+        let next: &Sha512CompressCols<AB::Var> = (*next).borrow();
+
+        self.eval_control_flow_flags(builder, local, next);
+
+        self.eval_memory(builder, local);
+
+        self.eval_compression_ops(builder, local, next);
+
+        self.eval_finalize_ops(builder, local);
+
+        builder.assert_eq(
+            local.start,
+            local.is_real * local.octet[0] * local.octet_num[0],
+        );
+        builder.receive_syscall(
+            local.shard,
+            local.clk,
+            AB::F::from_canonical_u32(SyscallCode::SHA512_COMPRESS.syscall_id()),
+            local.w_ptr,
+            local.h_ptr,
+            local.start,
+        );
+    }
+}
+
+impl Sha512CompressChip {
+    fn eval_control_flow_flags<AB: SP1AirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &Sha512CompressCols<AB::Var>,
+        next: &Sha512CompressCols<AB::Var>,
+    ) {
+        // Verify that all of the octet columns are bool.
+        for i in 0..8 {
+            builder.assert_bool(local.octet[i]);
+        }
+
+        // Verify that exactly one of the octet columns is true.
+        let mut octet_sum = AB::Expr::zero();
+        for i in 0..8 {
+            octet_sum += local.octet[i].into();
+        }
+        builder.when(local.is_real).assert_one(octet_sum);
+
+        // Verify that the first row's octet value is correct.
+        builder
+            .when_first_row()
+            .when(local.is_real)
+            .assert_one(local.octet[0]);
+
+        // Verify correct transition for octet column.
+        for i in 0..8 {
+            builder
+                .when_transition()
+                .when(next.is_real)
+                .when(local.octet[i])
+                .assert_one(next.octet[(i + 1) % 8])
+        }
+
+        // Verify that all of the octet_num columns are bool.
+        for i in 0..12 {
+            builder.assert_bool(local.octet_num[i]);
+        }
+
+        // Verify that exactly one of the octet_num columns is true.
+        let mut octet_num_sum = AB::Expr::zero();
+        for i in 0..12 {
+            octet_num_sum += local.octet_num[i].into();
+        }
+        builder.when(local.is_real).assert_one(octet_num_sum);
+
+        // The first row should have octet_num[0] = 1 if it's real.
+        builder
+            .when_first_row()
+            .when(local.is_real)
+            .assert_one(local.octet_num[0]);
+
+        // If current row is not last of an octet and next row is real, octet_num should be the same.
+        for i in 0..12 {
+            builder
+                .when_transition()
+                .when(next.is_real)
+                .when_not(local.octet[7])
+                .assert_eq(local.octet_num[i], next.octet_num[i]);
+        }
+
+        // If current row is last of an octet and next row is real, octet_num should rotate by 1.
+        for i in 0..12 {
+            builder
+                .when_transition()
+                .when(next.is_real)
+                .when(local.octet[7])
+                .assert_eq(local.octet_num[i], next.octet_num[(i + 1) % 12]);
+        }
+
+        // Constrain A-H columns
+        let vars = [
+            local.a, local.b, local.c, local.d, local.e, local.f, local.g, local.h,
+        ];
+        let next_vars = [
+            next.a, next.b, next.c, next.d, next.e, next.f, next.g, next.h,
+        ];
+        let mem_value = u64_from_words(local.mem[0].value(), local.mem[1].value());
+        for (i, var) in vars.iter().enumerate() {
+            // For all initialize and finalize cycles, A-H should be the same in the next row. The
+            // last cycle is an exception since the next row must be a new 96-cycle loop or nonreal.
+            builder
+                .when_transition()
+                .when(local.octet_num[0] + local.octet_num[11] * (AB::Expr::one() - local.octet[7]))
+                .assert_all_eq(*var, next_vars[i]);
+
+            // When column is read from memory during init, is should be equal to the memory value.
+            builder
+                .when_transition()
+                .when(local.octet_num[0] * local.octet[i])
+                .assert_all_eq(*var, mem_value);
+        }
+
+        // Assert that the is_compression flag is correct.
+        let mut is_compression = AB::Expr::zero();
+        for i in 1..11 {
+            is_compression += local.octet_num[i].into();
+        }
+        builder.assert_eq(local.is_compression, is_compression);
+
+        builder.assert_eq(
+            local.is_last_row.into(),
+            local.octet[7] * local.octet_num[11],
+        );
+
+        // If this row is real and not the last cycle, then next row should have same inputs
+        builder
+            .when_transition()
+            .when(local.is_real)
+            .when_not(local.is_last_row)
+            .assert_eq(local.shard, next.shard);
+        builder
+            .when_transition()
+            .when(local.is_real)
+            .when_not(local.is_last_row)
+            .assert_eq(local.clk, next.clk);
+        builder
+            .when_transition()
+            .when(local.is_real)
+            .when_not(local.is_last_row)
+            .assert_eq(local.w_ptr, next.w_ptr);
+        builder
+            .when_transition()
+            .when(local.is_real)
+            .when_not(local.is_last_row)
+            .assert_eq(local.h_ptr, next.h_ptr);
+
+        // If this row is real and not the last cycle, then next row should also be real.
+        builder
+            .when_transition()
+            .when(local.is_real)
+            .when_not(local.is_last_row)
+            .assert_one(next.is_real);
+
+        // Assert that the table ends in nonreal columns. Since each compress ecall is 96 cycles and
+        // the table is padded to a power of 2, the last row of the table should always be padding.
+        builder.when_last_row().assert_zero(local.is_real);
+    }
+
+    /// Constrains that memory address is correct and that memory is correctly written/read.
+    fn eval_memory<AB: SP1AirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &Sha512CompressCols<AB::Var>,
+    ) {
+        let is_initialize = local.octet_num[0];
+        let is_finalize = local.octet_num[11];
+        for (i, mem) in local.mem.iter().enumerate() {
+            builder.eval_memory_access(
+                local.shard,
+                local.clk + is_finalize,
+                local.mem_addr + AB::F::from_canonical_usize(i * 4),
+                mem,
+                is_initialize + local.is_compression + is_finalize,
+            );
+        }
+
+        // Calculate the current cycle_num.
+        let mut cycle_num = AB::Expr::zero();
+        for i in 0..12 {
+            cycle_num += local.octet_num[i] * AB::Expr::from_canonical_usize(i);
+        }
+
+        // Calculate the current step of the cycle 8.
+        let mut cycle_step = AB::Expr::zero();
+        for i in 0..8 {
+            cycle_step += local.octet[i] * AB::Expr::from_canonical_usize(i);
+        }
+
+        // Verify correct mem address for initialize phase
+        builder.when(is_initialize).assert_eq(
+            local.mem_addr,
+            local.h_ptr + cycle_step.clone() * AB::Expr::from_canonical_u32(8),
+        );
+
+        // Verify correct mem address for compression phase
+        builder.when(local.is_compression).assert_eq(
+            local.mem_addr,
+            local.w_ptr
+                + (((cycle_num - AB::Expr::one()) * AB::Expr::from_canonical_u32(8))
+                    + cycle_step.clone())
+                    * AB::Expr::from_canonical_u32(8),
+        );
+
+        // Verify correct mem address for finalize phase
+        builder.when(is_finalize).assert_eq(
+            local.mem_addr,
+            local.h_ptr + cycle_step.clone() * AB::Expr::from_canonical_u32(8),
+        );
+
+        // In the initialize phase, verify that local.a, local.b, ... is correctly read from memory
+        // and does not change
+        let vars = [
+            local.a, local.b, local.c, local.d, local.e, local.f, local.g, local.h,
+        ];
+        let mem_prev_value = u64_from_words(local.mem[0].prev_value(), local.mem[1].prev_value());
+        let mem_value = u64_from_words(local.mem[0].value(), local.mem[1].value());
+        for (i, var) in vars.iter().enumerate() {
+            builder
+                .when(is_initialize)
+                .when(local.octet[i])
+                .assert_all_eq(*var, mem_prev_value);
+            builder
+                .when(is_initialize)
+                .when(local.octet[i])
+                .assert_all_eq(*var, mem_value);
+        }
+
+        // During compression, verify that memory is read only and does not change.
+        builder
+            .when(local.is_compression)
+            .assert_all_eq(mem_prev_value, mem_value);
+
+        // In the finalize phase, verify that the correct value is written to memory.
+        builder
+            .when(is_finalize)
+            .assert_all_eq(mem_value, local.finalize_add.value);
+    }
+
+    fn eval_compression_ops<AB: SP1AirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &Sha512CompressCols<AB::Var>,
+        next: &Sha512CompressCols<AB::Var>,
+    ) {
+        // Constrain k column which loops over 80 constant values.
+        for i in 0..80 {
+            let octet_num = i / 8;
+            let inner_index = i % 8;
+            builder
+                .when(local.octet_num[octet_num + 1] * local.octet[inner_index])
+                .assert_all_eq(local.k, u64_to_bytes::<AB::F>(SHA512_COMPRESS_K[i]));
+        }
+
+        // S1 := (e rightrotate 14) xor (e rightrotate 18) xor (e rightrotate 41).
+        // Calculate e rightrotate 14.
+        FixedRotateRightU64Operation::<AB::F>::eval(
+            builder,
+            local.e,
+            14,
+            local.e_rr_14,
+            local.shard,
+            local.is_compression,
+        );
+        // Calculate e rightrotate 18.
+        FixedRotateRightU64Operation::<AB::F>::eval(
+            builder,
+            local.e,
+            18,
+            local.e_rr_18,
+            local.shard,
+            local.is_compression,
+        );
+        // Calculate e rightrotate 41.
+        FixedRotateRightU64Operation::<AB::F>::eval(
+            builder,
+            local.e,
+            41,
+            local.e_rr_41,
+            local.shard,
+            local.is_compression,
+        );
+        // Calculate (e rightrotate 14) xor (e rightrotate 18).
+        XorU64Operation::<AB::F>::eval(
+            builder,
+            local.e_rr_14.value,
+            local.e_rr_18.value,
+            local.s1_intermediate,
+            local.shard,
+            local.is_compression,
+        );
+        // Calculate S1 := ((e rightrotate 14) xor (e rightrotate 18)) xor (e rightrotate 41).
+        XorU64Operation::<AB::F>::eval(
+            builder,
+            local.s1_intermediate.value,
+            local.e_rr_41.value,
+            local.s1,
+            local.shard,
+            local.is_compression,
+        );
+
+        // Calculate ch := (e and f) xor ((not e) and g).
+        // Calculate e and f.
+        AndU64Operation::<AB::F>::eval(
+            builder,
+            local.e,
+            local.f,
+            local.e_and_f,
+            local.shard,
+            local.is_compression,
+        );
+        // Calculate not e.
+        NotU64Operation::<AB::F>::eval(
+            builder,
+            local.e,
+            local.e_not,
+            local.shard,
+            local.is_compression,
+        );
+        // Calculate (not e) and g.
+        AndU64Operation::<AB::F>::eval(
+            builder,
+            local.e_not.value,
+            local.g,
+            local.e_not_and_g,
+            local.shard,
+            local.is_compression,
+        );
+        // Calculate ch := (e and f) xor ((not e) and g).
+        XorU64Operation::<AB::F>::eval(
+            builder,
+            local.e_and_f.value,
+            local.e_not_and_g.value,
+            local.ch,
+            local.shard,
+            local.is_compression,
+        );
+
+        // Calculate temp1 := h + S1 + ch + k[i] + w[i].
+        let w_i = u64_from_words(local.mem[0].value(), local.mem[1].value());
+        AddU64Operation::<AB::F>::eval(
+            builder,
+            &[local.h, local.s1.value, local.ch.value, local.k, w_i],
+            local.shard,
+            local.is_compression,
+            local.temp1,
+        );
+
+        // Calculate S0 := (a rightrotate 28) xor (a rightrotate 34) xor (a rightrotate 39).
+        // Calculate a rightrotate 28.
+        FixedRotateRightU64Operation::<AB::F>::eval(
+            builder,
+            local.a,
+            28,
+            local.a_rr_28,
+            local.shard,
+            local.is_compression,
+        );
+        // Calculate a rightrotate 34.
+        FixedRotateRightU64Operation::<AB::F>::eval(
+            builder,
+            local.a,
+            34,
+            local.a_rr_34,
+            local.shard,
+            local.is_compression,
+        );
+        // Calculate a rightrotate 39.
+        FixedRotateRightU64Operation::<AB::F>::eval(
+            builder,
+            local.a,
+            39,
+            local.a_rr_39,
+            local.shard,
+            local.is_compression,
+        );
+        // Calculate (a rightrotate 28) xor (a rightrotate 34).
+        XorU64Operation::<AB::F>::eval(
+            builder,
+            local.a_rr_28.value,
+            local.a_rr_34.value,
+            local.s0_intermediate,
+            local.shard,
+            local.is_compression,
+        );
+        // Calculate S0 := ((a rightrotate 28) xor (a rightrotate 34)) xor (a rightrotate 39).
+        XorU64Operation::<AB::F>::eval(
+            builder,
+            local.s0_intermediate.value,
+            local.a_rr_39.value,
+            local.s0,
+            local.shard,
+            local.is_compression,
+        );
+
+        // Calculate maj := (a and b) xor (a and c) xor (b and c).
+        // Calculate a and b.
+        AndU64Operation::<AB::F>::eval(
+            builder,
+            local.a,
+            local.b,
+            local.a_and_b,
+            local.shard,
+            local.is_compression,
+        );
+        // Calculate a and c.
+        AndU64Operation::<AB::F>::eval(
+            builder,
+            local.a,
+            local.c,
+            local.a_and_c,
+            local.shard,
+            local.is_compression,
+        );
+        // Calculate b and c.
+        AndU64Operation::<AB::F>::eval(
+            builder,
+            local.b,
+            local.c,
+            local.b_and_c,
+            local.shard,
+            local.is_compression,
+        );
+        // Calculate (a and b) xor (a and c).
+        XorU64Operation::<AB::F>::eval(
+            builder,
+            local.a_and_b.value,
+            local.a_and_c.value,
+            local.maj_intermediate,
+            local.shard,
+            local.is_compression,
+        );
+        // Calculate maj := ((a and b) xor (a and c)) xor (b and c).
+        XorU64Operation::<AB::F>::eval(
+            builder,
+            local.maj_intermediate.value,
+            local.b_and_c.value,
+            local.maj,
+            local.shard,
+            local.is_compression,
+        );
+
+        // Calculate temp2 := s0 + maj.
+        AddU64Operation::<AB::F>::eval(
+            builder,
+            &[local.s0.value, local.maj.value],
+            local.shard,
+            local.is_compression,
+            local.temp2,
+        );
+
+        // Calculate d + temp1 for the new value of e.
+        AddU64Operation::<AB::F>::eval(
+            builder,
+            &[local.d, local.temp1.value],
+            local.shard,
+            local.is_compression,
+            local.d_add_temp1,
+        );
+
+        // Calculate temp1 + temp2 for the new value of a.
+        AddU64Operation::<AB::F>::eval(
+            builder,
+            &[local.temp1.value, local.temp2.value],
+            local.shard,
+            local.is_compression,
+            local.temp1_add_temp2,
+        );
+
+        // h := g
+        // g := f
+        // f := e
+        // e := d + temp1
+        // d := c
+        // c := b
+        // b := a
+        // a := temp1 + temp2
+        builder
+            .when_transition()
+            .when(local.is_compression)
+            .assert_all_eq(next.h, local.g);
+        builder
+            .when_transition()
+            .when(local.is_compression)
+            .assert_all_eq(next.g, local.f);
+        builder
+            .when_transition()
+            .when(local.is_compression)
+            .assert_all_eq(next.f, local.e);
+        builder
+            .when_transition()
+            .when(local.is_compression)
+            .assert_all_eq(next.e, local.d_add_temp1.value);
+        builder
+            .when_transition()
+            .when(local.is_compression)
+            .assert_all_eq(next.d, local.c);
+        builder
+            .when_transition()
+            .when(local.is_compression)
+            .assert_all_eq(next.c, local.b);
+        builder
+            .when_transition()
+            .when(local.is_compression)
+            .assert_all_eq(next.b, local.a);
+        builder
+            .when_transition()
+            .when(local.is_compression)
+            .assert_all_eq(next.a, local.temp1_add_temp2.value);
+    }
+
+    fn eval_finalize_ops<AB: SP1AirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &Sha512CompressCols<AB::Var>,
+    ) {
+        let is_finalize = local.octet_num[11];
+        // In the finalize phase, need to execute h[0] + a, h[1] + b, ..., h[7] + h, for each of the
+        // phase's 8 rows.
+        // We can get the needed operand (a,b,c,...,h) by doing an inner product between octet and
+        // [a,b,c,...,h] which will act as a selector.
+        let add_operands = [
+            local.a, local.b, local.c, local.d, local.e, local.f, local.g, local.h,
+        ];
+        let mut filtered_operand: [AB::Expr; U64_SIZE] = core::array::from_fn(|_| AB::Expr::zero());
+        for (i, operand) in local.octet.iter().zip(add_operands.iter()) {
+            for j in 0..U64_SIZE {
+                filtered_operand[j] += *i * operand[j];
+            }
+        }
+
+        for j in 0..U64_SIZE {
+            builder
+                .when(is_finalize)
+                .assert_eq(filtered_operand[j].clone(), local.finalized_operand[j]);
+        }
+
+        // finalize_add.result = h[i] + finalized_operand
+        let mem_prev_value = u64_from_words(local.mem[0].prev_value(), local.mem[1].prev_value());
+        AddU64Operation::<AB::F>::eval(
+            builder,
+            &[mem_prev_value, local.finalized_operand],
+            local.shard,
+            is_finalize,
+            local.finalize_add,
+        );
+
+        // Memory write is constrained in constrain_memory.
+    }
+}
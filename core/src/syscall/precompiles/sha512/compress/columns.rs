@@ -0,0 +1,107 @@
+use std::mem::size_of;
+
+use sp1_derive::AlignedBorrow;
+
+use crate::memory::MemoryReadWriteCols;
+use crate::operations::uint64::AddU64Operation;
+use crate::operations::uint64::AndU64Operation;
+use crate::operations::uint64::FixedRotateRightU64Operation;
+use crate::operations::uint64::NotU64Operation;
+use crate::operations::uint64::XorU64Operation;
+use crate::operations::uint64::U64_SIZE;
+
+pub const NUM_SHA512_COMPRESS_COLS: usize = size_of::<Sha512CompressCols<u8>>();
+
+/// A set of columns needed to compute the SHA-512 compression function.
+///
+/// Each sha512 compress syscall is processed over 96 rows, split into 12 octets. The first octet
+/// is for initialization, the next 10 octets are for compression, and the last octet is for
+/// finalize. During init, the columns are initialized with the input values, one u64 at a time.
+/// During each compression cycle, one iteration of sha512 compress is computed. During finalize,
+/// the columns are combined and written back to memory.
+#[derive(AlignedBorrow, Default, Debug, Clone, Copy)]
+#[repr(C)]
+pub struct Sha512CompressCols<T> {
+    /// Inputs.
+    pub shard: T,
+    pub clk: T,
+    pub w_ptr: T,
+    pub h_ptr: T,
+
+    pub start: T,
+
+    /// Which cycle within the octet we are currently processing.
+    pub octet: [T; 8],
+
+    /// This will specify which octet we are currently processing.
+    ///  - The first octet is for initialize.
+    ///  - The next 10 octets are for compress.
+    ///  - The last octet is for finalize.
+    pub octet_num: [T; 12],
+
+    /// Memory access to the low and high words of a u64. During init and compression, this is
+    /// read only. During finalize, this is used to write the result into memory.
+    pub mem: [MemoryReadWriteCols<T>; 2],
+    /// Current memory address being written/read. During init and finalize, this is A-H. During
+    /// compression, this is w[i] being read only.
+    pub mem_addr: T,
+
+    pub a: [T; U64_SIZE],
+    pub b: [T; U64_SIZE],
+    pub c: [T; U64_SIZE],
+    pub d: [T; U64_SIZE],
+    pub e: [T; U64_SIZE],
+    pub f: [T; U64_SIZE],
+    pub g: [T; U64_SIZE],
+    pub h: [T; U64_SIZE],
+
+    /// Current value of K[i]. This is a constant array that loops around every 80 iterations.
+    pub k: [T; U64_SIZE],
+
+    pub e_rr_14: FixedRotateRightU64Operation<T>,
+    pub e_rr_18: FixedRotateRightU64Operation<T>,
+    pub e_rr_41: FixedRotateRightU64Operation<T>,
+    pub s1_intermediate: XorU64Operation<T>,
+    /// `S1 := (e rightrotate 14) xor (e rightrotate 18) xor (e rightrotate 41)`.
+    pub s1: XorU64Operation<T>,
+
+    pub e_and_f: AndU64Operation<T>,
+    pub e_not: NotU64Operation<T>,
+    pub e_not_and_g: AndU64Operation<T>,
+    /// `ch := (e and f) xor ((not e) and g)`.
+    pub ch: XorU64Operation<T>,
+
+    /// `temp1 := h + S1 + ch + k[i] + w[i]`.
+    pub temp1: AddU64Operation<T>,
+
+    pub a_rr_28: FixedRotateRightU64Operation<T>,
+    pub a_rr_34: FixedRotateRightU64Operation<T>,
+    pub a_rr_39: FixedRotateRightU64Operation<T>,
+    pub s0_intermediate: XorU64Operation<T>,
+    /// `S0 := (a rightrotate 28) xor (a rightrotate 34) xor (a rightrotate 39)`.
+    pub s0: XorU64Operation<T>,
+
+    pub a_and_b: AndU64Operation<T>,
+    pub a_and_c: AndU64Operation<T>,
+    pub b_and_c: AndU64Operation<T>,
+    pub maj_intermediate: XorU64Operation<T>,
+    /// `maj := (a and b) xor (a and c) xor (b and c)`.
+    pub maj: XorU64Operation<T>,
+
+    /// `temp2 := S0 + maj`.
+    pub temp2: AddU64Operation<T>,
+
+    /// The next value of `e` is `d + temp1`.
+    pub d_add_temp1: AddU64Operation<T>,
+    /// The next value of `a` is `temp1 + temp2`.
+    pub temp1_add_temp2: AddU64Operation<T>,
+
+    /// During finalize, this is one of a-h and is being written into `mem`.
+    pub finalized_operand: [T; U64_SIZE],
+    pub finalize_add: AddU64Operation<T>,
+
+    pub is_compression: T,
+    pub is_last_row: T,
+
+    pub is_real: T,
+}
@@ -0,0 +1,230 @@
+use std::borrow::BorrowMut;
+
+use p3_field::PrimeField32;
+use p3_matrix::dense::RowMajorMatrix;
+
+use super::{
+    columns::{Sha512CompressCols, NUM_SHA512_COMPRESS_COLS},
+    Sha512CompressChip, SHA512_COMPRESS_K,
+};
+use crate::{
+    air::MachineAir,
+    operations::uint64::u64_to_bytes,
+    runtime::{ExecutionRecord, Program},
+    utils::pad_rows,
+};
+
+impl<F: PrimeField32> MachineAir<F> for Sha512CompressChip {
+    type Record = ExecutionRecord;
+
+    type Program = Program;
+
+    fn name(&self) -> String {
+        "Sha512Compress".to_string()
+    }
+
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        output: &mut ExecutionRecord,
+    ) -> RowMajorMatrix<F> {
+        let mut rows = Vec::new();
+
+        let mut new_byte_lookup_events = Vec::new();
+        for i in 0..input.sha512_compress_events.len() {
+            let mut event = input.sha512_compress_events[i].clone();
+            let shard = event.shard;
+
+            let og_h = event.h;
+
+            let mut octet_num_idx = 0;
+
+            // Load a, b, c, d, e, f, g, h.
+            for j in 0..8usize {
+                let mut row = [F::zero(); NUM_SHA512_COMPRESS_COLS];
+                let cols: &mut Sha512CompressCols<F> = row.as_mut_slice().borrow_mut();
+
+                cols.shard = F::from_canonical_u32(event.shard);
+                cols.clk = F::from_canonical_u32(event.clk);
+                cols.w_ptr = F::from_canonical_u32(event.w_ptr);
+                cols.h_ptr = F::from_canonical_u32(event.h_ptr);
+
+                cols.octet[j] = F::one();
+                cols.octet_num[octet_num_idx] = F::one();
+
+                for k in 0..2 {
+                    cols.mem[k]
+                        .populate_read(event.h_read_records[j][k], &mut new_byte_lookup_events);
+                }
+                cols.mem_addr = F::from_canonical_u32(event.h_ptr + (j * 8) as u32);
+
+                cols.a = u64_to_bytes(og_h[0]);
+                cols.b = u64_to_bytes(og_h[1]);
+                cols.c = u64_to_bytes(og_h[2]);
+                cols.d = u64_to_bytes(og_h[3]);
+                cols.e = u64_to_bytes(og_h[4]);
+                cols.f = u64_to_bytes(og_h[5]);
+                cols.g = u64_to_bytes(og_h[6]);
+                cols.h = u64_to_bytes(og_h[7]);
+
+                cols.is_real = F::one();
+                cols.start = cols.is_real * cols.octet_num[0] * cols.octet[0];
+                rows.push(row);
+            }
+
+            // Performs the compress operation.
+            for j in 0..80 {
+                if j % 8 == 0 {
+                    octet_num_idx += 1;
+                }
+                let mut row = [F::zero(); NUM_SHA512_COMPRESS_COLS];
+                let cols: &mut Sha512CompressCols<F> = row.as_mut_slice().borrow_mut();
+
+                cols.k = u64_to_bytes(SHA512_COMPRESS_K[j]);
+                cols.is_compression = F::one();
+                cols.octet[j % 8] = F::one();
+                cols.octet_num[octet_num_idx] = F::one();
+
+                cols.shard = F::from_canonical_u32(event.shard);
+                cols.clk = F::from_canonical_u32(event.clk);
+                cols.w_ptr = F::from_canonical_u32(event.w_ptr);
+                cols.h_ptr = F::from_canonical_u32(event.h_ptr);
+                for k in 0..2 {
+                    cols.mem[k]
+                        .populate_read(event.w_i_read_records[j][k], &mut new_byte_lookup_events);
+                }
+                cols.mem_addr = F::from_canonical_u32(event.w_ptr + (j * 8) as u32);
+
+                let a = event.h[0];
+                let b = event.h[1];
+                let c = event.h[2];
+                let d = event.h[3];
+                let e = event.h[4];
+                let f = event.h[5];
+                let g = event.h[6];
+                let h = event.h[7];
+                cols.a = u64_to_bytes(a);
+                cols.b = u64_to_bytes(b);
+                cols.c = u64_to_bytes(c);
+                cols.d = u64_to_bytes(d);
+                cols.e = u64_to_bytes(e);
+                cols.f = u64_to_bytes(f);
+                cols.g = u64_to_bytes(g);
+                cols.h = u64_to_bytes(h);
+
+                let e_rr_14 = cols.e_rr_14.populate(output, shard, e, 14);
+                let e_rr_18 = cols.e_rr_18.populate(output, shard, e, 18);
+                let e_rr_41 = cols.e_rr_41.populate(output, shard, e, 41);
+                let s1_intermediate = cols
+                    .s1_intermediate
+                    .populate(output, shard, e_rr_14, e_rr_18);
+                let s1 = cols.s1.populate(output, shard, s1_intermediate, e_rr_41);
+
+                let e_and_f = cols.e_and_f.populate(output, shard, e, f);
+                let e_not = cols.e_not.populate(output, shard, e);
+                let e_not_and_g = cols.e_not_and_g.populate(output, shard, e_not, g);
+                let ch = cols.ch.populate(output, shard, e_and_f, e_not_and_g);
+
+                let temp1 = cols.temp1.populate(
+                    output,
+                    shard,
+                    &[h, s1, ch, SHA512_COMPRESS_K[j], event.w[j]],
+                );
+
+                let a_rr_28 = cols.a_rr_28.populate(output, shard, a, 28);
+                let a_rr_34 = cols.a_rr_34.populate(output, shard, a, 34);
+                let a_rr_39 = cols.a_rr_39.populate(output, shard, a, 39);
+                let s0_intermediate = cols
+                    .s0_intermediate
+                    .populate(output, shard, a_rr_28, a_rr_34);
+                let s0 = cols.s0.populate(output, shard, s0_intermediate, a_rr_39);
+
+                let a_and_b = cols.a_and_b.populate(output, shard, a, b);
+                let a_and_c = cols.a_and_c.populate(output, shard, a, c);
+                let b_and_c = cols.b_and_c.populate(output, shard, b, c);
+                let maj_intermediate = cols
+                    .maj_intermediate
+                    .populate(output, shard, a_and_b, a_and_c);
+                let maj = cols.maj.populate(output, shard, maj_intermediate, b_and_c);
+
+                let temp2 = cols.temp2.populate(output, shard, &[s0, maj]);
+
+                let d_add_temp1 = cols.d_add_temp1.populate(output, shard, &[d, temp1]);
+                let temp1_add_temp2 = cols
+                    .temp1_add_temp2
+                    .populate(output, shard, &[temp1, temp2]);
+
+                event.h[7] = g;
+                event.h[6] = f;
+                event.h[5] = e;
+                event.h[4] = d_add_temp1;
+                event.h[3] = c;
+                event.h[2] = b;
+                event.h[1] = a;
+                event.h[0] = temp1_add_temp2;
+
+                cols.is_real = F::one();
+                cols.start = cols.is_real * cols.octet_num[0] * cols.octet[0];
+
+                rows.push(row);
+            }
+
+            let mut v: [u64; 8] = event.h;
+
+            octet_num_idx += 1;
+            // Store a, b, c, d, e, f, g, h.
+            for j in 0..8usize {
+                let mut row = [F::zero(); NUM_SHA512_COMPRESS_COLS];
+                let cols: &mut Sha512CompressCols<F> = row.as_mut_slice().borrow_mut();
+
+                cols.shard = F::from_canonical_u32(event.shard);
+                cols.clk = F::from_canonical_u32(event.clk);
+                cols.w_ptr = F::from_canonical_u32(event.w_ptr);
+                cols.h_ptr = F::from_canonical_u32(event.h_ptr);
+
+                cols.octet[j] = F::one();
+                cols.octet_num[octet_num_idx] = F::one();
+
+                cols.finalize_add
+                    .populate(output, shard, &[og_h[j], event.h[j]]);
+                for k in 0..2 {
+                    cols.mem[k]
+                        .populate_write(event.h_write_records[j][k], &mut new_byte_lookup_events);
+                }
+                cols.mem_addr = F::from_canonical_u32(event.h_ptr + (j * 8) as u32);
+
+                v[j] = event.h[j];
+                cols.a = u64_to_bytes(v[0]);
+                cols.b = u64_to_bytes(v[1]);
+                cols.c = u64_to_bytes(v[2]);
+                cols.d = u64_to_bytes(v[3]);
+                cols.e = u64_to_bytes(v[4]);
+                cols.f = u64_to_bytes(v[5]);
+                cols.g = u64_to_bytes(v[6]);
+                cols.h = u64_to_bytes(v[7]);
+
+                cols.finalized_operand = u64_to_bytes(v[j]);
+
+                cols.is_real = F::one();
+                cols.is_last_row = cols.octet[7] * cols.octet_num[11];
+                cols.start = cols.is_real * cols.octet_num[0] * cols.octet[0];
+
+                rows.push(row);
+            }
+        }
+
+        output.add_byte_lookup_events(new_byte_lookup_events);
+
+        pad_rows(&mut rows, || [F::zero(); NUM_SHA512_COMPRESS_COLS]);
+
+        // Convert the trace to a row major matrix.
+        RowMajorMatrix::new(
+            rows.into_iter().flatten().collect::<Vec<_>>(),
+            NUM_SHA512_COMPRESS_COLS,
+        )
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        !shard.sha512_compress_events.is_empty()
+    }
+}
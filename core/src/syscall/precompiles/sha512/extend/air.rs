@@ -0,0 +1,221 @@
+use p3_air::{Air, AirBuilder, BaseAir};
+use p3_field::AbstractField;
+use p3_matrix::Matrix;
+
+use super::{Sha512ExtendChip, Sha512ExtendCols, NUM_SHA512_EXTEND_COLS};
+use crate::air::{BaseAirBuilder, SP1AirBuilder};
+use crate::memory::MemoryCols;
+use crate::operations::uint64::{
+    u64_from_words, AddU64Operation, FixedRotateRightU64Operation, FixedShiftRightU64Operation,
+    XorU64Operation,
+};
+use crate::runtime::SyscallCode;
+use core::borrow::Borrow;
+
+impl<F> BaseAir<F> for Sha512ExtendChip {
+    fn width(&self) -> usize {
+        NUM_SHA512_EXTEND_COLS
+    }
+}
+
+impl<AB> Air<AB> for Sha512ExtendChip
+where
+    AB: SP1AirBuilder,
+{
+    fn eval(&self, builder: &mut AB) {
+        // Initialize columns.
+        let main = builder.main();
+        let (local, next) = (main.row_slice(0), main.row_slice(1));
+        let local: &Sha512ExtendCols<AB::Var> = (*local).borrow();
+        let next: &Sha512ExtendCols<AB::Var> = (*next).borrow();
+        let i_start = AB::F::from_canonical_u32(16);
+        let nb_bytes_in_u64 = AB::F::from_canonical_u32(8);
+
+        // Evaluate the control flags.
+        self.eval_flags(builder);
+
+        // Copy over the inputs until the result has been computed (every 64 rows).
+        builder
+            .when_transition()
+            .when_not(local.cycle_64_end.result)
+            .assert_eq(local.shard, next.shard);
+        builder
+            .when_transition()
+            .when_not(local.cycle_64_end.result)
+            .assert_eq(local.clk, next.clk);
+        builder
+            .when_transition()
+            .when_not(local.cycle_64_end.result)
+            .assert_eq(local.w_ptr, next.w_ptr);
+
+        // Read w[i-15], w[i-2], w[i-16] and w[i-7], each as its low word and then its high word.
+        let clk = local.clk + (local.i - i_start);
+        for (access, offset) in [
+            (&local.w_i_minus_15, 15),
+            (&local.w_i_minus_2, 2),
+            (&local.w_i_minus_16, 16),
+            (&local.w_i_minus_7, 7),
+        ] {
+            for (j, word) in access.iter().enumerate() {
+                builder.eval_memory_access(
+                    local.shard,
+                    clk.clone(),
+                    local.w_ptr
+                        + (local.i - AB::F::from_canonical_u32(offset)) * nb_bytes_in_u64
+                        + AB::F::from_canonical_usize(j * 4),
+                    word,
+                    local.is_real,
+                );
+            }
+        }
+
+        let w_i_minus_15 =
+            u64_from_words(local.w_i_minus_15[0].value(), local.w_i_minus_15[1].value());
+        let w_i_minus_2 =
+            u64_from_words(local.w_i_minus_2[0].value(), local.w_i_minus_2[1].value());
+        let w_i_minus_16 =
+            u64_from_words(local.w_i_minus_16[0].value(), local.w_i_minus_16[1].value());
+        let w_i_minus_7 =
+            u64_from_words(local.w_i_minus_7[0].value(), local.w_i_minus_7[1].value());
+
+        // Compute `s0`.
+        // w[i-15] rightrotate 1.
+        FixedRotateRightU64Operation::<AB::F>::eval(
+            builder,
+            w_i_minus_15,
+            1,
+            local.w_i_minus_15_rr_1,
+            local.shard,
+            local.is_real,
+        );
+        // w[i-15] rightrotate 8.
+        FixedRotateRightU64Operation::<AB::F>::eval(
+            builder,
+            w_i_minus_15,
+            8,
+            local.w_i_minus_15_rr_8,
+            local.shard,
+            local.is_real,
+        );
+        // w[i-15] rightshift 7.
+        FixedShiftRightU64Operation::<AB::F>::eval(
+            builder,
+            w_i_minus_15,
+            7,
+            local.w_i_minus_15_rs_7,
+            local.shard,
+            local.is_real,
+        );
+        // (w[i-15] rightrotate 1) xor (w[i-15] rightrotate 8)
+        XorU64Operation::<AB::F>::eval(
+            builder,
+            local.w_i_minus_15_rr_1.value,
+            local.w_i_minus_15_rr_8.value,
+            local.s0_intermediate,
+            local.shard,
+            local.is_real,
+        );
+        // s0 := (w[i-15] rightrotate 1) xor (w[i-15] rightrotate 8) xor (w[i-15] rightshift 7)
+        XorU64Operation::<AB::F>::eval(
+            builder,
+            local.s0_intermediate.value,
+            local.w_i_minus_15_rs_7.value,
+            local.s0,
+            local.shard,
+            local.is_real,
+        );
+
+        // Compute `s1`.
+        // w[i-2] rightrotate 19.
+        FixedRotateRightU64Operation::<AB::F>::eval(
+            builder,
+            w_i_minus_2,
+            19,
+            local.w_i_minus_2_rr_19,
+            local.shard,
+            local.is_real,
+        );
+        // w[i-2] rightrotate 61.
+        FixedRotateRightU64Operation::<AB::F>::eval(
+            builder,
+            w_i_minus_2,
+            61,
+            local.w_i_minus_2_rr_61,
+            local.shard,
+            local.is_real,
+        );
+        // w[i-2] rightshift 6.
+        FixedShiftRightU64Operation::<AB::F>::eval(
+            builder,
+            w_i_minus_2,
+            6,
+            local.w_i_minus_2_rs_6,
+            local.shard,
+            local.is_real,
+        );
+        // (w[i-2] rightrotate 19) xor (w[i-2] rightrotate 61)
+        XorU64Operation::<AB::F>::eval(
+            builder,
+            local.w_i_minus_2_rr_19.value,
+            local.w_i_minus_2_rr_61.value,
+            local.s1_intermediate,
+            local.shard,
+            local.is_real,
+        );
+        // s1 := (w[i-2] rightrotate 19) xor (w[i-2] rightrotate 61) xor (w[i-2] rightshift 6)
+        XorU64Operation::<AB::F>::eval(
+            builder,
+            local.s1_intermediate.value,
+            local.w_i_minus_2_rs_6.value,
+            local.s1,
+            local.shard,
+            local.is_real,
+        );
+
+        // s2 := w[i-16] + s0 + w[i-7] + s1.
+        AddU64Operation::<AB::F>::eval(
+            builder,
+            &[w_i_minus_16, local.s0.value, w_i_minus_7, local.s1.value],
+            local.shard,
+            local.is_real,
+            local.s2,
+        );
+
+        // Write `s2` to `w[i]`.
+        let w_i = u64_from_words(local.w_i[0].value(), local.w_i[1].value());
+        for i in 0..w_i.len() {
+            builder
+                .when(local.is_real)
+                .assert_eq(local.s2.value[i], w_i[i]);
+        }
+        for (j, word) in local.w_i.iter().enumerate() {
+            builder.eval_memory_access(
+                local.shard,
+                clk.clone(),
+                local.w_ptr + local.i * nb_bytes_in_u64 + AB::F::from_canonical_usize(j * 4),
+                word,
+                local.is_real,
+            );
+        }
+
+        // Receive syscall event in first row of 64-cycle.
+        builder.receive_syscall(
+            local.shard,
+            local.clk,
+            AB::F::from_canonical_u32(SyscallCode::SHA512_EXTEND.syscall_id()),
+            local.w_ptr,
+            AB::Expr::zero(),
+            local.is_start,
+        );
+
+        // If this row is real and not the last cycle, then next row should also be real.
+        builder
+            .when_transition()
+            .when(local.is_real - local.is_end)
+            .assert_one(next.is_real);
+
+        // Assert that the table ends in nonreal columns. Since a run of extend ecalls can fill a
+        // power of 2 exactly, the trace is padded with at least one row to guarantee this.
+        builder.when_last_row().assert_zero(local.is_real);
+    }
+}
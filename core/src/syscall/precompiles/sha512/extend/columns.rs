@@ -0,0 +1,71 @@
+use std::mem::size_of;
+
+use sp1_derive::AlignedBorrow;
+
+use crate::memory::MemoryReadCols;
+use crate::memory::MemoryWriteCols;
+use crate::operations::uint64::AddU64Operation;
+use crate::operations::uint64::FixedRotateRightU64Operation;
+use crate::operations::uint64::FixedShiftRightU64Operation;
+use crate::operations::uint64::XorU64Operation;
+use crate::operations::IsZeroOperation;
+
+pub const NUM_SHA512_EXTEND_COLS: usize = size_of::<Sha512ExtendCols<u8>>();
+
+#[derive(AlignedBorrow, Default, Debug, Clone, Copy)]
+#[repr(C)]
+pub struct Sha512ExtendCols<T> {
+    /// Inputs.
+    pub shard: T,
+    pub clk: T,
+    pub w_ptr: T,
+
+    /// Control flags.
+    pub i: T,
+
+    /// g^n where g is generator with order 64 and n is the row number.
+    pub cycle_64: T,
+
+    /// Checks whether current row is start of a 64-row cycle. Bool result is stored in `result`.
+    pub cycle_64_start: IsZeroOperation<T>,
+
+    /// Checks whether current row is end of a 64-row cycle. Bool result is stored in `result`.
+    pub cycle_64_end: IsZeroOperation<T>,
+
+    /// Whether the current row is the first or last of a real 64-row cycle.
+    pub is_start: T,
+    pub is_end: T,
+
+    /// Inputs to `s0`.
+    pub w_i_minus_15: [MemoryReadCols<T>; 2],
+    pub w_i_minus_15_rr_1: FixedRotateRightU64Operation<T>,
+    pub w_i_minus_15_rr_8: FixedRotateRightU64Operation<T>,
+    pub w_i_minus_15_rs_7: FixedShiftRightU64Operation<T>,
+    pub s0_intermediate: XorU64Operation<T>,
+
+    /// `s0 := (w[i-15] rightrotate 1) xor (w[i-15] rightrotate 8) xor (w[i-15] rightshift 7)`.
+    pub s0: XorU64Operation<T>,
+
+    /// Inputs to `s1`.
+    pub w_i_minus_2: [MemoryReadCols<T>; 2],
+    pub w_i_minus_2_rr_19: FixedRotateRightU64Operation<T>,
+    pub w_i_minus_2_rr_61: FixedRotateRightU64Operation<T>,
+    pub w_i_minus_2_rs_6: FixedShiftRightU64Operation<T>,
+    pub s1_intermediate: XorU64Operation<T>,
+
+    /// `s1 := (w[i-2] rightrotate 19) xor (w[i-2] rightrotate 61) xor (w[i-2] rightshift 6)`.
+    pub s1: XorU64Operation<T>,
+
+    /// Inputs to `s2`.
+    pub w_i_minus_16: [MemoryReadCols<T>; 2],
+    pub w_i_minus_7: [MemoryReadCols<T>; 2],
+
+    /// `w[i] := w[i-16] + s0 + w[i-7] + s1`.
+    pub s2: AddU64Operation<T>,
+
+    /// Result.
+    pub w_i: [MemoryWriteCols<T>; 2],
+
+    /// Selector.
+    pub is_real: T,
+}
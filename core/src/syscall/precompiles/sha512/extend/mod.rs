@@ -0,0 +1,108 @@
+mod air;
+mod columns;
+mod execute;
+mod flags;
+mod trace;
+
+pub use columns::*;
+
+use crate::runtime::{MemoryReadRecord, MemoryWriteRecord};
+use serde::{Deserialize, Serialize};
+
+/// Each u64 is accessed as its low word followed by its high word.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Sha512ExtendEvent {
+    pub shard: u32,
+    pub clk: u32,
+    pub w_ptr: u32,
+    pub w_i_minus_15_reads: Vec<[MemoryReadRecord; 2]>,
+    pub w_i_minus_2_reads: Vec<[MemoryReadRecord; 2]>,
+    pub w_i_minus_16_reads: Vec<[MemoryReadRecord; 2]>,
+    pub w_i_minus_7_reads: Vec<[MemoryReadRecord; 2]>,
+    pub w_i_writes: Vec<[MemoryWriteRecord; 2]>,
+}
+
+/// Implements the SHA-512 extension operation which loops over i = [16, 79] and modifies w[i] in
+/// each iteration. The only input to the syscall is the 8byte-aligned pointer to the w array of
+/// 80 u64s.
+///
+/// In the AIR, each SHA-512 extend syscall takes up 64 rows, where each row corresponds to a
+/// single iteration of the loop.
+#[derive(Default)]
+pub struct Sha512ExtendChip;
+
+impl Sha512ExtendChip {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+pub fn sha512_extend(w: &mut [u64]) {
+    for i in 16..80 {
+        let s0 = w[i - 15].rotate_right(1) ^ w[i - 15].rotate_right(8) ^ (w[i - 15] >> 7);
+        let s1 = w[i - 2].rotate_right(19) ^ w[i - 2].rotate_right(61) ^ (w[i - 2] >> 6);
+        w[i] = w[i - 16]
+            .wrapping_add(s0)
+            .wrapping_add(w[i - 7])
+            .wrapping_add(s1);
+    }
+}
+
+#[cfg(test)]
+pub mod extend_tests {
+    use rand::Rng;
+
+    use crate::{
+        runtime::{Instruction, Opcode, Program, Runtime, SyscallCode},
+        utils::{run_test, setup_logger},
+    };
+
+    use super::super::u64s_to_words;
+    use super::sha512_extend;
+
+    /// Writes the message schedule `w` to `w_ptr` and extends it.
+    pub fn sha512_extend_program(w_ptr: u32, w: &[u32]) -> Program {
+        let mut instructions = vec![];
+        for (i, word) in w.iter().enumerate() {
+            instructions.extend(vec![
+                Instruction::new(Opcode::ADD, 29, 0, *word, false, true),
+                Instruction::new(Opcode::ADD, 30, 0, w_ptr + i as u32 * 4, false, true),
+                Instruction::new(Opcode::SW, 29, 30, 0, false, true),
+            ]);
+        }
+        instructions.extend(vec![
+            Instruction::new(
+                Opcode::ADD,
+                5,
+                0,
+                SyscallCode::SHA512_EXTEND as u32,
+                false,
+                true,
+            ),
+            Instruction::new(Opcode::ADD, 10, 0, w_ptr, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+        ]);
+        Program::new(instructions, 0, 0)
+    }
+
+    #[test]
+    fn test_sha512_extend() {
+        setup_logger();
+        let w_ptr = 100;
+        let mut rng = rand::thread_rng();
+        let mut w = [0u64; 80];
+        for x in w.iter_mut().take(16) {
+            *x = rng.gen();
+        }
+
+        let program = sha512_extend_program(w_ptr, &u64s_to_words(&w));
+        let mut runtime = Runtime::new(program.clone());
+        runtime.run();
+
+        sha512_extend(&mut w);
+        for (i, word) in u64s_to_words(&w).iter().enumerate() {
+            assert_eq!(runtime.word(w_ptr + i as u32 * 4), *word);
+        }
+        run_test(program).unwrap();
+    }
+}
@@ -0,0 +1,32 @@
+mod compress;
+mod extend;
+
+pub use compress::*;
+pub use extend::*;
+
+use crate::runtime::{MemoryReadRecord, MemoryWriteRecord};
+use crate::syscall::precompiles::SyscallContext;
+
+/// Reads the little-endian u64 at `addr` as two words.
+fn mr_u64(rt: &mut SyscallContext, addr: u32) -> ([MemoryReadRecord; 2], u64) {
+    let (lo_record, lo) = rt.mr(addr);
+    let (hi_record, hi) = rt.mr(addr + 4);
+    ([lo_record, hi_record], lo as u64 | (hi as u64) << 32)
+}
+
+/// Writes `value` as a little-endian u64 at `addr` as two words.
+fn mw_u64(rt: &mut SyscallContext, addr: u32, value: u64) -> [MemoryWriteRecord; 2] {
+    [
+        rt.mw(addr, value as u32),
+        rt.mw(addr + 4, (value >> 32) as u32),
+    ]
+}
+
+/// Splits u64s into little-endian words, as they are laid out in memory.
+#[cfg(test)]
+fn u64s_to_words(values: &[u64]) -> Vec<u32> {
+    values
+        .iter()
+        .flat_map(|x| [*x as u32, (*x >> 32) as u32])
+        .collect()
+}
@@ -0,0 +1,390 @@
+use core::borrow::{Borrow, BorrowMut};
+use core::mem::size_of;
+
+use num::{BigUint, One, Zero};
+use p3_air::AirBuilder;
+use p3_air::{Air, BaseAir};
+use p3_field::AbstractField;
+use p3_field::PrimeField32;
+use p3_matrix::dense::RowMajorMatrix;
+use p3_matrix::Matrix;
+use sp1_derive::AlignedBorrow;
+use typenum::U32;
+
+use super::{uint256_modulus, uint256_to_words_le, U256Field, Uint256MulModEvent, WORDS_UINT256};
+use crate::air::MachineAir;
+use crate::air::Polynomial;
+use crate::air::SP1AirBuilder;
+use crate::memory::MemoryCols;
+use crate::memory::MemoryReadCols;
+use crate::memory::MemoryWriteCols;
+use crate::operations::field::field_op::FieldOpCols;
+use crate::operations::field::field_op::FieldOperation;
+use crate::operations::field::params::Limbs;
+use crate::operations::IsZeroOperation;
+use crate::runtime::ExecutionRecord;
+use crate::runtime::Program;
+use crate::runtime::Syscall;
+use crate::runtime::SyscallCode;
+use crate::syscall::precompiles::SyscallContext;
+use crate::utils::ec::field::FieldParameters;
+use crate::utils::limbs_from_prev_access;
+use crate::utils::pad_rows;
+
+pub const NUM_UINT256_MULMOD_COLS: usize = size_of::<Uint256MulModCols<u8>>();
+
+/// A set of columns to compute `x * y mod modulus` for 256-bit integers, where a zero modulus
+/// stands for `2^256`.
+///
+/// The modulus is a degree 32 polynomial in the limbs: its first 32 coefficients are the bytes of
+/// the modulus read from memory, and the last one is set iff they are all zero.
+///
+/// `x` is reduced before it is multiplied, so that both quotients fit in 256 bits for any `x` and
+/// `y`, whatever the modulus.
+#[derive(Debug, Clone, AlignedBorrow)]
+#[repr(C)]
+pub struct Uint256MulModCols<T> {
+    pub is_real: T,
+    pub shard: T,
+    pub clk: T,
+    pub x_ptr: T,
+    pub y_ptr: T,
+    pub x_access: [MemoryWriteCols<T>; WORDS_UINT256],
+    /// The reads of `y` followed by the reads of the modulus.
+    pub y_access: [MemoryReadCols<T>; 2 * WORDS_UINT256],
+    pub(crate) modulus_byte_sum: T,
+    pub(crate) modulus_is_zero: IsZeroOperation<T>,
+    pub(crate) x_reduced: FieldOpCols<T, U256Field>,
+    pub(crate) output: FieldOpCols<T, U256Field>,
+}
+
+#[derive(Default)]
+pub struct Uint256MulModChip;
+
+impl Uint256MulModChip {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Syscall for Uint256MulModChip {
+    fn execute(&self, rt: &mut SyscallContext, arg1: u32, arg2: u32) -> Option<u32> {
+        let start_clk = rt.clk;
+        let x_ptr = arg1;
+        if x_ptr % 4 != 0 {
+            panic!();
+        }
+        let y_ptr = arg2;
+        if y_ptr % 4 != 0 {
+            panic!();
+        }
+
+        let x = rt.slice_unsafe(x_ptr, WORDS_UINT256);
+        let (y_memory_records, y_and_modulus) = rt.mr_slice(y_ptr, 2 * WORDS_UINT256);
+        // When we write to x, we want the clk to be incremented because x and y could be the same.
+        rt.clk += 1;
+
+        let (y, modulus) = y_and_modulus.split_at(WORDS_UINT256);
+        let modulus_big = uint256_modulus(modulus);
+        let x_reduced = BigUint::from_slice(&x) % &modulus_big;
+        let result = uint256_to_words_le(&(x_reduced * BigUint::from_slice(y) % &modulus_big));
+        let x_memory_records = rt.mw_slice(x_ptr, &result);
+
+        let shard = rt.current_shard();
+        rt.record_mut()
+            .uint256_mulmod_events
+            .push(Uint256MulModEvent {
+                shard,
+                clk: start_clk,
+                x_ptr,
+                x,
+                y_ptr,
+                y: y.to_vec(),
+                modulus: modulus.to_vec(),
+                x_memory_records,
+                y_memory_records,
+            });
+
+        None
+    }
+
+    fn num_extra_cycles(&self) -> u32 {
+        1
+    }
+}
+
+impl<F: PrimeField32> MachineAir<F> for Uint256MulModChip {
+    type Record = ExecutionRecord;
+    type Program = Program;
+
+    fn name(&self) -> String {
+        "Uint256MulMod".to_string()
+    }
+
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        output: &mut ExecutionRecord,
+    ) -> RowMajorMatrix<F> {
+        let mut rows = Vec::new();
+
+        let mut new_byte_lookup_events = Vec::new();
+
+        for event in input.uint256_mulmod_events.iter() {
+            let mut row = [F::zero(); NUM_UINT256_MULMOD_COLS];
+            let cols: &mut Uint256MulModCols<F> = row.as_mut_slice().borrow_mut();
+
+            // Populate basic columns.
+            cols.is_real = F::one();
+            cols.shard = F::from_canonical_u32(event.shard);
+            cols.clk = F::from_canonical_u32(event.clk);
+            cols.x_ptr = F::from_canonical_u32(event.x_ptr);
+            cols.y_ptr = F::from_canonical_u32(event.y_ptr);
+
+            // Populate the modulus zero check.
+            let modulus_byte_sum = event
+                .modulus
+                .iter()
+                .flat_map(|word| word.to_le_bytes())
+                .map(u32::from)
+                .sum::<u32>();
+            cols.modulus_byte_sum = F::from_canonical_u32(modulus_byte_sum);
+            cols.modulus_is_zero.populate(modulus_byte_sum);
+
+            let modulus = uint256_modulus(&event.modulus);
+            let x_reduced = cols.x_reduced.populate_with_modulus(
+                &BigUint::from_slice(&event.x),
+                &BigUint::zero(),
+                &modulus,
+                FieldOperation::Add,
+            );
+            cols.output.populate_with_modulus(
+                &x_reduced,
+                &BigUint::from_slice(&event.y),
+                &modulus,
+                FieldOperation::Mul,
+            );
+
+            // Populate the memory access columns.
+            for i in 0..cols.y_access.len() {
+                cols.y_access[i].populate(event.y_memory_records[i], &mut new_byte_lookup_events);
+            }
+            for i in 0..cols.x_access.len() {
+                cols.x_access[i].populate(event.x_memory_records[i], &mut new_byte_lookup_events);
+            }
+
+            rows.push(row);
+        }
+        output.add_byte_lookup_events(new_byte_lookup_events);
+
+        pad_rows(&mut rows, || {
+            let mut row = [F::zero(); NUM_UINT256_MULMOD_COLS];
+            let cols: &mut Uint256MulModCols<F> = row.as_mut_slice().borrow_mut();
+            let zero = BigUint::zero();
+            let modulus = BigUint::one() << 256;
+            cols.modulus_is_zero.populate(0);
+            cols.x_reduced
+                .populate_with_modulus(&zero, &zero, &modulus, FieldOperation::Add);
+            cols.output
+                .populate_with_modulus(&zero, &zero, &modulus, FieldOperation::Mul);
+            row
+        });
+
+        // Convert the trace to a row major matrix.
+        RowMajorMatrix::new(
+            rows.into_iter().flatten().collect::<Vec<_>>(),
+            NUM_UINT256_MULMOD_COLS,
+        )
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        !shard.uint256_mulmod_events.is_empty()
+    }
+}
+
+impl<F> BaseAir<F> for Uint256MulModChip {
+    fn width(&self) -> usize {
+        NUM_UINT256_MULMOD_COLS
+    }
+}
+
+impl<AB> Air<AB> for Uint256MulModChip
+where
+    AB: SP1AirBuilder,
+{
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let row = main.row_slice(0);
+        let row: &Uint256MulModCols<AB::Var> = (*row).borrow();
+
+        let x: Limbs<AB::Var, U32> = limbs_from_prev_access(&row.x_access);
+        let y: Limbs<AB::Var, U32> = limbs_from_prev_access(&row.y_access[0..WORDS_UINT256]);
+        let modulus: Limbs<AB::Var, U32> = limbs_from_prev_access(&row.y_access[WORDS_UINT256..]);
+
+        // Check whether the modulus is zero, in which case it stands for 2^256.
+        let modulus_byte_sum = modulus
+            .0
+            .iter()
+            .fold(AB::Expr::zero(), |acc, &limb| acc + limb);
+        builder.assert_eq(row.modulus_byte_sum, modulus_byte_sum);
+        IsZeroOperation::<AB::F>::eval(
+            builder,
+            row.modulus_byte_sum.into(),
+            row.modulus_is_zero,
+            row.is_real.into(),
+        );
+
+        // The modulus polynomial is the limbs of the modulus plus `modulus_is_zero * x^32`.
+        let p_modulus: Polynomial<AB::Expr> = modulus
+            .0
+            .iter()
+            .map(|&limb| limb.into())
+            .chain(std::iter::once(row.modulus_is_zero.result.into()))
+            .collect();
+
+        let zero: Polynomial<AB::Expr> = std::iter::once(AB::Expr::zero()).collect();
+        row.x_reduced.eval_with_modulus::<AB, _, _>(
+            builder,
+            &x,
+            &zero,
+            &p_modulus,
+            FieldOperation::Add,
+        );
+        row.output.eval_with_modulus::<AB, _, _>(
+            builder,
+            &row.x_reduced.result,
+            &y,
+            &p_modulus,
+            FieldOperation::Mul,
+        );
+
+        // Constraint self.x_access.value = self.output.result. This is to ensure that x_access is
+        // updated with the new value.
+        for i in 0..U256Field::NB_LIMBS {
+            builder
+                .when(row.is_real)
+                .assert_eq(row.output.result[i], row.x_access[i / 4].value()[i % 4]);
+        }
+
+        builder.eval_memory_access_slice(
+            row.shard,
+            row.clk.into(),
+            row.y_ptr,
+            &row.y_access,
+            row.is_real,
+        );
+        builder.eval_memory_access_slice(
+            row.shard,
+            row.clk + AB::F::from_canonical_u32(1), // We write x at +1 since x, y could be the same.
+            row.x_ptr,
+            &row.x_access,
+            row.is_real,
+        );
+
+        builder.receive_syscall(
+            row.shard,
+            row.clk,
+            AB::F::from_canonical_u32(SyscallCode::UINT256_MULMOD.syscall_id()),
+            row.x_ptr,
+            row.y_ptr,
+            row.is_real,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::{BigUint, One};
+
+    use crate::{
+        runtime::{Instruction, Opcode, Program, Runtime, SyscallCode},
+        syscall::precompiles::uint256::uint256_to_words_le,
+        utils::{run_test, setup_logger},
+    };
+
+    /// Writes `x` to `x_ptr` and `y` followed by the modulus to `y_ptr`, and multiplies them
+    /// modulo the modulus.
+    pub fn uint256_mulmod_program(
+        x_ptr: u32,
+        x: &[u32],
+        y_ptr: u32,
+        y_and_modulus: &[u32],
+    ) -> Program {
+        let mut instructions = vec![];
+        for (ptr, words) in [(x_ptr, x), (y_ptr, y_and_modulus)] {
+            for (i, word) in words.iter().enumerate() {
+                instructions.extend(vec![
+                    Instruction::new(Opcode::ADD, 29, 0, *word, false, true),
+                    Instruction::new(Opcode::ADD, 30, 0, ptr + i as u32 * 4, false, true),
+                    Instruction::new(Opcode::SW, 29, 30, 0, false, true),
+                ]);
+            }
+        }
+        instructions.extend(vec![
+            Instruction::new(
+                Opcode::ADD,
+                5,
+                0,
+                SyscallCode::UINT256_MULMOD as u32,
+                false,
+                true,
+            ),
+            Instruction::new(Opcode::ADD, 10, 0, x_ptr, false, true),
+            Instruction::new(Opcode::ADD, 11, 0, y_ptr, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+        ]);
+        Program::new(instructions, 0, 0)
+    }
+
+    fn test_uint256_mulmod(x: &BigUint, y: &BigUint, modulus: &BigUint, expected: &BigUint) {
+        setup_logger();
+        let (x_ptr, y_ptr) = (1000, 2000);
+        let mut y_and_modulus = uint256_to_words_le(y);
+        y_and_modulus.extend(uint256_to_words_le(modulus));
+
+        let program = uint256_mulmod_program(x_ptr, &uint256_to_words_le(x), y_ptr, &y_and_modulus);
+        let mut runtime = Runtime::new(program.clone());
+        runtime.run();
+        for (i, word) in uint256_to_words_le(expected).iter().enumerate() {
+            assert_eq!(runtime.word(x_ptr + i as u32 * 4), *word);
+        }
+        run_test(program).unwrap();
+    }
+
+    #[test]
+    fn test_uint256_mulmod_simple() {
+        let modulus = (BigUint::one() << 255) - 19u32;
+        let x = &modulus - 2u32;
+        let y = (BigUint::one() << 200) + 12345u32;
+        let expected = (&x * &y) % &modulus;
+        test_uint256_mulmod(&x, &y, &modulus, &expected);
+    }
+
+    #[test]
+    fn test_uint256_mulmod_small_modulus() {
+        let modulus = BigUint::from(1_000_000_007u32);
+        let x = BigUint::from(999_999_999u32);
+        let y = (BigUint::one() << 256) - 1u32;
+        let expected = (&x * &y) % &modulus;
+        test_uint256_mulmod(&x, &y, &modulus, &expected);
+    }
+
+    #[test]
+    fn test_uint256_mulmod_unreduced() {
+        // Neither operand is reduced, so `x * y / modulus` does not fit in 256 bits.
+        let modulus = BigUint::from(1_000_000_007u32);
+        let x = (BigUint::one() << 256) - 1u32;
+        let y = (BigUint::one() << 256) - 5u32;
+        let expected = (&x * &y) % &modulus;
+        test_uint256_mulmod(&x, &y, &modulus, &expected);
+    }
+
+    #[test]
+    fn test_uint256_mulmod_zero_modulus() {
+        // A zero modulus stands for 2^256, so this is a wrapping multiplication.
+        let x = (BigUint::one() << 256) - 1u32;
+        let y = (BigUint::one() << 128) + 3u32;
+        let expected = (&x * &y) % (BigUint::one() << 256);
+        test_uint256_mulmod(&x, &y, &BigUint::from(0u32), &expected);
+    }
+}
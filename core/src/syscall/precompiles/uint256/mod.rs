@@ -0,0 +1,72 @@
+mod air;
+
+pub use air::*;
+
+use num::{BigUint, One, Zero};
+use serde::{Deserialize, Serialize};
+use typenum::{U32, U63};
+
+use crate::runtime::{MemoryReadRecord, MemoryWriteRecord};
+use crate::utils::ec::field::{FieldParameters, NumLimbs};
+
+/// The number of words in a 256-bit integer.
+pub const WORDS_UINT256: usize = 8;
+
+/// Parameters for 256-bit integer arithmetic with a modulus of up to `2^256`.
+///
+/// The modulus is not fixed, so `MODULUS` holds the largest supported value `2^256`, which takes
+/// one more limb than the operands. The witness is sized accordingly.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct U256Field;
+
+impl FieldParameters for U256Field {
+    const MODULUS: &'static [u8] = &[
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0, 1,
+    ];
+
+    const NB_WITNESS_LIMBS: usize = 2 * Self::NB_LIMBS - 1;
+
+    // A rough witness-offset estimate given the size of the limbs and the size of the field.
+    const WITNESS_OFFSET: usize = 1usize << 14;
+}
+
+impl NumLimbs for U256Field {
+    type Limbs = U32;
+    type Witness = U63;
+}
+
+/// Uint256 mulmod event.
+///
+/// The syscall reads `x` from `x_ptr` and `y || modulus` from `y_ptr`, and overwrites `x` with
+/// `x * y mod modulus`. A zero modulus stands for `2^256`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Uint256MulModEvent {
+    pub shard: u32,
+    pub clk: u32,
+    pub x_ptr: u32,
+    pub x: Vec<u32>,
+    pub y_ptr: u32,
+    pub y: Vec<u32>,
+    pub modulus: Vec<u32>,
+    pub x_memory_records: Vec<MemoryWriteRecord>,
+    /// The reads of `y` followed by the reads of the modulus.
+    pub y_memory_records: Vec<MemoryReadRecord>,
+}
+
+/// Interprets the words of a modulus, mapping zero to `2^256`.
+pub fn uint256_modulus(words: &[u32]) -> BigUint {
+    let modulus = BigUint::from_slice(words);
+    if modulus.is_zero() {
+        BigUint::one() << 256
+    } else {
+        modulus
+    }
+}
+
+/// Serializes a 256-bit integer as little-endian words.
+pub fn uint256_to_words_le(x: &BigUint) -> Vec<u32> {
+    let mut words = x.to_u32_digits();
+    words.resize(WORDS_UINT256, 0);
+    words
+}
@@ -1,5 +1,192 @@
 mod weierstrass_add;
+mod weierstrass_decompress;
 mod weierstrass_double;
+mod weierstrass_msm;
 
 pub use weierstrass_add::*;
+pub use weierstrass_decompress::*;
 pub use weierstrass_double::*;
+pub use weierstrass_msm::*;
+
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use num::BigUint;
+
+use crate::runtime::{Syscall, SyscallCode};
+use crate::utils::ec::weierstrass::bls12381::{bls12381_sqrt, Bls12381Parameters};
+use crate::utils::ec::weierstrass::bn254::Bn254Parameters;
+use crate::utils::ec::weierstrass::secp256k1::Secp256k1Parameters;
+use crate::utils::ec::weierstrass::secp256r1::{secp256r1_sqrt, Secp256r1Parameters};
+use crate::utils::ec::weierstrass::{SwCurve, WeierstrassParameters};
+use crate::utils::ec::EllipticCurve;
+
+/// Passes the short Weierstrass curves with precompiles to the macro `$callback`.
+///
+/// Each curve is given by its parameters, the name which prefixes its chips, and the `RiscvAir`
+/// variant and the `SyscallCode` of its add and double chips. A curve may also have a decompress
+/// chip, given with the square root function of its base field, and an MSM chip. The list is
+/// expanded into `SyscallCode`, `RiscvAir`, the [`WeierstrassSyscalls`],
+/// [`WeierstrassDecompressSyscall`] and [`WeierstrassMsmSyscall`] impls and
+/// [`insert_weierstrass_syscalls`]. Adding a curve with a base field of at most 256 bits to the
+/// prover only takes its `FieldParameters`, `EllipticCurveParameters` and `WeierstrassParameters`
+/// impls and an entry here, while guests call its syscalls through functions added to
+/// `sp1-zkvm`'s `syscalls` module and `sp1-precompiles`.
+macro_rules! weierstrass_curves {
+    ($callback:ident) => {
+        $callback! {
+            Secp256k1Parameters {
+                name: "Secp256k1",
+                add: Secp256k1Add(SECP256K1_ADD = 0x00_01_01_0A),
+                double: Secp256k1Double(SECP256K1_DOUBLE = 0x00_00_01_0B),
+                msm: Secp256k1Msm(SECP256K1_MSM = 0x44_00_01_25),
+            }
+            Secp256r1Parameters {
+                name: "Secp256r1",
+                add: Secp256r1Add(SECP256R1_ADD = 0x00_01_01_11),
+                double: Secp256r1Double(SECP256R1_DOUBLE = 0x00_00_01_12),
+                decompress: Secp256r1Decompress(
+                    SECP256R1_DECOMPRESS = 0x00_00_01_13,
+                    secp256r1_sqrt
+                ),
+            }
+            Bn254Parameters {
+                name: "Bn254",
+                add: Bn254Add(BN254_ADD = 0x00_01_01_0E),
+                double: Bn254Double(BN254_DOUBLE = 0x00_00_01_0F),
+                msm: Bn254Msm(BN254_MSM = 0x44_00_01_26),
+            }
+            Bls12381Parameters {
+                name: "Bls12381",
+                add: Bls12381Add(BLS12381_ADD = 0x00_01_01_14),
+                double: Bls12381Double(BLS12381_DOUBLE = 0x00_00_01_15),
+                decompress: Bls12381Decompress(
+                    BLS12381_DECOMPRESS = 0x00_00_01_16,
+                    bls12381_sqrt
+                ),
+            }
+        }
+    };
+}
+
+pub(crate) use weierstrass_curves;
+
+/// The add and double syscalls of a short Weierstrass curve.
+///
+/// This is all the `WeierstrassAddAssignChip` and `WeierstrassDoubleAssignChip` need to know about
+/// a curve besides its parameters: the chips are named after `NAME`, and record their events under
+/// the `ADD` and `DOUBLE` syscalls, which they receive. It is implemented for the curves of
+/// [`weierstrass_curves`].
+pub trait WeierstrassSyscalls: WeierstrassParameters {
+    /// The name of the curve, which prefixes the names of its chips.
+    const NAME: &'static str;
+
+    /// The syscall which adds two points.
+    const ADD: SyscallCode;
+
+    /// The syscall which doubles a point.
+    const DOUBLE: SyscallCode;
+}
+
+/// The decompress syscall of a short Weierstrass curve, for `WeierstrassDecompressChip`.
+pub trait WeierstrassDecompressSyscall: WeierstrassSyscalls {
+    /// The syscall which decompresses a point.
+    const DECOMPRESS: SyscallCode;
+
+    /// Computes a square root in the base field of the curve.
+    fn sqrt(n: &BigUint) -> BigUint;
+}
+
+/// The multi-scalar multiplication syscall of a short Weierstrass curve, for `WeierstrassMsmChip`.
+pub trait WeierstrassMsmSyscall: WeierstrassSyscalls {
+    /// The syscall which computes a multi-scalar multiplication.
+    const MSM: SyscallCode;
+}
+
+impl<E: WeierstrassSyscalls> WeierstrassSyscalls for SwCurve<E> {
+    const NAME: &'static str = E::NAME;
+    const ADD: SyscallCode = E::ADD;
+    const DOUBLE: SyscallCode = E::DOUBLE;
+}
+
+impl<E: WeierstrassDecompressSyscall> WeierstrassDecompressSyscall for SwCurve<E> {
+    const DECOMPRESS: SyscallCode = E::DECOMPRESS;
+
+    fn sqrt(n: &BigUint) -> BigUint {
+        E::sqrt(n)
+    }
+}
+
+impl<E: WeierstrassMsmSyscall> WeierstrassMsmSyscall for SwCurve<E> {
+    const MSM: SyscallCode = E::MSM;
+}
+
+macro_rules! impl_weierstrass_syscalls {
+    ($(
+        $params:ident {
+            name: $name:literal,
+            add: $add_variant:ident($add:ident = $add_code:literal),
+            double: $double_variant:ident($double:ident = $double_code:literal),
+            $(decompress: $decompress_variant:ident(
+                $decompress:ident = $decompress_code:literal,
+                $sqrt:ident
+            ),)?
+            $(msm: $msm_variant:ident($msm:ident = $msm_code:literal),)?
+        }
+    )*) => {
+        $(
+            impl WeierstrassSyscalls for $params {
+                const NAME: &'static str = $name;
+                const ADD: SyscallCode = SyscallCode::$add;
+                const DOUBLE: SyscallCode = SyscallCode::$double;
+            }
+
+            $(
+                impl WeierstrassDecompressSyscall for $params {
+                    const DECOMPRESS: SyscallCode = SyscallCode::$decompress;
+
+                    fn sqrt(n: &BigUint) -> BigUint {
+                        $sqrt(n)
+                    }
+                }
+            )?
+
+            $(
+                impl WeierstrassMsmSyscall for $params {
+                    const MSM: SyscallCode = SyscallCode::$msm;
+                }
+            )?
+        )*
+
+        /// Registers the syscalls of the curves of [`weierstrass_curves`].
+        pub fn insert_weierstrass_syscalls(
+            syscall_map: &mut HashMap<SyscallCode, Rc<dyn Syscall>>,
+        ) {
+            $(
+                insert_curve_syscalls::<SwCurve<$params>>(syscall_map);
+                $(
+                    syscall_map.insert(
+                        SyscallCode::$decompress,
+                        Rc::new(WeierstrassDecompressChip::<SwCurve<$params>>::new()),
+                    );
+                )?
+                $(
+                    syscall_map.insert(
+                        SyscallCode::$msm,
+                        Rc::new(WeierstrassMsmChip::<SwCurve<$params>>::new()),
+                    );
+                )?
+            )*
+        }
+    };
+}
+
+weierstrass_curves!(impl_weierstrass_syscalls);
+
+/// Registers the add and double syscalls of the curve `E`.
+fn insert_curve_syscalls<E: EllipticCurve + WeierstrassSyscalls>(
+    syscall_map: &mut HashMap<SyscallCode, Rc<dyn Syscall>>,
+) {
+    syscall_map.insert(E::ADD, Rc::new(WeierstrassAddAssignChip::<E>::new()));
+    syscall_map.insert(E::DOUBLE, Rc::new(WeierstrassDoubleAssignChip::<E>::new()));
+}
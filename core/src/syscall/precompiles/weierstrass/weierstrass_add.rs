@@ -15,6 +15,7 @@ use p3_matrix::Matrix;
 use sp1_derive::AlignedBorrow;
 use typenum::Unsigned;
 
+use super::WeierstrassSyscalls;
 use crate::air::MachineAir;
 use crate::air::SP1AirBuilder;
 use crate::memory::MemoryCols;
@@ -26,15 +27,12 @@ use crate::operations::field::params::Limbs;
 use crate::runtime::ExecutionRecord;
 use crate::runtime::Program;
 use crate::runtime::Syscall;
-use crate::runtime::SyscallCode;
 use crate::syscall::precompiles::create_ec_add_event;
 use crate::syscall::precompiles::SyscallContext;
 use crate::utils::ec::field::FieldParameters;
 use crate::utils::ec::field::NumLimbs;
 use crate::utils::ec::field::NumWords;
-use crate::utils::ec::weierstrass::WeierstrassParameters;
 use crate::utils::ec::AffinePoint;
-use crate::utils::ec::CurveType;
 use crate::utils::ec::EllipticCurve;
 use crate::utils::limbs_from_prev_access;
 use crate::utils::pad_rows;
@@ -73,14 +71,14 @@ pub struct WeierstrassAddAssignChip<E> {
     _marker: PhantomData<E>,
 }
 
-impl<E: EllipticCurve> Syscall for WeierstrassAddAssignChip<E> {
+impl<E: EllipticCurve + WeierstrassSyscalls> Syscall for WeierstrassAddAssignChip<E> {
     fn execute(&self, rt: &mut SyscallContext, arg1: u32, arg2: u32) -> Option<u32> {
         let event = create_ec_add_event::<E>(rt, arg1, arg2);
-        match E::CURVE_TYPE {
-            CurveType::Secp256k1 => rt.record_mut().secp256k1_add_events.push(event),
-            CurveType::Bn254 => rt.record_mut().bn254_add_events.push(event),
-            _ => panic!("Unsupported curve"),
-        }
+        rt.record_mut()
+            .weierstrass_add_events
+            .entry(E::ADD)
+            .or_default()
+            .push(event);
         None
     }
 
@@ -142,7 +140,7 @@ impl<E: EllipticCurve> WeierstrassAddAssignChip<E> {
     }
 }
 
-impl<F: PrimeField32, E: EllipticCurve + WeierstrassParameters> MachineAir<F>
+impl<F: PrimeField32, E: EllipticCurve + WeierstrassSyscalls> MachineAir<F>
     for WeierstrassAddAssignChip<E>
 where
     [(); num_weierstrass_add_cols::<E::BaseField>()]:,
@@ -151,11 +149,7 @@ where
     type Program = Program;
 
     fn name(&self) -> String {
-        match E::CURVE_TYPE {
-            CurveType::Secp256k1 => "Secp256k1AddAssign".to_string(),
-            CurveType::Bn254 => "Bn254AddAssign".to_string(),
-            _ => panic!("Unsupported curve"),
-        }
+        format!("{}AddAssign", E::NAME)
     }
 
     fn generate_trace(
@@ -163,12 +157,11 @@ where
         input: &ExecutionRecord,
         output: &mut ExecutionRecord,
     ) -> RowMajorMatrix<F> {
-        // collects the events based on the curve type.
-        let events = match E::CURVE_TYPE {
-            CurveType::Secp256k1 => &input.secp256k1_add_events,
-            CurveType::Bn254 => &input.bn254_add_events,
-            _ => panic!("Unsupported curve"),
-        };
+        // collects the events of the curve.
+        let events = input
+            .weierstrass_add_events
+            .get(&E::ADD)
+            .map_or(&[][..], |events| events.as_slice());
 
         let mut rows = Vec::new();
 
@@ -226,11 +219,10 @@ where
     }
 
     fn included(&self, shard: &Self::Record) -> bool {
-        match E::CURVE_TYPE {
-            CurveType::Secp256k1 => !shard.secp256k1_add_events.is_empty(),
-            CurveType::Bn254 => !shard.bn254_add_events.is_empty(),
-            _ => panic!("Unsupported curve"),
-        }
+        shard
+            .weierstrass_add_events
+            .get(&E::ADD)
+            .is_some_and(|events| !events.is_empty())
     }
 }
 
@@ -240,7 +232,7 @@ impl<F, E: EllipticCurve> BaseAir<F> for WeierstrassAddAssignChip<E> {
     }
 }
 
-impl<AB, E: EllipticCurve> Air<AB> for WeierstrassAddAssignChip<E>
+impl<AB, E: EllipticCurve + WeierstrassSyscalls> Air<AB> for WeierstrassAddAssignChip<E>
 where
     AB: SP1AirBuilder,
     Limbs<AB::Var, <E::BaseField as NumLimbs>::Limbs>: Copy,
@@ -320,9 +312,10 @@ where
             builder
                 .when(row.is_real)
                 .assert_eq(row.x3_ins.result[i], row.p_access[i / 4].value()[i % 4]);
-            builder
-                .when(row.is_real)
-                .assert_eq(row.y3_ins.result[i], row.p_access[8 + i / 4].value()[i % 4]);
+            builder.when(row.is_real).assert_eq(
+                row.y3_ins.result[i],
+                row.p_access[num_words_field_element + i / 4].value()[i % 4],
+            );
         }
 
         builder.eval_memory_access_slice(
@@ -340,14 +333,8 @@ where
             row.is_real,
         );
 
-        // Fetch the syscall id for the curve type.
-        let syscall_id_fe = match E::CURVE_TYPE {
-            CurveType::Secp256k1 => {
-                AB::F::from_canonical_u32(SyscallCode::SECP256K1_ADD.syscall_id())
-            }
-            CurveType::Bn254 => AB::F::from_canonical_u32(SyscallCode::BN254_ADD.syscall_id()),
-            _ => panic!("Unsupported curve"),
-        };
+        // Fetch the syscall id for the curve.
+        let syscall_id_fe = AB::F::from_canonical_u32(E::ADD.syscall_id());
 
         builder.receive_syscall(
             row.shard,
@@ -364,13 +351,41 @@ where
 mod tests {
 
     use crate::{
-        runtime::Program,
+        runtime::{Instruction, Opcode, Program, Runtime, SyscallCode},
         utils::{
+            ec::weierstrass::{bls12381::Bls12381, secp256r1::Secp256r1},
             run_test, setup_logger,
             tests::{BN254_ADD_ELF, BN254_MUL_ELF, SECP256K1_ADD_ELF, SECP256K1_MUL_ELF},
         },
     };
 
+    /// Writes the points `p` and `q` to `p_ptr` and `q_ptr` and adds `q` to `p` with `syscall`.
+    pub fn weierstrass_add_program(
+        syscall: SyscallCode,
+        p_ptr: u32,
+        p: &[u32],
+        q_ptr: u32,
+        q: &[u32],
+    ) -> Program {
+        let mut instructions = vec![];
+        for (ptr, words) in [(p_ptr, p), (q_ptr, q)] {
+            for (i, word) in words.iter().enumerate() {
+                instructions.extend(vec![
+                    Instruction::new(Opcode::ADD, 29, 0, *word, false, true),
+                    Instruction::new(Opcode::ADD, 30, 0, ptr + i as u32 * 4, false, true),
+                    Instruction::new(Opcode::SW, 29, 30, 0, false, true),
+                ]);
+            }
+        }
+        instructions.extend(vec![
+            Instruction::new(Opcode::ADD, 5, 0, syscall as u32, false, true),
+            Instruction::new(Opcode::ADD, 10, 0, p_ptr, false, true),
+            Instruction::new(Opcode::ADD, 11, 0, q_ptr, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+        ]);
+        Program::new(instructions, 0, 0)
+    }
+
     #[test]
     fn test_secp256k1_add_simple() {
         setup_logger();
@@ -398,4 +413,50 @@ mod tests {
         let program = Program::from(SECP256K1_MUL_ELF);
         run_test(program).unwrap();
     }
+
+    #[test]
+    fn test_secp256r1_add_simple() {
+        setup_logger();
+        let (p_ptr, q_ptr) = (1000, 2000);
+        let generator = Secp256r1::generator();
+        let generator_doubled = generator.sw_double();
+        let expected = generator.sw_add(&generator_doubled).to_words_le();
+
+        let program = weierstrass_add_program(
+            SyscallCode::SECP256R1_ADD,
+            p_ptr,
+            &generator.to_words_le(),
+            q_ptr,
+            &generator_doubled.to_words_le(),
+        );
+        let mut runtime = Runtime::new(program.clone());
+        runtime.run();
+        for (i, word) in expected.iter().enumerate() {
+            assert_eq!(runtime.word(p_ptr + i as u32 * 4), *word);
+        }
+        run_test(program).unwrap();
+    }
+
+    #[test]
+    fn test_bls12381_add_simple() {
+        setup_logger();
+        let (p_ptr, q_ptr) = (1000, 2000);
+        let generator = Bls12381::generator();
+        let generator_doubled = generator.sw_double();
+        let expected = generator.sw_add(&generator_doubled).to_words_le();
+
+        let program = weierstrass_add_program(
+            SyscallCode::BLS12381_ADD,
+            p_ptr,
+            &generator.to_words_le(),
+            q_ptr,
+            &generator_doubled.to_words_le(),
+        );
+        let mut runtime = Runtime::new(program.clone());
+        runtime.run();
+        for (i, word) in expected.iter().enumerate() {
+            assert_eq!(runtime.word(p_ptr + i as u32 * 4), *word);
+        }
+        run_test(program).unwrap();
+    }
 }
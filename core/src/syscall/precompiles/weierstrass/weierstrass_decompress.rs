@@ -0,0 +1,483 @@
+use core::borrow::{Borrow, BorrowMut};
+use core::mem::size_of;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+use generic_array::GenericArray;
+use num::BigUint;
+use num::Zero;
+use p3_air::AirBuilder;
+use p3_air::{Air, BaseAir};
+use p3_field::AbstractField;
+use p3_field::PrimeField32;
+use p3_matrix::dense::RowMajorMatrix;
+use p3_matrix::Matrix;
+use sp1_derive::AlignedBorrow;
+
+use super::WeierstrassDecompressSyscall;
+use crate::air::BaseAirBuilder;
+use crate::air::MachineAir;
+use crate::air::SP1AirBuilder;
+use crate::memory::MemoryReadCols;
+use crate::memory::MemoryReadWriteCols;
+use crate::operations::field::field_op::FieldOpCols;
+use crate::operations::field::field_op::FieldOperation;
+use crate::operations::field::field_sqrt::FieldSqrtCols;
+use crate::operations::field::params::Limbs;
+use crate::runtime::ExecutionRecord;
+use crate::runtime::Program;
+use crate::runtime::Syscall;
+use crate::syscall::precompiles::ECDecompressEvent;
+use crate::syscall::precompiles::SyscallContext;
+use crate::utils::ec::field::FieldParameters;
+use crate::utils::ec::field::NumLimbs;
+use crate::utils::ec::field::NumWords;
+use crate::utils::ec::EllipticCurve;
+use crate::utils::limbs_from_access;
+use crate::utils::limbs_from_prev_access;
+use crate::utils::pad_rows;
+
+pub const fn num_weierstrass_decompress_cols<P: FieldParameters + NumWords>() -> usize {
+    size_of::<WeierstrassDecompressCols<u8, P>>()
+}
+
+/// A set of columns to decompress a point on a Weierstrass curve: y^2 = x^3 + ax + b.
+#[derive(Debug, Clone, AlignedBorrow)]
+#[repr(C)]
+pub struct WeierstrassDecompressCols<T, P: FieldParameters + NumWords> {
+    pub is_real: T,
+    pub shard: T,
+    pub clk: T,
+    pub ptr: T,
+    pub is_odd: T,
+    pub x_access: GenericArray<MemoryReadCols<T>, P::WordsFieldElement>,
+    pub y_access: GenericArray<MemoryReadWriteCols<T>, P::WordsFieldElement>,
+    pub(crate) x_2: FieldOpCols<T, P>,
+    pub(crate) x_3: FieldOpCols<T, P>,
+    pub(crate) ax: FieldOpCols<T, P>,
+    pub(crate) x_3_plus_ax: FieldOpCols<T, P>,
+    pub(crate) x_3_plus_ax_plus_b: FieldOpCols<T, P>,
+    pub(crate) y: FieldSqrtCols<T, P>,
+    pub(crate) neg_y: FieldOpCols<T, P>,
+    pub(crate) y_least_bits: [T; 8],
+}
+
+/// A chip that decompresses a point on a Weierstrass curve given a pointer to a slice of two field
+/// elements. The second half of the slice is the compressed X in little endian, and the second
+/// syscall argument is the sign bit of Y.
+///
+/// After decompression, the first half of the slice is overwritten with the decompressed Y.
+#[derive(Default)]
+pub struct WeierstrassDecompressChip<E> {
+    _marker: PhantomData<E>,
+}
+
+impl<E: EllipticCurve + WeierstrassDecompressSyscall> Syscall for WeierstrassDecompressChip<E> {
+    fn execute(&self, rt: &mut SyscallContext, slice_ptr: u32, is_odd: u32) -> Option<u32> {
+        let start_clk = rt.clk;
+        assert!(slice_ptr % 4 == 0, "slice_ptr must be 4-byte aligned");
+        assert!(is_odd <= 1, "is_odd must be 0 or 1");
+
+        let num_limbs = E::BaseField::NB_LIMBS;
+        let num_words_field_element = num_limbs / 4;
+
+        let (x_memory_records, x_vec) =
+            rt.mr_slice(slice_ptr + num_limbs as u32, num_words_field_element);
+        let x_bytes = x_vec
+            .iter()
+            .flat_map(|word| word.to_le_bytes())
+            .collect::<Vec<_>>();
+        let x = BigUint::from_bytes_le(&x_bytes);
+
+        // Compute the actual decompressed Y.
+        let y = Self::decompress(&x, is_odd == 1);
+        let decompressed_y_bytes = E::BaseField::to_limbs(&y);
+        let y_words = decompressed_y_bytes
+            .chunks_exact(4)
+            .map(|chunk| u32::from_le_bytes(chunk.try_into().unwrap()))
+            .collect::<Vec<_>>();
+
+        let y_memory_records = rt.mw_slice(slice_ptr, &y_words);
+
+        let shard = rt.current_shard();
+        let event = ECDecompressEvent {
+            shard,
+            clk: start_clk,
+            ptr: slice_ptr,
+            is_odd: is_odd != 0,
+            x_bytes,
+            decompressed_y_bytes,
+            x_memory_records,
+            y_memory_records,
+        };
+        rt.record_mut()
+            .weierstrass_decompress_events
+            .entry(E::DECOMPRESS)
+            .or_default()
+            .push(event);
+
+        None
+    }
+
+    fn num_extra_cycles(&self) -> u32 {
+        0
+    }
+}
+
+impl<E: EllipticCurve + WeierstrassDecompressSyscall> WeierstrassDecompressChip<E> {
+    pub fn new() -> Self {
+        Self {
+            _marker: PhantomData,
+        }
+    }
+
+    /// Computes the Y coordinate of the curve point with the given X coordinate and parity of Y.
+    fn decompress(x: &BigUint, is_odd: bool) -> BigUint {
+        let modulus = E::BaseField::modulus();
+        let y_squared = (x * x * x + E::a_int() * x + E::b_int()) % &modulus;
+        let y = E::sqrt(&y_squared);
+        assert_eq!(
+            (&y * &y) % &modulus,
+            y_squared,
+            "x is not the x-coordinate of a point on the curve"
+        );
+        if y.bit(0) == is_odd {
+            y
+        } else {
+            (&modulus - &y) % &modulus
+        }
+    }
+
+    fn populate_field_ops<F: PrimeField32>(
+        cols: &mut WeierstrassDecompressCols<F, E::BaseField>,
+        x: &BigUint,
+    ) {
+        // Y = sqrt(x^3 + ax + b)
+        let x_2 = cols.x_2.populate(x, x, FieldOperation::Mul);
+        let x_3 = cols.x_3.populate(&x_2, x, FieldOperation::Mul);
+        let ax = cols.ax.populate(&E::a_int(), x, FieldOperation::Mul);
+        let x_3_plus_ax = cols.x_3_plus_ax.populate(&x_3, &ax, FieldOperation::Add);
+        let x_3_plus_ax_plus_b =
+            cols.x_3_plus_ax_plus_b
+                .populate(&x_3_plus_ax, &E::b_int(), FieldOperation::Add);
+        let y = cols.y.populate(&x_3_plus_ax_plus_b, E::sqrt);
+        let zero = BigUint::zero();
+        cols.neg_y.populate(&zero, &y, FieldOperation::Sub);
+        // Decompose bits of least significant Y byte
+        let y_bytes = y.to_bytes_le();
+        let y_lsb = if y_bytes.is_empty() { 0 } else { y_bytes[0] };
+        for i in 0..8 {
+            cols.y_least_bits[i] = F::from_canonical_u32(((y_lsb >> i) & 1) as u32);
+        }
+    }
+}
+
+impl<F: PrimeField32, E: EllipticCurve + WeierstrassDecompressSyscall> MachineAir<F>
+    for WeierstrassDecompressChip<E>
+where
+    [(); num_weierstrass_decompress_cols::<E::BaseField>()]:,
+{
+    type Record = ExecutionRecord;
+    type Program = Program;
+
+    fn name(&self) -> String {
+        format!("{}Decompress", E::NAME)
+    }
+
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        output: &mut ExecutionRecord,
+    ) -> RowMajorMatrix<F> {
+        // collects the events for this curve.
+        let events = input
+            .weierstrass_decompress_events
+            .get(&E::DECOMPRESS)
+            .map_or(&[][..], |events| events.as_slice());
+
+        let mut rows = Vec::new();
+
+        let mut new_byte_lookup_events = Vec::new();
+
+        for event in events.iter() {
+            let mut row = [F::zero(); num_weierstrass_decompress_cols::<E::BaseField>()];
+            let cols: &mut WeierstrassDecompressCols<F, E::BaseField> =
+                row.as_mut_slice().borrow_mut();
+
+            // Populate basic columns.
+            cols.is_real = F::one();
+            cols.shard = F::from_canonical_u32(event.shard);
+            cols.clk = F::from_canonical_u32(event.clk);
+            cols.ptr = F::from_canonical_u32(event.ptr);
+            cols.is_odd = F::from_bool(event.is_odd);
+
+            let x = BigUint::from_bytes_le(&event.x_bytes);
+            Self::populate_field_ops(cols, &x);
+
+            // Populate the memory access columns.
+            for i in 0..cols.x_access.len() {
+                cols.x_access[i].populate(event.x_memory_records[i], &mut new_byte_lookup_events);
+            }
+            for i in 0..cols.y_access.len() {
+                cols.y_access[i]
+                    .populate_write(event.y_memory_records[i], &mut new_byte_lookup_events);
+            }
+
+            rows.push(row);
+        }
+        output.add_byte_lookup_events(new_byte_lookup_events);
+
+        pad_rows(&mut rows, || {
+            let mut row = [F::zero(); num_weierstrass_decompress_cols::<E::BaseField>()];
+            let cols: &mut WeierstrassDecompressCols<F, E::BaseField> =
+                row.as_mut_slice().borrow_mut();
+            // The x-coordinate of the generator always has a valid square root for x^3 + ax + b.
+            let (dummy_x, _) = E::generator();
+            let dummy_limbs = E::BaseField::to_limbs_field::<F, F>(&dummy_x);
+            for i in 0..cols.x_access.len() {
+                for j in 0..4 {
+                    cols.x_access[i].access.value[j] = dummy_limbs[i * 4 + j];
+                }
+            }
+            Self::populate_field_ops(cols, &dummy_x);
+            row
+        });
+
+        // Convert the trace to a row major matrix.
+        RowMajorMatrix::new(
+            rows.into_iter().flatten().collect::<Vec<_>>(),
+            num_weierstrass_decompress_cols::<E::BaseField>(),
+        )
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        shard
+            .weierstrass_decompress_events
+            .get(&E::DECOMPRESS)
+            .is_some_and(|events| !events.is_empty())
+    }
+}
+
+impl<F, E: EllipticCurve + WeierstrassDecompressSyscall> BaseAir<F>
+    for WeierstrassDecompressChip<E>
+{
+    fn width(&self) -> usize {
+        num_weierstrass_decompress_cols::<E::BaseField>()
+    }
+}
+
+impl<AB, E: EllipticCurve + WeierstrassDecompressSyscall> Air<AB> for WeierstrassDecompressChip<E>
+where
+    AB: SP1AirBuilder,
+    Limbs<AB::Var, <E::BaseField as NumLimbs>::Limbs>: Copy,
+{
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let row = main.row_slice(0);
+        let row: &WeierstrassDecompressCols<AB::Var, E::BaseField> = (*row).borrow();
+
+        let num_limbs = E::BaseField::NB_LIMBS;
+
+        builder.assert_bool(row.is_odd);
+
+        let x: Limbs<AB::Var, <E::BaseField as NumLimbs>::Limbs> =
+            limbs_from_prev_access(&row.x_access);
+        let a = E::BaseField::to_limbs_field::<AB::Expr, _>(&E::a_int());
+        let b = E::BaseField::to_limbs_field::<AB::Expr, _>(&E::b_int());
+
+        row.x_2
+            .eval::<AB, _, _>(builder, &x, &x, FieldOperation::Mul);
+        row.x_3
+            .eval::<AB, _, _>(builder, &row.x_2.result, &x, FieldOperation::Mul);
+        row.ax
+            .eval::<AB, _, _>(builder, &a, &x, FieldOperation::Mul);
+        row.x_3_plus_ax.eval::<AB, _, _>(
+            builder,
+            &row.x_3.result,
+            &row.ax.result,
+            FieldOperation::Add,
+        );
+        row.x_3_plus_ax_plus_b.eval::<AB, _, _>(
+            builder,
+            &row.x_3_plus_ax.result,
+            &b,
+            FieldOperation::Add,
+        );
+        row.y.eval::<AB>(builder, &row.x_3_plus_ax_plus_b.result);
+        row.neg_y.eval::<AB, _, _>(
+            builder,
+            &[AB::Expr::zero()].iter(),
+            &row.y.multiplication.result,
+            FieldOperation::Sub,
+        );
+
+        // Constrain decomposition of least significant byte of Y into `y_least_bits`
+        for i in 0..8 {
+            builder.when(row.is_real).assert_bool(row.y_least_bits[i]);
+        }
+        let y_least_byte = row.y.multiplication.result[0];
+        let powers_of_two = [1, 2, 4, 8, 16, 32, 64, 128].map(AB::F::from_canonical_u32);
+        let recomputed_byte: AB::Expr = row
+            .y_least_bits
+            .iter()
+            .zip(powers_of_two)
+            .map(|(p, b)| (*p).into() * b)
+            .sum();
+        builder
+            .when(row.is_real)
+            .assert_eq(recomputed_byte, y_least_byte);
+
+        // Interpret the lowest bit of Y as whether it is odd or not.
+        let y_is_odd = row.y_least_bits[0];
+
+        // Constrain that the result is written into the Y memory access.
+        // When y_is_odd == should_be_odd, result is y
+        // (Equivalent: y_is_odd != !should_be_odd)
+        let y_limbs: Limbs<AB::Var, <E::BaseField as NumLimbs>::Limbs> =
+            limbs_from_access(&row.y_access);
+        builder
+            .when(row.is_real)
+            .when_ne(y_is_odd.into(), AB::Expr::one() - row.is_odd)
+            .assert_all_eq(row.y.multiplication.result, y_limbs);
+        // When y_is_odd != should_be_odd, result is -y.
+        builder
+            .when(row.is_real)
+            .when_ne(y_is_odd, row.is_odd)
+            .assert_all_eq(row.neg_y.result, y_limbs);
+
+        for i in 0..row.x_access.len() {
+            builder.eval_memory_access(
+                row.shard,
+                row.clk,
+                row.ptr.into() + AB::F::from_canonical_usize(i * 4 + num_limbs),
+                &row.x_access[i],
+                row.is_real,
+            );
+        }
+        for i in 0..row.y_access.len() {
+            builder.eval_memory_access(
+                row.shard,
+                row.clk,
+                row.ptr.into() + AB::F::from_canonical_usize(i * 4),
+                &row.y_access[i],
+                row.is_real,
+            );
+        }
+
+        // Fetch the syscall id for the curve.
+        let syscall_id_fe = AB::F::from_canonical_u32(E::DECOMPRESS.syscall_id());
+
+        builder.receive_syscall(
+            row.shard,
+            row.clk,
+            syscall_id_fe,
+            row.ptr,
+            row.is_odd,
+            row.is_real,
+        );
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+
+    use crate::{
+        runtime::{Instruction, Opcode, Program, Runtime, SyscallCode},
+        utils::{
+            ec::field::FieldParameters,
+            ec::weierstrass::bls12381::{Bls12381, Bls12381BaseField},
+            ec::weierstrass::secp256r1::{Secp256r1, Secp256r1BaseField},
+            run_test, setup_logger,
+        },
+    };
+
+    /// Writes the compressed `x` to the second half of the slice at `ptr` and decompresses it
+    /// with `syscall`, picking the root with the parity of `is_odd`.
+    pub fn weierstrass_decompress_program(
+        syscall: SyscallCode,
+        ptr: u32,
+        x: &[u32],
+        is_odd: bool,
+    ) -> Program {
+        let x_ptr = ptr + x.len() as u32 * 4;
+        let mut instructions = vec![];
+        for (i, word) in x.iter().enumerate() {
+            instructions.extend(vec![
+                Instruction::new(Opcode::ADD, 29, 0, *word, false, true),
+                Instruction::new(Opcode::ADD, 30, 0, x_ptr + i as u32 * 4, false, true),
+                Instruction::new(Opcode::SW, 29, 30, 0, false, true),
+            ]);
+        }
+        instructions.extend(vec![
+            Instruction::new(Opcode::ADD, 5, 0, syscall as u32, false, true),
+            Instruction::new(Opcode::ADD, 10, 0, ptr, false, true),
+            Instruction::new(Opcode::ADD, 11, 0, is_odd as u32, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+        ]);
+        Program::new(instructions, 0, 0)
+    }
+
+    #[test]
+    fn test_secp256r1_decompress() {
+        setup_logger();
+        let ptr = 1000;
+        let generator = Secp256r1::generator();
+        let words = generator.to_words_le();
+        let modulus = Secp256r1BaseField::modulus();
+
+        for is_odd in [true, false] {
+            let expected_y = if generator.y.bit(0) == is_odd {
+                generator.y.clone()
+            } else {
+                &modulus - &generator.y
+            };
+            let mut expected_words = expected_y.to_u32_digits();
+            expected_words.resize(8, 0);
+
+            let program = weierstrass_decompress_program(
+                SyscallCode::SECP256R1_DECOMPRESS,
+                ptr,
+                &words[..8],
+                is_odd,
+            );
+            let mut runtime = Runtime::new(program.clone());
+            runtime.run();
+            for (i, word) in expected_words.iter().enumerate() {
+                assert_eq!(runtime.word(ptr + i as u32 * 4), *word);
+            }
+            run_test(program).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_bls12381_decompress() {
+        setup_logger();
+        let ptr = 1000;
+        let generator = Bls12381::generator();
+        let words = generator.to_words_le();
+        let modulus = Bls12381BaseField::modulus();
+
+        for is_odd in [true, false] {
+            let expected_y = if generator.y.bit(0) == is_odd {
+                generator.y.clone()
+            } else {
+                &modulus - &generator.y
+            };
+            let mut expected_words = expected_y.to_u32_digits();
+            expected_words.resize(12, 0);
+
+            let program = weierstrass_decompress_program(
+                SyscallCode::BLS12381_DECOMPRESS,
+                ptr,
+                &words[..12],
+                is_odd,
+            );
+            let mut runtime = Runtime::new(program.clone());
+            runtime.run();
+            for (i, word) in expected_words.iter().enumerate() {
+                assert_eq!(runtime.word(ptr + i as u32 * 4), *word);
+            }
+            run_test(program).unwrap();
+        }
+    }
+}
@@ -17,6 +17,7 @@ use p3_maybe_rayon::prelude::ParallelSlice;
 use sp1_derive::AlignedBorrow;
 use tracing::instrument;
 
+use super::WeierstrassSyscalls;
 use crate::air::MachineAir;
 use crate::air::SP1AirBuilder;
 use crate::memory::MemoryCols;
@@ -27,18 +28,14 @@ use crate::operations::field::params::Limbs;
 use crate::runtime::ExecutionRecord;
 use crate::runtime::Program;
 use crate::runtime::Syscall;
-use crate::runtime::SyscallCode;
 use crate::stark::MachineRecord;
 use crate::syscall::precompiles::create_ec_double_event;
 use crate::syscall::precompiles::SyscallContext;
 use crate::utils::ec::field::FieldParameters;
 use crate::utils::ec::field::NumLimbs;
 use crate::utils::ec::field::NumWords;
-use crate::utils::ec::weierstrass::WeierstrassParameters;
 use crate::utils::ec::AffinePoint;
-use crate::utils::ec::CurveType;
 use crate::utils::ec::EllipticCurve;
-use crate::utils::ec::NUM_WORDS_FIELD_ELEMENT;
 use crate::utils::limbs_from_prev_access;
 use crate::utils::pad_rows;
 
@@ -76,14 +73,14 @@ pub struct WeierstrassDoubleAssignChip<E> {
     _marker: PhantomData<E>,
 }
 
-impl<E: EllipticCurve + WeierstrassParameters> Syscall for WeierstrassDoubleAssignChip<E> {
+impl<E: EllipticCurve + WeierstrassSyscalls> Syscall for WeierstrassDoubleAssignChip<E> {
     fn execute(&self, rt: &mut SyscallContext, arg1: u32, arg2: u32) -> Option<u32> {
         let event = create_ec_double_event::<E>(rt, arg1, arg2);
-        match E::CURVE_TYPE {
-            CurveType::Secp256k1 => rt.record_mut().secp256k1_double_events.push(event),
-            CurveType::Bn254 => rt.record_mut().bn254_double_events.push(event),
-            _ => panic!("Unsupported curve"),
-        }
+        rt.record_mut()
+            .weierstrass_double_events
+            .entry(E::DOUBLE)
+            .or_default()
+            .push(event);
         None
     }
 
@@ -92,13 +89,24 @@ impl<E: EllipticCurve + WeierstrassParameters> Syscall for WeierstrassDoubleAssi
     }
 }
 
-impl<E: EllipticCurve + WeierstrassParameters> WeierstrassDoubleAssignChip<E> {
+impl<E: EllipticCurve + WeierstrassSyscalls> WeierstrassDoubleAssignChip<E> {
     pub fn new() -> Self {
         Self {
             _marker: PhantomData,
         }
     }
 
+    /// The point doubled by padding rows. This is the zero point, but doubling it divides `a` by
+    /// zero, which only satisfies the constraints when `a` is zero, so curves like secp256r1 use
+    /// their generator instead.
+    fn padding_point() -> (BigUint, BigUint) {
+        if E::a_int().is_zero() {
+            (BigUint::zero(), BigUint::zero())
+        } else {
+            E::generator()
+        }
+    }
+
     fn populate_field_ops<F: PrimeField32>(
         cols: &mut WeierstrassDoubleAssignCols<F, E::BaseField>,
         p_x: BigUint,
@@ -153,7 +161,7 @@ impl<E: EllipticCurve + WeierstrassParameters> WeierstrassDoubleAssignChip<E> {
     }
 }
 
-impl<F: PrimeField32, E: EllipticCurve + WeierstrassParameters> MachineAir<F>
+impl<F: PrimeField32, E: EllipticCurve + WeierstrassSyscalls> MachineAir<F>
     for WeierstrassDoubleAssignChip<E>
 where
     [(); num_weierstrass_double_cols::<E::BaseField>()]:,
@@ -162,11 +170,7 @@ where
     type Program = Program;
 
     fn name(&self) -> String {
-        match E::CURVE_TYPE {
-            CurveType::Secp256k1 => "Secp256k1DoubleAssign".to_string(),
-            CurveType::Bn254 => "Bn254DoubleAssign".to_string(),
-            _ => panic!("Unsupported curve"),
-        }
+        format!("{}DoubleAssign", E::NAME)
     }
 
     #[instrument(
@@ -179,12 +183,11 @@ where
         input: &ExecutionRecord,
         output: &mut ExecutionRecord,
     ) -> RowMajorMatrix<F> {
-        // collects the events based on the curve type.
-        let events = match E::CURVE_TYPE {
-            CurveType::Secp256k1 => &input.secp256k1_double_events,
-            CurveType::Bn254 => &input.bn254_double_events,
-            _ => panic!("Unsupported curve"),
-        };
+        // collects the events of the curve.
+        let events = input
+            .weierstrass_double_events
+            .get(&E::DOUBLE)
+            .map_or(&[][..], |events| events.as_slice());
 
         let chunk_size = std::cmp::max(events.len() / num_cpus::get(), 1);
 
@@ -239,8 +242,8 @@ where
             let mut row = [F::zero(); num_weierstrass_double_cols::<E::BaseField>()];
             let cols: &mut WeierstrassDoubleAssignCols<F, E::BaseField> =
                 row.as_mut_slice().borrow_mut();
-            let zero = BigUint::zero();
-            Self::populate_field_ops(cols, zero.clone(), zero.clone());
+            let (x, y) = Self::padding_point();
+            Self::populate_field_ops(cols, x, y);
             row
         });
 
@@ -252,21 +255,20 @@ where
     }
 
     fn included(&self, shard: &Self::Record) -> bool {
-        match E::CURVE_TYPE {
-            CurveType::Secp256k1 => !shard.secp256k1_double_events.is_empty(),
-            CurveType::Bn254 => !shard.bn254_double_events.is_empty(),
-            _ => panic!("Unsupported curve"),
-        }
+        shard
+            .weierstrass_double_events
+            .get(&E::DOUBLE)
+            .is_some_and(|events| !events.is_empty())
     }
 }
 
-impl<F, E: EllipticCurve + WeierstrassParameters> BaseAir<F> for WeierstrassDoubleAssignChip<E> {
+impl<F, E: EllipticCurve + WeierstrassSyscalls> BaseAir<F> for WeierstrassDoubleAssignChip<E> {
     fn width(&self) -> usize {
         num_weierstrass_double_cols::<E::BaseField>()
     }
 }
 
-impl<AB, E: EllipticCurve + WeierstrassParameters> Air<AB> for WeierstrassDoubleAssignChip<E>
+impl<AB, E: EllipticCurve + WeierstrassSyscalls> Air<AB> for WeierstrassDoubleAssignChip<E>
 where
     AB: SP1AirBuilder,
     Limbs<AB::Var, <E::BaseField as NumLimbs>::Limbs>: Copy,
@@ -365,7 +367,7 @@ where
                 .assert_eq(row.x3_ins.result[i], row.p_access[i / 4].value()[i % 4]);
             builder.when(row.is_real).assert_eq(
                 row.y3_ins.result[i],
-                row.p_access[NUM_WORDS_FIELD_ELEMENT + i / 4].value()[i % 4],
+                row.p_access[num_words_field_element + i / 4].value()[i % 4],
             );
         }
 
@@ -377,14 +379,8 @@ where
             row.is_real,
         );
 
-        // Fetch the syscall id for the curve type.
-        let syscall_id_fe = match E::CURVE_TYPE {
-            CurveType::Secp256k1 => {
-                AB::F::from_canonical_u32(SyscallCode::SECP256K1_DOUBLE.syscall_id())
-            }
-            CurveType::Bn254 => AB::F::from_canonical_u32(SyscallCode::BN254_DOUBLE.syscall_id()),
-            _ => panic!("Unsupported curve"),
-        };
+        // Fetch the syscall id for the curve.
+        let syscall_id_fe = AB::F::from_canonical_u32(E::DOUBLE.syscall_id());
 
         builder.receive_syscall(
             row.shard,
@@ -400,11 +396,39 @@ where
 #[cfg(test)]
 pub mod tests {
 
+    use num::{BigUint, Zero};
+
+    use super::WeierstrassDoubleAssignChip;
     use crate::{
-        runtime::Program,
-        utils::{run_test, setup_logger, tests::BN254_DOUBLE_ELF, tests::SECP256K1_DOUBLE_ELF},
+        runtime::{Instruction, Opcode, Program, Runtime, SyscallCode},
+        utils::{
+            ec::weierstrass::{
+                bls12381::Bls12381, secp256k1::Secp256k1, secp256r1::Secp256r1,
+                WeierstrassParameters,
+            },
+            run_test, setup_logger,
+            tests::{BN254_DOUBLE_ELF, SECP256K1_DOUBLE_ELF},
+        },
     };
 
+    /// Writes the point `p` to `p_ptr` and doubles it with `syscall`.
+    pub fn weierstrass_double_program(syscall: SyscallCode, p_ptr: u32, p: &[u32]) -> Program {
+        let mut instructions = vec![];
+        for (i, word) in p.iter().enumerate() {
+            instructions.extend(vec![
+                Instruction::new(Opcode::ADD, 29, 0, *word, false, true),
+                Instruction::new(Opcode::ADD, 30, 0, p_ptr + i as u32 * 4, false, true),
+                Instruction::new(Opcode::SW, 29, 30, 0, false, true),
+            ]);
+        }
+        instructions.extend(vec![
+            Instruction::new(Opcode::ADD, 5, 0, syscall as u32, false, true),
+            Instruction::new(Opcode::ADD, 10, 0, p_ptr, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+        ]);
+        Program::new(instructions, 0, 0)
+    }
+
     #[test]
     fn test_secp256k1_double_simple() {
         setup_logger();
@@ -418,4 +442,57 @@ pub mod tests {
         let program = Program::from(BN254_DOUBLE_ELF);
         run_test(program).unwrap();
     }
+
+    #[test]
+    fn test_secp256r1_double_simple() {
+        setup_logger();
+        let p_ptr = 1000;
+        let generator = Secp256r1::generator();
+        let expected = generator.sw_double().to_words_le();
+
+        let program = weierstrass_double_program(
+            SyscallCode::SECP256R1_DOUBLE,
+            p_ptr,
+            &generator.to_words_le(),
+        );
+        let mut runtime = Runtime::new(program.clone());
+        runtime.run();
+        for (i, word) in expected.iter().enumerate() {
+            assert_eq!(runtime.word(p_ptr + i as u32 * 4), *word);
+        }
+        run_test(program).unwrap();
+    }
+
+    #[test]
+    fn test_bls12381_double_simple() {
+        setup_logger();
+        let p_ptr = 1000;
+        let generator = Bls12381::generator();
+        let expected = generator.sw_double().to_words_le();
+
+        let program = weierstrass_double_program(
+            SyscallCode::BLS12381_DOUBLE,
+            p_ptr,
+            &generator.to_words_le(),
+        );
+        let mut runtime = Runtime::new(program.clone());
+        runtime.run();
+        for (i, word) in expected.iter().enumerate() {
+            assert_eq!(runtime.word(p_ptr + i as u32 * 4), *word);
+        }
+        run_test(program).unwrap();
+    }
+
+    #[test]
+    fn test_double_padding_point() {
+        // Curves with `a = 0` keep padding with the zero point, so their traces are unchanged.
+        assert_eq!(
+            WeierstrassDoubleAssignChip::<Secp256k1>::padding_point(),
+            (BigUint::zero(), BigUint::zero())
+        );
+        assert_eq!(
+            WeierstrassDoubleAssignChip::<Secp256r1>::padding_point(),
+            <Secp256r1 as WeierstrassParameters>::generator()
+        );
+    }
 }
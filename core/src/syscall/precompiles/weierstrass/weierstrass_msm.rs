@@ -0,0 +1,1044 @@
+use core::borrow::{Borrow, BorrowMut};
+use core::mem::size_of;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+use generic_array::GenericArray;
+use p3_air::AirBuilder;
+use p3_air::{Air, BaseAir};
+use p3_field::AbstractField;
+use p3_field::PrimeField32;
+use p3_matrix::dense::RowMajorMatrix;
+use p3_matrix::Matrix;
+use sp1_derive::AlignedBorrow;
+use typenum::Unsigned;
+
+use super::{WeierstrassAddAssignChip, WeierstrassDoubleAssignChip, WeierstrassMsmSyscall};
+use crate::air::BaseAirBuilder;
+use crate::air::MachineAir;
+use crate::air::SP1AirBuilder;
+use crate::air::WordAirBuilder;
+use crate::bytes::{ByteLookupEvent, ByteOpcode};
+use crate::memory::MemoryCols;
+use crate::memory::MemoryReadCols;
+use crate::memory::MemoryWriteCols;
+use crate::operations::IsZeroOperation;
+use crate::runtime::ExecutionRecord;
+use crate::runtime::MemoryReadRecord;
+use crate::runtime::Program;
+use crate::runtime::Syscall;
+use crate::syscall::precompiles::ECMsmEvent;
+use crate::syscall::precompiles::SyscallContext;
+use crate::utils::ec::field::FieldParameters;
+use crate::utils::ec::field::NumWords;
+use crate::utils::ec::EllipticCurve;
+use crate::utils::pad_rows;
+
+/// The maximum number of points in a single multi-scalar multiplication.
+pub const MAX_MSM_POINTS: usize = 16;
+
+/// The number of words in a scalar.
+const SCALAR_WORDS: usize = 8;
+
+/// The number of words read from the arguments: the number of points, and the pointers to the
+/// points and to the scalars. The word after them is written with whether the result is the
+/// identity.
+const MSM_ARGS_WORDS: usize = 3;
+
+/// The number of cycles of a row. A row which adds a point reads the accumulator and the point at
+/// its first cycle, and sends the addition or the doubling at its third cycle.
+const MSM_ROW_CYCLES: u32 = 4;
+
+/// There is one row per bit for the doubling and for each point. The cycles are part of the
+/// syscall code, so they are reserved for `MAX_MSM_POINTS` points whatever the number of points.
+const MSM_NUM_EXTRA_CYCLES: u32 =
+    MSM_ROW_CYCLES * (32 * SCALAR_WORDS * (MAX_MSM_POINTS + 1)) as u32;
+
+pub const fn num_weierstrass_msm_cols<P: FieldParameters + NumWords>() -> usize {
+    size_of::<WeierstrassMsmCols<u8, P>>()
+}
+
+/// A set of columns to compute a multi-scalar multiplication on a Weierstrass curve.
+///
+/// Each multiplication is a double-and-add over the bits of the scalars, from the most significant
+/// one. For each bit, there is a row which doubles the accumulator, followed by a row for each
+/// point which adds it to the accumulator if its scalar has the bit set. The doublings and the
+/// additions are sent to the `WeierstrassDoubleAssignChip` and `WeierstrassAddAssignChip` as
+/// syscalls, at `MSM_ROW_CYCLES` cycles per row.
+///
+/// The accumulator starts as the identity, and becomes a copy of a point added to the identity.
+/// Otherwise, the point is compared with the accumulator: if their x coordinates differ, it is
+/// added, if they are equal the accumulator is doubled, and if they are opposite the accumulator
+/// becomes the identity.
+#[derive(Debug, Clone, AlignedBorrow)]
+#[repr(C)]
+pub struct WeierstrassMsmCols<T, P: FieldParameters + NumWords> {
+    pub is_real: T,
+    pub shard: T,
+    pub clk: T,
+    pub result_ptr: T,
+    pub args_ptr: T,
+    pub num_points: T,
+    pub points_ptr: T,
+    pub scalars_ptr: T,
+
+    /// Whether this is the first row of the multiplication, in which the syscall is received.
+    pub is_start: T,
+
+    /// Whether this is the last row of the multiplication, in which the identity flag is written.
+    pub is_end: T,
+
+    pub is_double_row: T,
+    pub is_add_row: T,
+
+    /// The point of this row, and the address of the scalar word which holds the current bit.
+    pub point_index: T,
+    pub point_ptr: T,
+    pub scalar_word_ptr: T,
+
+    /// One-hot encodings of the current word of the scalars and of the bit within that word.
+    pub word_selector: [T; SCALAR_WORDS],
+    pub bit_selector: [T; 32],
+
+    /// The bits of the scalar word, and the current bit.
+    pub scalar_bits: [T; 32],
+    pub bit: T,
+
+    pub is_last_point: IsZeroOperation<T>,
+    pub is_last_bit: T,
+    pub is_next_point: T,
+    pub is_next_bit: T,
+
+    /// Whether the accumulator is the identity before this row.
+    pub is_identity: T,
+
+    pub do_double: T,
+    pub do_add: T,
+    pub do_copy: T,
+
+    /// Whether the point of this row is added, in which case it is read.
+    pub do_read: T,
+
+    /// Whether the point is added to an accumulator which is not the identity, in which case both
+    /// are read and compared.
+    pub do_compare: T,
+
+    /// Whether the x coordinates, and then the points, of the accumulator and the point are equal.
+    pub is_x_equal: T,
+    pub is_y_equal: T,
+
+    /// Coefficients of the byte differences of the coordinates whose sum is one when they differ.
+    pub x_diff_coeffs: GenericArray<T, P::Limbs>,
+    pub y_diff_coeffs: GenericArray<T, P::Limbs>,
+
+    pub args_reads: [MemoryReadCols<T>; MSM_ARGS_WORDS],
+    pub scalar_read: MemoryReadCols<T>,
+    pub point_reads: GenericArray<MemoryReadCols<T>, P::WordsCurvePoint>,
+    pub acc_reads: GenericArray<MemoryReadCols<T>, P::WordsCurvePoint>,
+    pub result_writes: GenericArray<MemoryWriteCols<T>, P::WordsCurvePoint>,
+    pub is_identity_write: MemoryWriteCols<T>,
+}
+
+#[derive(Default)]
+pub struct WeierstrassMsmChip<E> {
+    _marker: PhantomData<E>,
+}
+
+impl<E: EllipticCurve + WeierstrassMsmSyscall> Syscall for WeierstrassMsmChip<E> {
+    fn execute(&self, rt: &mut SyscallContext, arg1: u32, arg2: u32) -> Option<u32> {
+        let start_clk = rt.clk;
+        let result_ptr = arg1;
+        let args_ptr = arg2;
+        if result_ptr % 4 != 0 || args_ptr % 4 != 0 {
+            panic!();
+        }
+
+        let (args_read_records, args) = rt.mr_slice(args_ptr, MSM_ARGS_WORDS);
+        let (num_points, points_ptr, scalars_ptr) = (args[0], args[1], args[2]);
+        let n = num_points as usize;
+        assert!(
+            (1..=MAX_MSM_POINTS).contains(&n),
+            "msm: the number of points must be between 1 and {}",
+            MAX_MSM_POINTS
+        );
+        if points_ptr % 4 != 0 || scalars_ptr % 4 != 0 {
+            panic!();
+        }
+
+        // The regions must not overlap, since they are accessed at the same timestamps.
+        let num_words = <E::BaseField as NumWords>::WordsCurvePoint::USIZE;
+        let regions = [
+            (result_ptr, num_words),
+            (args_ptr, MSM_ARGS_WORDS + 1),
+            (points_ptr, num_words * n),
+            (scalars_ptr, SCALAR_WORDS * n),
+        ];
+        for (i, &(a, a_len)) in regions.iter().enumerate() {
+            for &(b, b_len) in regions[i + 1..].iter() {
+                assert!(
+                    a as u64 + 4 * a_len as u64 <= b as u64
+                        || b as u64 + 4 * b_len as u64 <= a as u64,
+                    "msm: the result, arguments, points and scalars must not overlap"
+                );
+            }
+        }
+
+        let scalars = rt.slice_unsafe(scalars_ptr, SCALAR_WORDS * n);
+        let add_chip = WeierstrassAddAssignChip::<E>::new();
+        let double_chip = WeierstrassDoubleAssignChip::<E>::new();
+
+        let mut is_identity = true;
+        let mut clk = start_clk;
+        let mut scalar_read_records = Vec::with_capacity(32 * SCALAR_WORDS * n);
+        let mut point_read_records = Vec::new();
+        let mut acc_read_records = Vec::new();
+        let mut copy_write_records = Vec::new();
+        for word_index in (0..SCALAR_WORDS).rev() {
+            for bit_index in (0..32).rev() {
+                if !is_identity {
+                    rt.clk = clk;
+                    double_chip.execute(rt, result_ptr, 0);
+                }
+                clk += MSM_ROW_CYCLES;
+
+                for i in 0..n {
+                    rt.clk = clk;
+                    let point_ptr = points_ptr + (4 * num_words * i) as u32;
+                    let scalar_word_ptr =
+                        scalars_ptr + (4 * (SCALAR_WORDS * i + word_index)) as u32;
+                    let (record, word) = rt.mr(scalar_word_ptr);
+                    scalar_read_records.push(record);
+
+                    if (word >> bit_index) & 1 == 1 {
+                        let (read_records, point) = rt.mr_slice(point_ptr, num_words);
+                        point_read_records.push(read_records);
+                        if is_identity {
+                            rt.clk += 1;
+                            copy_write_records.push(rt.mw_slice(result_ptr, &point));
+                            is_identity = false;
+                        } else {
+                            let (read_records, acc) = rt.mr_slice(result_ptr, num_words);
+                            acc_read_records.push(read_records);
+                            rt.clk += 2;
+                            if acc[..num_words / 2] != point[..num_words / 2] {
+                                add_chip.execute(rt, result_ptr, point_ptr);
+                            } else if acc == point {
+                                double_chip.execute(rt, result_ptr, 0);
+                            } else {
+                                is_identity = true;
+                            }
+                        }
+                    }
+                    clk += MSM_ROW_CYCLES;
+                }
+            }
+        }
+
+        // Write the identity flag in the last row.
+        rt.clk = clk - MSM_ROW_CYCLES + 1;
+        let is_identity_write_record =
+            rt.mw(args_ptr + (4 * MSM_ARGS_WORDS) as u32, is_identity as u32);
+
+        let event = ECMsmEvent {
+            shard: rt.current_shard(),
+            clk: start_clk,
+            result_ptr,
+            args_ptr,
+            num_points,
+            points_ptr,
+            scalars_ptr,
+            scalars,
+            args_read_records,
+            scalar_read_records,
+            point_read_records,
+            acc_read_records,
+            copy_write_records,
+            is_identity_write_record,
+        };
+        rt.record_mut()
+            .weierstrass_msm_events
+            .entry(E::MSM)
+            .or_default()
+            .push(event);
+        None
+    }
+
+    fn num_extra_cycles(&self) -> u32 {
+        MSM_NUM_EXTRA_CYCLES
+    }
+}
+
+impl<E: EllipticCurve + WeierstrassMsmSyscall> WeierstrassMsmChip<E> {
+    pub fn new() -> Self {
+        Self {
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<F: PrimeField32, E: EllipticCurve + WeierstrassMsmSyscall> MachineAir<F>
+    for WeierstrassMsmChip<E>
+where
+    [(); num_weierstrass_msm_cols::<E::BaseField>()]:,
+{
+    type Record = ExecutionRecord;
+    type Program = Program;
+
+    fn name(&self) -> String {
+        format!("{}Msm", E::NAME)
+    }
+
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        output: &mut ExecutionRecord,
+    ) -> RowMajorMatrix<F> {
+        // collects the events for this curve.
+        let events = input
+            .weierstrass_msm_events
+            .get(&E::MSM)
+            .map_or(&[][..], |events| events.as_slice());
+
+        let num_words = <E::BaseField as NumWords>::WordsCurvePoint::USIZE;
+
+        let mut rows = Vec::new();
+
+        let mut new_byte_lookup_events = Vec::new();
+
+        for event in events.iter() {
+            let n = event.num_points as usize;
+            let mut is_identity = true;
+            let mut is_start = true;
+            let mut clk = event.clk;
+            let mut scalar_read_records = event.scalar_read_records.iter();
+            let mut point_read_records = event.point_read_records.iter();
+            let mut acc_read_records = event.acc_read_records.iter();
+            let mut copy_write_records = event.copy_write_records.iter();
+
+            for word_index in (0..SCALAR_WORDS).rev() {
+                for bit_index in (0..32).rev() {
+                    // The first row of each bit doubles, and the next ones add the points.
+                    for step in 0..=n {
+                        let mut row = [F::zero(); num_weierstrass_msm_cols::<E::BaseField>()];
+                        let cols: &mut WeierstrassMsmCols<F, E::BaseField> =
+                            row.as_mut_slice().borrow_mut();
+                        let point_index = step.saturating_sub(1);
+                        let is_last_bit = word_index == 0 && bit_index == 0;
+
+                        // Populate basic columns.
+                        cols.is_real = F::one();
+                        cols.shard = F::from_canonical_u32(event.shard);
+                        cols.clk = F::from_canonical_u32(clk);
+                        cols.result_ptr = F::from_canonical_u32(event.result_ptr);
+                        cols.args_ptr = F::from_canonical_u32(event.args_ptr);
+                        cols.num_points = F::from_canonical_u32(event.num_points);
+                        cols.points_ptr = F::from_canonical_u32(event.points_ptr);
+                        cols.scalars_ptr = F::from_canonical_u32(event.scalars_ptr);
+                        cols.is_start = F::from_bool(is_start);
+                        cols.point_index = F::from_canonical_usize(point_index);
+                        cols.point_ptr = F::from_canonical_u32(
+                            event.points_ptr + (4 * num_words * point_index) as u32,
+                        );
+                        cols.scalar_word_ptr = F::from_canonical_u32(
+                            event.scalars_ptr
+                                + (4 * (SCALAR_WORDS * point_index + word_index)) as u32,
+                        );
+                        cols.word_selector[word_index] = F::one();
+                        cols.bit_selector[bit_index] = F::one();
+                        cols.is_last_point.populate_from_field_element(
+                            F::from_canonical_usize(point_index + 1)
+                                - F::from_canonical_u32(event.num_points),
+                        );
+                        cols.is_last_bit = F::from_bool(is_last_bit);
+                        cols.is_identity = F::from_bool(is_identity);
+
+                        if step == 0 {
+                            cols.is_double_row = F::one();
+                            cols.do_double = F::from_bool(!is_identity);
+
+                            if is_start {
+                                for (i, record) in event.args_read_records.iter().enumerate() {
+                                    cols.args_reads[i]
+                                        .populate(*record, &mut new_byte_lookup_events);
+                                }
+                                new_byte_lookup_events.push(ByteLookupEvent::new(
+                                    event.shard,
+                                    ByteOpcode::LTU,
+                                    1,
+                                    0,
+                                    0,
+                                    event.num_points,
+                                ));
+                                new_byte_lookup_events.push(ByteLookupEvent::new(
+                                    event.shard,
+                                    ByteOpcode::LTU,
+                                    1,
+                                    0,
+                                    event.num_points,
+                                    MAX_MSM_POINTS as u32 + 1,
+                                ));
+                            }
+                        } else {
+                            cols.is_add_row = F::one();
+                            let is_last_point = point_index == n - 1;
+                            cols.is_next_point = F::from_bool(!is_last_point);
+                            cols.is_next_bit = F::from_bool(is_last_point && !is_last_bit);
+                            cols.is_end = F::from_bool(is_last_point && is_last_bit);
+
+                            let record = scalar_read_records.next().unwrap();
+                            cols.scalar_read
+                                .populate(*record, &mut new_byte_lookup_events);
+                            let word = event.scalars[SCALAR_WORDS * point_index + word_index];
+                            for k in 0..32 {
+                                cols.scalar_bits[k] = F::from_canonical_u32((word >> k) & 1);
+                            }
+                            let bit = (word >> bit_index) & 1 == 1;
+                            cols.bit = F::from_bool(bit);
+
+                            if bit {
+                                cols.do_read = F::one();
+                                let point_records = point_read_records.next().unwrap();
+                                for i in 0..num_words {
+                                    cols.point_reads[i]
+                                        .populate(point_records[i], &mut new_byte_lookup_events);
+                                }
+                                if is_identity {
+                                    cols.do_copy = F::one();
+                                    let write_records = copy_write_records.next().unwrap();
+                                    for i in 0..num_words {
+                                        cols.result_writes[i].populate(
+                                            write_records[i],
+                                            &mut new_byte_lookup_events,
+                                        );
+                                    }
+                                    is_identity = false;
+                                } else {
+                                    cols.do_compare = F::one();
+                                    let acc_records = acc_read_records.next().unwrap();
+                                    for i in 0..num_words {
+                                        cols.acc_reads[i]
+                                            .populate(acc_records[i], &mut new_byte_lookup_events);
+                                    }
+                                    let bytes = |records: &[MemoryReadRecord]| {
+                                        records
+                                            .iter()
+                                            .flat_map(|record| record.value.to_le_bytes())
+                                            .collect::<Vec<_>>()
+                                    };
+                                    let (acc, point) = (bytes(acc_records), bytes(point_records));
+                                    let (acc_x, acc_y) = acc.split_at(acc.len() / 2);
+                                    let (point_x, point_y) = point.split_at(point.len() / 2);
+                                    let is_x_equal = populate_diff_coeffs(
+                                        &mut cols.x_diff_coeffs,
+                                        acc_x,
+                                        point_x,
+                                    );
+                                    if !is_x_equal {
+                                        cols.do_add = F::one();
+                                    } else if populate_diff_coeffs(
+                                        &mut cols.y_diff_coeffs,
+                                        acc_y,
+                                        point_y,
+                                    ) {
+                                        cols.is_x_equal = F::one();
+                                        cols.is_y_equal = F::one();
+                                    } else {
+                                        cols.is_x_equal = F::one();
+                                        is_identity = true;
+                                    }
+                                }
+                            }
+
+                            if is_last_point && is_last_bit {
+                                cols.is_identity_write.populate(
+                                    event.is_identity_write_record,
+                                    &mut new_byte_lookup_events,
+                                );
+                            }
+                        }
+
+                        is_start = false;
+                        clk += MSM_ROW_CYCLES;
+                        rows.push(row);
+                    }
+                }
+            }
+        }
+        output.add_byte_lookup_events(new_byte_lookup_events);
+
+        pad_rows(&mut rows, || {
+            [F::zero(); num_weierstrass_msm_cols::<E::BaseField>()]
+        });
+
+        // Convert the trace to a row major matrix.
+        RowMajorMatrix::new(
+            rows.into_iter().flatten().collect::<Vec<_>>(),
+            num_weierstrass_msm_cols::<E::BaseField>(),
+        )
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        shard
+            .weierstrass_msm_events
+            .get(&E::MSM)
+            .is_some_and(|events| !events.is_empty())
+    }
+}
+
+/// Populates the coefficients of the byte differences `a - b`, so that their sum is one, and
+/// returns whether `a` and `b` are equal, in which case the coefficients are left zero.
+fn populate_diff_coeffs<F: PrimeField32>(coeffs: &mut [F], a: &[u8], b: &[u8]) -> bool {
+    match a.iter().zip(b).position(|(a, b)| a != b) {
+        Some(i) => {
+            let diff = F::from_canonical_u8(a[i]) - F::from_canonical_u8(b[i]);
+            coeffs[i] = diff.inverse();
+            false
+        }
+        None => true,
+    }
+}
+
+impl<F, E: EllipticCurve> BaseAir<F> for WeierstrassMsmChip<E> {
+    fn width(&self) -> usize {
+        num_weierstrass_msm_cols::<E::BaseField>()
+    }
+}
+
+impl<AB, E: EllipticCurve + WeierstrassMsmSyscall> Air<AB> for WeierstrassMsmChip<E>
+where
+    AB: SP1AirBuilder,
+{
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let (local, next) = (main.row_slice(0), main.row_slice(1));
+        let local: &WeierstrassMsmCols<AB::Var, E::BaseField> = (*local).borrow();
+        let next: &WeierstrassMsmCols<AB::Var, E::BaseField> = (*next).borrow();
+
+        let num_words = <E::BaseField as NumWords>::WordsCurvePoint::USIZE;
+
+        // Constrain the flags of the row.
+        builder.assert_bool(local.is_real);
+        builder.assert_bool(local.is_start);
+        builder.assert_bool(local.is_double_row);
+        builder.assert_bool(local.is_add_row);
+        builder.assert_bool(local.is_identity);
+        builder.assert_bool(local.is_x_equal);
+        builder.assert_bool(local.is_y_equal);
+        builder.assert_eq(local.is_double_row + local.is_add_row, local.is_real);
+        builder.when(local.is_start).assert_one(local.is_real);
+
+        // The selectors are one-hot on real rows, and pick the current bit of the scalar word.
+        let mut word_selector_sum = AB::Expr::zero();
+        let mut word_index = AB::Expr::zero();
+        for k in 0..SCALAR_WORDS {
+            builder.assert_bool(local.word_selector[k]);
+            word_selector_sum = word_selector_sum + local.word_selector[k];
+            word_index = word_index + local.word_selector[k] * AB::F::from_canonical_usize(k);
+        }
+        builder.assert_eq(word_selector_sum, local.is_real);
+        let mut bit_selector_sum = AB::Expr::zero();
+        let mut bit = AB::Expr::zero();
+        for k in 0..32 {
+            builder.assert_bool(local.bit_selector[k]);
+            builder.assert_bool(local.scalar_bits[k]);
+            bit_selector_sum = bit_selector_sum + local.bit_selector[k];
+            bit = bit + local.bit_selector[k] * local.scalar_bits[k];
+        }
+        builder.assert_eq(bit_selector_sum, local.is_real);
+        builder.assert_eq(local.bit, bit);
+
+        // The bits are the bits of the scalar word.
+        let scalar_word = local.scalar_read.value();
+        for j in 0..4 {
+            let mut byte = AB::Expr::zero();
+            for k in 0..8 {
+                byte = byte + local.scalar_bits[8 * j + k] * AB::F::from_canonical_u32(1 << k);
+            }
+            builder
+                .when(local.is_add_row)
+                .assert_eq(scalar_word[j], byte);
+        }
+
+        // Constrain the addresses of the point and of the scalar word.
+        builder.when(local.is_real).assert_eq(
+            local.point_ptr,
+            local.points_ptr + local.point_index * AB::F::from_canonical_usize(4 * num_words),
+        );
+        builder.when(local.is_real).assert_eq(
+            local.scalar_word_ptr,
+            local.scalars_ptr
+                + local.point_index * AB::F::from_canonical_usize(4 * SCALAR_WORDS)
+                + word_index * AB::F::from_canonical_u32(4),
+        );
+
+        // Constrain the position of the row within the multiplication.
+        IsZeroOperation::<AB::F>::eval(
+            builder,
+            local.point_index + AB::F::one() - local.num_points,
+            local.is_last_point,
+            local.is_real.into(),
+        );
+        builder.assert_eq(
+            local.is_last_bit,
+            local.word_selector[0] * local.bit_selector[0],
+        );
+        builder.assert_eq(
+            local.is_end,
+            local.is_add_row * local.is_last_point.result * local.is_last_bit,
+        );
+        builder.assert_eq(
+            local.is_next_point,
+            local.is_add_row * (AB::Expr::one() - local.is_last_point.result),
+        );
+        builder.assert_eq(
+            local.is_next_bit,
+            local.is_add_row * local.is_last_point.result - local.is_end,
+        );
+
+        // Constrain the operation of the row.
+        builder.assert_eq(
+            local.do_double,
+            local.is_double_row * (AB::Expr::one() - local.is_identity),
+        );
+        builder.assert_eq(local.do_read, local.is_add_row * local.bit);
+        builder.assert_eq(local.do_copy, local.do_read * local.is_identity);
+        builder.assert_eq(local.do_compare, local.do_read - local.do_copy);
+
+        // Compare the accumulator with the point. The x coordinates are equal iff no combination of
+        // their byte differences is one, and likewise for the y coordinates when the x coordinates
+        // are equal.
+        builder
+            .when_not(local.do_compare)
+            .assert_zero(local.is_x_equal);
+        builder
+            .when_not(local.is_x_equal)
+            .assert_zero(local.is_y_equal);
+        let bytes = |reads: &GenericArray<MemoryReadCols<AB::Var>, _>| {
+            reads
+                .iter()
+                .flat_map(|read| read.value().0)
+                .collect::<Vec<_>>()
+        };
+        let (acc, point) = (bytes(&local.acc_reads), bytes(&local.point_reads));
+        let (acc_x, acc_y) = acc.split_at(acc.len() / 2);
+        let (point_x, point_y) = point.split_at(point.len() / 2);
+        let mut x_diff = AB::Expr::zero();
+        let mut y_diff = AB::Expr::zero();
+        for i in 0..acc_x.len() {
+            builder
+                .when(local.is_x_equal)
+                .assert_eq(acc_x[i], point_x[i]);
+            builder
+                .when(local.is_y_equal)
+                .assert_eq(acc_y[i], point_y[i]);
+            x_diff = x_diff + local.x_diff_coeffs[i] * (acc_x[i] - point_x[i]);
+            y_diff = y_diff + local.y_diff_coeffs[i] * (acc_y[i] - point_y[i]);
+        }
+        builder.assert_eq(x_diff, local.do_compare - local.is_x_equal);
+        builder.assert_eq(y_diff, local.is_x_equal - local.is_y_equal);
+
+        // The point is added if the x coordinates differ, and the accumulator is doubled if the
+        // points are equal, or becomes the identity if they are opposite.
+        builder.assert_eq(local.do_add, local.do_compare - local.is_x_equal);
+        let is_cancelled = local.is_x_equal - local.is_y_equal;
+
+        // A multiplication starts with a doubling of the identity, at the most significant bit.
+        builder.when(local.is_start).assert_one(local.is_double_row);
+        builder.when(local.is_start).assert_zero(local.point_index);
+        builder
+            .when(local.is_start)
+            .assert_one(local.word_selector[SCALAR_WORDS - 1]);
+        builder
+            .when(local.is_start)
+            .assert_one(local.bit_selector[31]);
+        builder.when(local.is_start).assert_one(local.is_identity);
+        builder
+            .when_first_row()
+            .assert_eq(local.is_start, local.is_real);
+        builder
+            .when_transition()
+            .when(local.is_end)
+            .assert_eq(next.is_start, next.is_real);
+        builder
+            .when_transition()
+            .when_not(local.is_real)
+            .assert_zero(next.is_real);
+        builder
+            .when_last_row()
+            .when(local.is_real)
+            .assert_one(local.is_end);
+
+        // Until its last row, a multiplication continues on the next row.
+        let mut when_continued = builder.when_transition().when(local.is_real - local.is_end);
+        when_continued.assert_one(next.is_real);
+        when_continued.assert_zero(next.is_start);
+        when_continued.assert_eq(local.shard, next.shard);
+        when_continued.assert_eq(
+            local.clk + AB::F::from_canonical_u32(MSM_ROW_CYCLES),
+            next.clk,
+        );
+        when_continued.assert_eq(local.result_ptr, next.result_ptr);
+        when_continued.assert_eq(local.args_ptr, next.args_ptr);
+        when_continued.assert_eq(local.num_points, next.num_points);
+        when_continued.assert_eq(local.points_ptr, next.points_ptr);
+        when_continued.assert_eq(local.scalars_ptr, next.scalars_ptr);
+        when_continued.assert_eq(
+            local.is_identity - local.do_copy + is_cancelled.clone(),
+            next.is_identity,
+        );
+
+        // After the doubling, add the first point.
+        let mut when_double = builder.when_transition().when(local.is_double_row);
+        when_double.assert_one(next.is_add_row);
+        when_double.assert_zero(next.point_index);
+
+        // After a point which is not the last one, add the next point.
+        let mut when_next_point = builder.when_transition().when(local.is_next_point);
+        when_next_point.assert_one(next.is_add_row);
+        when_next_point.assert_eq(local.point_index + AB::F::one(), next.point_index);
+
+        // Both stay on the same bit.
+        let mut when_same_bit = builder
+            .when_transition()
+            .when(local.is_double_row + local.is_next_point);
+        for k in 0..SCALAR_WORDS {
+            when_same_bit.assert_eq(local.word_selector[k], next.word_selector[k]);
+        }
+        for k in 0..32 {
+            when_same_bit.assert_eq(local.bit_selector[k], next.bit_selector[k]);
+        }
+
+        // After the last point, double for the next bit.
+        let mut when_next_bit = builder.when_transition().when(local.is_next_bit);
+        when_next_bit.assert_one(next.is_double_row);
+        for k in 0..32 {
+            when_next_bit.assert_eq(local.bit_selector[(k + 1) % 32], next.bit_selector[k]);
+        }
+        let is_next_word = local.bit_selector[0];
+        for k in 0..SCALAR_WORDS {
+            let mut word_selector = local.word_selector[k] * (AB::Expr::one() - is_next_word);
+            if k + 1 < SCALAR_WORDS {
+                word_selector = word_selector + local.word_selector[k + 1] * is_next_word;
+            }
+            when_next_bit.assert_eq(word_selector, next.word_selector[k]);
+        }
+
+        // Read the arguments in the first row.
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.clk.into(),
+            local.args_ptr,
+            &local.args_reads,
+            local.is_start,
+        );
+        let num_points_word = local.args_reads[0].value();
+        builder
+            .when(local.is_start)
+            .assert_eq(local.num_points, num_points_word.reduce::<AB>());
+        builder
+            .when(local.is_start)
+            .assert_eq(local.points_ptr, local.args_reads[1].value().reduce::<AB>());
+        builder.when(local.is_start).assert_eq(
+            local.scalars_ptr,
+            local.args_reads[2].value().reduce::<AB>(),
+        );
+
+        // Check that 0 < num_points <= MAX_MSM_POINTS.
+        for j in 1..4 {
+            builder.when(local.is_start).assert_zero(num_points_word[j]);
+        }
+        builder.send_byte(
+            AB::F::from_canonical_u32(ByteOpcode::LTU as u32),
+            AB::F::one(),
+            AB::F::zero(),
+            num_points_word[0],
+            local.shard,
+            local.is_start,
+        );
+        builder.send_byte(
+            AB::F::from_canonical_u32(ByteOpcode::LTU as u32),
+            AB::F::one(),
+            num_points_word[0],
+            AB::F::from_canonical_usize(MAX_MSM_POINTS + 1),
+            local.shard,
+            local.is_start,
+        );
+
+        // Read the scalar word in the rows of the points.
+        builder.eval_memory_access(
+            local.shard,
+            local.clk,
+            local.scalar_word_ptr,
+            &local.scalar_read,
+            local.is_add_row,
+        );
+
+        // Read the point to add it, and the accumulator to compare it with the point.
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.clk.into(),
+            local.point_ptr,
+            &local.point_reads,
+            local.do_read,
+        );
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.clk.into(),
+            local.result_ptr,
+            &local.acc_reads,
+            local.do_compare,
+        );
+
+        // Copy the point into the accumulator when it is the identity. We write at +1 like the
+        // additions.
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.clk + AB::F::one(),
+            local.result_ptr,
+            &local.result_writes,
+            local.do_copy,
+        );
+        for i in 0..num_words {
+            builder.when(local.do_copy).assert_word_eq(
+                *local.result_writes[i].value(),
+                *local.point_reads[i].value(),
+            );
+        }
+
+        // Write whether the result is the identity in the last row.
+        builder.eval_memory_access(
+            local.shard,
+            local.clk + AB::F::one(),
+            local.args_ptr + AB::F::from_canonical_usize(4 * MSM_ARGS_WORDS),
+            &local.is_identity_write,
+            local.is_end,
+        );
+        let is_identity_word = local.is_identity_write.value();
+        builder.when(local.is_end).assert_eq(
+            is_identity_word[0],
+            local.is_identity - local.do_copy + is_cancelled,
+        );
+        for j in 1..4 {
+            builder.when(local.is_end).assert_zero(is_identity_word[j]);
+        }
+
+        // Fetch the syscall ids for the curve.
+        let (msm_id, add_id, double_id) = (E::MSM, E::ADD, E::DOUBLE);
+
+        // Send the doublings and the additions of the accumulator to their chips. Those of the
+        // points come after the reads of the comparison.
+        builder.send_syscall(
+            local.shard,
+            local.clk,
+            AB::F::from_canonical_u32(double_id.syscall_id()),
+            local.result_ptr,
+            AB::Expr::zero(),
+            local.do_double,
+        );
+        builder.send_syscall(
+            local.shard,
+            local.clk + AB::F::two(),
+            AB::F::from_canonical_u32(double_id.syscall_id()),
+            local.result_ptr,
+            AB::Expr::zero(),
+            local.is_y_equal,
+        );
+        builder.send_syscall(
+            local.shard,
+            local.clk + AB::F::two(),
+            AB::F::from_canonical_u32(add_id.syscall_id()),
+            local.result_ptr,
+            local.point_ptr,
+            local.do_add,
+        );
+
+        builder.receive_syscall(
+            local.shard,
+            local.clk,
+            AB::F::from_canonical_u32(msm_id.syscall_id()),
+            local.result_ptr,
+            local.args_ptr,
+            local.is_start,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigUint;
+    use rand::Rng;
+
+    use crate::{
+        runtime::{Instruction, Opcode, Program, Runtime, SyscallCode},
+        utils::{
+            ec::{
+                weierstrass::{
+                    bn254::Bn254Parameters, secp256k1::Secp256k1Parameters, SwCurve,
+                    WeierstrassParameters,
+                },
+                AffinePoint,
+            },
+            run_test, setup_logger,
+        },
+    };
+
+    use super::SCALAR_WORDS;
+
+    const RESULT_PTR: u32 = 100;
+    const ARGS_PTR: u32 = 400;
+    const POINTS_PTR: u32 = 1000;
+    const SCALARS_PTR: u32 = 4000;
+
+    fn scalar_to_words(scalar: &BigUint) -> Vec<u32> {
+        let mut words = scalar.to_u32_digits();
+        words.resize(SCALAR_WORDS, 0);
+        words
+    }
+
+    /// Writes the points and scalars and computes their MSM with `code` into `RESULT_PTR`. The
+    /// identity flag in the arguments starts as 2, so that the test sees whether it is written.
+    pub fn weierstrass_msm_program(code: SyscallCode, points: &[u32], scalars: &[u32]) -> Program {
+        let num_points = (scalars.len() / SCALAR_WORDS) as u32;
+        let args = [num_points, POINTS_PTR, SCALARS_PTR, 2];
+        let mut instructions = vec![];
+        for (ptr, words) in [
+            (ARGS_PTR, &args[..]),
+            (POINTS_PTR, points),
+            (SCALARS_PTR, scalars),
+        ] {
+            for (i, word) in words.iter().enumerate() {
+                instructions.extend(vec![
+                    Instruction::new(Opcode::ADD, 29, 0, *word, false, true),
+                    Instruction::new(Opcode::ADD, 30, 0, ptr + i as u32 * 4, false, true),
+                    Instruction::new(Opcode::SW, 29, 30, 0, false, true),
+                ]);
+            }
+        }
+        instructions.extend(vec![
+            Instruction::new(Opcode::ADD, 5, 0, code as u32, false, true),
+            Instruction::new(Opcode::ADD, 10, 0, RESULT_PTR, false, true),
+            Instruction::new(Opcode::ADD, 11, 0, ARGS_PTR, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+        ]);
+        Program::new(instructions, 0, 0)
+    }
+
+    /// Runs and proves the syscall, and returns the result or `None` if it is the identity.
+    fn run_msm<E: WeierstrassParameters>(
+        code: SyscallCode,
+        points: &[AffinePoint<SwCurve<E>>],
+        scalars: &[BigUint],
+    ) -> Option<Vec<u32>> {
+        setup_logger();
+        let points_words = points
+            .iter()
+            .flat_map(|point| point.to_words_le())
+            .collect::<Vec<_>>();
+        let scalars_words = scalars.iter().flat_map(scalar_to_words).collect::<Vec<_>>();
+
+        let program = weierstrass_msm_program(code, &points_words, &scalars_words);
+        let mut runtime = Runtime::new(program.clone());
+        runtime.run();
+
+        let is_identity = runtime.word(ARGS_PTR + 12);
+        let result = (0..points_words.len() / points.len())
+            .map(|i| runtime.word(RESULT_PTR + i as u32 * 4))
+            .collect::<Vec<_>>();
+        run_test(program).unwrap();
+        match is_identity {
+            0 => Some(result),
+            1 => None,
+            _ => panic!("invalid identity flag"),
+        }
+    }
+
+    fn test_msm_random<E: WeierstrassParameters>(code: SyscallCode) {
+        let mut rng = rand::thread_rng();
+        let generator = SwCurve::<E>::generator();
+        let other = generator.sw_scalar_mul(&BigUint::from_bytes_le(&rng.gen::<[u8; 32]>()));
+        let points = [generator, other];
+        let scalars = [
+            BigUint::from_bytes_le(&rng.gen::<[u8; 31]>()),
+            BigUint::from_bytes_le(&rng.gen::<[u8; 31]>()),
+        ];
+
+        let expected = points[0]
+            .sw_scalar_mul(&scalars[0])
+            .sw_add(&points[1].sw_scalar_mul(&scalars[1]));
+        assert_eq!(
+            run_msm(code, &points, &scalars),
+            Some(expected.to_words_le())
+        );
+    }
+
+    #[test]
+    fn test_secp256k1_msm() {
+        test_msm_random::<Secp256k1Parameters>(SyscallCode::SECP256K1_MSM);
+    }
+
+    #[test]
+    fn test_bn254_msm() {
+        test_msm_random::<Bn254Parameters>(SyscallCode::BN254_MSM);
+    }
+
+    #[test]
+    fn test_secp256k1_msm_single_point() {
+        let generator = SwCurve::<Secp256k1Parameters>::generator();
+        let scalar = BigUint::from(3u32);
+        assert_eq!(
+            run_msm(
+                SyscallCode::SECP256K1_MSM,
+                &[generator.clone()],
+                &[scalar.clone()]
+            ),
+            Some(generator.sw_scalar_mul(&scalar).to_words_le())
+        );
+    }
+
+    #[test]
+    fn test_bn254_msm_zero_scalars() {
+        let generator = SwCurve::<Bn254Parameters>::generator();
+        let points = [generator.clone(), generator.sw_double()];
+        let scalars = [BigUint::from(0u32), BigUint::from(0u32)];
+        assert_eq!(run_msm(SyscallCode::BN254_MSM, &points, &scalars), None);
+    }
+
+    #[test]
+    fn test_secp256k1_msm_equal_and_opposite_points() {
+        let generator = SwCurve::<Secp256k1Parameters>::generator();
+        let order = Secp256k1Parameters::prime_group_order();
+        let neg_generator = generator.sw_scalar_mul(&(&order - 1u32));
+
+        // Adding a point equal to the accumulator doubles it.
+        let points = [generator.clone(), generator.clone()];
+        let scalars = [BigUint::from(1u32), BigUint::from(1u32)];
+        assert_eq!(
+            run_msm(SyscallCode::SECP256K1_MSM, &points, &scalars),
+            Some(generator.sw_double().to_words_le())
+        );
+
+        // Adding a point opposite to the accumulator cancels it, after which the next point is
+        // copied again.
+        let points = [generator.clone(), neg_generator, generator.sw_double()];
+        let scalars = [
+            BigUint::from(5u32),
+            BigUint::from(5u32),
+            BigUint::from(1u32),
+        ];
+        assert_eq!(
+            run_msm(SyscallCode::SECP256K1_MSM, &points, &scalars),
+            Some(generator.sw_double().to_words_le())
+        );
+    }
+
+    #[test]
+    fn test_bn254_msm_group_order() {
+        // The accumulator is the opposite of the point before the last addition.
+        let generator = SwCurve::<Bn254Parameters>::generator();
+        let order = Bn254Parameters::prime_group_order();
+        assert_eq!(
+            run_msm(SyscallCode::BN254_MSM, &[generator], &[order]),
+            None
+        );
+    }
+}
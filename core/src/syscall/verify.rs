@@ -73,3 +73,79 @@ impl Syscall for SyscallVerifySP1Proof {
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use p3_baby_bear::BabyBear;
+    use p3_field::PrimeField32;
+
+    use crate::{
+        runtime::{Program, Runtime, ShardingConfig},
+        stark::{RiscvAir, StarkGenericConfig},
+        utils::{
+            prove_core, run_and_prove_runtime, setup_logger,
+            tests::{FIBONACCI_ELF, VERIFY_PROOF_ELF},
+            BabyBearPoseidon2, BabyBearPoseidon2Inner,
+        },
+        SP1Stdin,
+    };
+
+    #[test]
+    fn test_verify_proof_batched() {
+        setup_logger();
+
+        // Prove a program whose proof will be verified by the guest.
+        let fibonacci_program = Program::from(FIBONACCI_ELF);
+        let machine = RiscvAir::machine(BabyBearPoseidon2Inner::new());
+        let (_, fibonacci_vk) = machine.setup(&fibonacci_program);
+        let mut runtime = Runtime::new(fibonacci_program);
+        runtime.run();
+        let fibonacci_proof = prove_core(BabyBearPoseidon2Inner::new(), runtime);
+
+        let vk_words: &[BabyBear; 8] = fibonacci_vk.commit.as_ref();
+        let vk_raw = vk_words.map(|x| x.as_canonical_u32());
+        let mut pv_digest_raw = [0u32; 8];
+        for (raw, val) in pv_digest_raw
+            .iter_mut()
+            .zip(fibonacci_proof.shard_proofs[0].public_values[..8].iter())
+        {
+            *raw = val.as_canonical_u32();
+        }
+
+        let mut stdin = SP1Stdin::new();
+        stdin.write(&vk_raw);
+        stdin.write(&pv_digest_raw);
+
+        let config = BabyBearPoseidon2::new();
+        let machine = RiscvAir::machine(config.clone());
+        let program = Program::from(VERIFY_PROOF_ELF);
+        let (_, vk) = machine.setup(&program);
+
+        // Execute without batching first, to count the shards of the program.
+        let shard_size = 1 << 15;
+        let mut runtime = Runtime::new(program.clone());
+        runtime.shard_size = shard_size as u32 * 4;
+        runtime.shard_batch_size = 0;
+        runtime.write_vecs(&stdin.buffer);
+        runtime.write_proof(fibonacci_proof.clone(), fibonacci_vk.clone());
+        runtime.run();
+        let num_shards = runtime.record.cpu_events.last().unwrap().shard as usize;
+        assert!(num_shards > 1);
+
+        // Use batches of as many cycles as a shard has rows, so that execution is checkpointed
+        // and replayed several times, which requires the proof stream to round-trip through the
+        // checkpoints. A batch may end in the middle of a shard, since syscalls take more than
+        // one cycle, but the shards must be the same as without batching.
+        let mut runtime = Runtime::new(program);
+        runtime.shard_size = shard_size as u32 * 4;
+        runtime.shard_batch_size = shard_size as u32;
+        runtime.write_vecs(&stdin.buffer);
+        runtime.write_proof(fibonacci_proof, fibonacci_vk);
+        let (proof, _) =
+            run_and_prove_runtime(runtime, config, &ShardingConfig::new(shard_size)).unwrap();
+        assert_eq!(proof.shard_proofs.len(), num_shards);
+
+        let mut challenger = machine.config().challenger();
+        machine.verify(&vk, &proof, &mut challenger).unwrap();
+    }
+}
@@ -63,6 +63,7 @@ impl Syscall for SyscallWrite {
                 }
             } else if fd == 2 {
                 let s = core::str::from_utf8(slice).unwrap();
+                rt.last_stderr_write = Some(s.to_string());
                 let flush_s = update_io_buf(ctx, fd, s);
                 if !flush_s.is_empty() {
                     flush_s
@@ -25,16 +25,20 @@ pub const NUM_WORDS_EC_POINT: usize = 2 * NUM_WORDS_FIELD_ELEMENT;
 #[derive(Debug, PartialEq, Eq)]
 pub enum CurveType {
     Secp256k1,
+    Secp256r1,
     Bn254,
     Ed25519,
+    Bls12381,
 }
 
 impl Display for CurveType {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         match self {
             CurveType::Secp256k1 => write!(f, "Secp256k1"),
+            CurveType::Secp256r1 => write!(f, "Secp256r1"),
             CurveType::Bn254 => write!(f, "Bn254"),
             CurveType::Ed25519 => write!(f, "Ed25519"),
+            CurveType::Bls12381 => write!(f, "Bls12381"),
         }
     }
 }
@@ -73,29 +77,23 @@ impl<E> AffinePoint<E> {
             _marker: std::marker::PhantomData,
         }
     }
+}
 
-    pub fn to_words_le(&self) -> [u32; 16] {
+impl<E: EllipticCurveParameters> AffinePoint<E> {
+    /// Serializes the point as `x || y` little-endian words, each coordinate padded to the width
+    /// of the curve's base field.
+    pub fn to_words_le(&self) -> Vec<u32> {
+        let num_bytes = E::BaseField::NB_LIMBS;
         let mut x_bytes = self.x.to_bytes_le();
-        x_bytes.resize(32, 0u8);
+        x_bytes.resize(num_bytes, 0u8);
         let mut y_bytes = self.y.to_bytes_le();
-        y_bytes.resize(32, 0u8);
-
-        let mut words = [0u32; 16];
-        for i in 0..8 {
-            words[i] = u32::from_le_bytes([
-                x_bytes[i * 4],
-                x_bytes[i * 4 + 1],
-                x_bytes[i * 4 + 2],
-                x_bytes[i * 4 + 3],
-            ]);
-            words[i + 8] = u32::from_le_bytes([
-                y_bytes[i * 4],
-                y_bytes[i * 4 + 1],
-                y_bytes[i * 4 + 2],
-                y_bytes[i * 4 + 3],
-            ]);
-        }
-        words
+        y_bytes.resize(num_bytes, 0u8);
+
+        x_bytes
+            .chunks_exact(4)
+            .chain(y_bytes.chunks_exact(4))
+            .map(|chunk| u32::from_le_bytes(chunk.try_into().unwrap()))
+            .collect()
     }
 }
 
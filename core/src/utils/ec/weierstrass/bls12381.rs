@@ -0,0 +1,140 @@
+//! Modulo defining the BLS12-381 G1 curve and its base field. The constants are all taken from
+//! https://neuromancer.sk/std/bls/BLS12-381.
+
+use std::str::FromStr;
+
+use generic_array::GenericArray;
+use num::{BigUint, Num};
+use serde::{Deserialize, Serialize};
+use typenum::{U48, U94};
+
+use super::{SwCurve, WeierstrassParameters};
+use crate::utils::ec::field::FieldParameters;
+use crate::utils::ec::field::NumLimbs;
+use crate::utils::ec::CurveType;
+use crate::utils::ec::EllipticCurveParameters;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+/// Bls12381 curve parameter
+pub struct Bls12381Parameters;
+
+pub type Bls12381 = SwCurve<Bls12381Parameters>;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+/// Bls12381 base field parameter
+pub struct Bls12381BaseField;
+
+impl FieldParameters for Bls12381BaseField {
+    const MODULUS: &'static [u8] = &[
+        0xab, 0xaa, 0xff, 0xff, 0xff, 0xff, 0xfe, 0xb9, 0xff, 0xff, 0x53, 0xb1, 0xfe, 0xff, 0xab,
+        0x1e, 0x24, 0xf6, 0xb0, 0xf6, 0xa0, 0xd2, 0x30, 0x67, 0xbf, 0x12, 0x85, 0xf3, 0x84, 0x4b,
+        0x77, 0x64, 0xd7, 0xac, 0x4b, 0x43, 0xb6, 0xa7, 0x1b, 0x4b, 0x9a, 0xe6, 0x7f, 0x39, 0xea,
+        0x11, 0x01, 0x1a,
+    ];
+
+    /// A rough witness-offset estimate given the size of the limbs and the size of the field.
+    const WITNESS_OFFSET: usize = 1usize << 15;
+
+    fn modulus() -> BigUint {
+        BigUint::from_str_radix(
+            "1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaaab",
+            16,
+        )
+        .unwrap()
+    }
+}
+
+impl NumLimbs for Bls12381BaseField {
+    type Limbs = U48;
+    type Witness = U94;
+}
+
+impl EllipticCurveParameters for Bls12381Parameters {
+    type BaseField = Bls12381BaseField;
+    const CURVE_TYPE: CurveType = CurveType::Bls12381;
+}
+
+impl WeierstrassParameters for Bls12381Parameters {
+    const A: GenericArray<u8, U48> = GenericArray::from_array([
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    ]);
+
+    const B: GenericArray<u8, U48> = GenericArray::from_array([
+        4, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    ]);
+
+    fn generator() -> (BigUint, BigUint) {
+        let x = BigUint::from_str(
+            "3685416753713387016781088315183077757961620795782546409894578378688607592378376318836054947676345821548104185464507",
+        )
+        .unwrap();
+        let y = BigUint::from_str(
+            "1339506544944476473020471379941921221584933875938349620426543736416511423956333506472724655353366534992391756441569",
+        )
+        .unwrap();
+        (x, y)
+    }
+
+    fn prime_group_order() -> num::BigUint {
+        BigUint::from_slice(&[
+            0x00000001, 0xFFFFFFFF, 0xFFFE5BFE, 0x53BDA402, 0x09A1D805, 0x3339D808, 0x299D7D48,
+            0x73EDA753,
+        ])
+    }
+}
+
+/// Computes a square root of `n` in the Bls12381 base field.
+///
+/// Since the modulus satisfies `p = 3 mod 4`, a square root is given by `n^((p + 1) / 4)`. The
+/// caller is responsible for ensuring `n` is a quadratic residue.
+pub fn bls12381_sqrt(n: &BigUint) -> BigUint {
+    let modulus = Bls12381BaseField::modulus();
+    let exponent = (&modulus + 1u32) >> 2;
+    n.modpow(&exponent, &modulus)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::utils::ec::utils::biguint_from_limbs;
+    use num::bigint::RandBigInt;
+    use rand::thread_rng;
+
+    #[test]
+    fn test_weierstrass_biguint_scalar_mul() {
+        assert_eq!(
+            biguint_from_limbs(Bls12381BaseField::MODULUS),
+            Bls12381BaseField::modulus()
+        );
+    }
+
+    #[test]
+    fn test_bls12381_curve_parameters() {
+        let p = Bls12381BaseField::modulus();
+
+        // Check that the generator lies on the curve y^2 = x^3 + 4.
+        let (x, y) = Bls12381Parameters::generator();
+        let lhs = (&y * &y) % &p;
+        let rhs = (&x * &x * &x + Bls12381Parameters::b_int()) % &p;
+        assert_eq!(lhs, rhs);
+    }
+
+    #[test]
+    fn test_bls12381_sqrt() {
+        let mut rng = thread_rng();
+        for _ in 0..10 {
+            // Check that sqrt(x^2)^2 == x^2
+            // We use x^2 since not all field elements have a square root
+            let x = rng.gen_biguint(384) % Bls12381BaseField::modulus();
+            let x_2 = (&x * &x) % Bls12381BaseField::modulus();
+            let sqrt = bls12381_sqrt(&x_2);
+
+            let sqrt_2 = (&sqrt * &sqrt) % Bls12381BaseField::modulus();
+
+            assert_eq!(sqrt_2, x_2);
+        }
+    }
+}
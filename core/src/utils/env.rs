@@ -45,3 +45,22 @@ pub fn shard_batch_size() -> u32 {
         Err(_) => 0,
     }
 }
+
+/// Gets the path to write the guest's collapsed-stack profile to, if profiling is enabled.
+///
+/// The collapsed stacks can be rendered with flamegraph tools such as `inferno-flamegraph`.
+pub fn profile_file() -> Option<String> {
+    std::env::var("PROFILE_FILE").ok()
+}
+
+/// Gets the localhost port to serve a GDB remote debugging session on, if debugging is enabled.
+pub fn gdb_port() -> Option<u16> {
+    std::env::var("GDB_PORT")
+        .ok()
+        .map(|val| val.parse().expect("GDB_PORT must be a port number"))
+}
+
+/// Gets the path to write the guest's lcov coverage report to, if coverage is enabled.
+pub fn coverage_file() -> Option<String> {
+    std::env::var("COVERAGE_FILE").ok()
+}
@@ -9,10 +9,10 @@ use serde::de::DeserializeOwned;
 use serde::Serialize;
 use size::Size;
 
-use crate::runtime::{ExecutionRecord, ShardingConfig};
+use crate::runtime::{ExecutionRecord, GuestEnv, GuestPanic, ShardingConfig};
 use crate::stark::MachineRecord;
-use crate::stark::{Com, PcsProverData, RiscvAir, ShardProof, UniConfig};
-use crate::utils::env::shard_batch_size;
+use crate::stark::{Com, PcsProverData, RiscvAir, ShardProof, UniConfig, VerifyingKey};
+use crate::utils::BabyBearPoseidon2Inner;
 use crate::{
     runtime::{Program, Runtime},
     stark::StarkGenericConfig,
@@ -36,6 +36,7 @@ pub fn run_test_io(
     let runtime = tracing::info_span!("runtime.run(...)").in_scope(|| {
         let mut runtime = Runtime::new(program);
         runtime.write_vecs(&inputs.buffer);
+        runtime.write_env(inputs.env.clone());
         runtime.run();
         runtime
     });
@@ -97,10 +98,15 @@ pub fn run_test_core(
     Ok(proof)
 }
 
-fn trace_checkpoint(program: Program, file: &File) -> ExecutionRecord {
+/// Replays the checkpoint in `file` with the shard sizes of `parent`, the runtime which saved it.
+fn trace_checkpoint(program: Program, file: &File, parent: &Runtime) -> ExecutionRecord {
     let mut reader = std::io::BufReader::new(file);
     let state = bincode::deserialize_from(&mut reader).expect("failed to deserialize state");
-    let mut runtime = Runtime::recover(program.clone(), state);
+    let mut runtime = Runtime::new(program);
+    runtime.shard_size = parent.shard_size;
+    runtime.shard_batch_size = parent.shard_batch_size;
+    runtime.fail_on_panic = parent.fail_on_panic;
+    runtime.restore(state);
     let (events, _) = tracing::debug_span!("runtime.trace").in_scope(|| runtime.execute_record());
     events
 }
@@ -114,7 +120,79 @@ pub fn run_and_prove<SC: StarkGenericConfig + Send + Sync>(
     program: Program,
     stdin: &[Vec<u8>],
     config: SC,
-) -> (crate::stark::Proof<SC>, Vec<u8>)
+) -> Result<(crate::stark::Proof<SC>, Vec<u8>), GuestPanic>
+where
+    SC::Challenger: Clone,
+    OpeningProof<SC>: Send + Sync,
+    Com<SC>: Send + Sync,
+    PcsProverData<SC>: Send + Sync,
+    ShardMainData<SC>: Serialize + DeserializeOwned,
+    <SC as StarkGenericConfig>::Val: PrimeField32,
+{
+    run_and_prove_with_proofs(program, stdin, Vec::new(), config)
+}
+
+/// Runs and proves a program which may verify the given proofs through `verify_sp1_proof`.
+pub fn run_and_prove_with_proofs<SC: StarkGenericConfig + Send + Sync>(
+    program: Program,
+    stdin: &[Vec<u8>],
+    proofs: Vec<(
+        crate::stark::Proof<BabyBearPoseidon2Inner>,
+        VerifyingKey<BabyBearPoseidon2Inner>,
+    )>,
+    config: SC,
+) -> Result<(crate::stark::Proof<SC>, Vec<u8>), GuestPanic>
+where
+    SC::Challenger: Clone,
+    OpeningProof<SC>: Send + Sync,
+    Com<SC>: Send + Sync,
+    PcsProverData<SC>: Send + Sync,
+    ShardMainData<SC>: Serialize + DeserializeOwned,
+    <SC as StarkGenericConfig>::Val: PrimeField32,
+{
+    let mut runtime = Runtime::new(program);
+    runtime.write_vecs(stdin);
+    for (proof, vk) in proofs {
+        runtime.write_proof(proof, vk);
+    }
+    run_and_prove_runtime(runtime, config, &ShardingConfig::default())
+}
+
+/// Runs and proves a program with the environment variables and arguments provided by the host.
+pub fn run_and_prove_with_env<SC: StarkGenericConfig + Send + Sync>(
+    program: Program,
+    stdin: &[Vec<u8>],
+    env: GuestEnv,
+    config: SC,
+) -> Result<(crate::stark::Proof<SC>, Vec<u8>), GuestPanic>
+where
+    SC::Challenger: Clone,
+    OpeningProof<SC>: Send + Sync,
+    Com<SC>: Send + Sync,
+    PcsProverData<SC>: Send + Sync,
+    ShardMainData<SC>: Serialize + DeserializeOwned,
+    <SC as StarkGenericConfig>::Val: PrimeField32,
+{
+    let mut runtime = Runtime::new(program);
+    runtime.write_vecs(stdin);
+    runtime.write_env(env);
+    run_and_prove_runtime(runtime, config, &ShardingConfig::default())
+}
+
+/// Runs and proves a runtime whose inputs have been written.
+///
+/// Execution is checkpointed every `runtime.shard_batch_size` cycles, and the events of each
+/// checkpoint are split into shards with `sharding_config`, which should match
+/// `runtime.shard_size`. If the batch size is 0, the whole program is proven at once with the
+/// default config.
+///
+/// If the guest panics and `runtime.fail_on_panic` is enabled, the panic is returned instead of a
+/// proof.
+pub fn run_and_prove_runtime<SC: StarkGenericConfig + Send + Sync>(
+    mut runtime: Runtime,
+    config: SC,
+    sharding_config: &ShardingConfig,
+) -> Result<(crate::stark::Proof<SC>, Vec<u8>), GuestPanic>
 where
     SC::Challenger: Clone,
     OpeningProof<SC>: Send + Sync,
@@ -126,14 +204,13 @@ where
     let mut challenger = config.challenger();
 
     let machine = RiscvAir::machine(config);
-    let mut runtime = Runtime::new(program.clone());
-    runtime.write_vecs(stdin);
+    let program = runtime.program.as_ref().clone();
     let (pk, _) = machine.setup(runtime.program.as_ref());
-    let should_batch = shard_batch_size() > 0;
+    let should_batch = runtime.shard_batch_size > 0;
 
     // If we don't need to batch, we can just run the program normally and prove it.
     if !should_batch {
-        runtime.run();
+        runtime.try_run()?;
         #[cfg(feature = "debug")]
         {
             let record_clone = runtime.record.clone();
@@ -141,7 +218,7 @@ where
         }
         let public_values = std::mem::take(&mut runtime.state.public_values_stream);
         let proof = prove_core(machine.config().clone(), runtime);
-        return (proof, public_values);
+        return Ok((proof, public_values));
     }
 
     // Execute the program, saving checkpoints at the start of every `shard_batch_size` cycle range.
@@ -149,26 +226,28 @@ where
     let mut prove_time = 0;
     let mut checkpoints = Vec::new();
     let mut public_values: Vec<SC::Val> = Vec::new();
-    let public_values_stream = tracing::info_span!("runtime.state").in_scope(|| loop {
-        // Get checkpoint + move to next checkpoint, then save checkpoint to temp file
-        let (state, done) = runtime.execute_state();
-        let mut tempfile = tempfile::tempfile().expect("failed to create tempfile");
-        let mut writer = std::io::BufWriter::new(&mut tempfile);
-        bincode::serialize_into(&mut writer, &state).expect("failed to serialize state");
-        writer.flush().expect("failed to flush writer");
-        drop(writer);
-        tempfile
-            .seek(std::io::SeekFrom::Start(0))
-            .expect("failed to seek to start of tempfile");
-        checkpoints.push(tempfile);
-        if done {
-            public_values = runtime.record.public_values();
-            return std::mem::take(&mut runtime.state.public_values_stream);
-        }
-    });
+    let public_values_stream =
+        tracing::info_span!("runtime.state").in_scope(|| -> Result<_, GuestPanic> {
+            loop {
+                // Get checkpoint + move to next checkpoint, then save checkpoint to temp file
+                let (state, done) = runtime.try_execute_state()?;
+                let mut tempfile = tempfile::tempfile().expect("failed to create tempfile");
+                let mut writer = std::io::BufWriter::new(&mut tempfile);
+                bincode::serialize_into(&mut writer, &state).expect("failed to serialize state");
+                writer.flush().expect("failed to flush writer");
+                drop(writer);
+                tempfile
+                    .seek(std::io::SeekFrom::Start(0))
+                    .expect("failed to seek to start of tempfile");
+                checkpoints.push(tempfile);
+                if done {
+                    public_values = runtime.record.public_values();
+                    return Ok(std::mem::take(&mut runtime.state.public_values_stream));
+                }
+            }
+        })?;
 
     // For each checkpoint, generate events, shard them, commit shards, and observe in challenger.
-    let sharding_config = ShardingConfig::default();
     let mut shard_main_datas = Vec::new();
 
     // If there's only one batch, it already must fit in memory so reuse it later in open multi
@@ -177,11 +256,11 @@ where
     let mut all_shards = None;
 
     for file in checkpoints.iter_mut() {
-        let events = trace_checkpoint(program.clone(), file);
+        let events = trace_checkpoint(program.clone(), file, &runtime);
         reset_seek(&mut *file);
         cycles += events.cpu_events.len();
         let shards =
-            tracing::debug_span!("shard").in_scope(|| machine.shard(events, &sharding_config));
+            tracing::debug_span!("shard").in_scope(|| machine.shard(events, sharding_config));
         let (commitments, commit_data) = tracing::info_span!("commit")
             .in_scope(|| LocalProver::commit_shards(&machine, &shards));
 
@@ -203,9 +282,9 @@ where
         let shards = if reuse_shards {
             Option::take(&mut all_shards).unwrap()
         } else {
-            let events = trace_checkpoint(program.clone(), &file);
+            let events = trace_checkpoint(program.clone(), &file, &runtime);
             reset_seek(&mut file);
-            tracing::debug_span!("shard").in_scope(|| machine.shard(events, &sharding_config))
+            tracing::debug_span!("shard").in_scope(|| machine.shard(events, sharding_config))
         };
         let start = Instant::now();
         let mut new_proofs = shards
@@ -235,7 +314,7 @@ where
         Size::from_bytes(nb_bytes),
     );
 
-    (proof, public_values_stream)
+    Ok((proof, public_values_stream))
 }
 
 pub fn prove_core<SC: StarkGenericConfig>(config: SC, runtime: Runtime) -> crate::stark::Proof<SC>
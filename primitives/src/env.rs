@@ -0,0 +1,19 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// The parts of the guest environment which the guest commits to its public values on start-up,
+/// as chosen by the host.
+///
+/// It is committed before anything the program commits itself, so a verifier reads it back with
+/// `public_values.read::<CommittedEnv>()` first. The guest serves the committed parts from this
+/// copy rather than asking the host again, so they are the values the program actually ran with.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommittedEnv {
+    pub vars: Option<BTreeMap<String, String>>,
+    pub args: Option<Vec<String>>,
+    pub random_seed: Option<[u8; 32]>,
+
+    /// The SHA-256 hash of each file of the guest filesystem.
+    pub file_hashes: Option<BTreeMap<String, [u8; 32]>>,
+}
@@ -165,7 +165,7 @@ impl SP1ProverImpl {
         let program = Program::from(elf);
         let (_, vk) = machine.setup(&program);
         let start = Instant::now();
-        let (proof, _) = run_and_prove(program, stdin, config);
+        let (proof, _) = run_and_prove(program, stdin, config).unwrap();
         let duration = start.elapsed().as_secs_f64();
         println!("leaf proving time = {:?}", duration);
         let mut challenger_ver = machine.config().challenger();
@@ -592,7 +592,8 @@ mod tests {
                     fibonacci_program.clone(),
                     &[bincode::serialize::<u32>(&4).unwrap()],
                     config,
-                );
+                )
+                .unwrap();
                 println!("shards: {:?}", fibonacci_proof.shard_proofs.len());
 
                 let mut challenger = sp1_machine.config().challenger();
@@ -1,4 +1,5 @@
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sp1_core::runtime::GuestEnv;
 use sp1_core::utils::Buffer;
 
 /// Standard input for the prover.
@@ -7,6 +8,9 @@ pub struct SP1Stdin {
     pub buffer: Vec<Vec<u8>>,
     #[serde(skip)]
     pub ptr: usize,
+    /// The environment variables, arguments, random seed and files of the guest.
+    #[serde(default)]
+    pub env: GuestEnv,
 }
 
 /// Standard output for the prover.
@@ -27,6 +31,7 @@ impl SP1Stdin {
         Self {
             buffer: Vec::new(),
             ptr: 0,
+            env: GuestEnv::default(),
         }
     }
 
@@ -35,6 +40,7 @@ impl SP1Stdin {
         Self {
             buffer: vec![data.to_vec()],
             ptr: 0,
+            env: GuestEnv::default(),
         }
     }
 
@@ -67,6 +73,48 @@ impl SP1Stdin {
     pub fn write_vec(&mut self, vec: Vec<u8>) {
         self.buffer.push(vec);
     }
+
+    /// Set an environment variable of the guest, read with `std::env::var`.
+    pub fn set_env_var(&mut self, name: &str, value: &str) {
+        self.env.vars.insert(name.to_string(), value.to_string());
+    }
+
+    /// Set the arguments of the guest, read with `std::env::args`. By convention, the first one is
+    /// the program name.
+    pub fn set_args<S: AsRef<str>>(&mut self, args: &[S]) {
+        self.env.args = args.iter().map(|arg| arg.as_ref().to_string()).collect();
+    }
+
+    /// Have the guest commit its environment variables and arguments to its public values before
+    /// running, so the verifier can check them. They are read back first with
+    /// `public_values.read::<CommittedEnv>()`.
+    pub fn commit_env(&mut self) {
+        self.env.commit = true;
+    }
+
+    /// Seed the random number generator behind the guest's `getrandom`, which makes randomized
+    /// programs reproducible for a given seed.
+    pub fn set_random_seed(&mut self, seed: [u8; 32]) {
+        self.env.random_seed = Some(seed);
+    }
+
+    /// Have the guest commit its random seed to its public values before running. It is read back
+    /// first with `public_values.read::<CommittedEnv>()`.
+    pub fn commit_random_seed(&mut self) {
+        self.env.commit_random_seed = true;
+    }
+
+    /// Add a file to the read-only filesystem of the guest, read with `sp1_zkvm::fs`.
+    pub fn add_file(&mut self, path: &str, contents: Vec<u8>) {
+        self.env.files.insert(path.to_string(), contents);
+    }
+
+    /// Have the guest commit the SHA-256 hash of each of its files to its public values before
+    /// running, and check the files it opens against them. They are read back first with
+    /// `public_values.read::<CommittedEnv>()`.
+    pub fn commit_file_hashes(&mut self) {
+        self.env.commit_file_hashes = true;
+    }
 }
 
 impl Default for SP1PublicValues {
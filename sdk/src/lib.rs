@@ -16,6 +16,7 @@ pub mod utils {
 }
 
 pub use sp1_core::air::PublicValues;
+pub use sp1_core::runtime::CommittedEnv;
 
 pub use crate::io::*;
 use proto::network::{ProofStatus, TransactionStatus};
@@ -25,12 +26,13 @@ use crate::client::NetworkClient;
 use anyhow::{Context, Ok, Result};
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
-use sp1_core::runtime::{Program, Runtime};
+use sp1_core::runtime::{Coverage, Profiler, Program, Runtime, ShardingConfig};
 use sp1_core::stark::{Com, PcsProverData, RiscvAir};
 use sp1_core::stark::{
     OpeningProof, ProgramVerificationError, Proof, ShardMainData, StarkGenericConfig,
 };
-use sp1_core::utils::run_and_prove;
+use sp1_core::utils::env::{coverage_file, gdb_port, profile_file};
+use sp1_core::utils::run_and_prove_runtime;
 use std::env;
 use std::fs;
 use std::time::Duration;
@@ -51,6 +53,11 @@ pub struct SP1ProofWithIO<SC: StarkGenericConfig + Serialize + DeserializeOwned>
 pub struct ProverClient {
     /// An optional Succinct prover network client used for remote operations.
     pub client: Option<NetworkClient>,
+
+    /// Whether to prove guests which panic, i.e. halt with a non-zero exit code, instead of
+    /// returning their [GuestPanic](sp1_core::runtime::GuestPanic) as an error. Only applies to
+    /// local proving.
+    pub prove_guest_panics: bool,
 }
 
 impl ProverClient {
@@ -67,9 +74,13 @@ impl ProverClient {
                 .unwrap_or_else(|_| panic!("PRIVATE_KEY must be set for remote proving"));
             Self {
                 client: Some(NetworkClient::new(&private_key)),
+                prove_guest_panics: false,
             }
         } else {
-            Self { client: None }
+            Self {
+                client: None,
+                prove_guest_panics: false,
+            }
         }
     }
 
@@ -78,17 +89,47 @@ impl ProverClient {
         self
     }
 
+    /// Proves guests which panic, with their exit code in the public values, instead of failing.
+    pub fn with_prove_guest_panics(mut self, prove_guest_panics: bool) -> Self {
+        self.prove_guest_panics = prove_guest_panics;
+        self
+    }
+
     /// Executes the elf with the given inputs and returns the output.
     pub fn execute(elf: &[u8], stdin: SP1Stdin) -> Result<SP1PublicValues> {
         let program = Program::from(elf);
         let mut runtime = Runtime::new(program);
         runtime.write_vecs(&stdin.buffer);
-        runtime.run();
+        runtime.write_env(stdin.env);
+        let profile_file = profile_file();
+        if profile_file.is_some() {
+            runtime.profiler = Some(Profiler::new(elf));
+        }
+        let coverage_file = coverage_file();
+        if coverage_file.is_some() {
+            runtime.coverage = Some(Coverage::new(elf));
+        }
+        match gdb_port() {
+            Some(port) => runtime.run_gdb(port)?,
+            None => runtime.try_run()?,
+        }
+        if let (Some(path), Some(mut profiler)) = (profile_file, runtime.profiler.take()) {
+            let mut file = fs::File::create(&path).context("failed to create profile file")?;
+            profiler.write_collapsed(&mut file)?;
+            profiler.write_report(&mut std::io::stdout())?;
+        }
+        if let (Some(path), Some(coverage)) = (coverage_file, runtime.coverage.take()) {
+            let mut file = fs::File::create(&path).context("failed to create coverage file")?;
+            coverage.write_lcov(&mut file)?;
+        }
         Ok(SP1PublicValues::from(&runtime.state.public_values_stream))
     }
 
     /// Generate a proof for the execution of the ELF with the given public inputs. If a
     /// NetworkClient is configured, it uses remote proving, otherwise, it proves locally.
+    ///
+    /// Unless `prove_guest_panics` is enabled, a guest which panics while proving locally fails
+    /// with its [GuestPanic](sp1_core::runtime::GuestPanic).
     pub fn prove(&self, elf: &[u8], stdin: SP1Stdin) -> Result<SP1ProofWithIO<BabyBearPoseidon2>> {
         if self.client.is_some() {
             println!("Proving remotely");
@@ -172,8 +213,12 @@ impl ProverClient {
         ShardMainData<SC>: Serialize + DeserializeOwned,
         SC::Val: p3_field::PrimeField32,
     {
-        let program = Program::from(elf);
-        let (proof, public_values_vec) = run_and_prove(program, &stdin.buffer, config);
+        let mut runtime = Runtime::new(Program::from(elf));
+        runtime.write_vecs(&stdin.buffer);
+        runtime.write_env(stdin.env.clone());
+        runtime.fail_on_panic = !self.prove_guest_panics;
+        let (proof, public_values_vec) =
+            run_and_prove_runtime(runtime, config, &ShardingConfig::default())?;
         let public_values = SP1PublicValues::from(&public_values_vec);
         Ok(SP1ProofWithIO {
             proof,
@@ -0,0 +1,68 @@
+#![no_main]
+sp1_zkvm::entrypoint!(main);
+
+use ed25519_dalek::{Signer, SigningKey, Verifier, VerifyingKey};
+use sp1_zkvm::precompiles::ed25519::verify;
+
+/// The order of the base point, in little-endian.
+const L: [u8; 32] = [
+    0xed, 0xd3, 0xf5, 0x5c, 0x1a, 0x63, 0x12, 0x58, 0xd6, 0x9c, 0xf7, 0xa2, 0xde, 0xf9, 0xde, 0x14,
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x10,
+];
+
+/// Checks that `verify` agrees with ed25519-dalek, and returns the result.
+fn check(pubkey: &[u8; 32], msg: &[u8], sig: &[u8; 64]) -> bool {
+    let expected = match VerifyingKey::from_bytes(pubkey) {
+        Ok(key) => key
+            .verify(msg, &ed25519_dalek::Signature::from_bytes(sig))
+            .is_ok(),
+        Err(_) => false,
+    };
+    let result = verify(pubkey, msg, sig);
+    assert_eq!(result, expected);
+    result
+}
+
+pub fn main() {
+    let signing_key = SigningKey::from_bytes(&[7u8; 32]);
+    let pubkey = signing_key.verifying_key().to_bytes();
+
+    let long_msg = [0xab_u8; 300];
+    for msg in [&b""[..], &b"hello world"[..], &long_msg[..]] {
+        let sig = signing_key.sign(msg).to_bytes();
+        assert!(check(&pubkey, msg, &sig));
+
+        // A different message.
+        let mut other_msg = msg.to_vec();
+        other_msg.push(1);
+        assert!(!check(&pubkey, &other_msg, &sig));
+
+        // A different R or S.
+        let mut bad_sig = sig;
+        bad_sig[0] ^= 1;
+        assert!(!check(&pubkey, msg, &bad_sig));
+        let mut bad_sig = sig;
+        bad_sig[32] ^= 1;
+        assert!(!check(&pubkey, msg, &bad_sig));
+
+        // S + L is rejected even though it is the same scalar.
+        let mut malleable_sig = sig;
+        let mut carry = 0u16;
+        for i in 0..32 {
+            let sum = sig[32 + i] as u16 + L[i] as u16 + carry;
+            malleable_sig[32 + i] = sum as u8;
+            carry = sum >> 8;
+        }
+        assert!(!check(&pubkey, msg, &malleable_sig));
+    }
+
+    // Public keys which are or are not points, such as y = 2 and y = 7.
+    let sig = signing_key.sign(b"hello world").to_bytes();
+    for y in 2..12u8 {
+        let mut bad_pubkey = [0u8; 32];
+        bad_pubkey[0] = y;
+        assert!(!check(&bad_pubkey, b"hello world", &sig));
+    }
+
+    println!("done");
+}
@@ -0,0 +1,67 @@
+#![no_main]
+sp1_zkvm::entrypoint!(main);
+
+use k256::schnorr::{Signature, SigningKey, VerifyingKey};
+use sp1_zkvm::precompiles::secp256k1::schnorr_verify;
+
+/// The order of the curve, in big-endian.
+const N: [u8; 32] = [
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xfe,
+    0xba, 0xae, 0xdc, 0xe6, 0xaf, 0x48, 0xa0, 0x3b, 0xbf, 0xd2, 0x5e, 0x8c, 0xd0, 0x36, 0x41, 0x41,
+];
+
+/// Checks that `schnorr_verify` agrees with k256, and returns the result.
+fn check(pubkey: &[u8; 32], msg: &[u8], sig: &[u8; 64]) -> bool {
+    let expected = match (
+        VerifyingKey::from_bytes(pubkey),
+        Signature::try_from(&sig[..]),
+    ) {
+        (Ok(key), Ok(sig)) => key.verify_raw(msg, &sig).is_ok(),
+        _ => false,
+    };
+    let result = schnorr_verify(pubkey, msg, sig);
+    assert_eq!(result, expected);
+    result
+}
+
+pub fn main() {
+    let signing_key = SigningKey::from_bytes(&[7u8; 32]).unwrap();
+    let pubkey: [u8; 32] = signing_key.verifying_key().to_bytes().into();
+
+    let long_msg = [0xab_u8; 300];
+    for msg in [&b""[..], &[0u8; 32][..], &long_msg[..]] {
+        let sig: [u8; 64] = signing_key.sign_raw(msg, &[1u8; 32]).unwrap().to_bytes();
+        assert!(check(&pubkey, msg, &sig));
+
+        // A different message.
+        let mut other_msg = msg.to_vec();
+        other_msg.push(1);
+        assert!(!check(&pubkey, &other_msg, &sig));
+
+        // A different r or s.
+        let mut bad_sig = sig;
+        bad_sig[31] ^= 1;
+        assert!(!check(&pubkey, msg, &bad_sig));
+        let mut bad_sig = sig;
+        bad_sig[63] ^= 1;
+        assert!(!check(&pubkey, msg, &bad_sig));
+
+        // s = n is rejected, as is any s >= n.
+        let mut bad_sig = sig;
+        bad_sig[32..].copy_from_slice(&N);
+        assert!(!check(&pubkey, msg, &bad_sig));
+    }
+
+    // Public keys which are or are not x coordinates, such as 5 and 7.
+    let sig: [u8; 64] = signing_key
+        .sign_raw(b"hello world", &[1u8; 32])
+        .unwrap()
+        .to_bytes();
+    for x in 1..12u8 {
+        let mut bad_pubkey = [0u8; 32];
+        bad_pubkey[31] = x;
+        assert!(!check(&bad_pubkey, b"hello world", &sig));
+    }
+
+    println!("done");
+}
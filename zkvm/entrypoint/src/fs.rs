@@ -0,0 +1,120 @@
+//! A read-only filesystem provided by the host through `SP1Stdin`.
+//!
+//! The standard library has no filesystem inside the zkVM, so programs which read configuration
+//! and data files use these in place of their `std::fs` counterparts.
+
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::Path;
+
+use sha2::{Digest, Sha256};
+
+use crate::syscalls::{
+    committed_env, read_hint_into, syscall_fs_open, syscall_fs_read, syscall_fs_seek,
+};
+
+/// A file of the filesystem provided by the host.
+pub struct File {
+    inner: Inner,
+}
+
+enum Inner {
+    /// A file read from the host as the program reads it.
+    Host(usize),
+
+    /// A file whose hash was committed, which is read in full and checked when it is opened.
+    Committed(io::Cursor<Vec<u8>>),
+}
+
+impl File {
+    /// Opens the file at `path`, failing with `NotFound` if the host did not provide it.
+    ///
+    /// If the host committed the hashes of its files, the whole file is read and checked against
+    /// its committed hash here, and the program panics if they differ.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<File> {
+        let path = path.as_ref().to_str().ok_or_else(not_found)?;
+        let Some(hashes) = committed_env().and_then(|env| env.file_hashes.as_ref()) else {
+            return Ok(File {
+                inner: Inner::Host(open_host(path)?),
+            });
+        };
+
+        let hash = hashes.get(path).ok_or_else(not_found)?;
+        let contents = read_host(open_host(path)?, usize::MAX);
+        assert!(
+            Sha256::digest(&contents)[..] == hash[..],
+            "file {} does not match its committed hash",
+            path
+        );
+        Ok(File {
+            inner: Inner::Committed(io::Cursor::new(contents)),
+        })
+    }
+}
+
+impl Read for File {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match &mut self.inner {
+            Inner::Host(fd) => {
+                let bytes = read_host(*fd, buf.len());
+                buf[..bytes.len()].copy_from_slice(&bytes);
+                Ok(bytes.len())
+            }
+            Inner::Committed(cursor) => cursor.read(buf),
+        }
+    }
+}
+
+impl Seek for File {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let fd = match &mut self.inner {
+            Inner::Host(fd) => *fd,
+            Inner::Committed(cursor) => return cursor.seek(pos),
+        };
+        // Files live in the 32-bit address space, so offsets from the start are unsigned 32-bit
+        // and the others are signed 32-bit.
+        let (offset, whence) = match pos {
+            SeekFrom::Start(offset) => (u32::try_from(offset).ok(), 0),
+            SeekFrom::Current(offset) => (i32::try_from(offset).ok().map(|o| o as u32), 1),
+            SeekFrom::End(offset) => (i32::try_from(offset).ok().map(|o| o as u32), 2),
+        };
+        let new_offset = offset.map(|offset| syscall_fs_seek(fd, offset as usize, whence));
+        match new_offset {
+            Some(new_offset) if new_offset != usize::MAX => Ok(new_offset as u64),
+            _ => Err(io::Error::new(io::ErrorKind::InvalidInput, "invalid seek")),
+        }
+    }
+}
+
+/// Reads the whole file at `path`.
+pub fn read<P: AsRef<Path>>(path: P) -> io::Result<Vec<u8>> {
+    let mut contents = Vec::new();
+    File::open(path)?.read_to_end(&mut contents)?;
+    Ok(contents)
+}
+
+/// Reads the whole file at `path`, which must be UTF-8.
+pub fn read_to_string<P: AsRef<Path>>(path: P) -> io::Result<String> {
+    let mut contents = String::new();
+    File::open(path)?.read_to_string(&mut contents)?;
+    Ok(contents)
+}
+
+fn not_found() -> io::Error {
+    io::Error::from(io::ErrorKind::NotFound)
+}
+
+fn open_host(path: &str) -> io::Result<usize> {
+    match syscall_fs_open(path.as_ptr(), path.len()) {
+        usize::MAX => Err(not_found()),
+        fd => Ok(fd),
+    }
+}
+
+/// Reads up to `len` bytes of the file `fd` from the host.
+fn read_host(fd: usize, len: usize) -> Vec<u8> {
+    let nbytes = syscall_fs_read(fd, len);
+    unsafe {
+        let ptr = read_hint_into(core::ptr::null_mut(), 0, nbytes);
+        core::slice::from_raw_parts(ptr, nbytes).to_vec()
+    }
+}
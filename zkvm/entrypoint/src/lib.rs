@@ -1,3 +1,4 @@
+pub mod fs;
 pub mod heap;
 pub mod syscalls;
 pub mod io {
@@ -32,9 +33,12 @@ macro_rules! entrypoint {
 #[cfg(all(target_os = "zkvm", feature = "libm"))]
 mod libm;
 
+#[cfg(target_os = "zkvm")]
+mod memops;
+
 #[cfg(target_os = "zkvm")]
 mod zkvm {
-    use crate::syscalls::syscall_halt;
+    use crate::syscalls::{commit_env, random_seed, syscall_halt};
 
     use getrandom::{register_custom_getrandom, Error};
     use p3_baby_bear::BabyBear;
@@ -50,6 +54,7 @@ mod zkvm {
         {
             PUBLIC_VALUES_HASHER = Some(Sha256::new());
             DEFERRED_PROOFS_DIGEST = Some([BabyBear::zero(); 8]);
+            commit_env();
 
             extern "C" {
                 fn main();
@@ -81,19 +86,23 @@ mod zkvm {
         sym STACK_TOP
     );
 
-    static GETRANDOM_WARNING_ONCE: std::sync::Once = std::sync::Once::new();
+    static mut RNG: Option<rand::rngs::StdRng> = None;
 
     fn zkvm_getrandom(s: &mut [u8]) -> Result<(), Error> {
-        use rand::Rng;
+        use rand::RngCore;
         use rand::SeedableRng;
 
-        GETRANDOM_WARNING_ONCE.call_once(|| {
-            println!("WARNING: Using insecure random number generator");
-        });
-        let mut rng = rand::rngs::StdRng::seed_from_u64(123);
-        for i in 0..s.len() {
-            s[i] = rng.gen();
-        }
+        // SAFETY: Single threaded, so nothing else can touch this while we're working.
+        let rng = unsafe {
+            RNG.get_or_insert_with(|| match random_seed() {
+                Some(seed) => rand::rngs::StdRng::from_seed(seed),
+                None => {
+                    println!("WARNING: Using insecure random number generator");
+                    rand::rngs::StdRng::seed_from_u64(123)
+                }
+            })
+        };
+        rng.fill_bytes(s);
         Ok(())
     }
 
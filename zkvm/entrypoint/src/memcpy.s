@@ -205,10 +205,10 @@
 	.attribute	4, 16
 	.attribute	5, "rv32im"
 	.file	"musl_memcpy.c"
-	.globl	memcpy
+	.globl	musl_memcpy
 	.p2align	2
-	.type	memcpy,@function
-memcpy:
+	.type	musl_memcpy,@function
+musl_memcpy:
 	andi	a3, a1, 3
 	seqz	a3, a3
 	seqz	a4, a2
@@ -491,7 +491,7 @@ memcpy:
 	bnez	a1, .LBBmemcpy0_29
 	j	.LBBmemcpy0_30
 .Lfuncmemcpy_end0:
-	.size	memcpy, .Lfuncmemcpy_end0-memcpy
+	.size	musl_memcpy, .Lfuncmemcpy_end0-musl_memcpy
 
 	.ident	"Ubuntu clang version 14.0.6-++20220622053131+f28c006a5895-1~exp1~20220622173215.157"
 	.section	".note.GNU-stack","",@progbits
@@ -0,0 +1,46 @@
+//! `memcpy` and `memset` for the zkVM, which copy word-aligned memory with the `MEMCPY`
+//! precompile and fall back to the musl implementations in `memcpy.s` and `memset.s`.
+
+use crate::syscalls::syscall_memcpy;
+
+/// The number of bytes copied by one `MEMCPY` syscall.
+const MEMCPY_BYTES: usize = 128;
+
+extern "C" {
+    fn musl_memcpy(dest: *mut u8, src: *const u8, n: usize) -> *mut u8;
+    fn musl_memset(s: *mut u8, c: i32, n: usize) -> *mut u8;
+}
+
+#[allow(clippy::missing_safety_doc)]
+#[no_mangle]
+pub unsafe extern "C" fn memcpy(dest: *mut u8, src: *const u8, n: usize) -> *mut u8 {
+    if (dest as usize | src as usize) % 4 != 0 || n < MEMCPY_BYTES {
+        return musl_memcpy(dest, src, n);
+    }
+
+    let mut offset = 0;
+    while n - offset >= MEMCPY_BYTES {
+        syscall_memcpy(dest.add(offset) as *mut u32, src.add(offset) as *const u32);
+        offset += MEMCPY_BYTES;
+    }
+    musl_memcpy(dest.add(offset), src.add(offset), n - offset);
+    dest
+}
+
+#[allow(clippy::missing_safety_doc)]
+#[no_mangle]
+pub unsafe extern "C" fn memset(s: *mut u8, c: i32, n: usize) -> *mut u8 {
+    if s as usize % 4 != 0 || n < 2 * MEMCPY_BYTES {
+        return musl_memset(s, c, n);
+    }
+
+    // Fill the first block, and then copy it over the following ones.
+    musl_memset(s, c, MEMCPY_BYTES);
+    let mut offset = MEMCPY_BYTES;
+    while n - offset >= MEMCPY_BYTES {
+        syscall_memcpy(s.add(offset) as *mut u32, s as *const u32);
+        offset += MEMCPY_BYTES;
+    }
+    musl_memset(s.add(offset), c, n - offset);
+    s
+}
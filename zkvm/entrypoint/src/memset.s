@@ -205,10 +205,10 @@
 	.attribute	4, 16
 	.attribute	5, "rv32im"
 	.file	"musl_memset.c"
-	.globl	memset
+	.globl	musl_memset
 	.p2align	2
-	.type	memset,@function
-memset:
+	.type	musl_memset,@function
+musl_memset:
 	beqz	a2, .LBB0_9memset
 	sb	a1, 0(a0)
 	add	a3, a2, a0
@@ -274,7 +274,7 @@ memset:
 .LBB0_9memset:
 	ret
 .Lfunc_end0memset:
-	.size	memset, .Lfunc_end0memset-memset
+	.size	musl_memset, .Lfunc_end0memset-musl_memset
 
 	.ident	"Ubuntu clang version 14.0.6-++20220622053131+f28c006a5895-1~exp1~20220622173215.157"
 	.section	".note.GNU-stack","",@progbits
@@ -0,0 +1,24 @@
+#[cfg(target_os = "zkvm")]
+use core::arch::asm;
+
+/// Multiplies two integers of up to 4096 bits modulo a third.
+///
+/// `y` points to the number of words `n`, which is between 1 and 128, followed by `n` words for
+/// `y` and `n` words for the modulus. `x` points to `n` words. All the integers are little-endian,
+/// and the modulus must be nonzero. The result is stored in `x`.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_bigint_mulmod(x: *mut u32, y: *const u32) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::BIGINT_MULMOD,
+            in("a0") x,
+            in("a1") y
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
@@ -0,0 +1,27 @@
+#[cfg(target_os = "zkvm")]
+use core::arch::asm;
+
+/// Blake2b compression function `F`, as specified in EIP-152.
+///
+/// `state` points to the 8 u64s of `h`. `input` points to 38 words: the number of rounds, the 16
+/// u64s of the message `m`, the 2 u64s of the offset counter `t`, and the final block flag `f`,
+/// which must be 0 or 1. The two must not overlap, and the number of rounds must be less than
+/// `2^24`.
+///
+/// The result is written over the input state.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_blake2b_compress(state: *mut u64, input: *const u32) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::BLAKE2B_COMPRESS,
+            in("a0") state,
+            in("a1") input
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
@@ -40,3 +40,96 @@ pub extern "C" fn syscall_bn254_double(p: *mut u32) {
     #[cfg(not(target_os = "zkvm"))]
     unreachable!()
 }
+
+/// Computes the multi-scalar multiplication of `n` Bn254 points by `n` scalars.
+///
+/// `points` holds the `n` points of 16 words each, and `scalars` holds the `n` scalars of 8
+/// little-endian words each. `n` must be between 1 and 16. The result is written to the 16 words
+/// at `result`, unless it is the identity, in which case `true` is returned and `result` is left
+/// unspecified.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_bn254_msm(
+    result: *mut u32,
+    points: *const u32,
+    scalars: *const u32,
+    n: usize,
+) -> bool {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        // The syscall only takes two arguments, so the number of points and the pointers are
+        // passed in memory, followed by a word in which the syscall writes whether the result is
+        // the identity.
+        let mut args = [n as u32, points as u32, scalars as u32, 0];
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::BN254_MSM,
+            in("a0") result,
+            in("a1") args.as_mut_ptr()
+        );
+        args[3] == 1
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
+
+/// Adds two elements of the quadratic extension of the Bn254 base field.
+///
+/// The result is stored in the first element.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_bn254_fp2_add(x: *mut u32, y: *const u32) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::BN254_FP2_ADD,
+            in("a0") x,
+            in("a1") y
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
+
+/// Subtracts two elements of the quadratic extension of the Bn254 base field.
+///
+/// The result is stored in the first element.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_bn254_fp2_sub(x: *mut u32, y: *const u32) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::BN254_FP2_SUB,
+            in("a0") x,
+            in("a1") y
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
+
+/// Multiplies two elements of the quadratic extension of the Bn254 base field.
+///
+/// The result is stored in the first element.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_bn254_fp2_mul(x: *mut u32, y: *const u32) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::BN254_FP2_MUL,
+            in("a0") x,
+            in("a1") y
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
@@ -47,3 +47,47 @@ pub extern "C" fn syscall_ed_decompress(point: &mut [u8; 64]) {
     #[cfg(not(target_os = "zkvm"))]
     unreachable!()
 }
+
+/// Multiplies an Edwards point by a word of a scalar.
+///
+/// `p` is a 32 word slice whose first half is an accumulator `A` and whose second half is a point
+/// `P`. `A` is overwritten with `[2^32]A + [w]P`, where `w` is the word at `scalar`, which must not
+/// overlap `p`. A 256-bit scalar is multiplied by setting `A` to the identity and calling this once
+/// per word, from the most significant one.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_ed_scalar_mul(p: *mut u32, scalar: *const u32) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::ED_SCALAR_MUL,
+            in("a0") p,
+            in("a1") scalar
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
+
+/// Compresses an Edwards point with reduced coordinates.
+///
+/// The sign of x is stored in the top bit of y, so that the second half of the input array is the
+/// compressed point.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_ed_compress(p: *mut u32) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::ED_COMPRESS,
+            in("a0") p,
+            in("a1") 0
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
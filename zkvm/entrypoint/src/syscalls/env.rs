@@ -0,0 +1,187 @@
+use sp1_primitives::CommittedEnv;
+
+cfg_if::cfg_if! {
+    if #[cfg(target_os = "zkvm")] {
+        use core::arch::asm;
+        use crate::syscalls::{sys_alloc_aligned, syscall_hint_read, syscall_write};
+        use sp1_precompiles::io::FD_PUBLIC_VALUES;
+
+        /// The environment committed on start-up, if any.
+        static mut COMMITTED_ENV: Option<CommittedEnv> = None;
+    }
+}
+
+/// Looks up the environment variable whose name is the `len` bytes at `name`.
+///
+/// If it is set, its value is the next slice of the hint stream and its length is returned, and
+/// otherwise `usize::MAX` is returned.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_getenv(name: *const u8, len: usize) -> usize {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        let value_len;
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::GETENV,
+            in("a0") name,
+            in("a1") len,
+            lateout("t0") value_len,
+        );
+        value_len
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
+
+/// Returns the number of arguments of the program.
+#[no_mangle]
+pub extern "C" fn syscall_argc() -> usize {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        let argc;
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::ARGC,
+            lateout("t0") argc,
+        );
+        argc
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
+
+/// Looks up the argument at `index`, which becomes the next slice of the hint stream, and returns
+/// its length.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_argv(index: usize) -> usize {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        let len;
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::ARGV,
+            in("a0") index,
+            lateout("t0") len,
+        );
+        len
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
+
+/// Returns the length of the environment which the host asked the program to commit, which is the
+/// next slice of the hint stream, or `usize::MAX` if the host committed nothing.
+#[no_mangle]
+pub extern "C" fn syscall_committed_env() -> usize {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        let len;
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::COMMITTED_ENV,
+            lateout("t0") len,
+        );
+        len
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
+
+/// Returns the length of the seed of the random number generator, which is the next slice of the
+/// hint stream, or `usize::MAX` if the host did not provide one.
+#[no_mangle]
+pub extern "C" fn syscall_random_seed() -> usize {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        let len;
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::RANDOM_SEED,
+            lateout("t0") len,
+        );
+        len
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
+
+/// Reads the next slice of the hint stream, of `len` bytes, into `buf` if it has room for it.
+/// Otherwise the slice is read into a scratch allocation, since a hint can only be read once.
+#[allow(unused_variables)]
+pub(crate) unsafe fn read_hint_into(buf: *mut u32, words: usize, len: usize) -> *mut u8 {
+    #[cfg(target_os = "zkvm")]
+    {
+        let ptr = if !buf.is_null() && len <= words * 4 {
+            buf as *mut u8
+        } else {
+            sys_alloc_aligned(len, 4)
+        };
+        syscall_hint_read(ptr, len);
+        ptr
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
+
+/// Copies `bytes` into `buf` if it has room for them, returning their length.
+#[cfg(target_os = "zkvm")]
+pub(crate) unsafe fn copy_into(buf: *mut u32, words: usize, bytes: &[u8]) -> usize {
+    if !buf.is_null() && bytes.len() <= words * 4 {
+        core::ptr::copy_nonoverlapping(bytes.as_ptr(), buf as *mut u8, bytes.len());
+    }
+    bytes.len()
+}
+
+/// Commits the environment which the host asked the program to commit to the public values. This
+/// runs before `main`, so it comes before anything the program commits itself.
+#[cfg(target_os = "zkvm")]
+pub(crate) fn commit_env() {
+    let len = syscall_committed_env();
+    if len != usize::MAX {
+        unsafe {
+            let ptr = read_hint_into(core::ptr::null_mut(), 0, len);
+            syscall_write(FD_PUBLIC_VALUES, ptr, len);
+            let bytes = core::slice::from_raw_parts(ptr, len);
+            COMMITTED_ENV = Some(bincode::deserialize(bytes).expect("deserialization failed"));
+        }
+    }
+}
+
+/// The environment committed on start-up. Its parts are served from it rather than by the host, so
+/// that they are the values the program actually ran with.
+pub(crate) fn committed_env() -> Option<&'static CommittedEnv> {
+    // SAFETY: Single threaded, and only written before `main`.
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        COMMITTED_ENV.as_ref()
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
+
+/// Reads the seed of the random number generator provided by the host, if any.
+#[cfg(target_os = "zkvm")]
+pub(crate) fn random_seed() -> Option<[u8; 32]> {
+    if let Some(seed) = committed_env().and_then(|env| env.random_seed) {
+        return Some(seed);
+    }
+    let len = syscall_random_seed();
+    if len == usize::MAX {
+        return None;
+    }
+    let mut seed = [0u8; 32];
+    unsafe {
+        let ptr = read_hint_into(core::ptr::null_mut(), 0, len);
+        seed.copy_from_slice(core::slice::from_raw_parts(ptr, len));
+    }
+    Some(seed)
+}
@@ -0,0 +1,69 @@
+#[cfg(target_os = "zkvm")]
+use core::arch::asm;
+
+/// Opens the file whose path is the `len` bytes at `path`, returning its file descriptor, or
+/// `usize::MAX` if there is no such file.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_fs_open(path: *const u8, len: usize) -> usize {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        let fd;
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::FS_OPEN,
+            in("a0") path,
+            in("a1") len,
+            lateout("t0") fd,
+        );
+        fd
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
+
+/// Reads up to `len` bytes of the file `fd`, which become the next slice of the hint stream, and
+/// returns their number.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_fs_read(fd: usize, len: usize) -> usize {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        let nbytes;
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::FS_READ,
+            in("a0") fd,
+            in("a1") len,
+            lateout("t0") nbytes,
+        );
+        nbytes
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
+
+/// Moves the offset of the file `fd` by `offset` from its start, its current offset or its end
+/// when `whence` is 0, 1 or 2, returning the new offset, or `usize::MAX` if it is out of range.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_fs_seek(fd: usize, offset: usize, whence: usize) -> usize {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        let new_offset;
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::FS_SEEK,
+            in("a0") fd,
+            in("a1") offset,
+            in("a2") whence,
+            lateout("t0") new_offset,
+        );
+        new_offset
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
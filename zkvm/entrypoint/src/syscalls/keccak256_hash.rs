@@ -0,0 +1,26 @@
+#[cfg(target_os = "zkvm")]
+use core::arch::asm;
+
+/// Computes the Keccak-256 hash of the `len` bytes at `input`, as used by Ethereum.
+///
+/// `input` must be word aligned, and `len` must be less than `2^24`. The 32-byte digest is written
+/// to the 8 words at `out`.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_keccak256_hash(input: *const u8, len: usize, out: *mut u32) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        // The syscall only takes two arguments, so the input pointer and length are passed in
+        // memory.
+        let args = [input as u32, len as u32];
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::KECCAK256_HASH,
+            in("a0") out,
+            in("a1") args.as_ptr()
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
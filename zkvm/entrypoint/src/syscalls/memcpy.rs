@@ -0,0 +1,22 @@
+#[cfg(target_os = "zkvm")]
+use core::arch::asm;
+
+/// Copies 32 words from `src` to `dst`.
+///
+/// Both pointers must be word aligned. The regions may overlap.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_memcpy(dst: *mut u32, src: *const u32) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::MEMCPY,
+            in("a0") dst,
+            in("a1") src
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
@@ -1,27 +1,53 @@
+mod bigint;
+mod blake2b_compress;
 mod blake3_compress;
+mod bls12381;
 mod bn254;
 mod ed25519;
+mod env;
+mod fs;
 mod halt;
 mod io;
+mod keccak256_hash;
 mod keccak_permute;
+mod memcpy;
 mod memory;
+mod poseidon2_permute;
+mod ripemd160_compress;
 mod secp256k1;
+mod secp256r1;
+mod sha512_compress;
+mod sha512_extend;
 mod sha_compress;
 mod sha_extend;
 mod sys;
+mod uint256;
 mod unconstrained;
 mod verify;
 
+pub use bigint::*;
+pub use blake2b_compress::*;
+pub use bls12381::*;
 pub use bn254::*;
 pub use ed25519::*;
+pub use env::*;
+pub use fs::*;
 pub use halt::*;
 pub use io::*;
+pub use keccak256_hash::*;
 pub use keccak_permute::*;
+pub use memcpy::*;
 pub use memory::*;
+pub use poseidon2_permute::*;
+pub use ripemd160_compress::*;
 pub use secp256k1::*;
+pub use secp256r1::*;
+pub use sha512_compress::*;
+pub use sha512_extend::*;
 pub use sha_compress::*;
 pub use sha_extend::*;
 pub use sys::*;
+pub use uint256::*;
 pub use unconstrained::*;
 pub use verify::*;
 
@@ -76,14 +102,113 @@ pub const BN254_DOUBLE: u32 = 0x00_00_01_0F;
 /// Executes the `COMMIT` precompile.
 pub const COMMIT: u32 = 0x00_00_00_10;
 
+/// Executes `SECP256R1_ADD`.
+pub const SECP256R1_ADD: u32 = 0x00_01_01_11;
+
+/// Executes `SECP256R1_DOUBLE`.
+pub const SECP256R1_DOUBLE: u32 = 0x00_00_01_12;
+
+/// Executes `SECP256R1_DECOMPRESS`.
+pub const SECP256R1_DECOMPRESS: u32 = 0x00_00_01_13;
+
+/// Executes `BLS12381_ADD`.
+pub const BLS12381_ADD: u32 = 0x00_01_01_14;
+
+/// Executes `BLS12381_DOUBLE`.
+pub const BLS12381_DOUBLE: u32 = 0x00_00_01_15;
+
+/// Executes `BLS12381_DECOMPRESS`.
+pub const BLS12381_DECOMPRESS: u32 = 0x00_00_01_16;
+
+/// Executes `BN254_FP2_ADD`.
+pub const BN254_FP2_ADD: u32 = 0x00_01_01_17;
+
+/// Executes `BN254_FP2_SUB`.
+pub const BN254_FP2_SUB: u32 = 0x00_01_01_18;
+
+/// Executes `BN254_FP2_MUL`.
+pub const BN254_FP2_MUL: u32 = 0x00_01_01_19;
+
 /// Executes the `COMMIT_DEFERRED_PROOFS` precompile.
 pub const COMMIT_DEFERRED_PROOFS: u32 = 0x00_00_00_1A;
 
 /// Executes the `VERIFY_SP1_PROOF` precompile.
 pub const VERIFY_SP1_PROOF: u32 = 0x00_00_00_1B;
 
+/// Executes `BLS12381_FP2_ADD`.
+pub const BLS12381_FP2_ADD: u32 = 0x00_01_01_1C;
+
+/// Executes `BLS12381_FP2_SUB`.
+pub const BLS12381_FP2_SUB: u32 = 0x00_01_01_1D;
+
+/// Executes `BLS12381_FP2_MUL`.
+pub const BLS12381_FP2_MUL: u32 = 0x00_01_01_1E;
+
+/// Executes `UINT256_MULMOD`.
+pub const UINT256_MULMOD: u32 = 0x00_01_01_1F;
+
+/// Executes `SHA512_EXTEND`.
+pub const SHA512_EXTEND: u32 = 0x00_40_01_20;
+
+/// Executes `SHA512_COMPRESS`.
+pub const SHA512_COMPRESS: u32 = 0x00_01_01_21;
+
+/// Executes `BLAKE2B_COMPRESS`.
+pub const BLAKE2B_COMPRESS: u32 = 0x00_01_01_22;
+
+/// Executes `POSEIDON2_PERMUTE`.
+pub const POSEIDON2_PERMUTE: u32 = 0x00_01_01_23;
+
+/// Executes `KECCAK256_HASH`.
+pub const KECCAK256_HASH: u32 = 0x00_01_01_24;
+
+/// Executes `SECP256K1_MSM`.
+pub const SECP256K1_MSM: u32 = 0x44_00_01_25;
+
+/// Executes `BN254_MSM`.
+pub const BN254_MSM: u32 = 0x44_00_01_26;
+
+/// Executes `MEMCPY`.
+pub const MEMCPY: u32 = 0x00_01_01_27;
+
+/// Executes `ED_SCALAR_MUL`.
+pub const ED_SCALAR_MUL: u32 = 0x00_80_01_28;
+
+/// Executes `ED_COMPRESS`.
+pub const ED_COMPRESS: u32 = 0x00_00_01_29;
+
+/// Executes `RIPEMD160_COMPRESS`.
+pub const RIPEMD160_COMPRESS: u32 = 0x00_01_01_2A;
+
+/// Executes `BIGINT_MULMOD`.
+pub const BIGINT_MULMOD: u32 = 0x00_01_01_2B;
+
 /// Executes `HINT_LEN`.
 pub const HINT_LEN: u32 = 0x00_00_00_F0;
 
 /// Executes `HINT_READ`.
 pub const HINT_READ: u32 = 0x00_00_00_F1;
+
+/// Executes `GETENV`.
+pub const GETENV: u32 = 0x00_00_00_F2;
+
+/// Executes `ARGC`.
+pub const ARGC: u32 = 0x00_00_00_F3;
+
+/// Executes `ARGV`.
+pub const ARGV: u32 = 0x00_00_00_F4;
+
+/// Executes `COMMITTED_ENV`.
+pub const COMMITTED_ENV: u32 = 0x00_00_00_F5;
+
+/// Executes `RANDOM_SEED`.
+pub const RANDOM_SEED: u32 = 0x00_00_00_F6;
+
+/// Executes `FS_OPEN`.
+pub const FS_OPEN: u32 = 0x00_00_00_F7;
+
+/// Executes `FS_READ`.
+pub const FS_READ: u32 = 0x00_00_00_F8;
+
+/// Executes `FS_SEEK`.
+pub const FS_SEEK: u32 = 0x00_00_00_F9;
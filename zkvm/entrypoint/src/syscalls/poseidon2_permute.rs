@@ -0,0 +1,23 @@
+#[cfg(target_os = "zkvm")]
+use core::arch::asm;
+
+/// Executes the Poseidon2 permutation over BabyBear on the given state.
+///
+/// `state` points to 16 words, each of which must be a canonical BabyBear element. The result is
+/// written over the input state.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_poseidon2_permute(state: *mut u32) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::POSEIDON2_PERMUTE,
+            in("a0") state,
+            in("a1") 0
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
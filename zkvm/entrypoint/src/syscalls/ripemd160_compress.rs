@@ -0,0 +1,25 @@
+#[cfg(target_os = "zkvm")]
+use core::arch::asm;
+
+/// The RIPEMD-160 compression function.
+///
+/// `state` points to the 5 words of `h`, and `block` points to the 16 little-endian words of the
+/// message block. The two must not overlap.
+///
+/// The result is written over the input state.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_ripemd160_compress(state: *mut u32, block: *const u32) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::RIPEMD160_COMPRESS,
+            in("a0") state,
+            in("a1") block
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
@@ -41,6 +41,39 @@ pub extern "C" fn syscall_secp256k1_double(p: *mut u32) {
     unreachable!()
 }
 
+/// Computes the multi-scalar multiplication of `n` Secp256k1 points by `n` scalars.
+///
+/// `points` holds the `n` points of 16 words each, and `scalars` holds the `n` scalars of 8
+/// little-endian words each. `n` must be between 1 and 16. The result is written to the 16 words
+/// at `result`, unless it is the identity, in which case `true` is returned and `result` is left
+/// unspecified.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_secp256k1_msm(
+    result: *mut u32,
+    points: *const u32,
+    scalars: *const u32,
+    n: usize,
+) -> bool {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        // The syscall only takes two arguments, so the number of points and the pointers are
+        // passed in memory, followed by a word in which the syscall writes whether the result is
+        // the identity.
+        let mut args = [n as u32, points as u32, scalars as u32, 0];
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::SECP256K1_MSM,
+            in("a0") result,
+            in("a1") args.as_mut_ptr()
+        );
+        args[3] == 1
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
+
 /// Decompresses a compressed Secp256k1 point.
 ///
 /// The input array should be 32 bytes long, with the first 16 bytes containing the X coordinate in
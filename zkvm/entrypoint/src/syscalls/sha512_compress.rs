@@ -0,0 +1,21 @@
+#[cfg(target_os = "zkvm")]
+use core::arch::asm;
+
+/// Runs the SHA-512 compression function over the 80-entry message schedule `w`, updating the 8
+/// u64s of `state` in place.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_sha512_compress(w: *mut u64, state: *mut u64) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::SHA512_COMPRESS,
+            in("a0") w,
+            in("a1") state,
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
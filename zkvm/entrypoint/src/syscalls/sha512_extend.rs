@@ -0,0 +1,20 @@
+#[cfg(target_os = "zkvm")]
+use core::arch::asm;
+
+/// Extends the first 16 u64s of `w` into the full 80-entry SHA-512 message schedule.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_sha512_extend(w: *mut u64) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::SHA512_EXTEND,
+            in("a0") w,
+            in("a1") 0
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
@@ -1,5 +1,14 @@
 use crate::syscalls::{syscall_halt, syscall_write};
 
+cfg_if::cfg_if! {
+    if #[cfg(target_os = "zkvm")] {
+        use crate::syscalls::{
+            committed_env, copy_into, read_hint_into, sys_alloc_aligned, syscall_argc,
+            syscall_argv, syscall_getenv,
+        };
+    }
+}
+
 #[allow(clippy::missing_safety_doc)]
 #[no_mangle]
 pub unsafe extern "C" fn sys_panic(msg_ptr: *const u8, len: usize) -> ! {
@@ -15,13 +24,70 @@ pub fn sys_getenv(
     varname: *const u8,
     varname_len: usize,
 ) -> usize {
-    0
+    #[cfg(target_os = "zkvm")]
+    {
+        if let Some(vars) = committed_env().and_then(|env| env.vars.as_ref()) {
+            let name = unsafe { core::slice::from_raw_parts(varname, varname_len) };
+            let value = core::str::from_utf8(name)
+                .ok()
+                .and_then(|name| vars.get(name));
+            return match value {
+                Some(value) => unsafe { copy_into(recv_buf, words, value.as_bytes()) },
+                None => usize::MAX,
+            };
+        }
+        let len = syscall_getenv(varname, varname_len);
+        if len != usize::MAX {
+            unsafe { read_hint_into(recv_buf, words, len) };
+        }
+        len
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
+
+#[no_mangle]
+pub fn sys_argc() -> usize {
+    #[cfg(target_os = "zkvm")]
+    {
+        match committed_env().and_then(|env| env.args.as_ref()) {
+            Some(args) => args.len(),
+            None => syscall_argc(),
+        }
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
+
+#[allow(unused_variables)]
+#[no_mangle]
+pub fn sys_argv(recv_buf: *mut u32, words: usize, index: usize) -> usize {
+    #[cfg(target_os = "zkvm")]
+    {
+        if let Some(args) = committed_env().and_then(|env| env.args.as_ref()) {
+            return unsafe { copy_into(recv_buf, words, args[index].as_bytes()) };
+        }
+        let len = syscall_argv(index);
+        unsafe { read_hint_into(recv_buf, words, len) };
+        len
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
 }
 
 #[allow(unused_variables)]
 #[no_mangle]
 pub fn sys_alloc_words(nwords: usize) -> *mut u32 {
-    core::ptr::null_mut()
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        sys_alloc_aligned(nwords * 4, 4) as *mut u32
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
 }
 
 #[allow(unused_unsafe)]
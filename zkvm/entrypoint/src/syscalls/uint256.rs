@@ -0,0 +1,23 @@
+#[cfg(target_os = "zkvm")]
+use core::arch::asm;
+
+/// Multiplies two 256-bit integers modulo a third.
+///
+/// `x` points to 8 little-endian words, and `y` points to 8 words for `y` followed by 8 words for
+/// the modulus. A zero modulus stands for `2^256`. The result is stored in `x`.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_uint256_mulmod(x: *mut u32, y: *const u32) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::UINT256_MULMOD,
+            in("a0") x,
+            in("a1") y
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
@@ -0,0 +1,172 @@
+use crate::syscall_bigint_mulmod;
+
+/// The largest number of words of an operand, which makes for 4096-bit integers.
+pub const MAX_WORDS: usize = 128;
+
+/// Computes `x * y mod modulus` for little-endian words using the bigint_mulmod precompile.
+///
+/// The three operands must have the same number of words, which is at most `MAX_WORDS`, and the
+/// modulus must be nonzero.
+pub fn mulmod(x: &[u32], y: &[u32], modulus: &[u32]) -> Vec<u32> {
+    let num_words = modulus.len();
+    assert!(
+        (1..=MAX_WORDS).contains(&num_words),
+        "bigint: the modulus must have between 1 and {} words",
+        MAX_WORDS
+    );
+    assert_eq!(
+        x.len(),
+        num_words,
+        "bigint: x must have as many words as the modulus"
+    );
+    assert_eq!(
+        y.len(),
+        num_words,
+        "bigint: y must have as many words as the modulus"
+    );
+    assert!(!is_zero(modulus), "bigint: the modulus must be nonzero");
+
+    let mut result = x.to_vec();
+    call_mulmod(&mut result, y, modulus);
+    result
+}
+
+/// Computes `base^exponent mod modulus` for big-endian byte encodings, as specified by the
+/// EIP-198 `MODEXP` precompile.
+///
+/// The result has as many bytes as the modulus, and is zero if the modulus is zero. The modulus
+/// must fit in 4096 bits, while the base and the exponent may have any length.
+pub fn modexp(base: &[u8], exponent: &[u8], modulus: &[u8]) -> Vec<u8> {
+    let mut output = vec![0u8; modulus.len()];
+    let modulus_words = words_from_be_bytes(modulus);
+    if is_zero(&modulus_words) {
+        return output;
+    }
+    assert!(
+        modulus_words.len() <= MAX_WORDS,
+        "bigint: the modulus must fit in {} bits",
+        32 * MAX_WORDS
+    );
+    let num_words = modulus_words.len();
+
+    let base = reduce(&words_from_be_bytes(base), &modulus_words);
+
+    // Square-and-multiply from the most significant bit, starting from `1 mod modulus`.
+    let mut one = vec![0u32; num_words];
+    one[0] = 1;
+    let mut result = mulmod(&one, &one, &modulus_words);
+    for byte in exponent.iter() {
+        for bit in (0..8).rev() {
+            result = mulmod(&result, &result, &modulus_words);
+            if (byte >> bit) & 1 == 1 {
+                result = mulmod(&result, &base, &modulus_words);
+            }
+        }
+    }
+
+    // The result is less than the modulus, so it fits in as many bytes.
+    for (i, byte) in output.iter_mut().rev().enumerate() {
+        if i < 4 * num_words {
+            *byte = (result[i / 4] >> (8 * (i % 4))) as u8;
+        }
+    }
+    output
+}
+
+fn call_mulmod(x: &mut [u32], y: &[u32], modulus: &[u32]) {
+    let mut args = Vec::with_capacity(2 * modulus.len() + 1);
+    args.push(modulus.len() as u32);
+    args.extend_from_slice(y);
+    args.extend_from_slice(modulus);
+    unsafe {
+        syscall_bigint_mulmod(x.as_mut_ptr(), args.as_ptr());
+    }
+}
+
+/// Reduces an integer of any number of words modulo `modulus`, whose most significant word is
+/// nonzero. The result has as many words as the modulus.
+fn reduce(x: &[u32], modulus: &[u32]) -> Vec<u32> {
+    let num_words = modulus.len();
+    if x.len() <= num_words {
+        let mut x = x.to_vec();
+        x.resize(num_words, 0);
+        let mut one = vec![0u32; num_words];
+        one[0] = 1;
+        return mulmod(&x, &one, modulus);
+    }
+
+    // Shift in one word at a time from the most significant one, as `acc * 2^32 + word`.
+    let mut shift = vec![0u32; num_words];
+    if num_words == 1 {
+        shift[0] = ((1u64 << 32) % modulus[0] as u64) as u32;
+    } else {
+        shift[1] = 1;
+    }
+    let mut acc = vec![0u32; num_words];
+    for &word in x.iter().rev() {
+        acc = mulmod(&acc, &shift, modulus);
+        let word = reduce(&[word], modulus);
+        let (sum, carry) = add(&acc, &word);
+        acc = if carry || !lt(&sum, modulus) {
+            sub(&sum, modulus)
+        } else {
+            sum
+        };
+    }
+    acc
+}
+
+/// Returns the wrapping sum of `x` and `y`, and whether it overflowed.
+fn add(x: &[u32], y: &[u32]) -> (Vec<u32>, bool) {
+    let mut carry = false;
+    let sum = x
+        .iter()
+        .zip(y.iter())
+        .map(|(&x, &y)| {
+            let (sum, c1) = x.overflowing_add(y);
+            let (sum, c2) = sum.overflowing_add(carry as u32);
+            carry = c1 || c2;
+            sum
+        })
+        .collect();
+    (sum, carry)
+}
+
+/// Returns the wrapping difference of `x` and `y`.
+fn sub(x: &[u32], y: &[u32]) -> Vec<u32> {
+    let mut borrow = false;
+    x.iter()
+        .zip(y.iter())
+        .map(|(&x, &y)| {
+            let (diff, b1) = x.overflowing_sub(y);
+            let (diff, b2) = diff.overflowing_sub(borrow as u32);
+            borrow = b1 || b2;
+            diff
+        })
+        .collect()
+}
+
+fn is_zero(x: &[u32]) -> bool {
+    x.iter().all(|&word| word == 0)
+}
+
+/// Returns true if `x < y`, for integers with the same number of words.
+fn lt(x: &[u32], y: &[u32]) -> bool {
+    x.iter().rev().lt(y.iter().rev())
+}
+
+/// Returns the little-endian words of a big-endian byte encoding, without leading zero words.
+fn words_from_be_bytes(bytes: &[u8]) -> Vec<u32> {
+    let mut words = bytes
+        .rchunks(4)
+        .map(|chunk| {
+            let mut word = [0u8; 4];
+            word[4 - chunk.len()..].copy_from_slice(chunk);
+            u32::from_be_bytes(word)
+        })
+        .collect::<Vec<_>>();
+    while words.last() == Some(&0) {
+        words.pop();
+    }
+    words
+}
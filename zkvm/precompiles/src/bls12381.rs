@@ -0,0 +1,94 @@
+use anyhow::{anyhow, Result};
+
+use crate::utils::CurveOperations;
+use crate::{syscall_bls12381_add, syscall_bls12381_decompress, syscall_bls12381_double};
+
+/// The number of words needed to represent a point on the BLS12-381 G1 curve.
+pub const NUM_WORDS: usize = 24;
+
+/// The base field modulus of BLS12-381 in big-endian byte order.
+const MODULUS: [u8; 48] = [
+    0x1a, 0x01, 0x11, 0xea, 0x39, 0x7f, 0xe6, 0x9a, 0x4b, 0x1b, 0xa7, 0xb6, 0x43, 0x4b, 0xac, 0xd7,
+    0x64, 0x77, 0x4b, 0x84, 0xf3, 0x85, 0x12, 0xbf, 0x67, 0x30, 0xd2, 0xa0, 0xf6, 0xb0, 0xf6, 0x24,
+    0x1e, 0xab, 0xff, 0xfe, 0xb1, 0x53, 0xff, 0xff, 0xb9, 0xfe, 0xff, 0xff, 0xff, 0xff, 0xaa, 0xab,
+];
+
+/// Flags stored in the most significant bits of a ZCash-encoded point.
+const COMPRESSION_FLAG: u8 = 0b1000_0000;
+const INFINITY_FLAG: u8 = 0b0100_0000;
+const SIGN_FLAG: u8 = 0b0010_0000;
+
+#[derive(Copy, Clone)]
+pub struct Bls12381;
+
+impl CurveOperations<NUM_WORDS> for Bls12381 {
+    // The values are taken from https://neuromancer.sk/std/bls/BLS12-381.
+    const GENERATOR: [u32; NUM_WORDS] = [
+        3676489403, 4214943754, 4185529071, 1817569343, 387689560, 2706258495, 2541009157,
+        3278408783, 1336519695, 647324556, 832034708, 401724327, 1187375073, 212476713, 2726857444,
+        3493644100, 738505709, 14358731, 3587181302, 4243972245, 1948093156, 2694721773,
+        3819610353, 146011265,
+    ];
+
+    fn add_assign(limbs: &mut [u32; NUM_WORDS], other: &[u32; NUM_WORDS]) {
+        unsafe {
+            syscall_bls12381_add(limbs.as_mut_ptr(), other.as_ptr());
+        }
+    }
+
+    fn double(limbs: &mut [u32; NUM_WORDS]) {
+        unsafe {
+            syscall_bls12381_double(limbs.as_mut_ptr());
+        }
+    }
+}
+
+/// Decompresses a G1 point in the ZCash serialization format using the bls12381_decompress
+/// precompile.
+///
+/// Returns the uncompressed point as the big-endian x coordinate followed by the big-endian y
+/// coordinate. The point at infinity has no affine representation and is rejected.
+///
+/// Warning: this function does not check that the point is in the prime-order subgroup.
+pub fn decompress_pubkey(compressed_key: &[u8; 48]) -> Result<[u8; 96]> {
+    let flags = compressed_key[0];
+    if flags & COMPRESSION_FLAG == 0 {
+        return Err(anyhow!("Point is not compressed"));
+    }
+    if flags & INFINITY_FLAG != 0 {
+        return Err(anyhow!("Point at infinity"));
+    }
+
+    let mut decompressed_key = [0u8; 96];
+    decompressed_key[..48].copy_from_slice(compressed_key);
+    decompressed_key[0] &= !(COMPRESSION_FLAG | INFINITY_FLAG | SIGN_FLAG);
+
+    // The precompile selects the root by parity, while the ZCash format selects it by whether it
+    // is the lexicographically largest of `y` and `p - y`, so we fix up the sign afterwards.
+    unsafe {
+        syscall_bls12381_decompress(&mut decompressed_key, false);
+    }
+
+    let y: [u8; 48] = decompressed_key[48..].try_into().unwrap();
+    let neg_y = sub_be(&MODULUS, &y);
+    if (y > neg_y) != (flags & SIGN_FLAG != 0) {
+        decompressed_key[48..].copy_from_slice(&neg_y);
+    }
+
+    Ok(decompressed_key)
+}
+
+/// Computes `a - b` for big-endian integers, assuming `a >= b`.
+fn sub_be(a: &[u8; 48], b: &[u8; 48]) -> [u8; 48] {
+    let mut result = [0u8; 48];
+    let mut borrow = 0i16;
+    for i in (0..48).rev() {
+        let mut diff = a[i] as i16 - b[i] as i16 - borrow;
+        borrow = (diff < 0) as i16;
+        if diff < 0 {
+            diff += 256;
+        }
+        result[i] = diff as u8;
+    }
+    result
+}
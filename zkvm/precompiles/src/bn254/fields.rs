@@ -0,0 +1,308 @@
+//! The BN254 extension field tower used by the pairing:
+//!
+//! - `Fp2 = Fp[u] / (u^2 + 1)`, accelerated by the `bn254_fp2_*` precompiles.
+//! - `Fp6 = Fp2[v] / (v^3 - xi)` with `xi = 9 + u`.
+//! - `Fp12 = Fp6[w] / (w^2 - v)`.
+//!
+//! Base field elements are embedded into `Fp2` with a zero `u` coefficient, so every arithmetic
+//! operation in the tower goes through the Fp2 precompiles.
+
+use crate::io;
+use crate::unconstrained;
+use crate::utils::{bytes_to_words_le, words_to_bytes_le};
+use crate::{syscall_bn254_fp2_add, syscall_bn254_fp2_mul, syscall_bn254_fp2_sub};
+
+/// The number of words in a BN254 base field element.
+const NUM_WORDS_FP: usize = 8;
+
+/// `p - 2`, the exponent used to invert base field elements.
+const MODULUS_MINUS_TWO: [u32; NUM_WORDS_FP] = [
+    3632069957, 1008765974, 1752287885, 2541841041, 2172737629, 3092268470, 3778125865, 811880050,
+];
+
+/// An element `c0 + c1 * u` of `Fp2`, stored as the little-endian words of `c0` followed by the
+/// little-endian words of `c1`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Fp2(pub [u32; 16]);
+
+impl Fp2 {
+    pub const ZERO: Self = Self([0; 16]);
+    pub const ONE: Self = Self([1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+
+    /// The non-residue `xi = 9 + u` used to build `Fp6`.
+    const XI: Self = Self([9, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0]);
+
+    /// Constructs `c0 + c1 * u` from the little-endian words of its coefficients.
+    pub fn new(c0: [u32; NUM_WORDS_FP], c1: [u32; NUM_WORDS_FP]) -> Self {
+        let mut limbs = [0u32; 16];
+        limbs[..NUM_WORDS_FP].copy_from_slice(&c0);
+        limbs[NUM_WORDS_FP..].copy_from_slice(&c1);
+        Self(limbs)
+    }
+
+    /// Embeds a base field element into `Fp2`.
+    pub fn from_fp(c0: [u32; NUM_WORDS_FP]) -> Self {
+        Self::new(c0, [0; NUM_WORDS_FP])
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.0 == [0; 16]
+    }
+
+    pub fn add(&self, other: &Self) -> Self {
+        let mut result = *self;
+        unsafe {
+            syscall_bn254_fp2_add(result.0.as_mut_ptr(), other.0.as_ptr());
+        }
+        result
+    }
+
+    pub fn sub(&self, other: &Self) -> Self {
+        let mut result = *self;
+        unsafe {
+            syscall_bn254_fp2_sub(result.0.as_mut_ptr(), other.0.as_ptr());
+        }
+        result
+    }
+
+    pub fn mul(&self, other: &Self) -> Self {
+        let mut result = *self;
+        unsafe {
+            syscall_bn254_fp2_mul(result.0.as_mut_ptr(), other.0.as_ptr());
+        }
+        result
+    }
+
+    pub fn neg(&self) -> Self {
+        Self::ZERO.sub(self)
+    }
+
+    pub fn double(&self) -> Self {
+        self.add(self)
+    }
+
+    pub fn square(&self) -> Self {
+        self.mul(self)
+    }
+
+    pub fn mul_by_xi(&self) -> Self {
+        self.mul(&Self::XI)
+    }
+
+    /// The conjugate `c0 - c1 * u`.
+    pub fn conjugate(&self) -> Self {
+        let mut c1 = Self::ZERO;
+        c1.0[NUM_WORDS_FP..].copy_from_slice(&self.0[NUM_WORDS_FP..]);
+        self.sub(&c1.double())
+    }
+
+    /// Computes the inverse. Zero maps to zero.
+    ///
+    /// The inverse is hinted and checked with a single multiplication, which is much cheaper than
+    /// exponentiating in the zkVM.
+    pub fn inv(&self) -> Self {
+        unconstrained! {
+            io::hint_slice(&words_to_bytes_le::<64>(&self.inv_by_exponentiation().0));
+        }
+
+        let inv = Self(bytes_to_words_le::<16>(&io::read_vec()));
+        if inv.is_zero() {
+            // The precompiles reduce their output, so this is zero exactly when `self` is.
+            assert!(self.mul(&Self::ONE).is_zero(), "invalid Fp2 inverse hint");
+        } else {
+            assert_eq!(self.mul(&inv), Self::ONE, "invalid Fp2 inverse hint");
+        }
+        inv
+    }
+
+    /// Computes the inverse as `conj(x) / (c0^2 + c1^2)`, inverting the norm in the base field
+    /// with Fermat's little theorem. Zero maps to zero.
+    fn inv_by_exponentiation(&self) -> Self {
+        let conjugate = self.conjugate();
+        let norm = self.mul(&conjugate);
+
+        let mut norm_inv = Self::ONE;
+        for i in (0..NUM_WORDS_FP * 32).rev() {
+            norm_inv = norm_inv.square();
+            if (MODULUS_MINUS_TWO[i / 32] >> (i % 32)) & 1 == 1 {
+                norm_inv = norm_inv.mul(&norm);
+            }
+        }
+
+        conjugate.mul(&norm_inv)
+    }
+}
+
+/// An element `c0 + c1 * v + c2 * v^2` of `Fp6`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Fp6 {
+    pub c0: Fp2,
+    pub c1: Fp2,
+    pub c2: Fp2,
+}
+
+impl Fp6 {
+    pub const ZERO: Self = Self {
+        c0: Fp2::ZERO,
+        c1: Fp2::ZERO,
+        c2: Fp2::ZERO,
+    };
+    pub const ONE: Self = Self {
+        c0: Fp2::ONE,
+        c1: Fp2::ZERO,
+        c2: Fp2::ZERO,
+    };
+
+    pub fn add(&self, other: &Self) -> Self {
+        Self {
+            c0: self.c0.add(&other.c0),
+            c1: self.c1.add(&other.c1),
+            c2: self.c2.add(&other.c2),
+        }
+    }
+
+    pub fn sub(&self, other: &Self) -> Self {
+        Self {
+            c0: self.c0.sub(&other.c0),
+            c1: self.c1.sub(&other.c1),
+            c2: self.c2.sub(&other.c2),
+        }
+    }
+
+    pub fn neg(&self) -> Self {
+        Self {
+            c0: self.c0.neg(),
+            c1: self.c1.neg(),
+            c2: self.c2.neg(),
+        }
+    }
+
+    /// Karatsuba multiplication, using 6 Fp2 multiplications.
+    pub fn mul(&self, other: &Self) -> Self {
+        let t0 = self.c0.mul(&other.c0);
+        let t1 = self.c1.mul(&other.c1);
+        let t2 = self.c2.mul(&other.c2);
+
+        let c0 = self
+            .c1
+            .add(&self.c2)
+            .mul(&other.c1.add(&other.c2))
+            .sub(&t1)
+            .sub(&t2)
+            .mul_by_xi()
+            .add(&t0);
+        let c1 = self
+            .c0
+            .add(&self.c1)
+            .mul(&other.c0.add(&other.c1))
+            .sub(&t0)
+            .sub(&t1)
+            .add(&t2.mul_by_xi());
+        let c2 = self
+            .c0
+            .add(&self.c2)
+            .mul(&other.c0.add(&other.c2))
+            .sub(&t0)
+            .sub(&t2)
+            .add(&t1);
+
+        Self { c0, c1, c2 }
+    }
+
+    /// Multiplies by `v`, using `v^3 = xi`.
+    pub fn mul_by_v(&self) -> Self {
+        Self {
+            c0: self.c2.mul_by_xi(),
+            c1: self.c0,
+            c2: self.c1,
+        }
+    }
+
+    pub fn inv(&self) -> Self {
+        let c0 = self.c0.square().sub(&self.c1.mul(&self.c2).mul_by_xi());
+        let c1 = self.c2.square().mul_by_xi().sub(&self.c0.mul(&self.c1));
+        let c2 = self.c1.square().sub(&self.c0.mul(&self.c2));
+
+        let norm = self
+            .c2
+            .mul(&c1)
+            .add(&self.c1.mul(&c2))
+            .mul_by_xi()
+            .add(&self.c0.mul(&c0));
+        let norm_inv = norm.inv();
+
+        Self {
+            c0: c0.mul(&norm_inv),
+            c1: c1.mul(&norm_inv),
+            c2: c2.mul(&norm_inv),
+        }
+    }
+}
+
+/// An element `c0 + c1 * w` of `Fp12`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Fp12 {
+    pub c0: Fp6,
+    pub c1: Fp6,
+}
+
+impl Fp12 {
+    pub const ONE: Self = Self {
+        c0: Fp6::ONE,
+        c1: Fp6::ZERO,
+    };
+
+    /// Karatsuba multiplication, using 3 Fp6 multiplications.
+    pub fn mul(&self, other: &Self) -> Self {
+        let t0 = self.c0.mul(&other.c0);
+        let t1 = self.c1.mul(&other.c1);
+        let c1 = self
+            .c0
+            .add(&self.c1)
+            .mul(&other.c0.add(&other.c1))
+            .sub(&t0)
+            .sub(&t1);
+
+        Self {
+            c0: t0.add(&t1.mul_by_v()),
+            c1,
+        }
+    }
+
+    pub fn square(&self) -> Self {
+        self.mul(self)
+    }
+
+    /// The conjugate `c0 - c1 * w`, which equals the `p^6`-power Frobenius.
+    pub fn conjugate(&self) -> Self {
+        Self {
+            c0: self.c0,
+            c1: self.c1.neg(),
+        }
+    }
+
+    pub fn inv(&self) -> Self {
+        let norm_inv = self
+            .c0
+            .mul(&self.c0)
+            .sub(&self.c1.mul(&self.c1).mul_by_v())
+            .inv();
+
+        Self {
+            c0: self.c0.mul(&norm_inv),
+            c1: self.c1.mul(&norm_inv).neg(),
+        }
+    }
+
+    /// Raises to the power of the little-endian words of `exponent`.
+    pub fn pow(&self, exponent: &[u32]) -> Self {
+        let mut result = Self::ONE;
+        for i in (0..exponent.len() * 32).rev() {
+            result = result.square();
+            if (exponent[i / 32] >> (i % 32)) & 1 == 1 {
+                result = result.mul(self);
+            }
+        }
+        result
+    }
+}
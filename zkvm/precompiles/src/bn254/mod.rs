@@ -0,0 +1,46 @@
+mod fields;
+pub mod pairing;
+
+pub use fields::{Fp12, Fp2, Fp6};
+
+use crate::utils::{CurveOperations, MAX_MSM_POINTS};
+use crate::{syscall_bn254_add, syscall_bn254_double, syscall_bn254_msm};
+
+#[derive(Copy, Clone)]
+pub struct Bn254;
+
+impl CurveOperations for Bn254 {
+    // The values has been taken from py_ecc python library by Ethereum Foundation.
+    // https://github.com/ethereum/py_pairing/blob/5f609da/py_ecc/bn128/bn128_field_elements.py
+    const GENERATOR: [u32; 16] = [1, 0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0];
+
+    fn add_assign(limbs: &mut [u32; 16], other: &[u32; 16]) {
+        unsafe {
+            syscall_bn254_add(limbs.as_mut_ptr(), other.as_ptr());
+        }
+    }
+
+    fn double(limbs: &mut [u32; 16]) {
+        unsafe {
+            syscall_bn254_double(limbs.as_mut_ptr());
+        }
+    }
+
+    fn msm(points: &[[u32; 16]], scalars: &[[u32; 8]]) -> Option<[u32; 16]> {
+        assert!(points.len() <= MAX_MSM_POINTS);
+        assert_eq!(points.len(), scalars.len());
+        if points.is_empty() {
+            return None;
+        }
+        let mut result = [0u32; 16];
+        let is_identity = unsafe {
+            syscall_bn254_msm(
+                result.as_mut_ptr(),
+                points.as_ptr() as *const u32,
+                scalars.as_ptr() as *const u32,
+                points.len(),
+            )
+        };
+        (!is_identity).then_some(result)
+    }
+}
@@ -0,0 +1,432 @@
+//! A BN254 pairing check built on top of the Fp2 precompiles.
+//!
+//! The pairing is the optimal ate pairing `e(P, Q) = (f_{6x+2,Q}(P) * l_{[6x+2]Q,pi(Q)}(P) *
+//! l_{[6x+2]Q+pi(Q),-pi^2(Q)}(P))^((p^12 - 1) / r)`, where `x` is the BN parameter and `pi` is the
+//! `p`-power Frobenius. This is the pairing of EIP-197, and libraries which raise it to a
+//! different multiple of `(p^12 - 1) / r` compute a fixed power of it.
+
+use super::fields::{Fp12, Fp2, Fp6};
+
+/// A G1 point as the little-endian words of `x` followed by those of `y`. All zeros encodes the
+/// point at infinity.
+pub type G1Affine = [u32; 16];
+
+/// A G2 point on the twist `y^2 = x^3 + 3 / (9 + u)` as the `Fp2` words of `x` followed by those
+/// of `y`. All zeros encodes the point at infinity.
+pub type G2Affine = [u32; 32];
+
+/// The Miller loop length `6x + 2` in non-adjacent form, least significant digit first.
+const ATE_LOOP_COUNT: [i8; 66] = [
+    0, 0, 0, 1, 0, 1, 0, -1, 0, 0, -1, 0, 0, 0, 1, 0, 0, -1, 0, -1, 0, 0, 0, 1, 0, -1, 0, 0, 0, 0,
+    -1, 0, 0, 1, 0, -1, 0, 0, 1, 0, 0, 0, 0, 0, -1, 0, 0, -1, 0, 1, 0, -1, 0, 0, 0, -1, 0, -1, 0,
+    0, 0, 1, 0, -1, 0, 1,
+];
+
+/// The hard part of the final exponentiation, `(p^4 - p^2 + 1) / r`, in little-endian words.
+const FINAL_EXPONENT_HARD: [u32; 24] = [
+    3437183665, 3894129794, 4103878356, 1522490564, 497030397, 4044705406, 2278406863, 3704075340,
+    2504773514, 2862891491, 3574161187, 1804107094, 4093481158, 2035818006, 3515425405, 991630165,
+    1636265835, 1314036312, 3585155704, 1812903202, 2199355311, 857653585, 185031085, 29010545,
+];
+
+/// Powers `gamma^i` for `i = 1..=5`, where `gamma = xi^((p^2 - 1) / 6)` lies in the base field.
+/// The `p^2`-power Frobenius multiplies the coefficient of `w^i` by `gamma^i`.
+const FROBENIUS_COEFF_P2: [[u32; 8]; 5] = [
+    [
+        1618804041, 3837609189, 3147198013, 3264153247, 3769420976, 1584257511, 3778125865,
+        811880050,
+    ],
+    [
+        1618804040, 3837609189, 3147198013, 3264153247, 3769420976, 1584257511, 3778125865,
+        811880050,
+    ],
+    [
+        3632069958, 1008765974, 1752287885, 2541841041, 2172737629, 3092268470, 3778125865,
+        811880050,
+    ],
+    [
+        2013265918, 1466124081, 2900057167, 3572655089, 2698283948, 1508010958, 0, 0,
+    ],
+    [
+        2013265919, 1466124081, 2900057167, 3572655089, 2698283948, 1508010958, 0, 0,
+    ],
+];
+
+/// `xi^((p - 1) / 3)` and `xi^((p - 1) / 2)`, which the `p`-power Frobenius of a twist point
+/// multiplies its conjugated `x` and `y` by.
+const TWIST_FROBENIUS_COEFF: [[u32; 16]; 2] = [
+    [
+        393172285, 2581828951, 3267572492, 3079455504, 4116296003, 1275849788, 1333334519,
+        800278424, 1678756770, 375772444, 192716238, 850274845, 3613001620, 1269091467, 1642835488,
+        382330192,
+    ],
+    [
+        1906316122, 3696492870, 2848545176, 3685409005, 3068328377, 3697198744, 1218115036,
+        104657669, 639873251, 2194898787, 2409782226, 562068937, 3967381291, 117749159, 2889942170,
+        130038972,
+    ],
+];
+
+/// Returns true if `prod e(P_i, Q_i) == 1`. Pairs where either point is the point at infinity
+/// contribute a factor of one.
+///
+/// Warning: this function does not check that the points are on the curve or in the correct
+/// subgroups.
+pub fn pairing_check(pairs: &[(G1Affine, G2Affine)]) -> bool {
+    let mut f = Fp12::ONE;
+    for (p, q) in pairs {
+        if p.iter().all(|&w| w == 0) || q.iter().all(|&w| w == 0) {
+            continue;
+        }
+        f = f.mul(&miller_loop(p, q));
+    }
+    final_exponentiation(&f) == Fp12::ONE
+}
+
+/// Evaluates the Miller function of the optimal ate pairing at `P`, dropping the vertical lines
+/// since they are eliminated by the final exponentiation.
+fn miller_loop(p: &G1Affine, q: &G2Affine) -> Fp12 {
+    let mut px = [0u32; 8];
+    let mut py = [0u32; 8];
+    px.copy_from_slice(&p[..8]);
+    py.copy_from_slice(&p[8..]);
+    let (px, py) = (Fp2::from_fp(px), Fp2::from_fp(py));
+
+    let mut qx = Fp2::ZERO;
+    let mut qy = Fp2::ZERO;
+    qx.0.copy_from_slice(&q[..16]);
+    qy.0.copy_from_slice(&q[16..]);
+
+    let three = Fp2::from_fp([3, 0, 0, 0, 0, 0, 0, 0]);
+    let (mut tx, mut ty) = (qx, qy);
+    let mut f = Fp12::ONE;
+
+    // Skip the most significant digit, which is accounted for by starting at T = Q.
+    for i in (0..ATE_LOOP_COUNT.len() - 1).rev() {
+        // Doubling step.
+        let slope = three.mul(&tx.square()).mul(&ty.double().inv());
+        f = f.square().mul(&line(&slope, &tx, &ty, &px, &py));
+        let x = slope.square().sub(&tx).sub(&tx);
+        ty = slope.mul(&tx.sub(&x)).sub(&ty);
+        tx = x;
+
+        // Addition step.
+        match ATE_LOOP_COUNT[i] {
+            1 => f = f.mul(&add_step(&mut tx, &mut ty, &qx, &qy, &px, &py)),
+            -1 => f = f.mul(&add_step(&mut tx, &mut ty, &qx, &qy.neg(), &px, &py)),
+            _ => {}
+        }
+    }
+
+    // Add pi(Q) and -pi^2(Q). On the twist, pi^2 multiplies `x` by `xi^((p^2 - 1) / 3)` and `y` by
+    // `xi^((p^2 - 1) / 2) = -1`, so negating it leaves `y` as it is.
+    let q1x = qx.conjugate().mul(&Fp2(TWIST_FROBENIUS_COEFF[0]));
+    let q1y = qy.conjugate().mul(&Fp2(TWIST_FROBENIUS_COEFF[1]));
+    f = f.mul(&add_step(&mut tx, &mut ty, &q1x, &q1y, &px, &py));
+    let q2x = qx.mul(&Fp2::from_fp(FROBENIUS_COEFF_P2[1]));
+    f = f.mul(&add_step(&mut tx, &mut ty, &q2x, &qy, &px, &py));
+
+    f
+}
+
+/// Sets `T = T + A` and returns the line through them evaluated at `P`.
+fn add_step(tx: &mut Fp2, ty: &mut Fp2, ax: &Fp2, ay: &Fp2, px: &Fp2, py: &Fp2) -> Fp12 {
+    let slope = ay.sub(ty).mul(&ax.sub(tx).inv());
+    let line = line(&slope, tx, ty, px, py);
+    let x = slope.square().sub(tx).sub(ax);
+    *ty = slope.mul(&tx.sub(&x)).sub(ty);
+    *tx = x;
+    line
+}
+
+/// Evaluates at `P` the line with the given slope through the untwisted point `(tx w^2, ty w^3)`:
+///
+/// `yP - (slope * w) * xP + (slope * tx - ty) * w^3`.
+fn line(slope: &Fp2, tx: &Fp2, ty: &Fp2, px: &Fp2, py: &Fp2) -> Fp12 {
+    Fp12 {
+        c0: Fp6 {
+            c0: *py,
+            c1: Fp2::ZERO,
+            c2: Fp2::ZERO,
+        },
+        c1: Fp6 {
+            c0: slope.mul(px).neg(),
+            c1: slope.mul(tx).sub(ty),
+            c2: Fp2::ZERO,
+        },
+    }
+}
+
+/// Computes `f^((p^12 - 1) / r)` as `(f^(p^6 - 1))^(p^2 + 1)` followed by the hard part.
+fn final_exponentiation(f: &Fp12) -> Fp12 {
+    let f = f.conjugate().mul(&f.inv());
+    let f = frobenius_p2(&f).mul(&f);
+    f.pow(&FINAL_EXPONENT_HARD)
+}
+
+/// Computes `f^(p^2)`. Since `Fp2` coefficients are fixed by the `p^2`-power Frobenius, only the
+/// powers of `w` pick up a factor. Here `Fp6` coefficient `j` of `c0` and `c1` multiplies
+/// `w^(2j)` and `w^(2j+1)` respectively.
+fn frobenius_p2(f: &Fp12) -> Fp12 {
+    let coeff = |i: usize| Fp2::from_fp(FROBENIUS_COEFF_P2[i - 1]);
+    Fp12 {
+        c0: Fp6 {
+            c0: f.c0.c0,
+            c1: f.c0.c1.mul(&coeff(2)),
+            c2: f.c0.c2.mul(&coeff(4)),
+        },
+        c1: Fp6 {
+            c0: f.c1.c0.mul(&coeff(1)),
+            c1: f.c1.c1.mul(&coeff(3)),
+            c2: f.c1.c2.mul(&coeff(5)),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::collections::VecDeque;
+
+    use num::{BigUint, One};
+
+    use super::*;
+    use crate::bn254::Bn254;
+    use crate::utils::CurveOperations;
+
+    const MODULUS: &str =
+        "21888242871839275222246405745257275088696311157297823662689037894645226208583";
+    const ORDER: &str =
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617";
+
+    /// The BN parameter `x`.
+    const X: i128 = 4965661367192848881;
+
+    /// The generator of G2.
+    const G2: G2Affine = [
+        3650287341, 1189002588, 4150188765, 1732453076, 1583105145, 1114243174, 304029302,
+        402710255, 2935165634, 2548336055, 900327186, 4054468915, 838556965, 1918943159,
+        2450343994, 428774291, 1727692202, 1290193921, 205771643, 3822184297, 2378907791,
+        1252749696, 3683413483, 315121317, 3508705115, 1437391580, 1890815731, 3159044403,
+        1762407317, 3969817005, 1482682485, 151423440,
+    ];
+
+    /// `[12345] G1`.
+    const A_G1: G1Affine = [
+        2192806058, 1974998392, 4097294637, 1091744617, 2879541956, 3086658643, 2078411460,
+        423032752, 902337050, 150577724, 632715033, 3627553388, 2036241104, 3187390388, 2065549307,
+        89929922,
+    ];
+
+    /// `[67890] G2`.
+    const B_G2: G2Affine = [
+        653046582, 3194627342, 3252065481, 211735973, 2332090333, 2656815868, 2451673380,
+        171396952, 3768252778, 10493679, 161995608, 3908460642, 3678181095, 440301715, 3419256419,
+        232200850, 3898846145, 1167566760, 1272505135, 1693041863, 267477528, 2663237167,
+        965230446, 444061136, 975222443, 2359036419, 1348173238, 1325247215, 4043960502, 209822630,
+        1410945921, 329732387,
+    ];
+
+    /// `e(G1, G2)`, as the words of its `Fp2` coefficients in the order of `Fp12`'s fields. Raised
+    /// to the power `2x(6x^2 + 3x + 1)`, this is the value arkworks computes.
+    const E_G1_G2: [u32; 96] = [
+        230269429, 2412413184, 1919098406, 469235229, 2122864375, 1359794544, 3777722484,
+        315035280, 2524792580, 2958371216, 2855400076, 3736026898, 730428308, 1807938282,
+        2242944646, 139408132, 1490242526, 2528647587, 1977835615, 3156737491, 1434681568,
+        2895324857, 3247063043, 243538986, 1204340184, 4139061324, 2920670826, 1612861157,
+        3344236092, 4026145080, 2244161934, 543643752, 3779802678, 3184534070, 2443004916,
+        2251303318, 1271218792, 1083984441, 3728898748, 23553365, 1760699076, 2514704708,
+        2705224410, 4291327102, 2255798896, 1926786552, 1693470167, 721641796, 1817598217,
+        818439719, 2633744684, 2938492805, 3366730736, 955986141, 3441534912, 743666827, 290676996,
+        2997585444, 1625521349, 1721500259, 3174029602, 1852902335, 2047563182, 669851790,
+        3987977546, 2641767239, 2377813321, 390143908, 3541908833, 2890277235, 939356916,
+        450485017, 4228091619, 1686674553, 559429749, 728693476, 2480463986, 624084932, 4191451433,
+        664646294, 2269487209, 401667792, 2494733151, 195348236, 3615236639, 43766849, 106347490,
+        230846244, 1466074135, 1668456274, 1817006966, 3603708890, 1079851094, 1154543888,
+        1603553015, 277617105,
+    ];
+
+    fn modulus() -> BigUint {
+        BigUint::parse_bytes(MODULUS.as_bytes(), 10).unwrap()
+    }
+
+    fn read_fp2(ptr: *const u32) -> (BigUint, BigUint) {
+        let words = unsafe { core::slice::from_raw_parts(ptr, 16) };
+        (
+            BigUint::from_slice(&words[..8]),
+            BigUint::from_slice(&words[8..]),
+        )
+    }
+
+    fn write_fp2(ptr: *mut u32, c0: BigUint, c1: BigUint) {
+        let words = unsafe { core::slice::from_raw_parts_mut(ptr, 16) };
+        let modulus = modulus();
+        for (chunk, c) in words.chunks_exact_mut(8).zip([c0, c1]) {
+            let mut digits = (c % &modulus).to_u32_digits();
+            digits.resize(8, 0);
+            chunk.copy_from_slice(&digits);
+        }
+    }
+
+    // Host versions of the Fp2 precompiles, so that the tower can be run outside the zkVM.
+
+    #[no_mangle]
+    extern "C" fn syscall_bn254_fp2_add(x: *mut u32, y: *const u32) {
+        let ((x0, x1), (y0, y1)) = (read_fp2(x), read_fp2(y));
+        write_fp2(x, x0 + y0, x1 + y1);
+    }
+
+    #[no_mangle]
+    extern "C" fn syscall_bn254_fp2_sub(x: *mut u32, y: *const u32) {
+        let ((x0, x1), (y0, y1)) = (read_fp2(x), read_fp2(y));
+        let modulus = modulus();
+        write_fp2(x, x0 + &modulus - y0, x1 + &modulus - y1);
+    }
+
+    #[no_mangle]
+    extern "C" fn syscall_bn254_fp2_mul(x: *mut u32, y: *const u32) {
+        let ((x0, x1), (y0, y1)) = (read_fp2(x), read_fp2(y));
+        let modulus = modulus();
+        write_fp2(
+            x,
+            &x0 * &y0 + &modulus * &modulus - &x1 * &y1,
+            x0 * y1 + x1 * y0,
+        );
+    }
+
+    // Host versions of the hint syscalls, which run unconstrained blocks eagerly and queue their
+    // hints per thread.
+
+    thread_local! {
+        static HINTS: RefCell<VecDeque<Vec<u8>>> = RefCell::new(VecDeque::new());
+    }
+
+    #[no_mangle]
+    extern "C" fn syscall_enter_unconstrained() -> bool {
+        true
+    }
+
+    #[no_mangle]
+    extern "C" fn syscall_exit_unconstrained() {}
+
+    #[no_mangle]
+    extern "C" fn syscall_write(fd: u32, write_buf: *const u8, nbytes: usize) {
+        assert_eq!(fd, 4, "only hints are written on the host");
+        let buf = unsafe { core::slice::from_raw_parts(write_buf, nbytes) };
+        HINTS.with(|hints| hints.borrow_mut().push_back(buf.to_vec()));
+    }
+
+    #[no_mangle]
+    extern "C" fn syscall_hint_len() -> usize {
+        HINTS.with(|hints| hints.borrow().front().map_or(0, Vec::len))
+    }
+
+    #[no_mangle]
+    extern "C" fn syscall_hint_read(ptr: *mut u8, len: usize) {
+        let hint = HINTS.with(|hints| hints.borrow_mut().pop_front().unwrap());
+        assert_eq!(hint.len(), len);
+        unsafe { core::ptr::copy_nonoverlapping(hint.as_ptr(), ptr, len) };
+    }
+
+    fn fp2_pow(base: &Fp2, exponent: &BigUint) -> Fp2 {
+        let mut result = Fp2::ONE;
+        for i in (0..exponent.bits()).rev() {
+            result = result.square();
+            if exponent.bit(i) {
+                result = result.mul(base);
+            }
+        }
+        result
+    }
+
+    fn fp12_from_words(words: &[u32; 96]) -> Fp12 {
+        let fp2 = |i: usize| Fp2(words[i * 16..(i + 1) * 16].try_into().unwrap());
+        Fp12 {
+            c0: Fp6 {
+                c0: fp2(0),
+                c1: fp2(1),
+                c2: fp2(2),
+            },
+            c1: Fp6 {
+                c0: fp2(3),
+                c1: fp2(4),
+                c2: fp2(5),
+            },
+        }
+    }
+
+    fn pairing(p: &G1Affine, q: &G2Affine) -> Fp12 {
+        final_exponentiation(&miller_loop(p, q))
+    }
+
+    fn neg_g1(p: &G1Affine) -> G1Affine {
+        let mut y = (modulus() - BigUint::from_slice(&p[8..])).to_u32_digits();
+        y.resize(8, 0);
+        let mut neg = *p;
+        neg[8..].copy_from_slice(&y);
+        neg
+    }
+
+    #[test]
+    fn test_ate_loop_count() {
+        let count = ATE_LOOP_COUNT
+            .iter()
+            .rev()
+            .fold(0i128, |acc, &digit| acc * 2 + digit as i128);
+        assert_eq!(count, 6 * X + 2);
+        assert!(ATE_LOOP_COUNT.windows(2).all(|w| w[0] == 0 || w[1] == 0));
+    }
+
+    #[test]
+    fn test_final_exponent_hard() {
+        let p = modulus();
+        let r = BigUint::parse_bytes(ORDER.as_bytes(), 10).unwrap();
+        assert_eq!(
+            BigUint::from_slice(&FINAL_EXPONENT_HARD) * r,
+            p.pow(4) - p.pow(2) + BigUint::one()
+        );
+    }
+
+    #[test]
+    fn test_frobenius_coeffs() {
+        let p = modulus();
+        let xi = Fp2::new([9, 0, 0, 0, 0, 0, 0, 0], [1, 0, 0, 0, 0, 0, 0, 0]);
+        for (i, coeff) in FROBENIUS_COEFF_P2.iter().enumerate() {
+            let exponent = (p.pow(2) - 1u32) / 6u32 * (i + 1);
+            assert_eq!(Fp2::from_fp(*coeff), fp2_pow(&xi, &exponent));
+        }
+        assert_eq!(
+            Fp2(TWIST_FROBENIUS_COEFF[0]),
+            fp2_pow(&xi, &((&p - 1u32) / 3u32))
+        );
+        assert_eq!(
+            Fp2(TWIST_FROBENIUS_COEFF[1]),
+            fp2_pow(&xi, &((&p - 1u32) / 2u32))
+        );
+    }
+
+    #[test]
+    fn test_fp2_inv() {
+        let x = Fp2::new([12345, 0, 0, 0, 0, 0, 0, 0], [67890, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(x.mul(&x.inv()), Fp2::ONE);
+        assert_eq!(Fp2::ZERO.inv(), Fp2::ZERO);
+    }
+
+    #[test]
+    fn test_pairing_known_answer() {
+        assert_eq!(pairing(&Bn254::GENERATOR, &G2), fp12_from_words(&E_G1_G2));
+    }
+
+    #[test]
+    fn test_pairing_bilinearity() {
+        let e = pairing(&Bn254::GENERATOR, &G2);
+        assert_eq!(pairing(&A_G1, &B_G2), e.pow(&[12345 * 67890]));
+
+        assert!(!pairing_check(&[(Bn254::GENERATOR, G2)]));
+        assert!(pairing_check(&[
+            (Bn254::GENERATOR, G2),
+            (neg_g1(&Bn254::GENERATOR), G2)
+        ]));
+        assert!(pairing_check(&[(A_G1, B_G2), (neg_g1(&A_G1), B_G2)]));
+    }
+}
@@ -0,0 +1,318 @@
+#![allow(unused)]
+
+use sha2::{Digest, Sha512};
+
+use crate::io;
+use crate::uint256::{addmod, lt, mulmod, powmod, submod, U256};
+use crate::unconstrained;
+use crate::utils::{bytes_to_words_le, words_to_bytes_le};
+use crate::{syscall_ed_add, syscall_ed_compress, syscall_ed_decompress, syscall_ed_scalar_mul};
+
+/// The modulus `2^255 - 19` of the base field.
+const P: U256 = [
+    0xFFFFFFED, 0xFFFFFFFF, 0xFFFFFFFF, 0xFFFFFFFF, 0xFFFFFFFF, 0xFFFFFFFF, 0xFFFFFFFF, 0x7FFFFFFF,
+];
+
+/// `p - 2`, the exponent of the inverse in the base field.
+const P_MINUS_2: U256 = [
+    0xFFFFFFEB, 0xFFFFFFFF, 0xFFFFFFFF, 0xFFFFFFFF, 0xFFFFFFFF, 0xFFFFFFFF, 0xFFFFFFFF, 0x7FFFFFFF,
+];
+
+/// `(p + 3) / 8`, the exponent of the candidate square root in the base field.
+const SQRT_EXP: U256 = [
+    0xFFFFFFFE, 0xFFFFFFFF, 0xFFFFFFFF, 0xFFFFFFFF, 0xFFFFFFFF, 0xFFFFFFFF, 0xFFFFFFFF, 0x0FFFFFFF,
+];
+
+/// A square root of -1 in the base field.
+const SQRT_M1: U256 = [
+    0x4A0EA0B0, 0xC4EE1B27, 0xAD2FE478, 0x2F431806, 0x3DFBD7A7, 0x2B4D0099, 0x4FC1DF0B, 0x2B832480,
+];
+
+/// The curve parameter `d = -121665 / 121666`.
+const D: U256 = [
+    0x135978A3, 0x75EB4DCA, 0x4141D8AB, 0x00700A4D, 0x7779E898, 0x8CC74079, 0x2B6FFE73, 0x52036CEE,
+];
+
+/// The order `2^252 + 27742317777372353535851937790883648493` of the base point.
+const L: U256 = [
+    0x5CF5D3ED, 0x5812631A, 0xA2F79CD6, 0x14DEF9DE, 0x00000000, 0x00000000, 0x00000000, 0x10000000,
+];
+
+/// `2^256 mod L`, used to reduce 512-bit hashes.
+const R_MOD_L: U256 = [
+    0x8D98951D, 0xD6EC3174, 0x737DCF70, 0xC6EF5BF4, 0xFFFFFFFE, 0xFFFFFFFF, 0xFFFFFFFF, 0x0FFFFFFF,
+];
+
+/// The base point in affine coordinates, as little-endian words with x followed by y.
+const BASEPOINT: [u32; 16] = [
+    0x8F25D51A, 0xC9562D60, 0x9525A7B2, 0x692CC760, 0xFDD6DC5C, 0xC0A4E231, 0xCD6E53FE, 0x216936D3,
+    0x66666658, 0x66666666, 0x66666666, 0x66666666, 0x66666666, 0x66666666, 0x66666666, 0x66666666,
+];
+
+/// The identity `(0, 1)`.
+const IDENTITY: [u32; 16] = [0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0];
+
+const ZERO: U256 = [0; 8];
+const ONE: U256 = [1, 0, 0, 0, 0, 0, 0, 0];
+
+/// Verifies an Ed25519 signature `R || S` of `msg` by the public key `pubkey` (RFC 8032), using
+/// the `ed_add`, `ed_decompress` and `ed_compress` precompiles.
+///
+/// This checks the cofactorless equation `[S]B = R + [k]A` by comparing the encoding of
+/// `[S]B - [k]A` to `R`, like `ed25519_dalek::VerifyingKey::verify`. Signatures with `S >= L`
+/// are rejected, and so are public keys which are not the canonical encoding of a point.
+pub fn verify(pubkey: &[u8; 32], msg: &[u8], sig: &[u8; 64]) -> bool {
+    let mut y_bytes = *pubkey;
+    let sign = y_bytes[31] >> 7 == 1;
+    y_bytes[31] &= 0b0111_1111;
+    let y = bytes_to_words_le::<8>(&y_bytes);
+    if !lt(&y, &P) {
+        return false;
+    }
+    // The points with `x = 0` have a single encoding, with the sign bit unset.
+    if sign && (y == ONE || y == submod(&ZERO, &ONE, &P)) {
+        return false;
+    }
+
+    let s = bytes_to_words_le::<8>(&sig[32..]);
+    if !lt(&s, &L) {
+        return false;
+    }
+
+    let hash = Sha512::new()
+        .chain_update(&sig[..32])
+        .chain_update(pubkey)
+        .chain_update(msg)
+        .finalize();
+    let hash: [u8; 64] = hash.as_slice().try_into().unwrap();
+
+    cfg_if::cfg_if! {
+        if #[cfg(all(target_os = "zkvm", target_vendor = "succinct"))] {
+            let a = match decompress(pubkey, &y) {
+                Some(a) => a,
+                None => return false,
+            };
+            let k = reduce_wide(&hash);
+
+            let r = double_scalar_mul(&s, &BASEPOINT, &k, &negate(&a));
+            compress(&r) == sig[..32]
+        } else {
+            use curve25519_dalek::edwards::{CompressedEdwardsY, EdwardsPoint};
+            use curve25519_dalek::scalar::Scalar;
+
+            let a = match CompressedEdwardsY(*pubkey).decompress() {
+                Some(a) => a,
+                None => return false,
+            };
+            let s = Scalar::from_bytes_mod_order(sig[32..].try_into().unwrap());
+            let k = Scalar::from_bytes_mod_order_wide(&hash);
+
+            let r = EdwardsPoint::vartime_double_scalar_mul_basepoint(&k, &-a, &s);
+            r.compress().as_bytes()[..] == sig[..32]
+        }
+    }
+}
+
+/// Decompresses the public key with canonical y coordinate `y`, or returns `None` if it is not the
+/// encoding of a point.
+///
+/// Whether `x^2 = u / v` has a solution, where `u = y^2 - 1` and `v = d y^2 + 1`, is hinted. If it
+/// does, the decompress precompile checks it. Otherwise the hint is a square root of `2u / v`,
+/// which proves that `u / v` is not a square since 2 is not a square modulo `p`.
+fn decompress(pubkey: &[u8; 32], y: &U256) -> Option<[u32; 16]> {
+    let yy = mulmod(y, y, &P);
+    let u = submod(&yy, &ONE, &P);
+    let v = addmod(&mulmod(&D, &yy, &P), &ONE, &P);
+
+    unconstrained! {
+        let u_div_v = mulmod(&u, &powmod(&v, &P_MINUS_2, &P), &P);
+        match sqrt(&u_div_v) {
+            Some(_) => io::hint_slice(&[1]),
+            None => {
+                let w = sqrt(&addmod(&u_div_v, &u_div_v, &P)).unwrap();
+                let mut hint = vec![0];
+                hint.extend_from_slice(&words_to_bytes_le::<32>(&w));
+                io::hint_slice(&hint);
+            }
+        }
+    }
+
+    let hint = io::read_vec();
+    if hint[0] == 1 {
+        let mut point = [0u32; 16];
+        // The precompile reads and writes words, so the buffer is word aligned.
+        let point_bytes = unsafe { &mut *(point.as_mut_ptr() as *mut [u8; 64]) };
+        point_bytes[32..].copy_from_slice(pubkey);
+        unsafe {
+            syscall_ed_decompress(point_bytes);
+        }
+        Some(point)
+    } else {
+        let w = bytes_to_words_le::<8>(&hint[1..]);
+        let ww = mulmod(&w, &w, &P);
+        assert!(
+            u != ZERO && mulmod(&ww, &v, &P) == addmod(&u, &u, &P),
+            "ed25519: invalid decompression hint"
+        );
+        None
+    }
+}
+
+/// Returns a square root of `a` in the base field, or `None` if `a` is not a square.
+fn sqrt(a: &U256) -> Option<U256> {
+    let beta = powmod(a, &SQRT_EXP, &P);
+    let beta_squared = mulmod(&beta, &beta, &P);
+    if beta_squared == *a {
+        Some(beta)
+    } else if beta_squared == submod(&ZERO, a, &P) {
+        Some(mulmod(&beta, &SQRT_M1, &P))
+    } else {
+        None
+    }
+}
+
+/// Reduces a 512-bit little-endian hash modulo `L`.
+fn reduce_wide(hash: &[u8; 64]) -> U256 {
+    let lo = bytes_to_words_le::<8>(&hash[..32]);
+    let hi = bytes_to_words_le::<8>(&hash[32..]);
+    addmod(&mulmod(&hi, &R_MOD_L, &L), &mulmod(&lo, &ONE, &L), &L)
+}
+
+/// Adds `q` to `p` with the `ed_add` precompile. The addition is complete, so the points may be
+/// equal or the identity.
+fn add_assign(p: &mut [u32; 16], q: &[u32; 16]) {
+    // The precompile reads `q` while writing `p`, so they must not alias.
+    let mut q = *q;
+    unsafe {
+        syscall_ed_add(p.as_mut_ptr(), q.as_mut_ptr());
+    }
+}
+
+/// Computes `[a]p + [b]q` with a shared double-and-add.
+fn double_scalar_mul(a: &U256, p: &[u32; 16], b: &U256, q: &[u32; 16]) -> [u32; 16] {
+    let mut p_plus_q = *p;
+    add_assign(&mut p_plus_q, q);
+
+    let mut res = IDENTITY;
+    for i in (0..256).rev() {
+        let res_copy = res;
+        add_assign(&mut res, &res_copy);
+        let bits = ((a[i / 32] >> (i % 32)) & 1, (b[i / 32] >> (i % 32)) & 1);
+        match bits {
+            (1, 1) => add_assign(&mut res, &p_plus_q),
+            (1, 0) => add_assign(&mut res, p),
+            (0, 1) => add_assign(&mut res, q),
+            _ => {}
+        }
+    }
+    res
+}
+
+/// Negates a point, which is `(-x, y)`.
+fn negate(p: &[u32; 16]) -> [u32; 16] {
+    let mut res = *p;
+    let x = submod(&ZERO, p[..8].try_into().unwrap(), &P);
+    res[..8].copy_from_slice(&x);
+    res
+}
+
+/// Computes `[k]p` with the `ed_scalar_mul` precompile, where `p` is an affine point as
+/// little-endian words with x followed by y, and `k` is any 256-bit scalar.
+pub fn scalar_mul(p: &[u32; 16], k: &U256) -> [u32; 16] {
+    // The precompile reads the point from the second half of the buffer, and accumulates the
+    // result in the first half, which starts as the identity. Each call handles one word of the
+    // scalar, from the most significant one.
+    let mut buf = [0u32; 32];
+    buf[8] = 1;
+    buf[16..].copy_from_slice(p);
+    for word in k.iter().rev() {
+        unsafe {
+            syscall_ed_scalar_mul(buf.as_mut_ptr(), word);
+        }
+    }
+    buf[..16].try_into().unwrap()
+}
+
+/// Encodes a point as its y coordinate, with the sign of x in the top bit, with the
+/// `ed_compress` precompile. The coordinates of `p` must be reduced, which is the case for the
+/// results of the other precompiles.
+pub fn compress(p: &[u32; 16]) -> [u8; 32] {
+    let mut buf = *p;
+    unsafe {
+        syscall_ed_compress(buf.as_mut_ptr());
+    }
+    words_to_bytes_le::<32>(&buf[8..])
+}
+
+#[cfg(test)]
+mod tests {
+    use ed25519_dalek::{Signer, SigningKey, Verifier, VerifyingKey};
+
+    use super::*;
+
+    /// Checks that `verify` agrees with ed25519-dalek, and returns the result.
+    fn check(pubkey: &[u8; 32], msg: &[u8], sig: &[u8; 64]) -> bool {
+        let expected = match VerifyingKey::from_bytes(pubkey) {
+            Ok(key) => key
+                .verify(msg, &ed25519_dalek::Signature::from_bytes(sig))
+                .is_ok(),
+            Err(_) => false,
+        };
+        let result = verify(pubkey, msg, sig);
+        assert_eq!(result, expected);
+        result
+    }
+
+    #[test]
+    fn test_verify() {
+        let signing_key = SigningKey::from_bytes(&[7u8; 32]);
+        let pubkey = signing_key.verifying_key().to_bytes();
+        let l = words_to_bytes_le::<32>(&L);
+
+        let long_msg = [0xab_u8; 300];
+        for msg in [&b""[..], &b"hello world"[..], &long_msg[..]] {
+            let sig = signing_key.sign(msg).to_bytes();
+            assert!(check(&pubkey, msg, &sig));
+
+            let mut other_msg = msg.to_vec();
+            other_msg.push(1);
+            assert!(!check(&pubkey, &other_msg, &sig));
+
+            let mut bad_sig = sig;
+            bad_sig[0] ^= 1;
+            assert!(!check(&pubkey, msg, &bad_sig));
+            let mut bad_sig = sig;
+            bad_sig[32] ^= 1;
+            assert!(!check(&pubkey, msg, &bad_sig));
+
+            // S + L is the same scalar, but is not canonical.
+            let mut malleable_sig = sig;
+            let mut carry = 0u16;
+            for i in 0..32 {
+                let sum = sig[32 + i] as u16 + l[i] as u16 + carry;
+                malleable_sig[32 + i] = sum as u8;
+                carry = sum >> 8;
+            }
+            assert!(!check(&pubkey, msg, &malleable_sig));
+        }
+
+        let sig = signing_key.sign(b"hello world").to_bytes();
+        for y in 2..12u8 {
+            let mut bad_pubkey = [0u8; 32];
+            bad_pubkey[0] = y;
+            assert!(!check(&bad_pubkey, b"hello world", &sig));
+        }
+    }
+
+    #[test]
+    fn test_negate() {
+        let basepoint = curve25519_dalek::constants::ED25519_BASEPOINT_POINT;
+        let mut expected = (-basepoint).compress().to_bytes();
+        let sign = expected[31] >> 7;
+        expected[31] &= 0b0111_1111;
+
+        let neg = negate(&BASEPOINT);
+        assert_eq!(words_to_bytes_le::<32>(&neg[8..]), expected);
+        assert_eq!(neg[0] & 1, sign as u32);
+    }
+}
@@ -0,0 +1,38 @@
+use crate::syscall_keccak256_hash;
+
+/// The input length must be less than this bound.
+pub const MAX_INPUT_LEN: usize = 1 << 24;
+
+/// Computes the Keccak-256 hash of the input, as used by Ethereum.
+///
+/// The whole input is absorbed by a single syscall. If the input is not word aligned, it is first
+/// copied into an aligned buffer.
+pub fn keccak256(input: &[u8]) -> [u8; 32] {
+    assert!(
+        input.len() < MAX_INPUT_LEN,
+        "keccak256: the input is too long"
+    );
+
+    let mut out = [0u32; 8];
+    if input.as_ptr() as usize % 4 == 0 {
+        unsafe {
+            syscall_keccak256_hash(input.as_ptr(), input.len(), out.as_mut_ptr());
+        }
+    } else {
+        let mut aligned = vec![0u32; input.len().div_ceil(4)];
+        for (word, chunk) in aligned.iter_mut().zip(input.chunks(4)) {
+            let mut bytes = [0u8; 4];
+            bytes[..chunk.len()].copy_from_slice(chunk);
+            *word = u32::from_le_bytes(bytes);
+        }
+        unsafe {
+            syscall_keccak256_hash(aligned.as_ptr() as *const u8, input.len(), out.as_mut_ptr());
+        }
+    }
+
+    let mut digest = [0u8; 32];
+    for (chunk, word) in digest.chunks_mut(4).zip(out.iter()) {
+        chunk.copy_from_slice(&word.to_le_bytes());
+    }
+    digest
+}
@@ -1,6 +1,14 @@
+pub mod bigint;
+pub mod bls12381;
 pub mod bn254;
+pub mod ed25519;
 pub mod io;
+pub mod keccak256;
+pub mod poseidon2;
+pub mod ripemd160;
 pub mod secp256k1;
+pub mod secp256r1;
+pub mod uint256;
 pub mod unconstrained;
 pub mod utils;
 pub mod verify;
@@ -13,13 +21,48 @@ extern "C" {
     pub fn syscall_sha256_compress(w: *mut u32, state: *mut u32);
     pub fn syscall_ed_add(p: *mut u32, q: *mut u32);
     pub fn syscall_ed_decompress(point: &mut [u8; 64]);
+    pub fn syscall_ed_scalar_mul(p: *mut u32, scalar: *const u32);
+    pub fn syscall_ed_compress(p: *mut u32);
     pub fn syscall_secp256k1_add(p: *mut u32, q: *const u32);
     pub fn syscall_secp256k1_double(p: *mut u32);
+    pub fn syscall_secp256k1_msm(
+        result: *mut u32,
+        points: *const u32,
+        scalars: *const u32,
+        n: usize,
+    ) -> bool;
     pub fn syscall_secp256k1_decompress(point: &mut [u8; 64], is_odd: bool);
+    pub fn syscall_secp256r1_add(p: *mut u32, q: *const u32);
+    pub fn syscall_secp256r1_double(p: *mut u32);
+    pub fn syscall_secp256r1_decompress(point: &mut [u8; 64], is_odd: bool);
     pub fn syscall_bn254_add(p: *mut u32, q: *const u32);
     pub fn syscall_bn254_double(p: *mut u32);
+    pub fn syscall_bn254_msm(
+        result: *mut u32,
+        points: *const u32,
+        scalars: *const u32,
+        n: usize,
+    ) -> bool;
+    pub fn syscall_bn254_fp2_add(x: *mut u32, y: *const u32);
+    pub fn syscall_bn254_fp2_sub(x: *mut u32, y: *const u32);
+    pub fn syscall_bn254_fp2_mul(x: *mut u32, y: *const u32);
+    pub fn syscall_bls12381_add(p: *mut u32, q: *const u32);
+    pub fn syscall_bls12381_double(p: *mut u32);
+    pub fn syscall_bls12381_decompress(point: &mut [u8; 96], is_odd: bool);
+    pub fn syscall_bls12381_fp2_add(x: *mut u32, y: *const u32);
+    pub fn syscall_bls12381_fp2_sub(x: *mut u32, y: *const u32);
+    pub fn syscall_bls12381_fp2_mul(x: *mut u32, y: *const u32);
+    pub fn syscall_uint256_mulmod(x: *mut u32, y: *const u32);
+    pub fn syscall_bigint_mulmod(x: *mut u32, y: *const u32);
+    pub fn syscall_sha512_extend(w: *mut u64);
+    pub fn syscall_sha512_compress(w: *mut u64, state: *mut u64);
     pub fn syscall_keccak_permute(state: *mut u64);
+    pub fn syscall_keccak256_hash(input: *const u8, len: usize, out: *mut u32);
     pub fn syscall_blake3_compress_inner(p: *mut u32, q: *const u32);
+    pub fn syscall_blake2b_compress(state: *mut u64, input: *const u32);
+    pub fn syscall_ripemd160_compress(state: *mut u32, block: *const u32);
+    pub fn syscall_poseidon2_permute(state: *mut u32);
+    pub fn syscall_memcpy(dst: *mut u32, src: *const u32);
     pub fn syscall_enter_unconstrained() -> bool;
     pub fn syscall_exit_unconstrained();
     pub fn syscall_verify_sp1_proof(vkey: &[u32; 8], pv_digest: &[u32; 8]);
@@ -0,0 +1,33 @@
+use crate::syscall_poseidon2_permute;
+
+/// The width of the Poseidon2 permutation.
+pub const WIDTH: usize = 16;
+
+/// The number of elements absorbed per permutation.
+pub const RATE: usize = 8;
+
+/// The number of elements in a digest.
+pub const DIGEST_SIZE: usize = 8;
+
+/// Applies the Poseidon2 permutation over BabyBear to a state of canonical BabyBear elements,
+/// encoded as `u32`s.
+pub fn poseidon2_permute(state: &mut [u32; WIDTH]) {
+    unsafe {
+        syscall_poseidon2_permute(state.as_mut_ptr());
+    }
+}
+
+/// Hashes canonical BabyBear elements, encoded as `u32`s, with the same padding-free sponge as
+/// `sp1_primitives::poseidon2_hash`, so that the digest can be checked cheaply in recursion.
+pub fn poseidon2_hash(input: &[u32]) -> [u32; DIGEST_SIZE] {
+    // Each block overwrites the beginning of the state, and the last block may be partial.
+    let mut state = [0u32; WIDTH];
+    for block in input.chunks(RATE) {
+        state[..block.len()].copy_from_slice(block);
+        poseidon2_permute(&mut state);
+    }
+
+    let mut digest = [0u32; DIGEST_SIZE];
+    digest.copy_from_slice(&state[..DIGEST_SIZE]);
+    digest
+}
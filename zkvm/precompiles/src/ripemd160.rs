@@ -0,0 +1,56 @@
+use sha2::{Digest, Sha256};
+
+use crate::syscall_ripemd160_compress;
+
+/// The initial state of RIPEMD-160.
+const IV: [u32; 5] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476, 0xc3d2e1f0];
+
+/// The size of a block in bytes.
+const BLOCK_LEN: usize = 64;
+
+/// Computes the RIPEMD-160 hash of the input, with one syscall per 64-byte block.
+pub fn ripemd160(input: &[u8]) -> [u8; 20] {
+    let mut state = IV;
+
+    let mut blocks = input.chunks_exact(BLOCK_LEN);
+    for block in &mut blocks {
+        compress(&mut state, block);
+    }
+
+    // Pad the remainder with a one bit, then zeros, and the length in bits as a little-endian u64,
+    // which takes one or two more blocks.
+    let remainder = blocks.remainder();
+    let mut tail = [0u8; 2 * BLOCK_LEN];
+    tail[..remainder.len()].copy_from_slice(remainder);
+    tail[remainder.len()] = 0x80;
+    let tail_len = if remainder.len() < BLOCK_LEN - 8 {
+        BLOCK_LEN
+    } else {
+        2 * BLOCK_LEN
+    };
+    tail[tail_len - 8..tail_len].copy_from_slice(&(8 * input.len() as u64).to_le_bytes());
+    for block in tail[..tail_len].chunks_exact(BLOCK_LEN) {
+        compress(&mut state, block);
+    }
+
+    let mut digest = [0u8; 20];
+    for (chunk, word) in digest.chunks_mut(4).zip(state.iter()) {
+        chunk.copy_from_slice(&word.to_le_bytes());
+    }
+    digest
+}
+
+/// Computes `RIPEMD-160(SHA-256(input))`, as used for Bitcoin addresses.
+pub fn hash160(input: &[u8]) -> [u8; 20] {
+    ripemd160(&Sha256::digest(input))
+}
+
+fn compress(state: &mut [u32; 5], block: &[u8]) {
+    let mut words = [0u32; 16];
+    for (word, chunk) in words.iter_mut().zip(block.chunks_exact(4)) {
+        *word = u32::from_le_bytes(chunk.try_into().unwrap());
+    }
+    unsafe {
+        syscall_ripemd160_compress(state.as_mut_ptr(), words.as_ptr());
+    }
+}
@@ -1,7 +1,13 @@
 #![allow(unused)]
 
-use crate::utils::{AffinePoint, CurveOperations};
-use crate::{syscall_secp256k1_add, syscall_secp256k1_decompress, syscall_secp256k1_double};
+use crate::uint256::{addmod, lt, mulmod, powmod, submod, words_from_be_bytes, U256};
+use crate::utils::{
+    bytes_to_words_le, words_to_bytes_le, AffinePoint, CurveOperations, MAX_MSM_POINTS,
+};
+use crate::{
+    syscall_secp256k1_add, syscall_secp256k1_decompress, syscall_secp256k1_double,
+    syscall_secp256k1_msm,
+};
 use anyhow::Context;
 use anyhow::{anyhow, Result};
 use core::convert::TryInto;
@@ -9,10 +15,11 @@ use k256::ecdsa::hazmat::bits2field;
 use k256::ecdsa::signature::hazmat::PrehashVerifier;
 use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
 use k256::elliptic_curve::ff::PrimeFieldBits;
-use k256::elliptic_curve::ops::Invert;
+use k256::elliptic_curve::ops::{Invert, Reduce};
 use k256::elliptic_curve::sec1::ToEncodedPoint;
 use k256::elliptic_curve::PrimeField;
-use k256::{PublicKey, Scalar, Secp256k1};
+use k256::{FieldBytes, PublicKey, Scalar, Secp256k1};
+use sha2::{Digest, Sha256};
 
 use crate::io;
 use crate::unconstrained;
@@ -38,6 +45,24 @@ impl CurveOperations for Secp256k1Operations {
             syscall_secp256k1_double(limbs.as_mut_ptr());
         }
     }
+
+    fn msm(points: &[[u32; 16]], scalars: &[[u32; 8]]) -> Option<[u32; 16]> {
+        assert!(points.len() <= MAX_MSM_POINTS);
+        assert_eq!(points.len(), scalars.len());
+        if points.is_empty() {
+            return None;
+        }
+        let mut result = [0u32; 16];
+        let is_identity = unsafe {
+            syscall_secp256k1_msm(
+                result.as_mut_ptr(),
+                points.as_ptr() as *const u32,
+                scalars.as_ptr() as *const u32,
+                points.len(),
+            )
+        };
+        (!is_identity).then_some(result)
+    }
 }
 
 /// Decompresses a compressed public key using secp256k1_decompress precompile.
@@ -85,15 +110,13 @@ pub fn verify_signature(
             let pubkey_x = Scalar::from_repr(bits2field::<Secp256k1>(&pubkey[1..33]).unwrap()).unwrap();
             let pubkey_y = Scalar::from_repr(bits2field::<Secp256k1>(&pubkey[33..]).unwrap()).unwrap();
 
-            let mut pubkey_x_le_bytes = pubkey_x.to_bytes();
+            let mut pubkey_x_le_bytes: [u8; 32] = pubkey_x.to_bytes().into();
             pubkey_x_le_bytes.reverse();
-            let mut pubkey_y_le_bytes = pubkey_y.to_bytes();
+            let mut pubkey_y_le_bytes: [u8; 32] = pubkey_y.to_bytes().into();
             pubkey_y_le_bytes.reverse();
 
             // Convert the public key to an affine point
-            let affine = AffinePoint::<Secp256k1Operations>::from(pubkey_x_le_bytes.into(), pubkey_y_le_bytes.into());
-
-            const GENERATOR: AffinePoint<Secp256k1Operations> = AffinePoint::<Secp256k1Operations>::generator_in_affine();
+            let affine = AffinePoint::<Secp256k1Operations>::from(pubkey_x_le_bytes, pubkey_y_le_bytes);
 
             let field = bits2field::<Secp256k1>(msg_hash);
             if field.is_err() {
@@ -117,7 +140,14 @@ pub fn verify_signature(
             let u1 = z * s_inv;
             let u2 = *r * s_inv;
 
-            let res = double_and_add_base(&u1, &GENERATOR, &u2, &affine).unwrap();
+            let res = AffinePoint::<Secp256k1Operations>::msm(
+                &[AffinePoint::generator_in_affine(), affine],
+                &[scalar_to_words_le(&u1), scalar_to_words_le(&u2)],
+            );
+            let res = match res {
+                Some(res) => res,
+                None => return false,
+            };
             let mut x_bytes_be = [0u8; 32];
             for i in 0..8 {
                 x_bytes_be[i * 4..(i * 4) + 4].copy_from_slice(&res.limbs[i].to_le_bytes());
@@ -146,39 +176,11 @@ pub fn verify_signature(
     }
 }
 
-#[allow(non_snake_case)]
-fn double_and_add_base(
-    a: &Scalar,
-    A: &AffinePoint<Secp256k1Operations>,
-    b: &Scalar,
-    B: &AffinePoint<Secp256k1Operations>,
-) -> Option<AffinePoint<Secp256k1Operations>> {
-    let mut res: Option<AffinePoint<Secp256k1Operations>> = None;
-    let mut temp_A = *A;
-    let mut temp_B = *B;
-
-    let a_bits = a.to_le_bits();
-    let b_bits = b.to_le_bits();
-    for (a_bit, b_bit) in a_bits.iter().zip(b_bits) {
-        if *a_bit {
-            match res.as_mut() {
-                Some(res) => res.add_assign(&temp_A),
-                None => res = Some(temp_A),
-            };
-        }
-
-        if b_bit {
-            match res.as_mut() {
-                Some(res) => res.add_assign(&temp_B),
-                None => res = Some(temp_B),
-            };
-        }
-
-        temp_A.double();
-        temp_B.double();
-    }
-
-    res
+/// Converts a scalar to 8 little-endian words.
+fn scalar_to_words_le(scalar: &Scalar) -> [u32; 8] {
+    let mut bytes = scalar.to_bytes();
+    bytes.reverse();
+    bytes_to_words_le::<8>(&bytes)
 }
 
 /// Outside of the VM, computes the pubkey and s_inverse value from a signature and a message hash.
@@ -230,3 +232,185 @@ pub fn ecrecover(sig: &[u8; 65], msg_hash: &[u8; 32]) -> Result<[u8; 65]> {
         Err(anyhow!("failed to verify signature"))
     }
 }
+
+/// The modulus of the secp256k1 base field.
+const FIELD_MODULUS: U256 = [
+    0xFFFFFC2F, 0xFFFFFFFE, 0xFFFFFFFF, 0xFFFFFFFF, 0xFFFFFFFF, 0xFFFFFFFF, 0xFFFFFFFF, 0xFFFFFFFF,
+];
+
+/// `(p + 1) / 4`, the exponent of the square root in the base field.
+const SQRT_EXP: U256 = [
+    0xBFFFFF0C, 0xFFFFFFFF, 0xFFFFFFFF, 0xFFFFFFFF, 0xFFFFFFFF, 0xFFFFFFFF, 0xFFFFFFFF, 0x3FFFFFFF,
+];
+
+/// Verifies a BIP-340 Schnorr signature `r || s` of `msg` by the x-only public key `pubkey`.
+pub fn schnorr_verify(pubkey: &[u8; 32], msg: &[u8], sig: &[u8; 64]) -> bool {
+    let px = words_from_be_bytes(pubkey);
+    let r = words_from_be_bytes(sig[..32].try_into().unwrap());
+    if !lt(&px, &FIELD_MODULUS) || !lt(&r, &FIELD_MODULUS) {
+        return false;
+    }
+    let s = match Option::<Scalar>::from(Scalar::from_repr(*FieldBytes::from_slice(&sig[32..]))) {
+        Some(s) => s,
+        None => return false,
+    };
+
+    // e = int(hash_BIP0340/challenge(r || P || m)) mod n.
+    let tag = Sha256::digest(b"BIP0340/challenge");
+    let e_bytes = Sha256::new()
+        .chain_update(tag)
+        .chain_update(tag)
+        .chain_update(&sig[..32])
+        .chain_update(pubkey)
+        .chain_update(msg)
+        .finalize();
+    let e = <Scalar as Reduce<k256::U256>>::reduce_bytes(&e_bytes);
+
+    cfg_if::cfg_if! {
+        if #[cfg(all(target_os = "zkvm", target_vendor = "succinct"))] {
+            let p = match lift_x(pubkey, &px) {
+                Some(p) => p,
+                None => return false,
+            };
+
+            // R = s * G - e * P.
+            let res = AffinePoint::<Secp256k1Operations>::msm(
+                &[AffinePoint::generator_in_affine(), p],
+                &[scalar_to_words_le(&s), scalar_to_words_le(&-e)],
+            );
+
+            // R must not be the identity, have an even y coordinate, and have x coordinate r.
+            match res {
+                Some(res) => res.limbs[8] & 1 == 0 && res.limbs[..8] == r,
+                None => false,
+            }
+        } else {
+            let verifying_key = match k256::schnorr::VerifyingKey::from_bytes(pubkey) {
+                Ok(verifying_key) => verifying_key,
+                Err(_) => return false,
+            };
+            let signature = match k256::schnorr::Signature::try_from(&sig[..]) {
+                Ok(signature) => signature,
+                Err(_) => return false,
+            };
+            verifying_key.verify_raw(msg, &signature).is_ok()
+        }
+    }
+}
+
+/// Returns the point with x coordinate `x` and an even y coordinate, or `None` if there is none.
+///
+/// Whether `y^2 = x^3 + 7` has a solution is hinted. If it does, the decompress precompile checks
+/// it. Otherwise the hint is a square root of `-(x^3 + 7)`, which proves that `x^3 + 7` is not a
+/// square since -1 is not a square modulo `p`.
+fn lift_x(x_bytes: &[u8; 32], x: &U256) -> Option<AffinePoint<Secp256k1Operations>> {
+    let seven = [7, 0, 0, 0, 0, 0, 0, 0];
+    let c = addmod(
+        &mulmod(&mulmod(x, x, &FIELD_MODULUS), x, &FIELD_MODULUS),
+        &seven,
+        &FIELD_MODULUS,
+    );
+    let neg_c = submod(&[0; 8], &c, &FIELD_MODULUS);
+
+    unconstrained! {
+        let y = powmod(&c, &SQRT_EXP, &FIELD_MODULUS);
+        if mulmod(&y, &y, &FIELD_MODULUS) == c {
+            io::hint_slice(&[1]);
+        } else {
+            let w = powmod(&neg_c, &SQRT_EXP, &FIELD_MODULUS);
+            let mut hint = vec![0];
+            hint.extend_from_slice(&words_to_bytes_le::<32>(&w));
+            io::hint_slice(&hint);
+        }
+    }
+
+    let hint = io::read_vec();
+    if hint[0] == 1 {
+        let mut point = [0u32; 16];
+        // The precompile reads and writes words, so the buffer is word aligned.
+        let point_bytes = unsafe { &mut *(point.as_mut_ptr() as *mut [u8; 64]) };
+        point_bytes[..32].copy_from_slice(x_bytes);
+        unsafe {
+            syscall_secp256k1_decompress(point_bytes, false);
+        }
+
+        // The precompile returns the coordinates in big-endian.
+        let mut x_le: [u8; 32] = point_bytes[..32].try_into().unwrap();
+        let mut y_le: [u8; 32] = point_bytes[32..].try_into().unwrap();
+        x_le.reverse();
+        y_le.reverse();
+        Some(AffinePoint::<Secp256k1Operations>::from(x_le, y_le))
+    } else {
+        let w = bytes_to_words_le::<8>(&hint[1..]);
+        assert!(
+            c != [0; 8] && mulmod(&w, &w, &FIELD_MODULUS) == neg_c,
+            "secp256k1: invalid lift_x hint"
+        );
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use k256::schnorr::{SigningKey, VerifyingKey};
+
+    use super::*;
+
+    /// The order of the curve, in big-endian.
+    const N: [u8; 32] = [
+        0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+        0xfe, 0xba, 0xae, 0xdc, 0xe6, 0xaf, 0x48, 0xa0, 0x3b, 0xbf, 0xd2, 0x5e, 0x8c, 0xd0, 0x36,
+        0x41, 0x41,
+    ];
+
+    /// Checks that `schnorr_verify` agrees with k256, and returns the result.
+    fn check(pubkey: &[u8; 32], msg: &[u8], sig: &[u8; 64]) -> bool {
+        let expected = match (
+            VerifyingKey::from_bytes(pubkey),
+            k256::schnorr::Signature::try_from(&sig[..]),
+        ) {
+            (Ok(key), Ok(sig)) => key.verify_raw(msg, &sig).is_ok(),
+            _ => false,
+        };
+        let result = schnorr_verify(pubkey, msg, sig);
+        assert_eq!(result, expected);
+        result
+    }
+
+    #[test]
+    fn test_schnorr_verify() {
+        let signing_key = SigningKey::from_bytes(&[7u8; 32]).unwrap();
+        let pubkey: [u8; 32] = signing_key.verifying_key().to_bytes().into();
+
+        let long_msg = [0xab_u8; 300];
+        for msg in [&b""[..], &[0u8; 32][..], &long_msg[..]] {
+            let sig: [u8; 64] = signing_key.sign_raw(msg, &[1u8; 32]).unwrap().to_bytes();
+            assert!(check(&pubkey, msg, &sig));
+
+            let mut other_msg = msg.to_vec();
+            other_msg.push(1);
+            assert!(!check(&pubkey, &other_msg, &sig));
+
+            let mut bad_sig = sig;
+            bad_sig[31] ^= 1;
+            assert!(!check(&pubkey, msg, &bad_sig));
+            let mut bad_sig = sig;
+            bad_sig[63] ^= 1;
+            assert!(!check(&pubkey, msg, &bad_sig));
+
+            let mut bad_sig = sig;
+            bad_sig[32..].copy_from_slice(&N);
+            assert!(!check(&pubkey, msg, &bad_sig));
+        }
+
+        let sig: [u8; 64] = signing_key
+            .sign_raw(b"hello world", &[1u8; 32])
+            .unwrap()
+            .to_bytes();
+        for x in 1..12u8 {
+            let mut bad_pubkey = [0u8; 32];
+            bad_pubkey[31] = x;
+            assert!(!check(&bad_pubkey, b"hello world", &sig));
+        }
+    }
+}
@@ -0,0 +1,113 @@
+use crate::syscall_uint256_mulmod;
+
+/// The number of words in a 256-bit integer.
+pub const NUM_WORDS: usize = 8;
+
+/// A 256-bit unsigned integer as little-endian words.
+pub type U256 = [u32; NUM_WORDS];
+
+/// Computes `x * y mod modulus` using the uint256_mulmod precompile. A zero modulus stands for
+/// `2^256`, which makes this a wrapping multiplication.
+pub fn mulmod(x: &U256, y: &U256, modulus: &U256) -> U256 {
+    let mut y_and_modulus = [0u32; 2 * NUM_WORDS];
+    y_and_modulus[..NUM_WORDS].copy_from_slice(y);
+    y_and_modulus[NUM_WORDS..].copy_from_slice(modulus);
+
+    let mut result = *x;
+    unsafe {
+        syscall_uint256_mulmod(result.as_mut_ptr(), y_and_modulus.as_ptr());
+    }
+    result
+}
+
+/// Computes `x * y mod modulus` for big-endian byte encodings, as used by the EVM.
+pub fn mulmod_be_bytes(x: &[u8; 32], y: &[u8; 32], modulus: &[u8; 32]) -> [u8; 32] {
+    let result = mulmod(
+        &words_from_be_bytes(x),
+        &words_from_be_bytes(y),
+        &words_from_be_bytes(modulus),
+    );
+    words_to_be_bytes(&result)
+}
+
+/// Computes `x + y mod modulus` for `x` and `y` smaller than a nonzero `modulus`.
+pub fn addmod(x: &U256, y: &U256, modulus: &U256) -> U256 {
+    let (sum, carry) = add(x, y);
+    if carry || !lt(&sum, modulus) {
+        sub(&sum, modulus).0
+    } else {
+        sum
+    }
+}
+
+/// Computes `x - y mod modulus` for `x` and `y` smaller than a nonzero `modulus`.
+pub fn submod(x: &U256, y: &U256, modulus: &U256) -> U256 {
+    let (diff, borrow) = sub(x, y);
+    if borrow {
+        add(&diff, modulus).0
+    } else {
+        diff
+    }
+}
+
+/// Computes `x^exponent mod modulus` by square-and-multiply with `mulmod`.
+pub fn powmod(x: &U256, exponent: &U256, modulus: &U256) -> U256 {
+    let mut result = [0u32; NUM_WORDS];
+    result[0] = 1;
+    for word in exponent.iter().rev() {
+        for bit in (0..32).rev() {
+            result = mulmod(&result, &result, modulus);
+            if (word >> bit) & 1 == 1 {
+                result = mulmod(&result, x, modulus);
+            }
+        }
+    }
+    result
+}
+
+/// Returns the wrapping sum of `x` and `y`, and whether it overflowed.
+fn add(x: &U256, y: &U256) -> (U256, bool) {
+    let mut result = [0u32; NUM_WORDS];
+    let mut carry = false;
+    for i in 0..NUM_WORDS {
+        let (sum, c1) = x[i].overflowing_add(y[i]);
+        let (sum, c2) = sum.overflowing_add(carry as u32);
+        result[i] = sum;
+        carry = c1 || c2;
+    }
+    (result, carry)
+}
+
+/// Returns the wrapping difference of `x` and `y`, and whether it underflowed.
+fn sub(x: &U256, y: &U256) -> (U256, bool) {
+    let mut result = [0u32; NUM_WORDS];
+    let mut borrow = false;
+    for i in 0..NUM_WORDS {
+        let (diff, b1) = x[i].overflowing_sub(y[i]);
+        let (diff, b2) = diff.overflowing_sub(borrow as u32);
+        result[i] = diff;
+        borrow = b1 || b2;
+    }
+    (result, borrow)
+}
+
+/// Returns true if `x < y`.
+pub(crate) fn lt(x: &U256, y: &U256) -> bool {
+    x.iter().rev().lt(y.iter().rev())
+}
+
+pub(crate) fn words_from_be_bytes(bytes: &[u8; 32]) -> U256 {
+    let mut words = [0u32; NUM_WORDS];
+    for (word, chunk) in words.iter_mut().zip(bytes.rchunks_exact(4)) {
+        *word = u32::from_be_bytes(chunk.try_into().unwrap());
+    }
+    words
+}
+
+fn words_to_be_bytes(words: &U256) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    for (chunk, word) in bytes.rchunks_exact_mut(4).zip(words.iter()) {
+        chunk.copy_from_slice(&word.to_be_bytes());
+    }
+    bytes
+}
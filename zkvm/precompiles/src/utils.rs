@@ -1,17 +1,77 @@
-pub trait CurveOperations {
-    const GENERATOR: [u32; 16];
-    fn add_assign(limbs: &mut [u32; 16], other: &[u32; 16]);
-    fn double(limbs: &mut [u32; 16]);
+/// The maximum number of points in a single call to `CurveOperations::msm`.
+pub const MAX_MSM_POINTS: usize = 16;
+
+/// Curve operations backed by precompiles. Points are represented in affine form as `NUM_WORDS`
+/// little-endian words, with the x coordinate in the first half and the y coordinate in the second.
+pub trait CurveOperations<const NUM_WORDS: usize = 16> {
+    const GENERATOR: [u32; NUM_WORDS];
+
+    /// Adds `other` to `limbs`. The points must have different x coordinates.
+    fn add_assign(limbs: &mut [u32; NUM_WORDS], other: &[u32; NUM_WORDS]);
+
+    fn double(limbs: &mut [u32; NUM_WORDS]);
+
+    /// Computes the sum of `scalars[i] * points[i]` for at most `MAX_MSM_POINTS` points, where the
+    /// scalars are 8 little-endian words. Returns `None` if the result is the identity.
+    ///
+    /// The default implementation is a double-and-add with `add_assign` and `double`. Curves with
+    /// a multi-scalar multiplication precompile override it.
+    fn msm(points: &[[u32; NUM_WORDS]], scalars: &[[u32; 8]]) -> Option<[u32; NUM_WORDS]> {
+        debug_assert!(points.len() <= MAX_MSM_POINTS);
+        let mut res = None;
+        for word in (0..8).rev() {
+            for bit in (0..32).rev() {
+                if let Some(res) = res.as_mut() {
+                    Self::double(res);
+                }
+                for (point, scalar) in points.iter().zip(scalars) {
+                    if (scalar[word] >> bit) & 1 == 1 {
+                        res = add_complete::<Self, NUM_WORDS>(res, point);
+                    }
+                }
+            }
+        }
+        res
+    }
+}
+
+/// Adds a point to an accumulator which is `None` for the identity, handling the cases where the
+/// points are equal or opposite.
+fn add_complete<C: CurveOperations<N> + ?Sized, const N: usize>(
+    acc: Option<[u32; N]>,
+    point: &[u32; N],
+) -> Option<[u32; N]> {
+    match acc {
+        None => Some(*point),
+        Some(mut acc) if acc == *point => {
+            C::double(&mut acc);
+            Some(acc)
+        }
+        Some(acc) if acc[..N / 2] == point[..N / 2] => None,
+        Some(mut acc) => {
+            C::add_assign(&mut acc, point);
+            Some(acc)
+        }
+    }
+}
+
+/// Checks at compile time that byte arrays of length `B` fit points of `N` words. Referencing one
+/// of the constants fails to build when the lengths disagree.
+struct ByteLen<const B: usize, const N: usize>;
+
+impl<const B: usize, const N: usize> ByteLen<B, N> {
+    const COORDINATE: () = assert!(B == N * 2, "a coordinate must be two bytes per point word");
+    const POINT: () = assert!(B == N * 4, "a point must be four bytes per word");
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
-pub struct AffinePoint<C: CurveOperations> {
-    pub(crate) limbs: [u32; 16],
+pub struct AffinePoint<C: CurveOperations<N>, const N: usize = 16> {
+    pub(crate) limbs: [u32; N],
     _marker: std::marker::PhantomData<C>,
 }
 
-impl<C: CurveOperations + Copy> AffinePoint<C> {
-    const GENERATOR: [u32; 16] = C::GENERATOR;
+impl<C: CurveOperations<N> + Copy, const N: usize> AffinePoint<C, N> {
+    const GENERATOR: [u32; N] = C::GENERATOR;
 
     pub const fn generator_in_affine() -> Self {
         Self {
@@ -20,7 +80,7 @@ impl<C: CurveOperations + Copy> AffinePoint<C> {
         }
     }
 
-    pub fn new(limbs: [u32; 16]) -> Self {
+    pub fn new(limbs: [u32; N]) -> Self {
         Self {
             limbs,
             _marker: std::marker::PhantomData,
@@ -28,17 +88,20 @@ impl<C: CurveOperations + Copy> AffinePoint<C> {
     }
 
     /// Construct an AffinePoint from the x and y coordinates. The coordinates are expected to be
-    /// in little-endian byte order.
-    pub fn from(x_bytes: [u8; 32], y_bytes: [u8; 32]) -> Self {
-        let mut limbs = [0u32; 16];
-        let x = bytes_to_words_le::<8>(&x_bytes);
-        let y = bytes_to_words_le::<8>(&y_bytes);
-        limbs[..8].copy_from_slice(&x);
-        limbs[8..].copy_from_slice(&y);
+    /// in little-endian byte order, each `N * 2` bytes long.
+    pub fn from<const B: usize>(x_bytes: [u8; B], y_bytes: [u8; B]) -> Self {
+        let () = ByteLen::<B, N>::COORDINATE;
+        let mut limbs = [0u32; N];
+        for (limb, chunk) in limbs
+            .iter_mut()
+            .zip(x_bytes.chunks_exact(4).chain(y_bytes.chunks_exact(4)))
+        {
+            *limb = u32::from_le_bytes(chunk.try_into().unwrap());
+        }
         Self::new(limbs)
     }
 
-    pub fn add_assign(&mut self, other: &AffinePoint<C>) {
+    pub fn add_assign(&mut self, other: &AffinePoint<C, N>) {
         C::add_assign(&mut self.limbs, &other.limbs);
     }
 
@@ -46,36 +109,67 @@ impl<C: CurveOperations + Copy> AffinePoint<C> {
         C::double(&mut self.limbs);
     }
 
-    pub fn mul_assign(&mut self, scalar: &[u32; 8]) {
-        let mut res: Option<Self> = None;
-        let mut temp = *self;
-
-        for &words in scalar.iter() {
-            for i in 0..32 {
-                if (words >> i) & 1 == 1 {
-                    match res.as_mut() {
-                        Some(res) => res.add_assign(&temp),
-                        None => res = Some(temp),
-                    };
-                }
+    /// Adds two points, returning `None` if the result is the identity. Unlike `add_assign`, the
+    /// points may be equal or opposite.
+    pub fn add_complete(&self, other: &Self) -> Option<Self> {
+        add_complete::<C, N>(Some(self.limbs), &other.limbs).map(Self::new)
+    }
 
-                temp.double();
+    /// Multiplies the point by a scalar of 8 little-endian words, returning `None` if the result
+    /// is the identity.
+    pub fn mul(&self, scalar: &[u32; 8]) -> Option<Self> {
+        C::msm(&[self.limbs], &[*scalar]).map(Self::new)
+    }
+
+    /// Multiplies the generator by a scalar of 8 little-endian words, returning `None` if the
+    /// result is the identity.
+    pub fn base_mul(scalar: &[u32; 8]) -> Option<Self> {
+        Self::generator_in_affine().mul(scalar)
+    }
+
+    /// Computes the sum of `scalars[i] * points[i]`, returning `None` if the result is the
+    /// identity. The points are multiplied in chunks of `MAX_MSM_POINTS`, whose results are added
+    /// with `add_complete`.
+    pub fn msm(points: &[Self], scalars: &[[u32; 8]]) -> Option<Self> {
+        assert_eq!(points.len(), scalars.len());
+        let mut res: Option<Self> = None;
+        for (points, scalars) in points
+            .chunks(MAX_MSM_POINTS)
+            .zip(scalars.chunks(MAX_MSM_POINTS))
+        {
+            let limbs = points.iter().map(|point| point.limbs).collect::<Vec<_>>();
+            if let Some(chunk) = C::msm(&limbs, scalars).map(Self::new) {
+                res = match res {
+                    Some(res) => res.add_complete(&chunk),
+                    None => Some(chunk),
+                };
             }
         }
+        res
+    }
 
-        *self = res.unwrap();
+    /// Multiplies the point by a scalar of 8 little-endian words.
+    ///
+    /// Panics if the result is the identity, which cannot be represented. Use `mul` instead to
+    /// handle it.
+    pub fn mul_assign(&mut self, scalar: &[u32; 8]) {
+        *self = self.mul(scalar).expect("the product is the identity");
     }
 
-    pub fn from_le_bytes(limbs: [u8; 64]) -> Self {
-        let u32_limbs = bytes_to_words_le::<16>(&limbs);
+    /// Construct an AffinePoint from its `N * 4` little-endian bytes.
+    pub fn from_le_bytes<const B: usize>(limbs: [u8; B]) -> Self {
+        let () = ByteLen::<B, N>::POINT;
+        let u32_limbs = bytes_to_words_le::<N>(&limbs);
         Self {
             limbs: u32_limbs,
             _marker: std::marker::PhantomData,
         }
     }
 
-    pub fn to_le_bytes(&self) -> [u8; 64] {
-        words_to_bytes_le::<64>(&self.limbs)
+    /// The `N * 4` little-endian bytes of the point.
+    pub fn to_le_bytes<const B: usize>(&self) -> [u8; B] {
+        let () = ByteLen::<B, N>::POINT;
+        words_to_bytes_le::<B>(&self.limbs)
     }
 }
 